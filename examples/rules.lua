@@ -0,0 +1,34 @@
+-- Example rule script for lumon-mdr (build with `--features scripting`).
+-- Copy to ~/.config/lumon-mdr/rules.lua and edit to taste. Every function
+-- is optional; delete one, or return nil, to keep the built-in rule.
+
+-- The digit that replaces a refined number. Columns and rows count from 1.
+-- Here the first column is always sevens, which Kier found soothing.
+function digit(col, row)
+  if col == 1 then
+    return 7
+  end
+  return math.random(0, 9)
+end
+
+-- What a deposit adds to a bin (1-5). Bin 4 is the woe bin and refines slowly.
+function deposit(bin, value)
+  if bin == 4 then
+    return math.max(1, value // 2)
+  end
+  return value
+end
+
+-- The prize for a completed file, picked from the list of known prizes.
+-- Any other text works too.
+function prize(prizes)
+  if math.random() < 0.1 then
+    return "Waffle Party"
+  end
+  return prizes[math.random(#prizes)]
+end
+
+-- A note shown under the prize.
+function memo(user, prize)
+  return string.format("Kier smiles upon %s. Enjoy your %s.", user, prize)
+end
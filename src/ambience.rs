@@ -0,0 +1,123 @@
+//! Life on the severed floor: now and then, while refining, the lights
+//! flicker, a door chimes down the hall, Mr. Milchick sends a memo or a
+//! PSA asks for quiet.
+//!
+//! Events arrive at random, a few minutes apart on average, with the wait
+//! before each drawn afresh so they never fall into a rhythm. Each kind
+//! then rests for its own cooldown, so the same one does not come twice
+//! in a row. It is off unless `enabled` in `[ambience]` turns it on.
+
+use crate::animate::Timeline;
+use crate::random;
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+/// Average wait between events
+const MEAN_GAP: Duration = Duration::from_secs(180);
+/// Shortest wait between events, however the draw falls
+const MIN_GAP: Duration = Duration::from_secs(30);
+/// Longest wait between events
+const MAX_GAP: Duration = Duration::from_secs(15 * 60);
+/// How long the lights stay out each time they flicker
+const FLICKER_EACH: Duration = Duration::from_millis(150);
+/// How long the PSA banner stays up
+const PSA_LENGTH: Duration = Duration::from_secs(4);
+
+pub const CHIME: &str = "A door chimes somewhere down the hall";
+pub const PSA: &str = "PLEASE REFRAIN FROM NON-WORK CONVERSATION";
+
+const MEMOS: &[&str] = &[
+    "The break room is for breaks only.",
+    "Music Dance Experience vouchers may not be traded between departments.",
+    "Please keep desks free of personal effects.",
+    "Waffle party eligibility is reviewed at the end of each quarter.",
+    "Refiners are reminded that the hallways are not for loitering.",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Ambience {
+    /// The lights dip a few times
+    Flicker,
+    /// A toast about a door down the hall
+    Chime,
+    /// A memo from Mr. Milchick, kept in the inbox
+    Memo,
+    /// A banner across the top of the grid
+    Psa,
+}
+
+impl Ambience {
+    pub const ALL: [Ambience; 4] = [Ambience::Flicker, Ambience::Chime, Ambience::Memo, Ambience::Psa];
+
+    /// How long before the same event may come again
+    pub fn cooldown(self) -> Duration {
+        match self {
+            Ambience::Flicker => Duration::from_secs(5 * 60),
+            Ambience::Chime => Duration::from_secs(4 * 60),
+            Ambience::Memo => Duration::from_secs(15 * 60),
+            Ambience::Psa => Duration::from_secs(10 * 60),
+        }
+    }
+
+    /// How long the event stays on the board; the chime and the memo are
+    /// toasts, gone on the toast's own schedule
+    pub fn length(self) -> Duration {
+        match self {
+            Ambience::Flicker => flicker().length(),
+            Ambience::Psa => PSA_LENGTH,
+            Ambience::Chime | Ambience::Memo => Duration::ZERO,
+        }
+    }
+}
+
+/// The lights through a flicker: out at 1, on at 0
+pub fn flicker() -> Timeline {
+    Timeline::flashing(FLICKER_EACH, 3)
+}
+
+/// A wait before the next event, around `MEAN_GAP`
+pub fn gap(rng: &mut impl Rng) -> Duration {
+    random::exponential(rng, MEAN_GAP, (MIN_GAP, MAX_GAP))
+}
+
+/// A memo from Mr. Milchick
+pub fn memo(rng: &mut impl Rng) -> &'static str {
+    MEMOS[rng.random_range(0..MEMOS.len())]
+}
+
+/// When the next event is due, and when each kind last came
+#[derive(Clone, Debug, Default)]
+pub struct Scheduler {
+    next: Option<Instant>,
+    last: [Option<Instant>; Ambience::ALL.len()],
+}
+
+impl Scheduler {
+    /// The event due at `now`, if one is. The first poll only sets the
+    /// clock running. An event that comes due while every kind is cooling
+    /// down is skipped.
+    pub fn poll(&mut self, now: Instant, rng: &mut impl Rng) -> Option<Ambience> {
+        let due = *self.next.get_or_insert_with(|| now + gap(rng));
+        if now < due {
+            return None;
+        }
+        self.next = Some(now + gap(rng));
+        let ready: Vec<Ambience> = Ambience::ALL.into_iter().filter(|&ambience| self.is_ready(ambience, now)).collect();
+        if ready.is_empty() {
+            return None;
+        }
+        let ambience = ready[rng.random_range(0..ready.len())];
+        self.last[ambience as usize] = Some(now);
+        Some(ambience)
+    }
+
+    /// Whether `ambience` has rested long enough to come again
+    pub fn is_ready(&self, ambience: Ambience, now: Instant) -> bool {
+        self.last[ambience as usize].is_none_or(|last| now.duration_since(last) >= ambience.cooldown())
+    }
+
+    /// When the next event is due, once the clock is running
+    pub fn next(&self) -> Option<Instant> {
+        self.next
+    }
+}
@@ -0,0 +1,219 @@
+//! Numbers on screen that move toward a new value over a moment instead
+//! of jumping to it: the header's completion count and the bins' bars.
+//! An `Animated` keeps where it started, where it is going and when it
+//! set off, so its value at any instant comes from the clock rather than
+//! from how many frames have been drawn.
+//!
+//! A `Timeline` is for a fixed sequence played from one instant: the grid
+//! sliding into a resized window, a combo banner flashing. It is built up
+//! from segments,
+//!
+//! ```
+//! # use lumon_mdr::animate::Timeline;
+//! # use std::time::Duration;
+//! let ms = Duration::from_millis;
+//! let flash = Timeline::new().delay(ms(50)).ease_in(ms(100), 1.0).then(Timeline::new().ease_out(ms(200), 0.0));
+//! assert_eq!(flash.length(), ms(350));
+//! ```
+//!
+//! and asked for its value at a time since it started, so whoever plays
+//! one only keeps the instant it started at.
+
+use std::time::{Duration, Instant};
+
+/// How progress through an animation maps onto the distance covered
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    /// Slow to start, speeding up into the target
+    EaseIn,
+    /// Quick to start, settling gently onto the target
+    #[default]
+    EaseOut,
+    /// Slow at both ends
+    EaseInOut,
+}
+
+impl Easing {
+    /// Distance covered, 0 to 1, after `t` of the time, 0 to 1
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t.powi(3),
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut if t < 0.5 => 4.0 * t.powi(3),
+            Easing::EaseInOut => 1.0 - (2.0 - 2.0 * t).powi(3) / 2.0,
+        }
+    }
+}
+
+/// A value easing toward its target
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Animated {
+    from: f32,
+    target: f32,
+    /// When the current move began; `None` once settled
+    since: Option<Instant>,
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl Animated {
+    /// Settled at `value`, taking `duration` over each later move
+    pub fn new(value: f32, duration: Duration, easing: Easing) -> Self {
+        Self { from: value, target: value, since: None, duration, easing }
+    }
+
+    /// Head for `target` from wherever the value is at `now`
+    pub fn set(&mut self, target: f32, now: Instant) {
+        if target == self.target {
+            return;
+        }
+        self.from = self.value(now);
+        self.target = target;
+        self.since = Some(now);
+    }
+
+    /// Go straight to `value`
+    pub fn jump(&mut self, value: f32) {
+        *self = Self::new(value, self.duration, self.easing);
+    }
+
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    /// Where the current move set off from; the target once settled
+    pub fn origin(&self) -> f32 {
+        if self.since.is_some() { self.from } else { self.target }
+    }
+
+    /// Where the value is at `now`
+    pub fn value(&self, now: Instant) -> f32 {
+        let Some(since) = self.since else { return self.target };
+        if self.duration.is_zero() {
+            return self.target;
+        }
+        let t = now.saturating_duration_since(since).as_secs_f32() / self.duration.as_secs_f32();
+        self.from + (self.target - self.from) * self.easing.apply(t)
+    }
+
+    /// Whether the value is still on its way at `now`
+    pub fn is_moving(&self, now: Instant) -> bool {
+        self.since.is_some_and(|since| now.saturating_duration_since(since) < self.duration)
+    }
+}
+
+/// One stretch of a timeline: `length` spent heading for `to`
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Segment {
+    length: Duration,
+    to: f32,
+    easing: Easing,
+}
+
+/// A value's path over time, played one segment after another
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timeline {
+    start: f32,
+    segments: Vec<Segment>,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Timeline {
+    /// Starting from 0
+    pub fn new() -> Self {
+        Self::starting_at(0.0)
+    }
+
+    pub fn starting_at(value: f32) -> Self {
+        Self { start: value, segments: Vec::new() }
+    }
+
+    /// Head for `to` over `length`
+    pub fn tween(mut self, length: Duration, to: f32, easing: Easing) -> Self {
+        self.segments.push(Segment { length, to, easing });
+        self
+    }
+
+    /// Stay where it is for `length`
+    pub fn delay(self, length: Duration) -> Self {
+        let at = self.end();
+        self.tween(length, at, Easing::Linear)
+    }
+
+    /// Go straight to `to`
+    pub fn jump(self, to: f32) -> Self {
+        self.tween(Duration::ZERO, to, Easing::Linear)
+    }
+
+    pub fn linear(self, length: Duration, to: f32) -> Self {
+        self.tween(length, to, Easing::Linear)
+    }
+
+    pub fn ease_in(self, length: Duration, to: f32) -> Self {
+        self.tween(length, to, Easing::EaseIn)
+    }
+
+    pub fn ease_out(self, length: Duration, to: f32) -> Self {
+        self.tween(length, to, Easing::EaseOut)
+    }
+
+    pub fn ease_in_out(self, length: Duration, to: f32) -> Self {
+        self.tween(length, to, Easing::EaseInOut)
+    }
+
+    /// On at 1 and off at 0 for `each` at a time, `times` over
+    pub fn flashing(each: Duration, times: usize) -> Self {
+        Timeline::new().jump(1.0).delay(each).jump(0.0).delay(each).repeat(times)
+    }
+
+    /// Play `next` once this has finished, jumping to where `next` starts
+    pub fn then(self, next: Timeline) -> Self {
+        let mut joined = if next.start == self.end() { self } else { self.jump(next.start) };
+        joined.segments.extend(next.segments);
+        joined
+    }
+
+    /// Play the whole timeline `times` times over
+    pub fn repeat(self, times: usize) -> Self {
+        let once = self.clone();
+        (1..times).fold(self, |timeline, _| timeline.then(once.clone()))
+    }
+
+    /// How long it takes to play
+    pub fn length(&self) -> Duration {
+        self.segments.iter().map(|segment| segment.length).sum()
+    }
+
+    /// Where it finishes
+    pub fn end(&self) -> f32 {
+        self.segments.last().map_or(self.start, |segment| segment.to)
+    }
+
+    /// Where the value is `elapsed` after it started
+    pub fn value(&self, elapsed: Duration) -> f32 {
+        let mut from = self.start;
+        let mut left = elapsed;
+        for segment in &self.segments {
+            if left < segment.length {
+                let t = left.as_secs_f32() / segment.length.as_secs_f32();
+                return from + (segment.to - from) * segment.easing.apply(t);
+            }
+            left -= segment.length;
+            from = segment.to;
+        }
+        from
+    }
+
+    /// Whether it has finished playing `elapsed` after it started
+    pub fn is_done(&self, elapsed: Duration) -> bool {
+        elapsed >= self.length()
+    }
+}
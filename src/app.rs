@@ -1,19 +1,520 @@
+use crate::ambience::{self, Ambience, Scheduler};
+use crate::animate::{Animated, Easing, Timeline};
+use crate::autosave::{self, AUTOSAVE_INTERVAL, Autosave, Recovery};
+use crate::budget::{self, Usage};
+use crate::card::CompletionCard;
+use crate::clipboard::{Clipboard, Method};
+use crate::clock::{Clock, SystemClock};
+use crate::config::{ConfirmStyle, Config, Reload};
+use crate::context_menu::{ContextMenu, MenuItem, MenuTarget};
+use crate::content::{self, Content, Prize};
+use crate::crypto::{self, KeyDerivation, SealKey};
+use crate::diagnostics::{FrameStats, Quality};
+use crate::combo::Combo;
+use crate::error::{FailureKind, MdrError, RecoverableError};
+use crate::events::AppEvent;
+use crate::focus::{Focus, Routed};
+use crate::goals::{Goals, Target};
+use crate::grid::{self, GridModel, GridSnapshot};
+use crate::inject::Injector;
+use crate::journal::Journal;
+use crate::keyseq::{KONAMI, KeySequence};
+use crate::ledger::{self, Ledger, Recap};
+use crate::metrics::Metrics;
+use crate::observe::SessionLink;
+use crate::onboarding::{Lesson, Onboarding};
+use crate::orientation::{self, Step};
+use crate::race::{self, Race, RaceResult};
+use crate::ranks;
+use crate::recording::Recorder;
+use crate::save::{self, SaveState, Sealed, Stored};
+use crate::scoring::{Capture, Difficulty, Scoring};
+use crate::screen::{Screen, with_screen};
+use crate::selfcheck::{self, SELFCHECK_INTERVAL};
+use crate::setup::{Setup, SetupStep};
+use crate::slots::{self, SlotPicker};
+use crate::voucher::{self, Voucher};
+use crate::share::ShareCode;
+use crate::startup::{Loaded, Startup, Task};
+use crate::stats::{self, Profile, Stats};
+use crate::assist::{self, PULSE_LENGTH, Pulse};
+use crate::compliance::{self, ComplianceCheck};
+use crate::strategy::BinStrategy;
+use crate::temper::{self, Deposit, Provenance, Temper};
 use crate::theme::Palette;
-use rand::{Rng, rng};
-use crossterm::event::{MouseEvent, MouseEventKind, KeyCode};
-use std::collections::HashMap;
+use crate::updates::{self, Release, UpdateCheck};
+use crate::watch::{WATCH_INTERVAL, Watcher};
+use crate::random::{RngService, Stream};
+use rand::Rng;
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseEvent};
+use ratatui::layout::Rect;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
 
-pub enum AppState {
-    Login,    
-    Loading, 
-    Main,
-    Prize,  
+/// Longest employee name accepted at login, in characters
+pub const MAX_USERNAME_LEN: usize = 25;
+/// Longest line accepted at the `:` prompt, long enough for a journal note
+pub const MAX_COMMAND_LEN: usize = 80;
+
+/// Logging in under this name brings a visit from the founder
+pub const FOUNDER_NAME: &str = "kier";
+/// Added to the prize pool for the founder's session
+pub const FOUNDER_PRIZE: &str = "Perpetuity Wing Tour";
+
+/// How long a passing notice stays on the main screen
+pub const TOAST_DURATION: Duration = Duration::from_secs(4);
+/// How long a combo's banner stays over the grid
+pub const COMBO_BANNER_DURATION: Duration = Duration::from_millis(1500);
+
+/// How long the header's completion takes to count up to a new value
+pub const COMPLETION_COUNT: Duration = Duration::from_millis(800);
+/// How long a bin's bar takes to fill up to a deposit
+pub const BIN_FILL_EASE: Duration = Duration::from_millis(400);
+/// Each flash of a bin's number when it fills
+pub const BIN_FILLED_FLASH: Duration = Duration::from_millis(200);
+/// How long the grid takes to slide into a resized window
+pub const GRID_MORPH: Duration = Duration::from_millis(400);
+/// How long the completion pulses after passing a quarter
+pub const COMPLETION_PULSE: Duration = Duration::from_millis(600);
+
+/// Wrong passphrases allowed before the login starts over
+pub const MAX_PASSPHRASE_ATTEMPTS: u32 = 3;
+
+/// What Enter deposits into the focused bin
+pub const KEYBOARD_DEPOSIT: u16 = 5;
+
+/// How often the loading bar advances
+pub const LOADING_STEP: Duration = Duration::from_millis(900);
+/// Pause on a full loading bar before the main screen appears
+pub const LOADING_COMPLETE_DELAY: Duration = Duration::from_millis(1800);
+/// How long a completed file stays on screen before the prize is shown
+pub const PRIZE_DELAY: Duration = Duration::from_millis(2700);
+
+/// Game speeds `[` and `]` step through, in percent of the normal pace
+pub const GAME_SPEEDS: [u32; 6] = [50, 75, 100, 125, 150, 200];
+
+/// Length of one animation step; `animation_step` advances once per step
+/// regardless of how often the app actually ticks
+pub const ANIMATION_STEP: Duration = Duration::from_millis(300);
+/// Tick interval while something is moving or the refiner is active
+pub const ACTIVE_TICK_RATE: Duration = Duration::from_millis(100);
+/// Tick interval when nothing in particular is happening
+pub const DEFAULT_TICK_RATE: Duration = Duration::from_millis(300);
+/// Tick interval once the refiner has been away for a while
+pub const IDLE_TICK_RATE: Duration = Duration::from_millis(1000);
+/// Tick and redraw interval in eco mode (2 FPS)
+pub const ECO_TICK_RATE: Duration = Duration::from_millis(500);
+/// Input within this window counts as active refinement
+const ACTIVE_WINDOW: Duration = Duration::from_secs(2);
+/// No input for this long counts as idle
+pub const IDLE_AFTER: Duration = Duration::from_secs(10);
+
+pub use crate::state::AppState;
+
+/// Which part of the login form is showing
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LoginStep {
+    Name,
+    /// Choosing which save slot to continue, or starting a new one
+    Slot,
+    /// Unsealing a saved session, or choosing a passphrase for a new one
+    Passphrase,
+}
+
+/// A short notice over the main screen
+#[derive(Clone, Debug, PartialEq)]
+pub struct Toast {
+    pub text: String,
+    /// `None` stays until dismissed with Esc
+    pub expires: Option<Instant>,
+}
+
+/// Grid cells whose digits feel wrong to the refiner. Refining a cell
+/// takes it out of the cluster.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaryCluster {
+    pub cells: Vec<(usize, usize)>,
+    /// When the cursor came within reach of the cluster, if it still is
+    pub hovered_since: Option<Instant>,
+}
+
+impl ScaryCluster {
+    pub fn new(cells: Vec<(usize, usize)>) -> Self {
+        Self { cells, hovered_since: None }
+    }
+
+    /// How much dread the cluster gives off at `now`: rising from 0 to 1
+    /// over `DREAD_RAMP` while the cursor lingers, and gone once it leaves
+    pub fn dread(&self, now: Instant) -> f32 {
+        self.hovered_since.map_or(0.0, |since| {
+            (now.duration_since(since).as_secs_f32() / DREAD_RAMP.as_secs_f32()).min(1.0)
+        })
+    }
+
+    /// Grid cells between `cell` and the nearest cell of the cluster
+    pub fn distance(&self, cell: (usize, usize)) -> usize {
+        self.cells
+            .iter()
+            .map(|&(col, row)| col.abs_diff(cell.0).max(row.abs_diff(cell.1)))
+            .min()
+            .unwrap_or(usize::MAX)
+    }
+}
+
+/// How long the cursor has to linger by a scary cluster for full dread
+pub const DREAD_RAMP: Duration = Duration::from_secs(3);
+/// Grid cells from a scary cluster at which the cursor starts to feel it
+const DREAD_REACH: usize = 1;
+
+/// Sticky numbers scattered over each fresh file
+pub const STICKY_CELLS: usize = 12;
+/// Grid cells (columns, rows) sticky numbers are scattered over
+const STICKY_AREA: (usize, usize) = (20, 14);
+/// Shortest and longest hold a sticky number needs, in milliseconds
+const STICKY_HOLD_MS: (u64, u64) = (400, 1200);
+/// A held key counts as let go if its auto-repeat stops for this long,
+/// for terminals that do not report key releases
+pub const KEY_REPEAT_GAP: Duration = Duration::from_millis(600);
+/// How long Y is held to confirm a reset, with `confirm = "hold"`
+pub const HOLD_TO_CONFIRM: Duration = Duration::from_secs(1);
+
+/// Sticky numbers being held down until they come loose
+#[derive(Clone, Debug, PartialEq)]
+pub struct Hold {
+    /// `(col, row, digit)` of each held number
+    pub cells: Vec<(usize, usize, u16)>,
+    pub since: Instant,
+    /// What the stickiest of them needs
+    pub needs: Duration,
+    /// For a held key, when it last repeated
+    pub key_seen: Option<Instant>,
+    /// Bin picked with a number key, rather than one at random
+    pub bin: Option<usize>,
+}
+
+impl Hold {
+    /// From 0 when pressed to 1 when the numbers come loose
+    pub fn progress(&self, now: Instant) -> f32 {
+        (now.duration_since(self.since).as_secs_f32() / self.needs.as_secs_f32()).min(1.0)
+    }
+}
+
+/// What each bin not yet full gives up to a reshuffle
+pub const RESHUFFLE_PENALTY: u16 = 2;
+
+/// How long a capture waits at the foot of the grid before it lands, with
+/// `review_captures` on
+pub const STAGE_DURATION: Duration = Duration::from_millis(500);
+
+/// A capture shown before it is deposited, so Esc can call it off
+#[derive(Clone, Debug, PartialEq)]
+pub struct Staged {
+    /// `(col, row, digit)` of each captured number
+    pub cells: Vec<(usize, usize, u16)>,
+    /// Where it will land
+    pub bin: usize,
+    /// What it is worth, as things stand
+    pub value: u16,
+    pub since: Instant,
+}
+
+/// Where the number grid was last drawn, for mapping the cursor to cells
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GridGeometry {
+    pub area: Rect,
+    pub horizontal_spacing: u16,
+    pub vertical_spacing: u16,
+}
+
+impl GridGeometry {
+    /// Columns and rows of digits drawn
+    pub fn cells(&self) -> (usize, usize) {
+        let across = (self.area.width.saturating_sub(2) / self.horizontal_spacing.max(1)) as usize;
+        let down = (self.area.height.saturating_sub(1) / self.vertical_spacing.max(1)) as usize;
+        (across, down)
+    }
+
+    /// The grid cell nearest to a screen position inside the grid
+    pub fn cell_at(&self, x: u16, y: u16) -> Option<(usize, usize)> {
+        let inside = x >= self.area.x
+            && x < self.area.x + self.area.width
+            && y >= self.area.y
+            && y < self.area.y + self.area.height;
+        if !inside || self.horizontal_spacing == 0 || self.vertical_spacing == 0 {
+            return None;
+        }
+        // Digits sit two columns in and half a row down from their cell's
+        // corner; the margin past the last one belongs to it
+        let (cols, rows) = self.cells();
+        if cols == 0 || rows == 0 {
+            return None;
+        }
+        let col = (x - self.area.x).saturating_sub(2) / self.horizontal_spacing;
+        let row = (y - self.area.y).saturating_sub(self.vertical_spacing / 2) / self.vertical_spacing;
+        Some(((col as usize).min(cols - 1), (row as usize).min(rows - 1)))
+    }
+
+    /// The screen position of `cell`'s digit
+    pub fn position_of(&self, (col, row): (usize, usize)) -> (u16, u16) {
+        (
+            self.area.x + 2 + col as u16 * self.horizontal_spacing,
+            self.area.y + self.vertical_spacing / 2 + row as u16 * self.vertical_spacing,
+        )
+    }
+}
+
+/// The grid sliding into a resized window: each cell's digit sets off
+/// from the place its cell scales back to on the old grid, as a saved
+/// grid is scaled onto a new window, so a growing grid spreads out from
+/// the old one and a shrinking one gathers in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridMorph {
+    pub from: GridGeometry,
+    pub to: GridGeometry,
+    since: Instant,
+}
+
+impl GridMorph {
+    pub fn new(from: GridGeometry, to: GridGeometry, now: Instant) -> Self {
+        Self { from, to, since: now }
+    }
+
+    /// From 0 at the old place to 1 at the new
+    fn progress() -> Timeline {
+        Timeline::new().ease_out(GRID_MORPH, 1.0)
+    }
+
+    /// Where `cell`'s digit is drawn at `now`
+    pub fn position_of(&self, (col, row): (usize, usize), now: Instant) -> (u16, u16) {
+        let (from, to) = (self.from.cells(), self.to.cells());
+        let end = self.to.position_of((col, row));
+        // An empty grid has nowhere to slide from or to
+        if [from.0, from.1, to.0, to.1].contains(&0) {
+            return end;
+        }
+        let start = self.from.position_of(grid::scale_cell((col, row), to, from));
+        let t = Self::progress().value(now.saturating_duration_since(self.since));
+        let lerp = |a: u16, b: u16| (f32::from(a) + (f32::from(b) - f32::from(a)) * t).round() as u16;
+        (lerp(start.0, end.0), lerp(start.1, end.1))
+    }
+
+    pub fn is_moving(&self, now: Instant) -> bool {
+        !Self::progress().is_done(now.saturating_duration_since(self.since))
+    }
+}
+
+/// The grid every file had before files had their own seed
+pub const DEFAULT_GRID_SEED: u64 = 42;
+
+/// The work behind the loading bar when a saved session comes back, in
+/// the order it is done
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestoreStep {
+    /// Done at login, where the file has to be read to know if it is sealed
+    ReadSave,
+    /// Check the ledger file still reads, reporting it if it is damaged
+    CheckLedger,
+    /// Drop what was left over from the grid before the saved file's seed
+    RebuildGrid,
+}
+
+impl RestoreStep {
+    pub const ALL: [RestoreStep; 3] = [RestoreStep::ReadSave, RestoreStep::CheckLedger, RestoreStep::RebuildGrid];
+
+    /// What the loading screen says while this step is under way
+    pub fn label(self) -> &'static str {
+        match self {
+            RestoreStep::ReadSave => "Reading saved session...",
+            RestoreStep::CheckLedger => "Validating ledger...",
+            RestoreStep::RebuildGrid => "Rebuilding the grid...",
+        }
+    }
+}
+
+/// How much `r` wipes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ResetScope {
+    /// The focused bin
+    Bin,
+    /// Every bin, on the same file
+    AllBins,
+    /// A new file with a fresh grid
+    File,
+    /// The ledger and config too
+    Factory,
+    /// A new grid on the same file, for a small cost; asked for with
+    /// `:reshuffle` rather than listed
+    Reshuffle,
+}
+
+impl ResetScope {
+    /// The order the reset menu lists them
+    pub const ALL: [ResetScope; 4] = [ResetScope::Bin, ResetScope::AllBins, ResetScope::File, ResetScope::Factory];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ResetScope::Bin => "Reset current bin",
+            ResetScope::AllBins => "Reset all bins",
+            ResetScope::File => "Reset file (new grid)",
+            ResetScope::Factory => "Factory reset",
+            ResetScope::Reshuffle => "Reshuffle the grid",
+        }
+    }
+
+    /// What the confirmation asks
+    pub fn warning(self) -> &'static str {
+        match self {
+            ResetScope::Bin => "Empty the focused bin?",
+            ResetScope::AllBins => "Empty every bin on this file?",
+            ResetScope::File => "Abandon this file and start a new one?",
+            ResetScope::Factory => "Erase the ledger and set config.toml aside? This starts a new file too.",
+            ResetScope::Reshuffle => "Reshuffle the grid? Each bin not yet full gives up 2%.",
+        }
+    }
+}
+
+/// The buttons under a yes-or-no question
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Yes,
+    No,
+}
+
+/// The menu `r` opens, and the question it is asking if any
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ResetMenu {
+    pub selected: usize,
+    pub confirming: Option<ResetScope>,
+    /// The answer buttons while confirming, starting on yes
+    pub answer: Focus<Answer>,
+    /// With hold-to-confirm, while Y is held down
+    pub held: Option<KeyHold>,
+}
+
+impl ResetMenu {
+    /// Ask whether to reset `scope`
+    pub fn confirm(&mut self, scope: ResetScope) {
+        self.confirming = Some(scope);
+        self.answer = Focus::on(vec![Answer::Yes, Answer::No], Answer::Yes);
+        self.held = None;
+    }
+}
+
+/// The buttons on the error screen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorAction {
+    Retry,
+    Ignore,
+    Quit,
+}
+
+impl ErrorAction {
+    pub const ALL: [ErrorAction; 3] = [ErrorAction::Retry, ErrorAction::Ignore, ErrorAction::Quit];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ErrorAction::Retry => "[R] Retry",
+            ErrorAction::Ignore => "[I] Ignore",
+            ErrorAction::Quit => "[Q] Quit",
+        }
+    }
+}
+
+/// A key being held down to confirm something
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyHold {
+    pub since: Instant,
+    /// The latest press or auto-repeat
+    pub seen: Instant,
+}
+
+impl KeyHold {
+    pub fn new(now: Instant) -> Self {
+        Self { since: now, seen: now }
+    }
+
+    /// From 0 when pressed to 1 once held for `needs`
+    pub fn progress(&self, now: Instant, needs: Duration) -> f32 {
+        (now.duration_since(self.since).as_secs_f32() / needs.as_secs_f32()).min(1.0)
+    }
+}
+
+/// What the main screen's side panel shows
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SidePanel {
+    /// Today's totals and rank progress
+    Stats,
+    /// The session goals checklist
+    Goals,
+    /// The focused bin
+    Bin,
+    /// Notices shown this session
+    Inbox,
+    /// Session events, newest first
+    Log,
 }
 
+impl SidePanel {
+    /// The order `p` steps through
+    pub const ALL: [SidePanel; 5] =
+        [SidePanel::Stats, SidePanel::Goals, SidePanel::Bin, SidePanel::Inbox, SidePanel::Log];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            SidePanel::Stats => "STATS",
+            SidePanel::Goals => "GOALS",
+            SidePanel::Bin => "BIN",
+            SidePanel::Inbox => "INBOX",
+            SidePanel::Log => "LOG",
+        }
+    }
+}
+
+/// A bin being dragged to a new place on the floor
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BinDrag {
+    /// Index into `containers`
+    pub bin: usize,
+    /// Column where the bin was picked up
+    pub grab: u16,
+    /// Column under the mouse now
+    pub column: u16,
+}
+
+impl BinDrag {
+    /// A press only becomes a drag once the mouse travels a little, so
+    /// ordinary clicks on a bin are left alone
+    pub fn is_moving(&self) -> bool {
+        self.column.abs_diff(self.grab) >= DRAG_THRESHOLD
+    }
+}
+
+/// Columns the mouse must travel before a pressed bin is lifted
+const DRAG_THRESHOLD: u16 = 2;
+
 // Structure to track data for each container
 pub struct DataContainer {
     pub count: u16,            
     pub progress: f32,         
+    /// The bar as drawn, easing up to `progress` after a deposit
+    pub fill: Animated,
+    /// Position on the floor, counted from the left
+    pub slot: usize,
+    /// Amount deposited this session under each temper, in `Temper::ALL` order
+    pub tempers: [u16; 4],
+    /// Latest deposit last
+    pub deposits: VecDeque<Deposit>,
+    /// When the bin last filled up, for its number to flash
+    pub filled_at: Option<Instant>,
+}
+
+impl Default for DataContainer {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl DataContainer {
@@ -21,8 +522,28 @@ impl DataContainer {
         Self {
             count: 0,
             progress: 0.0,
+            fill: Animated::new(0.0, BIN_FILL_EASE, Easing::EaseOut),
+            slot: 0,
+            tempers: [0; 4],
+            deposits: VecDeque::new(),
+            filled_at: None,
         }
     }
+
+    /// The flashing of a bin that has just filled
+    pub fn celebration() -> Timeline {
+        Timeline::flashing(BIN_FILLED_FLASH, 3)
+    }
+
+    /// Whether the celebration is still playing at `now`
+    pub fn is_celebrating(&self, now: Instant) -> bool {
+        self.filled_at.is_some_and(|at| !Self::celebration().is_done(now.saturating_duration_since(at)))
+    }
+
+    /// Whether the celebration has the bin's number lit up at `now`
+    pub fn is_lit(&self, now: Instant) -> bool {
+        self.filled_at.is_some_and(|at| Self::celebration().value(now.saturating_duration_since(at)) >= 0.5)
+    }
     
     // Add value to container, respecting max of 100
     pub fn add(&mut self, value: u16) {
@@ -30,301 +551,2843 @@ impl DataContainer {
         self.progress = self.count as f32;
     }
     
+    /// Take up to `value` back out, as a penalty, returning what was taken.
+    /// The breakdown gives it up from the tempers it holds most of, and
+    /// the oldest recent deposits go until the log fits what is left.
+    pub fn take(&mut self, value: u16) -> u16 {
+        let taken = value.min(self.count);
+        self.count -= taken;
+        self.progress = self.count as f32;
+
+        let mut excess = self.tempers.iter().sum::<u16>().saturating_sub(self.count);
+        while excess > 0 {
+            let most = (0..self.tempers.len()).max_by_key(|&idx| self.tempers[idx]).unwrap_or(0);
+            let cut = excess.min(self.tempers[most]);
+            self.tempers[most] -= cut;
+            excess -= cut;
+        }
+        let history: u16 = self.tempers.iter().sum();
+        while self.deposits.iter().map(|deposit| deposit.value).sum::<u16>() > history {
+            self.deposits.pop_front();
+        }
+        taken
+    }
+
+    /// Note a deposit of `value` under `temper` in the breakdown, and in
+    /// the log of the latest `keep`
+    pub fn record(&mut self, deposit: Deposit, keep: usize) {
+        self.tempers[deposit.temper.index()] += deposit.value;
+        budget::push_capped(&mut self.deposits, deposit, keep);
+    }
+
+    /// Back to nothing, keeping its place on the floor
+    pub fn empty(&mut self) {
+        self.count = 0;
+        self.progress = 0.0;
+        self.fill.jump(0.0);
+        self.tempers = [0; 4];
+        self.deposits.clear();
+        self.filled_at = None;
+    }
+
     // Check if container is full
     pub fn is_full(&self) -> bool {
         self.count >= 100
     }
 }
 
+/// What belongs to the terminal rather than to whoever is logged in at
+/// it: where things are kept, the config and content, the outputs and
+/// the window. Carried over whole when a refiner logs out.
+pub struct Station {
+    /// Startup reads still out; loading does not finish until they are in
+    pub startup: Option<Startup>,
+    /// Picks up the config and content packs as they are saved
+    pub watcher: Option<Watcher>,
+    pub started_at: Instant,
+    /// Saves the session in the background, when sessions are kept
+    pub autosave: Option<Autosave>,
+    /// Where `C` on the report and prize screens and `:copy` copy to
+    pub clipboard: Clipboard,
+    /// Writes every drawn frame out (`--record-frames`)
+    pub recorder: Option<Recorder>,
+    pub window_size_warning: bool,
+    pub current_width: u16,
+    pub current_height: u16,
+    pub show_debug_hud: bool,
+    pub eco_mode: bool,
+    /// Draw grid digits as blocks unless they are near the cursor
+    pub redacted: bool,
+    /// Draw charts in plain ASCII, for terminals without block elements
+    pub ascii_only: bool,
+    pub frame_stats: FrameStats,
+    pub config: Config,
+    pub config_path: Option<PathBuf>,
+    /// Whether the terminal window has focus (assumed until told otherwise)
+    pub focused: bool,
+    pub stats: Stats,
+    /// A supervisor opened the floor outside shift hours; lasts until the
+    /// next shift starts
+    pub shift_override: bool,
+    /// Progress contributed by external tools (`--inject`)
+    pub injector: Option<Injector>,
+    /// Events not yet handed to the outputs
+    pub outbox: Vec<AppEvent>,
+    /// Counters shared with the `/metrics` listener (`--metrics`)
+    pub metrics: Option<Arc<Metrics>>,
+    #[cfg(feature = "telemetry")]
+    pub telemetry: Option<crate::telemetry::Telemetry>,
+    #[cfg(feature = "speech")]
+    pub speech: Option<crate::speech::Speech>,
+    /// Where sessions are kept between runs; `None` keeps nothing
+    pub save_dir: Option<PathBuf>,
+    /// Refinement on this terminal, by day
+    pub ledger: Ledger,
+    /// Where the ledger is kept; `None` keeps it in memory only
+    pub ledger_path: Option<PathBuf>,
+    pub update_check: Option<UpdateCheck>,
+    /// Release notes for `:changelog`, newest first
+    pub changelog: Vec<Release>,
+    /// Prizes, messages and themes, built in or from content packs
+    pub content: Content,
+    /// Folder of content packs; `None` uses only the built-in content
+    pub packs_dir: Option<PathBuf>,
+    /// Where completion cards are written; `None` keeps them in memory
+    pub cards_dir: Option<PathBuf>,
+    /// Where vouchers are written as they are issued; `None` keeps them
+    /// in memory only
+    pub vouchers_dir: Option<PathBuf>,
+    /// Rule script file; `None` keeps the built-in rules
+    #[cfg(feature = "scripting")]
+    pub rules_path: Option<PathBuf>,
+    #[cfg(feature = "scripting")]
+    pub rules: Option<crate::scripting::Rules>,
+}
+
+impl Station {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            startup: None,
+            watcher: None,
+            started_at: now,
+            autosave: None,
+            clipboard: Clipboard::default(),
+            recorder: None,
+            window_size_warning: false,
+            current_width: 0,
+            current_height: 0,
+            show_debug_hud: false,
+            eco_mode: false,
+            redacted: false,
+            ascii_only: false,
+            frame_stats: FrameStats::default(),
+            config: Config::default(),
+            config_path: None,
+            focused: true,
+            stats: Stats::new(now),
+            shift_override: false,
+            injector: None,
+            outbox: Vec::new(),
+            metrics: None,
+            #[cfg(feature = "telemetry")]
+            telemetry: None,
+            #[cfg(feature = "speech")]
+            speech: None,
+            save_dir: None,
+            ledger: Ledger::default(),
+            ledger_path: None,
+            update_check: None,
+            changelog: updates::bundled(),
+            content: Content::default(),
+            packs_dir: None,
+            vouchers_dir: None,
+            cards_dir: None,
+            #[cfg(feature = "scripting")]
+            rules_path: None,
+            #[cfg(feature = "scripting")]
+            rules: None,
+        }
+    }
+}
+
 pub struct App {
+    pub clock: Rc<dyn Clock>,
     pub palette: Palette,
     pub running: bool,
-    pub state: AppState,
+    /// State that outlives a login
+    pub station: Station,
+    /// The screen showing; moved between by `go_to`
+    state: AppState,
     pub username: String,           
     pub username_cursor: usize,     
     pub show_login_error: bool,     
-    pub loading_timer: u16,
+    /// Logged in as the founder: his portrait on the loading screen and a
+    /// prize of his own for this session
+    pub founder_visit: bool,
+    pub next_loading_step: Option<Instant>,
     pub progress_percentage: f32,
-    pub completion_delay: u8,
-    pub completion_timer: u8,          
+    pub loading_complete_at: Option<Instant>,
+    /// Restore steps still to run; the bar follows these instead of
+    /// guessing while a saved session is being put back
+    pub restore_steps: VecDeque<RestoreStep>,
+    pub file_complete_at: Option<Instant>,
     pub prize_name: String,            
-    pub animation_counter: u32,
+    pub last_input: Instant,
+    /// When the invariants were last checked
+    pub last_selfcheck: Instant,
+    pub last_autosave: Instant,
+    /// What the check at launch did with saves a crash interrupted, for
+    /// the loading screen
+    pub recovered: Vec<Recovery>,
     pub mouse_position: Option<(u16, u16)>,
     pub last_clicked: Option<(u16, u16)>,
     pub containers: Vec<DataContainer>,
-    pub replaced_numbers: HashMap<(usize, usize), u16>,  
-    pub window_size_warning: bool,
+    /// Where each bin was drawn last frame, left to right, for hit-testing
+    pub bin_areas: Vec<Rect>,
+    pub bin_drag: Option<BinDrag>,
+    /// Bin picked with Tab, shown in the side panel; the tab order
+    /// follows the bins along the floor
+    pub bin_focus: Focus<usize>,
+    pub side_panel: Option<SidePanel>,
+    /// Every notice shown this session, latest last
+    pub inbox: VecDeque<String>,
+    /// Session events with when they happened, latest last
+    pub event_log: VecDeque<(Instant, AppEvent)>,
+    pub goals: Goals,
+    /// Notes written with `:note` this session
+    pub journal: Journal,
+    /// `:copy screen` was asked for: the next frame drawn is copied
+    pub copy_screen: bool,
+    /// Refined cells and the digits that replaced them
+    pub grid: GridModel,
+    pub scary_clusters: Vec<ScaryCluster>,
+    pub grid_geometry: Option<GridGeometry>,
+    /// Where the grid was when the window was resized, until it is next
+    /// drawn
+    pub resized_from: Option<GridGeometry>,
+    /// The grid sliding into a resized window
+    pub grid_morph: Option<GridMorph>,
+    /// A saved file's grid, waiting for the size it will be drawn at
+    pub pending_grid: Option<GridSnapshot>,
+    /// Cells that must be held before capture, with how long each needs
+    pub sticky: HashMap<(usize, usize), Duration>,
+    pub hold: Option<Hold>,
+    /// A capture waiting to land, with `review_captures` on
+    pub staged: Option<Staged>,
+    /// The bin the round-robin strategy picked last
+    round_robin: Option<usize>,
+    /// Times this file's grid has been reshuffled
+    pub reshuffles: u32,
+    /// The best cluster, pulsing for a moment, with the assist on
+    pub assist_pulse: Option<Pulse>,
+    /// When the assist next pulses
+    next_assist: Option<Instant>,
+    /// When the floor's next ambient event is due
+    pub ambience: Scheduler,
+    /// A flicker or PSA on the board, and since when
+    pub ambient: Option<(Ambience, Instant)>,
+    /// A compliance check waiting to be retyped
+    pub compliance: Option<ComplianceCheck>,
+    /// When the next compliance check is due
+    next_audit: Option<Instant>,
+    /// The mouse button is down, so a click on sticky numbers can hold them
+    pub button_down: bool,
+    /// Space or a bin number was pressed to capture and the grid has not
+    /// answered yet
+    pub key_capture: Option<Instant>,
+    /// The bin number pressed, if that was the key
+    pub aimed_bin: Option<usize>,
     pub show_size_warning: bool,
-    pub current_width: u16,
-    pub current_height: u16,
+    pub error: Option<RecoverableError>,
+    /// The error screen's buttons, starting on ignore
+    pub error_buttons: Focus<ErrorAction>,
+    pub error_return_state: AppState,
+    pub pending_retry: Option<FailureKind>,
+    /// When the prize screen returns to a fresh file on its own, if the
+    /// auto-reset is enabled and nobody has cancelled it
+    pub prize_auto_reset_at: Option<Instant>,
+    pub supervisor_entry: String,
+    pub show_supervisor_error: bool,
+    /// Observer mode: the screen mirrors another session and input only
+    /// quits or toggles the HUD
+    pub read_only: bool,
+    pub session_link: Option<SessionLink>,
+    pub login_step: LoginStep,
+    /// The employee's save slots, while one is being chosen
+    pub slot_picker: Option<SlotPicker>,
+    /// Which of the employee's save slots this session is kept in
+    pub save_slot: usize,
+    /// The saved session found for the name entered at login
+    pub login_session: Option<Stored>,
+    pub passphrase: String,
+    pub passphrase_attempts: u32,
+    pub passphrase_error: Option<&'static str>,
+    /// Said on the login screen after an idle logout, until a key is typed
+    pub login_notice: Option<&'static str>,
+    /// Seals the session file on exit, once a passphrase has been given
+    pub save_key: Option<SealKey>,
+    /// The key for the passphrase just given, while it is worked out
+    pub key_derivation: Option<KeyDerivation>,
+    /// Text typed after `:` on the main screen, while the prompt is open
+    pub command_line: Option<String>,
+    /// Text typed into the cheat console, while it is open
+    pub cheat_console: Option<String>,
+    /// Watches the main screen for the code that opens the cheat console
+    pub konami: KeySequence,
+    /// Awarded instead of a random prize for the next completed file
+    pub forced_prize: Option<String>,
+    /// Lifetime experience, which decides the refiner's rank
+    pub xp: u64,
+    /// The week shown on the recap screen
+    pub recap: Option<Recap>,
+    /// Answers to the first-run questions, while they are being asked
+    pub setup: Option<Setup>,
+    pub toast: Option<Toast>,
+    /// The last combo and when it landed, while its banner is up
+    pub combo: Option<(Combo, Instant)>,
+    /// The file's completion as the header shows it, counting up
+    pub completion: Animated,
+    /// When the shown completion last passed a multiple of 25%
+    pub completion_pulse: Option<Instant>,
+    /// Quarters the shown completion had passed at the last tick
+    completion_quarters: u32,
+    /// Coach marks for a refiner's first file
+    pub onboarding: Onboarding,
+    /// The step reached on the Orientation file, while on it
+    pub orientation: Option<Step>,
+    /// Pace of the game's timers in percent, one of `GAME_SPEEDS`
+    pub game_speed: u32,
+    /// Seeds the file's grid of digits
+    pub grid_seed: u64,
+    /// Where every random draw comes from
+    pub random: RngService,
+    /// The file came from a share code, whose difficulty and grid size
+    /// hold until the next file
+    pub shared: Option<ShareCode>,
+    /// A share code from the command line, opened on reaching the main screen
+    pub pending_code: Option<ShareCode>,
+    /// When the file was started from a fresh board; `None` for a file
+    /// resumed from a save, which is not timed
+    pub file_started: Option<Instant>,
+    /// The race being run on this file, if any
+    pub race: Option<Race>,
+    /// A race from the command line, started on reaching the main screen
+    pub pending_race: Option<race::Target>,
+    /// Open while choosing what to reset
+    pub reset_menu: Option<ResetMenu>,
+    /// The menu a right click opened, while open
+    pub context_menu: Option<ContextMenu>,
+    /// The bin whose deposits are listed, while the inspection is open
+    pub inspecting: Option<usize>,
+    /// Where the numbers being deposited came from, for the deposit's
+    /// temper and log
+    deposit_origins: Vec<Provenance>,
+    /// Grid cells marked from the context menu, until refined or the
+    /// file is reset
+    pub marked: HashSet<(usize, usize)>,
+    /// Values captures, recomposed for each file
+    pub scoring: Scoring,
+    /// Captures in a row without a misclick
+    pub streak: u32,
+    /// Clicks on the grid that caught nothing since the last capture
+    pub misclicks: u32,
+    pub changelog_scroll: u16,
+    /// The file currently being refined
+    pub file_name: String,
+    /// Words of wisdom for the loading screen
+    pub quote: String,
+    /// A note under the prize, written by a rule script
+    pub prize_memo: Option<String>,
+    /// When the current break ends, by the wall clock so a suspended
+    /// machine does not stretch it
+    pub break_until: Option<SystemTime>,
+    /// Perk vouchers issued this session, printed on exit
+    pub vouchers: Vec<Voucher>,
+    /// The card for the file last completed, to copy from the prize screen
+    pub card: Option<CompletionCard>,
 }
 
 impl App {
     pub fn new(palette: Palette) -> Self {
+        Self::with_clock(palette, Rc::new(SystemClock))
+    }
+
+    /// Create an app driven by a specific clock (e.g. a `MockClock` in tests)
+    pub fn with_clock(palette: Palette, clock: Rc<dyn Clock>) -> Self {
+        Self::with_random(palette, clock, RngService::from_entropy())
+    }
+
+    /// Create an app whose random draws all come from `random`, so a run
+    /// on the same seed plays out the same
+    pub fn with_random(palette: Palette, clock: Rc<dyn Clock>, random: RngService) -> Self {
         // Initialize 5 data containers all at 0
         let mut containers = Vec::with_capacity(5);
-        for _ in 0..5 {
-            containers.push(DataContainer::new());
+        for slot in 0..5 {
+            containers.push(DataContainer { slot, ..DataContainer::new() });
         }
         
-        Self { 
+        let now = clock.now();
+
+        let mut app = Self { 
+            clock,
             palette, 
             running: true, 
+            station: Station::new(now),
             state: AppState::Login,   
             username: String::new(),
             username_cursor: 0,
             show_login_error: false,  
-            loading_timer: 0,
+            founder_visit: false,
+            next_loading_step: None,
             progress_percentage: 0.0,
-            completion_delay: 0,
-            completion_timer: 0,
+            loading_complete_at: None,
+            restore_steps: VecDeque::new(),
+            file_complete_at: None,
             prize_name: String::new(),
-            animation_counter: 0,
+            last_selfcheck: now,
+            last_autosave: now,
+            recovered: Vec::new(),
+            last_input: now,
             mouse_position: None,
             last_clicked: None,
             containers,
-            replaced_numbers: HashMap::new(),
-            window_size_warning: false,
+            bin_areas: Vec::new(),
+            bin_drag: None,
+            bin_focus: Focus::default(),
+            side_panel: None,
+            inbox: VecDeque::new(),
+            event_log: VecDeque::new(),
+            goals: Goals::default(),
+            journal: Journal::default(),
+            copy_screen: false,
+            grid: GridModel::default(),
+            scary_clusters: Vec::new(),
+            pending_grid: None,
+            grid_geometry: None,
+            resized_from: None,
+            grid_morph: None,
+            sticky: HashMap::new(),
+            hold: None,
+            staged: None,
+            round_robin: None,
+            reshuffles: 0,
+            assist_pulse: None,
+            next_assist: None,
+            ambience: Scheduler::default(),
+            ambient: None,
+            compliance: None,
+            next_audit: None,
+            button_down: false,
+            key_capture: None,
+            aimed_bin: None,
             show_size_warning: false,
-            current_width: 0,
-            current_height: 0,
-         }
+            error: None,
+            error_return_state: AppState::Login,
+            error_buttons: Focus::default(),
+            pending_retry: None,
+            prize_auto_reset_at: None,
+            supervisor_entry: String::new(),
+            show_supervisor_error: false,
+            read_only: false,
+            session_link: None,
+            login_step: LoginStep::Name,
+            slot_picker: None,
+            save_slot: 1,
+            login_session: None,
+            passphrase: String::new(),
+            passphrase_attempts: 0,
+            passphrase_error: None,
+            login_notice: None,
+            save_key: None,
+            key_derivation: None,
+            command_line: None,
+            cheat_console: None,
+            konami: KeySequence::new(KONAMI),
+            forced_prize: None,
+            xp: 0,
+            recap: None,
+            setup: None,
+            toast: None,
+            combo: None,
+            completion: Animated::new(0.0, COMPLETION_COUNT, Easing::EaseOut),
+            completion_pulse: None,
+            completion_quarters: 0,
+            onboarding: Onboarding::default(),
+            orientation: None,
+            game_speed: 100,
+            grid_seed: DEFAULT_GRID_SEED,
+            random,
+            shared: None,
+            pending_code: None,
+            file_started: None,
+            race: None,
+            pending_race: None,
+            reset_menu: None,
+            context_menu: None,
+            inspecting: None,
+            deposit_origins: Vec::new(),
+            marked: HashSet::new(),
+            scoring: Scoring::default(),
+            streak: 0,
+            misclicks: 0,
+            changelog_scroll: 0,
+            file_name: String::new(),
+            quote: String::new(),
+            prize_memo: None,
+            break_until: None,
+            vouchers: Vec::new(),
+            card: None,
+         };
+        app.scatter_sticky();
+        app.pick_flavour();
+        app
     }
 
-    pub fn on_key(&mut self, key: KeyCode) {
-        // If size warning is showing, dismiss it and process no further
-        if self.show_size_warning {
-            self.show_size_warning = false;
-            return;
+    /// Route a terminal event to the matching handler
+    pub fn update(&mut self, event: Event) {
+        if matches!(event, Event::Key(_) | Event::Mouse(_) | Event::Paste(_)) {
+            self.last_input = self.clock.now();
         }
 
-        match self.state {
-            AppState::Login => {
-                // Any input clears previous error
-                self.show_login_error = false;
-                
-                match key {
-                    KeyCode::Char(c) => {
-                        if self.username.len() < 25 { // Limit username length
-                            self.username.insert(self.username_cursor, c);
-                            self.username_cursor += 1;
-                        }
-                    },
-                    KeyCode::Backspace => {
-                        if self.username_cursor > 0 {
-                            self.username_cursor -= 1;
-                            self.username.remove(self.username_cursor);
-                        }
-                    },
-                    KeyCode::Delete => {
-                        if self.username_cursor < self.username.len() {
-                            self.username.remove(self.username_cursor);
-                        }
-                    },
-                    KeyCode::Left => {
-                        if self.username_cursor > 0 {
-                            self.username_cursor -= 1;
-                        }
-                    },
-                    KeyCode::Right => {
-                        if self.username_cursor < self.username.len() {
-                            self.username_cursor += 1;
-                        }
-                    },
-                    KeyCode::Enter => {
-                        if !self.username.trim().is_empty() {
-                            self.state = AppState::Loading;
-                        } else {
-                            // Set error flag if username is empty
-                            self.show_login_error = true;
-                        }
-                    },
-                    KeyCode::Esc => {
-                        self.running = false;
-                    },
-                    _ => {}
-                }
-            },
-            AppState::Prize => {
-                match key {
-                    KeyCode::Char('q') | KeyCode::Esc => {
-                        self.running = false;
-                    },
-                    KeyCode::Char('r') | KeyCode::Enter | KeyCode::Char(' ') => {
-                        // Reset all containers and go back to main screen
-                        self.reset_containers();
-                        self.state = AppState::Main;
-                    },
-                    _ => {}
-                }
-            },
-            _ => {
-                // Existing key handling
-                match key {
-                    KeyCode::Char('q') => {
-                        self.running = false;
-                    },
-                    // R key resets all containers
-                    KeyCode::Char('r') => self.reset_containers(),
-                    _ => {}
-                }
-            }
+        match event {
+            // Releases only end holds. Windows sends one after every press,
+            // which must not act a second time.
+            Event::Key(key) if key.kind == KeyEventKind::Release => self.on_key_release(key.code),
+            Event::Key(key) => self.on_key(key),
+            // Observers may look but not touch
+            Event::Mouse(_) | Event::Paste(_) if self.read_only => {},
+            Event::Mouse(_) if !self.station.config.input.mouse => {},
+            Event::Mouse(mouse) => self.on_mouse(mouse),
+            Event::Paste(text) => self.on_paste(&text),
+            Event::Resize(width, height) => self.on_resize(width, height),
+            Event::FocusGained => self.station.focused = true,
+            Event::FocusLost => self.station.focused = false,
         }
     }
-    
-    pub fn on_mouse(&mut self, event: MouseEvent) {
-        // Update current mouse position without affecting animation
-        self.mouse_position = Some((event.column, event.row));
-        
-        // Handle mouse clicks
-        match event.kind {
-            MouseEventKind::Down(_) => {
-                self.last_clicked = Some((event.column, event.row));
-                // Actual click processing is done in the UI rendering
-            }
-            _ => {}
+
+    // Check if window size matches desired size
+    pub fn on_resize(&mut self, width: u16, height: u16) {
+        use crate::input::{DESIRED_WIDTH, DESIRED_HEIGHT};
+        self.station.window_size_warning = width < DESIRED_WIDTH || height < DESIRED_HEIGHT;
+        self.station.current_width = width;
+        self.station.current_height = height;
+        if self.resized_from.is_none() {
+            self.resized_from = self.grid_geometry;
         }
     }
-    
-    // Replace a number at a specific position with a new random value
-    pub fn replace_number(&mut self, col: usize, row: usize) {
-        let mut rng = rng();
-        let new_digit = rng.random_range(0..=9);
-        self.replaced_numbers.insert((col, row), new_digit);
-    }
-    
-    // Replace multiple numbers at once
-    pub fn replace_numbers(&mut self, positions: Vec<(usize, usize)>) {
-        for (col, row) in positions {
-            self.replace_number(col, row);
+
+    // Pasted text only means something in the username field
+    pub fn on_paste(&mut self, text: &str) {
+        if self.state == AppState::Login && self.login_step == LoginStep::Passphrase {
+            self.passphrase_error = None;
+            self.passphrase.extend(text.chars().filter(|c| !c.is_control()));
+        } else if self.state == AppState::Login && self.login_step == LoginStep::Name {
+            self.show_login_error = false;
+            for c in text.chars() {
+                self.insert_username_char(c);
+            }
+        } else if let Some(setup) = &mut self.setup
+            && setup.step == SetupStep::Name
+        {
+            setup.name.extend(text.chars().filter(|c| !c.is_control()));
+            setup.name = setup.name.chars().take(MAX_USERNAME_LEN).collect();
         }
     }
-    
-    // Get a replaced number if it exists
-    pub fn get_replaced_number(&self, col: usize, row: usize) -> Option<u16> {
-        self.replaced_numbers.get(&(col, row)).copied()
+
+    // Byte offset of the username cursor, which counts characters
+    pub fn username_cursor_byte(&self) -> usize {
+        self.username
+            .char_indices()
+            .nth(self.username_cursor)
+            .map_or(self.username.len(), |(idx, _)| idx)
     }
-    
-    // Add a value to a specific container
-    pub fn add_to_container(&mut self, container_idx: usize, value: u16) {
-        if container_idx < self.containers.len() {
-            self.containers[container_idx].add(value);
-            // Reset the last click to avoid repeated processing
-            self.last_clicked = None;
+
+    pub fn insert_username_char(&mut self, c: char) {
+        if c.is_control() || self.username.chars().count() >= MAX_USERNAME_LEN {
+            return;
         }
+        let at = self.username_cursor_byte();
+        self.username.insert(at, c);
+        self.username_cursor += 1;
     }
-    
-    // Add a random value to a random container
-    pub fn add_random(&mut self) {
-        let mut rng = rng();
-        let container_idx = rng.random_range(0..self.containers.len());
-        let value = rng.random_range(1..=10);
-        
-        self.add_to_container(container_idx, value);
+
+    pub fn on_key(&mut self, event: KeyEvent) {
+        let key = event.code;
+        // Auto-repeat only drives what makes sense to hold down
+        if event.kind == KeyEventKind::Repeat && !self.repeats(key) {
+            return;
+        }
+        // Ctrl and Alt chords never type. AltGr arrives as both at once and
+        // is just a character.
+        let chord = event.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+        if !chord.is_empty() && chord != KeyModifiers::CONTROL | KeyModifiers::ALT {
+            self.on_chord(key, chord);
+            return;
+        }
+
+        // F12 toggles the debug overlay from any screen
+        if key == KeyCode::F(12) {
+            self.station.show_debug_hud = !self.station.show_debug_hud;
+            return;
+        }
+
+        // If size warning is showing, dismiss it and process no further
+        if self.show_size_warning {
+            self.show_size_warning = false;
+            return;
+        }
+
+        // The error screen still answers, so a broken link can be retried
+        if self.read_only && self.state != AppState::Error {
+            if matches!(key, KeyCode::Char('q') | KeyCode::Esc) {
+                self.running = false;
+            }
+            return;
+        }
+
+        with_screen!(self.state, screen => screen.handle_key(self, key));
     }
     
-    // Add a value to a random non-full container
-    pub fn add_to_random_non_full_container(&mut self, value: u16) {
-        // Find non-full containers
-        let non_full_indices: Vec<usize> = self.containers.iter()
-            .enumerate()
-            .filter(|(_, container)| !container.is_full())
-            .map(|(idx, _)| idx)
-            .collect();
-            
-        // If there are non-full containers, add to a random one
-        if !non_full_indices.is_empty() {
-            let mut rng = rng();
-            let idx = rng.random_range(0..non_full_indices.len());
-            let container_idx = non_full_indices[idx];
-            
-            self.add_to_container(container_idx, value);
+    // Look for saved sessions under the entered name. With none there is
+    // nothing to choose and the first slot starts; otherwise the slot
+    // picker asks which to continue.
+    pub fn submit_username(&mut self) {
+        // Whether sessions are sealed is the config's to say
+        self.wait_for_startup(Some(Task::Config));
+        self.founder_visit = self.username.trim().eq_ignore_ascii_case(FOUNDER_NAME);
+
+        let slots = self.station.save_dir.as_ref().map(|dir| slots::list(dir, &self.username)).unwrap_or_default();
+        if slots.is_empty() {
+            self.save_slot = 1;
+            self.open_slot();
+        } else {
+            self.slot_picker = Some(SlotPicker::new(slots));
+            self.login_step = LoginStep::Slot;
         }
-        
-        // Reset click regardless
-        self.last_clicked = None;
     }
 
-    pub fn tick(&mut self) {
-        // Increment animation counter at a steady rate
-        self.animation_counter = self.animation_counter.wrapping_add(1);
-        
-        match self.state {
-            AppState::Loading => {
-                self.loading_timer += 1;
-
-                if self.loading_timer >= 3 {
-                    self.loading_timer = 0;
-                    
-                    if self.progress_percentage >= 100.0 {
-                        self.progress_percentage = 100.0;
-                        self.completion_delay += 1;
-                        
-                        if self.completion_delay >= 2 {
-                            self.state = AppState::Main;
-                        }
-                    } else {
-                        let mut rng = rng();
-                        let progress_increment = rng.random_range(0.0..13.0);
-                        
-                        let new_progress = self.progress_percentage + progress_increment;
-                        if new_progress > 100.0 {
-                            self.progress_percentage = 100.0;
-                        } else {
-                            self.progress_percentage = new_progress;
-                        }
-                    }
+    pub fn on_slot_key(&mut self, key: KeyCode) {
+        let Some(picker) = &mut self.slot_picker else { return };
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => picker.select(-1),
+            KeyCode::Down | KeyCode::Char('j') => picker.select(1),
+            KeyCode::Enter => {
+                if let Some(slot) = picker.chosen() {
+                    self.save_slot = slot;
+                    self.slot_picker = None;
+                    self.login_step = LoginStep::Name;
+                    self.open_slot();
                 }
             },
-            AppState::Main => {
-                // Check if all containers are filled
-                if self.is_all_complete() {
-                    // Start completion timer
-                    self.completion_timer += 1;
-                    
-                    // After 3 seconds (9 ticks at 300ms per tick), transition to prize screen
-                    if self.completion_timer >= 9 {
-                        self.select_random_prize();
-                        self.state = AppState::Prize;
-                    }
+            KeyCode::Delete | KeyCode::Backspace if picker.highlighted().is_some() => {
+                if picker.confirm_delete {
+                    self.delete_slot();
                 } else {
-                    // Reset timer if containers are not full
-                    self.completion_timer = 0;
+                    picker.confirm_delete = true;
                 }
             },
-            _ => {}
+            KeyCode::Esc => self.restart_login(),
+            _ => picker.confirm_delete = false,
         }
     }
 
-    // Reset all containers to zero
-    pub fn reset_containers(&mut self) {
-        for container in &mut self.containers {
-            container.count = 0;
-            container.progress = 0.0;
+    // Discard the highlighted slot's file for good, after Delete twice
+    fn delete_slot(&mut self) {
+        let Some(picker) = &mut self.slot_picker else { return };
+        let Some(slot) = picker.highlighted().map(|slot| slot.number) else { return };
+        let Some(dir) = &self.station.save_dir else { return };
+        picker.confirm_delete = false;
+
+        let path = save::slot_path(dir, &self.username, slot);
+        if let Err(err) = std::fs::remove_file(&path) {
+            let detail = format!("{}: {}", path.display(), err);
+            self.report_error(RecoverableError::new(FailureKind::SaveFile, detail));
+            return;
         }
+        picker.slots = slots::list(dir, &self.username);
+        picker.selected = picker.selected.min(picker.rows() - 1);
     }
 
-    // Check if all containers are 100% full
-    pub fn is_all_complete(&self) -> bool {
-        self.containers.iter().all(|container| container.is_full())
-    }
+    // Read the chosen slot. Sealed sessions, and every session once
+    // encryption is on, need a passphrase first.
+    fn open_slot(&mut self) {
+        let Some(path) = self.session_path() else {
+            self.go_to(AppState::Loading);
+            return;
+        };
+
+        match save::read_stored(&path) {
+            Ok(stored) if self.station.config.save.encrypt || matches!(stored, Some(Stored::Sealed(_))) => {
+                self.login_session = stored;
+                self.login_step = LoginStep::Passphrase;
+            },
+            Ok(Some(Stored::Plain(save))) => self.restore(&save),
+            Ok(_) => {
+                self.go_to(AppState::Loading);
+            },
+            Err(err) => {
+                // Start afresh, but say why the old session is gone
+                self.go_to(AppState::Loading);
+                self.report_error(RecoverableError::new(FailureKind::SaveFile, err.to_string()));
+            },
+        }
+    }
+
+    /// Start working out the key for the passphrase typed. It takes a
+    /// moment, so it is done on a worker and picked up by
+    /// `poll_key_derivation`.
+    pub fn submit_passphrase(&mut self) {
+        if self.key_derivation.is_some() {
+            return;
+        }
+        if self.passphrase.is_empty() {
+            self.passphrase_error = Some("Passphrase cannot be empty");
+            return;
+        }
+        let passphrase = std::mem::take(&mut self.passphrase);
+        let (salt, iterations) = match &self.login_session {
+            Some(Stored::Sealed(sealed)) => (sealed.salt, sealed.iterations),
+            _ => (crypto::random_salt(), self.station.config.save.kdf_iterations),
+        };
+        self.key_derivation = Some(KeyDerivation::start(passphrase, salt, iterations, self.clock.now()));
+    }
+
+    /// Once the passphrase's key is in, unseal the saved session with it,
+    /// or keep it to seal the session from now on
+    pub fn poll_key_derivation(&mut self) {
+        let Some(key) = self.key_derivation.as_ref().and_then(KeyDerivation::poll) else { return };
+        self.key_derivation = None;
+
+        match self.login_session.take() {
+            Some(Stored::Sealed(sealed)) => {
+                match sealed.open(&key) {
+                    Ok(save) => {
+                        self.save_key = Some(key);
+                        self.restore(&save);
+                    },
+                    Err(_) => {
+                        self.passphrase_attempts += 1;
+                        if self.passphrase_attempts >= MAX_PASSPHRASE_ATTEMPTS {
+                            self.restart_login();
+                            self.passphrase_error = Some("Too many attempts. This incident will be reported.");
+                        } else {
+                            self.login_session = Some(Stored::Sealed(sealed));
+                            self.passphrase_error = Some("Passphrase not recognised. Please try again.");
+                        }
+                    },
+                }
+            },
+            Some(Stored::Plain(save)) => {
+                // Seal a session saved before encryption was turned on,
+                // right away rather than leaving it readable until exit
+                self.save_key = Some(key);
+                self.restore(&save);
+                if let Err(err) = self.store_session() {
+                    self.report_error(RecoverableError::new(FailureKind::SaveFile, err.to_string()));
+                }
+            },
+            None => {
+                self.save_key = Some(key);
+                self.go_to(AppState::Loading);
+            },
+        }
+    }
+
+    /// Ask the first-run questions when no config file has been written
+    /// yet. Called once at launch, before anything is shown.
+    pub fn offer_setup(&mut self) {
+        let first_run = self.station.config_path.as_ref().is_some_and(|path| !path.exists());
+        if first_run && !self.read_only && self.state == AppState::Login {
+            self.setup = Some(Setup::default());
+            self.go_to(AppState::Setup);
+        }
+    }
+
+    pub fn on_setup_key(&mut self, key: KeyCode) {
+        let themes: Vec<String> = self.station.content.themes.iter().map(|theme| theme.name.clone()).collect();
+        let themes: Vec<&str> = themes.iter().map(String::as_str).collect();
+        let Some(setup) = &mut self.setup else { return };
+        let rows = setup.choices(&themes).len();
+
+        let mut finished = false;
+        match (setup.step, key) {
+            (SetupStep::Name, KeyCode::Char(c)) if setup.name.chars().count() < MAX_USERNAME_LEN => setup.name.push(c),
+            (SetupStep::Name, KeyCode::Backspace) => {
+                setup.name.pop();
+            },
+            (_, KeyCode::Up) => setup.select(-1, rows),
+            (_, KeyCode::Down) => setup.select(1, rows),
+            (_, KeyCode::Enter) => finished = !setup.advance(&themes),
+            // Back a question; leaving the first one quits, like the login
+            (_, KeyCode::Esc) => self.running = setup.back(&themes),
+            _ => {},
+        }
+        if finished {
+            self.finish_setup();
+        } else {
+            self.preview_setup_theme();
+        }
+    }
+
+    // Show the highlighted theme while it is being chosen
+    fn preview_setup_theme(&mut self) {
+        let Some(setup) = &self.setup else { return };
+        // Black and white was asked for, whatever the theme
+        if setup.step != SetupStep::Theme || self.is_mono() {
+            return;
+        }
+        let theme = setup.selected.checked_sub(1).and_then(|idx| self.station.content.themes.get(idx));
+        match theme {
+            Some(theme) => self.palette = Palette::Custom { fg: theme.fg, bg: theme.bg },
+            None if matches!(self.palette, Palette::Custom { .. }) => self.palette = crate::theme::detect(),
+            None => {},
+        }
+    }
+
+    // Write the answers as the config file, take them up and go on to the
+    // login. Should the file not be written, they still hold for this launch.
+    fn finish_setup(&mut self) {
+        let Some(setup) = self.setup.take() else { return };
+        self.go_to(AppState::Login);
+        self.station.config.theme = setup.theme.clone();
+        self.station.config.input.mouse = setup.mouse;
+        self.station.config.difficulty = setup.difficulty;
+        self.station.config.login_name = Some(setup.name.trim().to_string()).filter(|name| !name.is_empty());
+        self.use_theme();
+        self.prefill_username();
+
+        let Some(path) = &self.station.config_path else { return };
+        let written = path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|()| std::fs::write(path, setup.to_toml()));
+        if let Err(err) = written {
+            let detail = format!("the setup could not be saved to {}: {}", path.display(), err);
+            self.report_error(RecoverableError::new(FailureKind::Config, detail));
+        }
+    }
+
+    // Start the login with the name from the config, unless one is typed
+    fn prefill_username(&mut self) {
+        if let Some(name) = &self.station.config.login_name
+            && self.state == AppState::Login
+            && self.login_step == LoginStep::Name
+            && self.username.is_empty()
+        {
+            self.username = name.chars().take(MAX_USERNAME_LEN).collect();
+            self.username_cursor = self.username.chars().count();
+        }
+    }
+
+    // Forget the passphrase step and ask for the name again
+    pub fn restart_login(&mut self) {
+        self.login_step = LoginStep::Name;
+        self.slot_picker = None;
+        self.login_session = None;
+        self.passphrase.clear();
+        self.passphrase_attempts = 0;
+        self.key_derivation = None;
+    }
+
+    // Put a saved session back and walk through the loading screen to it.
+    // The save itself was read at login, so the bar starts a step in.
+    fn restore(&mut self, save: &SaveState) {
+        // Whatever screen it was saved on, a session is reached through
+        // loading like any other
+        save.apply(self);
+        self.login_step = LoginStep::Name;
+        self.go_to(AppState::Loading);
+        self.restore_steps = RestoreStep::ALL[1..].iter().copied().collect();
+        self.progress_percentage = self.restore_progress();
+        self.next_loading_step = None;
+        self.loading_complete_at = None;
+    }
+
+    // How much of the restore is done, in percent
+    fn restore_progress(&self) -> f32 {
+        let total = RestoreStep::ALL.len();
+        (total - self.restore_steps.len()) as f32 / total as f32 * 100.0
+    }
+
+    // Run the next restore step, if one is left
+    pub fn run_restore_step(&mut self) {
+        let Some(step) = self.restore_steps.pop_front() else { return };
+        match step {
+            RestoreStep::ReadSave => {},
+            RestoreStep::CheckLedger => {
+                // The ledger in memory is kept; it may hold this launch's recap
+                if let Some(path) = &self.station.ledger_path
+                    && let Err(err) = Ledger::load(path)
+                {
+                    self.report_error(RecoverableError::new(FailureKind::SaveFile, err.to_string()));
+                }
+            },
+            RestoreStep::RebuildGrid => {
+                self.grid.clear();
+                self.marked.clear();
+                self.staged = None;
+                self.assist_pulse = None;
+                self.last_clicked = None;
+                self.scatter_sticky();
+            },
+        }
+        self.progress_percentage = self.restore_progress();
+    }
+
+    /// Finish loading now: run whatever restore work is left and go
+    /// straight to the main screen without the theatrical wait
+    pub fn skip_loading(&mut self) {
+        self.wait_for_startup(None);
+        while !self.restore_steps.is_empty() {
+            self.run_restore_step();
+        }
+        self.progress_percentage = 100.0;
+        self.next_loading_step = None;
+        self.loading_complete_at = None;
+        // A damaged ledger found on the way still gets its error screen
+        if self.state == AppState::Loading {
+            self.go_to(AppState::Main);
+        } else if self.state == AppState::Error {
+            self.error_return_state = AppState::Main;
+        }
+    }
+
+    /// The session file for the logged-in refiner's slot, if sessions are kept
+    pub fn session_path(&self) -> Option<PathBuf> {
+        if self.username.trim().is_empty() {
+            return None;
+        }
+        Some(save::slot_path(self.station.save_dir.as_ref()?, &self.username, self.save_slot))
+    }
+
+    /// Write the session to disk, sealed if a passphrase was given, ahead
+    /// of any background save still waiting. Does nothing for observers or
+    /// before anyone has logged in.
+    pub fn store_session(&self) -> crate::error::Result<()> {
+        let Some((path, contents)) = self.session_contents()? else { return Ok(()) };
+        match &self.station.autosave {
+            Some(autosave) => autosave.write_now(&path, &contents),
+            None => autosave::write_journaled(&path, &contents),
+        }
+    }
+
+    // The session file and what goes in it, with its folder made ready
+    fn session_contents(&self) -> crate::error::Result<Option<(PathBuf, String)>> {
+        let Some(path) = self.session_path() else { return Ok(None) };
+        let save = SaveState::capture(self);
+        if self.read_only || matches!(save.state, AppState::Login | AppState::Setup) {
+            return Ok(None);
+        }
+
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let contents = match &self.save_key {
+            Some(key) => Sealed::seal(&save, key).to_string(),
+            None => save.to_string(),
+        };
+        Ok(Some((path, contents)))
+    }
+
+    /// Hand the session to the background writer every
+    /// `AUTOSAVE_INTERVAL`, and report any save it could not make
+    fn check_autosave(&mut self, now: Instant) {
+        let Some(autosave) = &self.station.autosave else { return };
+        let failures = autosave.poll();
+        if now.duration_since(self.last_autosave) >= AUTOSAVE_INTERVAL {
+            self.last_autosave = now;
+            match self.session_contents() {
+                Ok(Some((path, contents))) => autosave.queue(path, contents),
+                Ok(None) => {},
+                Err(err) => self.report_error(RecoverableError::new(FailureKind::SaveFile, err.to_string())),
+            }
+        }
+        if let Some(err) = failures.into_iter().last() {
+            self.report_error(RecoverableError::new(FailureKind::SaveFile, err.to_string()));
+        }
+    }
+
+    /// Replay or discard the saves a crash left half written, before any
+    /// session is read
+    pub fn recover_sessions(&mut self) {
+        if let Some(dir) = &self.station.save_dir {
+            self.recovered = autosave::recover(dir);
+        }
+    }
+
+    // Whether the refiner has been away long enough to be logged out.
+    // Only a session in progress is left; the error screen waits to be read.
+    fn idle_logout_due(&self) -> bool {
+        let signed_in = matches!(
+            self.state,
+            AppState::Main | AppState::Prize | AppState::Report | AppState::Changelog | AppState::Locked | AppState::Break
+        );
+        signed_in && self.station.config.idle_logout.is_some_and(|after| self.since_input() >= after)
+    }
+
+    /// Save the session and go back to the login screen, as after a long
+    /// idle spell. Everything the refiner had open goes with them, holds
+    /// and animations included; the terminal's own settings, content and
+    /// ledger stay. Should the save fail, the session stays open and the
+    /// error screen says why.
+    pub fn log_out(&mut self) {
+        if let Err(err) = self.store_session().and(self.store_ledger()) {
+            self.report_error(RecoverableError::new(FailureKind::SaveFile, err.to_string()));
+            return;
+        }
+
+        let fresh = App::with_clock(self.palette, Rc::clone(&self.clock));
+        self.station = std::mem::replace(self, fresh).station;
+
+        self.pick_flavour();
+        self.prefill_username();
+        self.login_notice = Some("Your innie has gone home. Log in to pick up where you left off.");
+    }
+
+    // Commands typed at the `:` prompt
+    pub fn run_command(&mut self, command: &str) {
+        match command.trim() {
+            "" => {},
+            "changelog" => {
+                self.go_to(AppState::Changelog);
+            },
+            "orientation" => {
+                orientation::start(self);
+                self.show_toast("Orientation file opened", Some(TOAST_DURATION));
+            },
+            other if other.starts_with("goal ") => self.run_goal_command(other["goal ".len()..].trim()),
+            "copy report" => self.copy_report(),
+            "copy seed" => self.copy_text("Seed", &self.grid_seed.to_string()),
+            "copy code" => self.copy_text("Share code", &self.share_code().to_string()),
+            "copy screen" => self.copy_screen = true,
+            "copy card" => self.copy_card(),
+            "copy" => self.show_toast(
+                "Usage: :copy report, :copy code, :copy seed, :copy screen or :copy card",
+                Some(TOAST_DURATION),
+            ),
+            other if other.starts_with("code ") => match other["code ".len()..].parse() {
+                Ok(code) => self.open_shared(code),
+                Err(MdrError::Config(msg)) => self.show_toast(msg, Some(TOAST_DURATION)),
+                Err(err) => self.show_toast(err.to_string(), Some(TOAST_DURATION)),
+            },
+            "race" => self.show_toast(
+                "Usage: :race <time>, :race pb, :race <code> <time> or :race off",
+                Some(TOAST_DURATION),
+            ),
+            "race off" => {
+                if self.race.take().is_some() {
+                    self.show_toast("Race called off", Some(TOAST_DURATION));
+                }
+            },
+            other if other.starts_with("race ") => self.run_race_command(other["race ".len()..].trim()),
+            "bins" => self.show_toast(
+                format!("Bins: {}. Usage: :bins random, least-full, round-robin or temper", self.station.config.bin_strategy.name()),
+                Some(TOAST_DURATION),
+            ),
+            other if other.starts_with("bins ") => match other["bins ".len()..].parse::<BinStrategy>() {
+                Ok(strategy) => {
+                    self.station.config.bin_strategy = strategy;
+                    self.round_robin = None;
+                    self.show_toast(format!("Bins: {}", strategy.name()), Some(TOAST_DURATION));
+                },
+                Err(MdrError::Config(msg)) => self.show_toast(msg, Some(TOAST_DURATION)),
+                Err(err) => self.show_toast(err.to_string(), Some(TOAST_DURATION)),
+            },
+            "reshuffle" => {
+                let mut menu = ResetMenu::default();
+                menu.confirm(ResetScope::Reshuffle);
+                self.reset_menu = Some(menu);
+            },
+            "selfcheck" => self.run_selfcheck(true),
+            "note" => self.show_toast("Usage: :note <text>", Some(TOAST_DURATION)),
+            other if other.starts_with("note ") => {
+                let text = &other["note ".len()..];
+                if self.journal.add(self.clock.wall(), text, self.station.config.budget.notes) {
+                    self.show_toast("Noted in the journal", Some(TOAST_DURATION));
+                }
+            },
+            other => self.show_toast(format!("Unknown command: {}", other), Some(TOAST_DURATION)),
+        }
+    }
+
+    // `:race <time>` or `:race pb` restarts this board against the clock;
+    // `:race <code> <time>` opens a friend's board first
+    fn run_race_command(&mut self, args: &str) {
+        let parsed = match args.split_once(' ') {
+            Some((code, target)) => code.parse().and_then(|code| Ok((code, target.trim().parse()?))),
+            None => args.parse().map(|target| (self.share_code(), target)),
+        };
+        match parsed {
+            Ok((code, target)) => self.start_race(code, target),
+            Err(MdrError::Config(msg)) => self.show_toast(msg, Some(TOAST_DURATION)),
+            Err(err) => self.show_toast(err.to_string(), Some(TOAST_DURATION)),
+        }
+    }
+
+    // `:goal add <text>`, `:goal done <n>` or `:goal clear`
+    fn run_goal_command(&mut self, args: &str) {
+        let (verb, rest) = args.split_once(' ').unwrap_or((args, ""));
+        let text = match verb {
+            "add" if self.add_goal(rest) => format!("Goal added: {}", rest.trim().trim_matches('"')),
+            "add" => "That goal is already on the list".to_string(),
+            "done" => {
+                let goal = rest.trim().parse::<usize>().ok().and_then(|n| self.goals.items.get_mut(n.wrapping_sub(1)));
+                match goal {
+                    Some(goal) => {
+                        goal.done = true;
+                        format!("Goal met: {}", goal.text)
+                    },
+                    None => format!("No goal number '{}'", rest.trim()),
+                }
+            },
+            "clear" => {
+                self.goals.items.clear();
+                "Goals cleared".to_string()
+            },
+            _ => "Usage: :goal add <text>, :goal done <n>, :goal clear".to_string(),
+        };
+        self.show_toast(text, Some(TOAST_DURATION));
+    }
+
+    /// Add a session goal, ticking it straight away if the bin it names
+    /// is already full
+    pub fn add_goal(&mut self, text: &str) -> bool {
+        if !self.goals.add(text) {
+            return false;
+        }
+        let goal = self.goals.items.last_mut().unwrap();
+        if let Target::BinFull(bin) = goal.target {
+            goal.done = self.containers.get(bin.wrapping_sub(1)).is_some_and(|c| c.is_full());
+        }
+        true
+    }
+
+    /// Tick goals off against this frame's events
+    pub fn check_goals(&mut self) {
+        let had_all = self.goals.all_met();
+        let mut met = Vec::new();
+        for event in &self.station.outbox {
+            met.extend(self.goals.observe(event));
+        }
+        if !had_all && self.goals.all_met() {
+            self.show_toast("Every session goal met. The Board is pleased.", Some(TOAST_DURATION));
+        } else if let Some(goal) = met.last() {
+            self.show_toast(format!("Goal met: {}", goal), Some(TOAST_DURATION));
+        }
+    }
+
+    /// Tick off the lessons this frame's events taught
+    pub fn check_lessons(&mut self) {
+        let taught: Vec<Lesson> = self
+            .station
+            .outbox
+            .iter()
+            .filter_map(|event| match event {
+                AppEvent::NumbersRefined { .. } => Some(Lesson::Refine),
+                AppEvent::FileCompleted { .. } => Some(Lesson::FillBins),
+                AppEvent::BinFilled { .. } => None,
+            })
+            .collect();
+        for lesson in taught {
+            self.learn(lesson);
+        }
+    }
+
+    /// The refiner has done `lesson`, so it is not coached again
+    pub fn learn(&mut self, lesson: Lesson) {
+        if !self.read_only {
+            self.station.ledger.learned.insert(lesson);
+        }
+    }
+
+    // Bring up a coach mark once a refiner on their first file pauses, and
+    // take it down when they carry on
+    pub fn coach(&mut self) {
+        // Orientation gives its own instructions
+        if self.orientation.is_some() {
+            return;
+        }
+        let first_file = !self.read_only && self.station.ledger.days.values().all(|day| day.files == 0);
+        let next = Lesson::next(&self.station.ledger.learned);
+        self.onboarding.step(self.since_input(), next, first_file);
+    }
+
+    /// The watchdog caught a frame that took `render_time` to draw and
+    /// dropped to `quality`; say so, and keep it in the inbox
+    pub fn on_stuck_frame(&mut self, render_time: Duration, quality: Quality) {
+        let text = format!("A frame took {}ms to draw. {} to keep up.", render_time.as_millis(), quality.describe());
+        self.show_toast(text, Some(TOAST_DURATION));
+    }
+
+    /// How full each capped collection is
+    pub fn budget_usage(&self) -> Vec<Usage> {
+        let budget = &self.station.config.budget;
+        let deposits = self.containers.iter().map(|container| container.deposits.len()).max().unwrap_or(0);
+        vec![
+            Usage { name: "cells", used: self.grid.len(), cap: budget.replaced_numbers },
+            Usage { name: "events", used: self.event_log.len(), cap: budget.event_log },
+            Usage { name: "inbox", used: self.inbox.len(), cap: budget.inbox },
+            Usage { name: "deposit", used: deposits, cap: budget.deposits },
+            Usage { name: "people", used: self.station.stats.refiners(), cap: budget.refiners },
+            Usage { name: "notes", used: self.journal.len(), cap: budget.notes },
+        ]
+    }
+
+    /// Check the session's invariants. Anything amiss is listed in the inbox
+    /// and shown on the error screen; a clean check is only announced when
+    /// `asked` for.
+    pub fn run_selfcheck(&mut self, asked: bool) {
+        self.last_selfcheck = self.clock.now();
+        let report = selfcheck::run(self);
+        if report.is_clean() {
+            if asked {
+                self.show_toast(format!("Self-check passed: {} invariants hold", report.checked), Some(TOAST_DURATION));
+            }
+            return;
+        }
+        for discrepancy in &report.discrepancies {
+            budget::push_capped(&mut self.inbox, format!("Self-check: {}", discrepancy), self.station.config.budget.inbox);
+        }
+        let details: Vec<String> = report.discrepancies.iter().map(ToString::to_string).collect();
+        self.report_error(RecoverableError::new(FailureKind::Integrity, details.join("; ")));
+    }
+
+    /// Put a notice on the main screen, replacing any current one
+    pub fn show_toast(&mut self, text: impl Into<String>, lasts: Option<Duration>) {
+        let expires = lasts.map(|lasts| self.clock.now() + lasts);
+        let text = text.into();
+        budget::push_capped(&mut self.inbox, text.clone(), self.station.config.budget.inbox);
+        self.toast = Some(Toast { text, expires });
+    }
+
+    /// Take in the release feed once it has arrived, announcing a newer
+    /// release. A failed check is not worth interrupting anyone over.
+    pub fn poll_updates(&mut self) {
+        let Some(result) = self.station.update_check.as_ref().and_then(|check| check.poll()) else { return };
+        self.station.update_check = None;
+
+        let releases = match result {
+            Ok(text) => updates::parse_changelog(&text),
+            Err(_) => return,
+        };
+        if let Some(release) = updates::newer_release(&releases) {
+            let text = format!("Version {} is available. Type :changelog to read more, Esc to dismiss.", release.version);
+            self.show_toast(text, None);
+        }
+        if !releases.is_empty() {
+            self.station.changelog = releases;
+        }
+    }
+
+    pub fn on_mouse(&mut self, event: MouseEvent) {
+        // Update current mouse position without affecting animation
+        self.mouse_position = Some((event.column, event.row));
+        with_screen!(self.state, screen => screen.handle_mouse(self, event));
+    }
+
+    // The other button opens a menu of what can be done to the bin or
+    // grid cell under it; anywhere else it closes the menu
+    pub fn open_context_menu(&mut self, column: u16, row: u16) {
+        self.context_menu = None;
+        if self.state != AppState::Main || self.read_only || self.reset_menu.is_some() {
+            return;
+        }
+        let bin = self.slot_at(column, row).and_then(|slot| self.bin_in_slot(slot));
+        let cell = self.grid_geometry.and_then(|geometry| geometry.cell_at(column, row));
+        let (target, items) = match (bin, cell) {
+            (Some(bin), _) => (MenuTarget::Bin(bin), vec![MenuItem::Deposit, MenuItem::Inspect, MenuItem::BinDetails]),
+            (None, Some(cell)) => {
+                let mark = if self.marked.contains(&cell) { MenuItem::UnmarkCluster } else { MenuItem::MarkCluster };
+                (MenuTarget::Cell(cell), vec![MenuItem::Refine, mark])
+            },
+            (None, None) => return,
+        };
+        self.context_menu = Some(ContextMenu::new((column, row), target, items));
+    }
+
+    pub fn on_context_menu_key(&mut self, key: KeyCode) {
+        let Some(menu) = &mut self.context_menu else { return };
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => menu.select(-1),
+            KeyCode::Down | KeyCode::Char('j') => menu.select(1),
+            KeyCode::Enter => self.choose_from_context_menu(),
+            KeyCode::Esc | KeyCode::Char('q') => self.context_menu = None,
+            _ => {},
+        }
+    }
+
+    // Do what the highlighted item says, closing the menu
+    pub fn choose_from_context_menu(&mut self) {
+        let Some(menu) = self.context_menu.take() else { return };
+        let Some(item) = menu.chosen() else { return };
+        match (item, menu.target) {
+            (MenuItem::Deposit, MenuTarget::Bin(bin)) => self.add_to_container(bin, 3),
+            (MenuItem::Inspect, MenuTarget::Bin(bin)) => self.inspecting = Some(bin),
+            (MenuItem::BinDetails, MenuTarget::Bin(bin)) => {
+                self.focus_bin(Some(bin));
+                self.side_panel = Some(SidePanel::Bin);
+            },
+            // As if clicked, for the main screen to pick up
+            (MenuItem::Refine, MenuTarget::Cell(_)) => self.last_clicked = Some(menu.anchor),
+            (MenuItem::MarkCluster, MenuTarget::Cell((col, row))) => {
+                let (cols, rows) = self.grid.size().unwrap_or_default();
+                for c in col.saturating_sub(1)..=(col + 1).min(cols.saturating_sub(1)) {
+                    for r in row.saturating_sub(1)..=(row + 1).min(rows.saturating_sub(1)) {
+                        self.marked.insert((c, r));
+                    }
+                }
+            },
+            (MenuItem::UnmarkCluster, MenuTarget::Cell((col, row))) => {
+                self.marked.retain(|&(c, r)| c.abs_diff(col) > 1 || r.abs_diff(row) > 1);
+            },
+            _ => {},
+        }
+    }
+
+    // Keys worth acting on again while held: text entry, scrolling and
+    // hold-to-act keys
+    fn repeats(&self, key: KeyCode) -> bool {
+        let typing = self.state == AppState::Login
+            || self.setup.as_ref().is_some_and(|setup| setup.step == SetupStep::Name)
+            || self.command_line.is_some()
+            || self.cheat_console.is_some();
+        match key {
+            KeyCode::Char(' ') => true,
+            KeyCode::Char('1'..='9') if self.hold.is_some() => true,
+            KeyCode::Char('y') | KeyCode::Char('Y') if self.reset_menu.is_some() => true,
+            KeyCode::Char(_) | KeyCode::Backspace | KeyCode::Delete | KeyCode::Left | KeyCode::Right => typing,
+            KeyCode::Up | KeyCode::Down | KeyCode::PageUp | KeyCode::PageDown => true,
+            _ => false,
+        }
+    }
+
+    /// Ctrl+C quits from anywhere, since raw mode keeps it from the shell.
+    /// Alt+1 to Alt+5 focus a bin by number.
+    fn on_chord(&mut self, key: KeyCode, chord: KeyModifiers) {
+        match key {
+            KeyCode::Char('c') if chord == KeyModifiers::CONTROL => self.running = false,
+            KeyCode::Char(c @ '1'..='9') if chord == KeyModifiers::ALT && self.state == AppState::Main => {
+                let bin = c as usize - '1' as usize;
+                if bin < self.containers.len() && self.reset_menu.is_none() {
+                    self.focus_bin(Some(bin));
+                    self.side_panel = Some(SidePanel::Bin);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    pub fn on_reset_menu_key(&mut self, key: KeyCode) {
+        let hold_to_confirm = self.station.config.reset_confirm == ConfirmStyle::Hold;
+        let now = self.clock.now();
+        let Some(menu) = &mut self.reset_menu else { return };
+        if let Some(scope) = menu.confirming {
+            // Tab or the arrows pick a button, and Enter presses it
+            let key = match key {
+                KeyCode::Left => KeyCode::BackTab,
+                KeyCode::Right => KeyCode::Tab,
+                key => key,
+            };
+            let key = match menu.answer.route(key) {
+                Routed::Moved(_) => return,
+                Routed::To(Answer::No, KeyCode::Enter) => KeyCode::Char('n'),
+                _ => key,
+            };
+            match key {
+                // Held down, Y repeats; the tick confirms once it has been long enough
+                KeyCode::Char('y') | KeyCode::Char('Y') if hold_to_confirm => match &mut menu.held {
+                    Some(held) => held.seen = now,
+                    None => menu.held = Some(KeyHold::new(now)),
+                },
+                KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter if !hold_to_confirm => {
+                    self.reset_menu = None;
+                    self.reset(scope);
+                },
+                // Asked for from the command line, so there is no menu to go back to
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc if !ResetScope::ALL.contains(&scope) => {
+                    self.reset_menu = None;
+                },
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    menu.confirming = None;
+                    menu.answer = Focus::default();
+                    menu.held = None;
+                },
+                _ => {},
+            }
+            return;
+        }
+
+        let count = ResetScope::ALL.len();
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => menu.selected = (menu.selected + count - 1) % count,
+            KeyCode::Down | KeyCode::Char('j') => menu.selected = (menu.selected + 1) % count,
+            KeyCode::Char(c @ '1'..='4') => {
+                menu.selected = c as usize - '1' as usize;
+                menu.confirm(ResetScope::ALL[menu.selected]);
+            },
+            KeyCode::Enter => menu.confirm(ResetScope::ALL[menu.selected]),
+            KeyCode::Esc | KeyCode::Char('r') | KeyCode::Char('q') => self.reset_menu = None,
+            _ => {},
+        }
+    }
+
+    /// Let go of whatever a key was holding down. Only terminals that
+    /// report key releases send these; elsewhere holds end when the key's
+    /// auto-repeat stops.
+    fn on_key_release(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                if let Some(menu) = &mut self.reset_menu {
+                    menu.held = None;
+                }
+            },
+            KeyCode::Char(' ' | '1'..='9') if self.hold.as_ref().is_some_and(|hold| hold.key_seen.is_some()) => {
+                self.hold = None;
+            },
+            _ => {},
+        }
+    }
+
+    // Confirm a reset once Y has been held long enough, or drop the hold
+    // if the key was let go
+    pub fn check_reset_hold(&mut self, now: Instant) {
+        let Some(menu) = &mut self.reset_menu else { return };
+        let (Some(held), Some(scope)) = (menu.held, menu.confirming) else { return };
+        if held.progress(now, HOLD_TO_CONFIRM) >= 1.0 {
+            self.reset_menu = None;
+            self.reset(scope);
+        } else if now.duration_since(held.seen) > KEY_REPEAT_GAP {
+            menu.held = None;
+        }
+    }
+
+    /// Wipe what `scope` covers and say what was done
+    pub fn reset(&mut self, scope: ResetScope) {
+        match scope {
+            ResetScope::Bin => {
+                let Some(bin) = self.focused_bin() else {
+                    self.show_toast("Focus a bin with Tab first", Some(TOAST_DURATION));
+                    return;
+                };
+                self.containers[bin].empty();
+                self.show_toast(format!("Bin {} emptied", bin + 1), Some(TOAST_DURATION));
+            },
+            ResetScope::AllBins => {
+                for container in &mut self.containers {
+                    container.empty();
+                }
+                self.show_toast("Every bin emptied", Some(TOAST_DURATION));
+            },
+            ResetScope::File => {
+                self.new_file();
+                self.show_toast(format!("New file: {}", self.file_name), Some(TOAST_DURATION));
+            },
+            ResetScope::Reshuffle => self.reshuffle(),
+            ResetScope::Factory => {
+                let result = self.factory_reset();
+                self.new_file();
+                match result {
+                    Ok(()) => self.show_toast("Factory reset: ledger erased, config set aside", Some(TOAST_DURATION)),
+                    Err(err) => self.report_error(RecoverableError::new(FailureKind::SaveFile, err.to_string())),
+                }
+            },
+        }
+    }
+
+    /// Draw a new grid for this file when the refiner feels stuck. Each bin
+    /// not yet full gives up `RESHUFFLE_PENALTY`; the rest of the file, bar
+    /// animations included, carries on as it was.
+    pub fn reshuffle(&mut self) {
+        if self.shared.is_some() || self.race.is_some() {
+            self.show_toast("A shared board keeps its grid", Some(TOAST_DURATION));
+            return;
+        }
+        self.grid_seed = self.random.stream(Stream::Grid).random_range(0..1 << 24);
+        self.grid.clear();
+        self.pending_grid = None;
+        self.marked.clear();
+        self.staged = None;
+        self.hold = None;
+        self.assist_pulse = None;
+        self.last_clicked = None;
+        self.scatter_sticky();
+
+        let mut taken = 0;
+        for container in self.containers.iter_mut().filter(|container| !container.is_full()) {
+            taken += container.take(RESHUFFLE_PENALTY);
+        }
+        self.reshuffles += 1;
+        self.show_toast(format!("Grid reshuffled; the bins gave up {}%", taken), Some(TOAST_DURATION));
+    }
+
+    /// Start over on the file a share code describes
+    pub fn open_shared(&mut self, code: ShareCode) {
+        self.new_file();
+        self.grid_seed = code.seed;
+        self.shared = Some(code);
+        self.compose_scoring();
+        self.show_toast(format!("Shared file opened: {}", code), Some(TOAST_DURATION));
+    }
+
+    /// How forgiving this file's scoring is: the share code's, if it came
+    /// from one, otherwise the configured difficulty
+    pub fn difficulty(&self) -> Difficulty {
+        self.shared.map_or(self.station.config.difficulty, |code| code.difficulty)
+    }
+
+    /// The code that puts this file's board in front of someone else
+    pub fn share_code(&self) -> ShareCode {
+        let grid = self.shared.and_then(|code| code.pinned_grid()).or(self.grid.size()).unwrap_or((0, 0));
+        ShareCode { seed: self.grid_seed, difficulty: self.difficulty(), grid }
+    }
+
+    /// Restart the board `code` describes and race it against `target`
+    pub fn start_race(&mut self, code: ShareCode, target: race::Target) {
+        let target = match target {
+            race::Target::Time(time) => time,
+            race::Target::PersonalBest => match self.station.ledger.best {
+                Some(best) => best,
+                None => {
+                    self.show_toast("No personal best to race yet; complete a file first", Some(TOAST_DURATION));
+                    return;
+                },
+            },
+        };
+        self.open_shared(code);
+        self.race = Some(Race { code, target });
+        self.show_toast(format!("Racing {} against {}", code, race::format_time(target)), Some(TOAST_DURATION));
+    }
+
+    /// Where the race stands now, if one is being run on a timed file
+    pub fn race_pace(&self) -> Option<race::Pace> {
+        let (race, started) = self.race.as_ref().zip(self.file_started)?;
+        let elapsed = self.file_complete_at.unwrap_or(self.clock.now()).duration_since(started);
+        Some(race.pace(elapsed, self.completion_target()))
+    }
+
+    // The file was finished at `complete_at`: settle the race and the
+    // personal best, unless the file was resumed and so never timed
+    pub fn finish_timing(&mut self, complete_at: Instant) {
+        let race = self.race.take();
+        let Some(started) = self.file_started.take() else { return };
+        let time = complete_at.duration_since(started);
+        let mut toast = Vec::new();
+
+        if let Some(race) = race {
+            let result = RaceResult { day: self.today(), code: race.code, target: race.target, time };
+            toast.push(format!("{}.", result.verdict()));
+            if !self.read_only {
+                self.station.ledger.record_race(result);
+            }
+        }
+        if !self.read_only && self.station.ledger.best.is_none_or(|best| time < best) {
+            self.station.ledger.best = Some(time);
+            toast.push(format!("New personal best: {}.", race::format_time(time)));
+        }
+        if !toast.is_empty() {
+            self.show_toast(toast.join(" "), Some(TOAST_DURATION));
+        }
+    }
+
+    /// Start over on a fresh file with its own grid
+    pub fn new_file(&mut self) {
+        self.orientation = None;
+        self.shared = None;
+        self.race = None;
+        self.file_started = Some(self.clock.now());
+        // Small enough to keep share codes short
+        self.grid_seed = self.random.stream(Stream::Grid).random_range(0..1 << 24);
+        self.grid.clear();
+        self.pending_grid = None;
+        self.reset_containers();
+    }
+
+    // Erase the ledger and move config.toml aside as config.toml.bak,
+    // falling back to the built-in settings
+    fn factory_reset(&mut self) -> crate::error::Result<()> {
+        self.station.ledger = Ledger::default();
+        self.store_ledger()?;
+
+        if let Some(path) = &self.station.config_path
+            && path.exists()
+        {
+            let mut backup = path.clone().into_os_string();
+            backup.push(".bak");
+            std::fs::rename(path, backup)?;
+        }
+        self.station.config = Config::default();
+        self.goals = Goals::default();
+        if matches!(self.palette, Palette::Custom { .. }) {
+            self.palette = crate::theme::detect();
+        }
+        self.reload_content();
+        Ok(())
+    }
+
+    // Inspect the next bin along the floor
+    pub fn cycle_inspection(&mut self, step: isize) {
+        let order = self.bin_order();
+        let Some(slot) = self.inspecting.and_then(|bin| order.iter().position(|&b| b == bin)) else { return };
+        self.inspecting = Some(order[(slot as isize + step).rem_euclid(order.len() as isize) as usize]);
+    }
+
+    pub fn focused_bin(&self) -> Option<usize> {
+        self.bin_focus.focused()
+    }
+
+    /// Put the keyboard on `bin`, or with `None` take it off the bins
+    pub fn focus_bin(&mut self, bin: Option<usize>) {
+        self.bin_focus.set_order(self.bin_order());
+        match bin {
+            Some(bin) => {
+                self.bin_focus.focus(bin);
+            },
+            None => self.bin_focus.blur(),
+        }
+    }
+
+    /// Tab and Shift-Tab move the focus along the floor and open the bin
+    /// view for it; Enter deposits into the focused bin and `i` inspects it
+    pub fn on_bin_focus_key(&mut self, key: KeyCode) {
+        self.bin_focus.set_order(self.bin_order());
+        match self.bin_focus.route(key) {
+            Routed::Moved(_) => self.side_panel = Some(SidePanel::Bin),
+            Routed::To(bin, KeyCode::Enter) => self.add_to_container(bin, KEYBOARD_DEPOSIT),
+            Routed::To(bin, KeyCode::Char('i')) => self.inspecting = Some(bin),
+            _ => {},
+        }
+    }
+
+    /// Step the side panel through each view, then close it
+    pub fn cycle_side_panel(&mut self) {
+        let next = match self.side_panel {
+            None => Some(SidePanel::ALL[0]),
+            Some(panel) => {
+                let idx = SidePanel::ALL.iter().position(|&p| p == panel).unwrap_or(0);
+                SidePanel::ALL.get(idx + 1).copied()
+            },
+        };
+        self.side_panel = next;
+        // The bin view needs a bin, and Enter should only deposit while one is shown
+        if next == Some(SidePanel::Bin) {
+            self.focus_bin(self.focused_bin().or_else(|| self.bin_in_slot(0)));
+        } else {
+            self.focus_bin(None);
+        }
+    }
+
+    /// Keep a copy of each event for the log panel
+    pub fn log_events(&mut self) {
+        let now = self.clock.now();
+        for event in &self.station.outbox {
+            budget::push_capped(&mut self.event_log, (now, event.clone()), self.station.config.budget.event_log);
+        }
+    }
+
+    /// Space captures like a click at the cursor; held down, its
+    /// auto-repeat keeps a sticky hold going. A bin number does the same
+    /// and sends the catch to that bin.
+    pub fn press_capture_key(&mut self, bin: Option<usize>) {
+        let now = self.clock.now();
+        if let Some(hold) = &mut self.hold
+            && hold.key_seen.is_some()
+        {
+            hold.key_seen = Some(now);
+            return;
+        }
+        if self.mouse_position.is_some() {
+            self.last_clicked = self.mouse_position;
+            self.key_capture = Some(now);
+            self.aimed_bin = bin;
+        }
+    }
+
+    /// A bin number aims whatever is magnified under the cursor at that
+    /// bin. Away from the grid it tops the bin up by hand.
+    pub fn press_bin_key(&mut self, bin: usize) {
+        let over_grid = self
+            .mouse_position
+            .zip(self.grid_geometry)
+            .is_some_and(|((x, y), geometry)| geometry.cell_at(x, y).is_some());
+        if over_grid {
+            self.press_capture_key(Some(bin));
+        } else {
+            self.add_to_container(bin, KEYBOARD_DEPOSIT);
+        }
+    }
+
+    /// Capture numbers picked up from the grid. Ordinary numbers go
+    /// straight into a bin; sticky ones start a hold instead.
+    pub fn capture_or_hold(&mut self, cells: Vec<(usize, usize, u16)>) {
+        let by_key = self.key_capture.take();
+        let bin = self.aimed_bin.take();
+        let (sticky, loose): (Vec<_>, Vec<_>) = cells.into_iter().partition(|&(col, row, _)| self.sticky.contains_key(&(col, row)));
+
+        if !loose.is_empty() && self.station.config.input.review_captures {
+            self.stage(loose, bin);
+        } else if !loose.is_empty() {
+            self.capture(loose, bin);
+        }
+        // Only a press still held down holds anything
+        if !sticky.is_empty() && self.hold.is_none() && (by_key.is_some() || self.button_down) {
+            let needs = sticky.iter().filter_map(|&(col, row, _)| self.sticky.get(&(col, row)).copied()).max().unwrap_or_default();
+            let needs = self.game_time(needs);
+            self.hold = Some(Hold { cells: sticky, since: self.clock.now(), needs, key_seen: by_key, bin });
+        }
+        self.last_clicked = None;
+    }
+
+    // Hold a capture back at the foot of the grid for a moment, aimed at
+    // the bin it will land in. One already waiting lands first.
+    fn stage(&mut self, cells: Vec<(usize, usize, u16)>, bin: Option<usize>) {
+        self.land_staged();
+        let temper = temper::attribute(&self.origins(&cells));
+        let Some(bin) = bin.or_else(|| self.auto_bin(temper)) else {
+            // Nowhere for it to go, so nothing to review
+            self.capture(cells, None);
+            return;
+        };
+        let digits = cells.iter().map(|&(_, _, digit)| digit).collect();
+        let value = self.scoring.score(&Capture { digits, streak: self.streak, misclicks: self.misclicks });
+        self.staged = Some(Staged { cells, bin, value, since: self.clock.now() });
+    }
+
+    fn land_staged(&mut self) {
+        if let Some(staged) = self.staged.take() {
+            self.capture(staged.cells, Some(staged.bin));
+        }
+    }
+
+    // Score captured numbers into a bin, `bin` if one was aimed at, and put
+    // new ones in their place
+    fn capture(&mut self, cells: Vec<(usize, usize, u16)>, bin: Option<usize>) {
+        let digits: Vec<u16> = cells.iter().map(|&(_, _, digit)| digit).collect();
+        if let Some(combo) = Combo::find_at_least(&digits, self.station.content.scoring.combo_min) {
+            self.combo = Some((combo, self.clock.now()));
+        }
+        let capture = Capture { digits, streak: self.streak, misclicks: self.misclicks };
+        let value = self.scoring.score(&capture);
+        self.streak = if self.misclicks == 0 { self.streak + 1 } else { 0 };
+        self.misclicks = 0;
+
+        self.deposit_origins = self.origins(&cells);
+        if let Some(bin) = bin.or_else(|| self.auto_bin(temper::attribute(&self.deposit_origins))) {
+            self.add_to_container(bin, value);
+        }
+        self.deposit_origins.clear();
+        self.replace_numbers(cells.into_iter().map(|(col, row, _)| (col, row)).collect());
+    }
+
+    // Where each captured number came from
+    fn origins(&self, cells: &[(usize, usize, u16)]) -> Vec<Provenance> {
+        cells
+            .iter()
+            .map(|&(col, row, digit)| Provenance { cell: (col, row), digit, temper: self.grid.temper_at((col, row)) })
+            .collect()
+    }
+
+    /// A click on the grid that caught nothing breaks the streak and costs
+    /// the next capture a little
+    pub fn misclick(&mut self) {
+        self.misclicks += 1;
+        self.streak = 0;
+        self.last_clicked = None;
+        self.key_capture = None;
+        self.aimed_bin = None;
+    }
+
+    // The scoring rules for a new file, from the configured difficulty and
+    // any pack's values
+    fn compose_scoring(&mut self) {
+        self.scoring = Scoring::compose(self.difficulty(), &self.station.content.scoring);
+        self.streak = 0;
+        self.misclicks = 0;
+    }
+
+    /// Whether the assist pulses the best cluster: as configured, or at
+    /// the relaxed difficulty
+    pub fn assist_on(&self) -> bool {
+        self.station.config.assist.unwrap_or(self.difficulty() == Difficulty::Relaxed)
+    }
+
+    // End a pulse that has run its course and start the next when it is
+    // due, waiting longer the better the streak
+    pub fn check_assist(&mut self, now: Instant) {
+        if !self.assist_on() {
+            self.assist_pulse = None;
+            self.next_assist = None;
+            return;
+        }
+        if self.assist_pulse.as_ref().is_some_and(|pulse| now.duration_since(pulse.since) >= PULSE_LENGTH) {
+            self.assist_pulse = None;
+        }
+        let wait = self.game_time(assist::interval(self.streak));
+        let due = *self.next_assist.get_or_insert(now + wait);
+        if now < due {
+            return;
+        }
+        self.next_assist = Some(now + wait);
+
+        let Some((cols, _)) = self.grid.size() else { return };
+        let stride = self.shared.and_then(|code| code.pinned_grid()).map_or(cols, |(pinned, _)| pinned);
+        let digits = self.grid.digits(self.grid_seed, stride);
+        let (streak, misclicks) = (self.streak, self.misclicks);
+        let best = assist::best_cluster(&digits, |cells| {
+            self.scoring.score(&Capture { digits: cells.to_vec(), streak, misclicks })
+        });
+        self.assist_pulse = best.map(|cluster| Pulse { cluster, since: now });
+    }
+
+    // Clear an ambient event that has run its course and start the next
+    // when it is due. Orientation is left undisturbed.
+    pub fn check_ambience(&mut self, now: Instant) {
+        if self.ambient.is_some_and(|(ambience, since)| now.duration_since(since) >= ambience.length()) {
+            self.ambient = None;
+        }
+        if !self.station.config.ambience || self.orientation.is_some() {
+            self.ambience = Scheduler::default();
+            return;
+        }
+        if let Some(ambience) = self.ambience.poll(now, self.random.stream(Stream::Effects)) {
+            self.start_ambience(ambience, now);
+        }
+    }
+
+    /// Set `ambience` going on the board
+    pub fn start_ambience(&mut self, ambience: Ambience, now: Instant) {
+        match ambience {
+            // Flickering is the kind of motion reduced motion is for
+            Ambience::Flicker if self.reduce_motion() => {},
+            Ambience::Flicker | Ambience::Psa => self.ambient = Some((ambience, now)),
+            Ambience::Chime => self.show_toast(ambience::CHIME, Some(TOAST_DURATION)),
+            Ambience::Memo => {
+                let memo = ambience::memo(self.random.stream(Stream::Effects));
+                self.show_toast(format!("Memo from Mr. Milchick: {}", memo), Some(TOAST_DURATION * 2));
+            },
+        }
+    }
+
+    // Fail a check whose time is up and start the next when it is due. A
+    // check waits for any menu, prompt or capture in hand to be done with.
+    pub fn check_compliance(&mut self, now: Instant) {
+        if self.compliance.as_ref().is_some_and(|check| check.is_expired(now)) {
+            self.fail_compliance("Time is up");
+        }
+        if !self.station.config.compliance || self.orientation.is_some() {
+            self.compliance = None;
+            self.next_audit = None;
+            return;
+        }
+        let due = *self.next_audit.get_or_insert_with(|| now + compliance::gap(self.random.stream(Stream::Audits)));
+        let busy = self.reset_menu.is_some()
+            || self.command_line.is_some()
+            || self.cheat_console.is_some()
+            || self.context_menu.is_some()
+            || self.inspecting.is_some()
+            || self.staged.is_some()
+            || self.hold.is_some();
+        if now < due || self.compliance.is_some() || busy {
+            return;
+        }
+        self.next_audit = Some(now + compliance::gap(self.random.stream(Stream::Audits)));
+        self.compliance = Some(ComplianceCheck::new(self.random.stream(Stream::Audits), now));
+    }
+
+    /// Type into the compliance check; Enter hands it in and Esc refuses it
+    pub fn on_compliance_key(&mut self, key: KeyCode) {
+        let Some(check) = &mut self.compliance else { return };
+        match key {
+            KeyCode::Char(c) => check.push(c),
+            KeyCode::Backspace => check.pop(),
+            KeyCode::Enter if check.is_typed() => self.pass_compliance(),
+            KeyCode::Enter => self.fail_compliance("That is not what the handbook says"),
+            KeyCode::Esc => self.fail_compliance("Check refused"),
+            _ => {},
+        }
+    }
+
+    // Close the check and credit a bin with the bonus
+    fn pass_compliance(&mut self) {
+        self.compliance = None;
+        if let Some(bin) = self.auto_bin(None) {
+            self.deposit(bin, compliance::BONUS);
+        }
+        self.show_toast(format!("Compliance confirmed: +{} progress", compliance::BONUS), Some(TOAST_DURATION));
+    }
+
+    // Close the check and take the penalty off the streak
+    fn fail_compliance(&mut self, why: &str) {
+        self.compliance = None;
+        self.streak = self.streak.saturating_sub(compliance::STREAK_PENALTY);
+        self.show_toast(
+            format!("{}. Compliance lapse noted: streak -{}", why, compliance::STREAK_PENALTY),
+            Some(TOAST_DURATION),
+        );
+    }
+
+    // Land a staged capture once it has been on show long enough
+    pub fn check_staged(&mut self, now: Instant) {
+        if self.staged.as_ref().is_some_and(|staged| now.duration_since(staged.since) >= self.game_time(STAGE_DURATION)) {
+            self.land_staged();
+        }
+    }
+
+    // Finish a hold that has run its course, or drop one whose Space key
+    // has been let go
+    pub fn check_hold(&mut self, now: Instant) {
+        let Some(hold) = &self.hold else { return };
+        if hold.progress(now) >= 1.0 {
+            let hold = self.hold.take().unwrap();
+            self.capture(hold.cells, hold.bin);
+        } else if hold.key_seen.is_some_and(|seen| now.duration_since(seen) > KEY_REPEAT_GAP) {
+            self.hold = None;
+        }
+    }
+
+    // Sliding off the held numbers lets go of them
+    pub fn check_hold_position(&mut self) {
+        let Some(hold) = &self.hold else { return };
+        let cell = self
+            .mouse_position
+            .zip(self.grid_geometry)
+            .and_then(|((x, y), geometry)| geometry.cell_at(x, y));
+        let on_it = cell.is_some_and(|(col, row)| {
+            hold.cells.iter().any(|&(c, r, _)| c.abs_diff(col) <= 1 && r.abs_diff(row) <= 1)
+        });
+        if !on_it {
+            self.hold = None;
+        }
+    }
+
+    /// Scatter a fresh set of sticky numbers, each with its own hold time
+    pub fn scatter_sticky(&mut self) {
+        let rng = self.random.stream(Stream::Grid);
+        self.sticky.clear();
+        self.hold = None;
+        while self.sticky.len() < STICKY_CELLS {
+            let cell = (rng.random_range(0..STICKY_AREA.0), rng.random_range(0..STICKY_AREA.1));
+            let hold = Duration::from_millis(rng.random_range(STICKY_HOLD_MS.0..=STICKY_HOLD_MS.1));
+            self.sticky.insert(cell, hold);
+        }
+    }
+
+    /// The grid is about to be drawn at `geometry`. A restored file waiting
+    /// for its size is scaled onto it; otherwise cells that no longer fit
+    /// are recycled. After a resize the grid slides from where it was.
+    pub fn fit_grid(&mut self, geometry: GridGeometry) {
+        self.grid_geometry = Some(geometry);
+        if let Some(from) = self.resized_from.take()
+            && from != geometry
+            && !self.reduce_motion()
+        {
+            self.grid_morph = Some(GridMorph::new(from, geometry, self.clock.now()));
+        }
+        let (cols, rows) = geometry.cells();
+        self.grid.resize(cols, rows);
+        if cols == 0 || rows == 0 {
+            return;
+        }
+        let Some(saved) = self.pending_grid.take() else { return };
+        let fitted = saved.remap(cols, rows);
+        for &(cell, digit) in &fitted.replaced {
+            self.grid.replace(cell, digit, self.station.config.budget.replaced_numbers);
+        }
+        self.scary_clusters = fitted.clusters.into_iter().map(ScaryCluster::new).collect();
+        if let Some(cell) = fitted.cursor {
+            self.mouse_position = Some(geometry.position_of(cell));
+        }
+    }
+
+    /// What is on the grid, for the save file: the restored file still
+    /// waiting to be drawn, or the grid as last drawn
+    pub fn grid_snapshot(&self) -> Option<GridSnapshot> {
+        if self.pending_grid.is_some() {
+            return self.pending_grid.clone();
+        }
+        let size = self.grid.size()?;
+        let mut replaced: Vec<_> = self.grid.cells().filter_map(|cell| Some((cell, self.grid.replaced(cell)?))).collect();
+        replaced.sort_unstable();
+        let cursor = self
+            .mouse_position
+            .zip(self.grid_geometry)
+            .and_then(|((x, y), geometry)| geometry.cell_at(x, y));
+        Some(GridSnapshot {
+            size,
+            replaced,
+            clusters: self.scary_clusters.iter().map(|cluster| cluster.cells.clone()).collect(),
+            cursor,
+        })
+    }
+
+    // Start or stop each scary cluster's dread as the cursor comes and goes
+    pub fn track_dread(&mut self) {
+        let now = self.clock.now();
+        let cell = self
+            .mouse_position
+            .zip(self.grid_geometry)
+            .and_then(|((x, y), geometry)| geometry.cell_at(x, y));
+        for cluster in &mut self.scary_clusters {
+            let near = cell.is_some_and(|cell| cluster.distance(cell) <= DREAD_REACH);
+            match (near, cluster.hovered_since) {
+                (true, None) => cluster.hovered_since = Some(now),
+                (false, Some(_)) => cluster.hovered_since = None,
+                _ => {},
+            }
+        }
+    }
+
+    /// The strongest dread on the grid right now, from 0 to 1
+    pub fn dread(&self) -> f32 {
+        let now = self.clock.now();
+        self.scary_clusters.iter().map(|cluster| cluster.dread(now)).fold(0.0, f32::max)
+    }
+
+    /// Bins from left to right, as indices into `containers`
+    pub fn bin_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.containers.len()).collect();
+        order.sort_by_key(|&bin| self.containers[bin].slot);
+        order
+    }
+
+    pub fn bin_in_slot(&self, slot: usize) -> Option<usize> {
+        self.containers.iter().position(|container| container.slot == slot)
+    }
+
+    /// The slot drawn at a screen cell, if any
+    pub fn slot_at(&self, column: u16, row: u16) -> Option<usize> {
+        self.bin_areas.iter().position(|area| {
+            column >= area.x && column < area.x + area.width && row >= area.y && row < area.y + area.height
+        })
+    }
+
+    /// Where a bin dropped at `column` lands: the slot whose centre is
+    /// nearest, so dropping in a gap still counts
+    pub fn drop_slot(&self, column: u16) -> usize {
+        (0..self.bin_areas.len())
+            .min_by_key(|&slot| {
+                let area = self.bin_areas[slot];
+                (area.x + area.width / 2).abs_diff(column)
+            })
+            .unwrap_or(0)
+    }
+
+    /// Take `bin` out of the row and put it back at `slot`, shifting the
+    /// bins in between over by one
+    pub fn move_bin(&mut self, bin: usize, slot: usize) {
+        let Some(from) = self.containers.get(bin).map(|container| container.slot) else { return };
+        let slot = slot.min(self.containers.len() - 1);
+        for container in &mut self.containers {
+            if from < slot && (from + 1..=slot).contains(&container.slot) {
+                container.slot -= 1;
+            } else if slot < from && (slot..from).contains(&container.slot) {
+                container.slot += 1;
+            }
+        }
+        self.containers[bin].slot = slot;
+    }
+    
+    // Replace a number at a specific position with a new random value
+    pub fn replace_number(&mut self, col: usize, row: usize) {
+        self.sticky.remove(&(col, row));
+        for cluster in &mut self.scary_clusters {
+            cluster.cells.retain(|&cell| cell != (col, row));
+        }
+        self.scary_clusters.retain(|cluster| !cluster.cells.is_empty());
+
+        #[cfg(feature = "scripting")]
+        if let Some(digit) = self.scripted(|rules| rules.digit(col, row)) {
+            self.grid.replace((col, row), digit, self.station.config.budget.replaced_numbers);
+            return;
+        }
+        let new_digit = self.random.stream(Stream::Grid).random_range(0..=9);
+        self.grid.replace((col, row), new_digit, self.station.config.budget.replaced_numbers);
+    }
+    
+    // Replace multiple numbers at once
+    pub fn replace_numbers(&mut self, positions: Vec<(usize, usize)>) {
+        self.station.outbox.push(AppEvent::NumbersRefined { count: positions.len() });
+        for (col, row) in positions {
+            self.marked.remove(&(col, row));
+            self.replace_number(col, row);
+        }
+    }
+    
+    // Add a value to a specific container
+    pub fn add_to_container(&mut self, container_idx: usize, value: u16) {
+        if self.is_locked(container_idx) {
+            self.show_toast(format!("Bin {:02} is locked for now", container_idx + 1), Some(TOAST_DURATION));
+            self.last_clicked = None;
+            return;
+        }
+        if container_idx < self.containers.len() {
+            #[cfg(feature = "scripting")]
+            let value = self.scripted(|rules| rules.deposit(container_idx, value)).unwrap_or(value);
+            self.deposit(container_idx, value);
+            // Reset the last click to avoid repeated processing
+            self.last_clicked = None;
+        }
+    }
+
+    /// Put `value` into a bin, announcing it if that fills the bin
+    pub fn deposit(&mut self, container_idx: usize, value: u16) {
+        let at = self.clock.now();
+        let origins = std::mem::take(&mut self.deposit_origins);
+        let Some(container) = self.containers.get_mut(container_idx) else { return };
+        let was_full = container.is_full();
+        let before = container.count;
+        container.add(value);
+        if container.count > before {
+            let temper = temper::attribute(&origins).unwrap_or_else(|| Temper::random(self.random.stream(Stream::Bins)));
+            container.record(Deposit { value: container.count - before, temper, at, origins }, self.station.config.budget.deposits);
+        }
+        if !was_full && container.is_full() {
+            container.filled_at = Some(at);
+            self.station.outbox.push(AppEvent::BinFilled { bin: container_idx + 1 });
+            self.start_break();
+        }
+    }
+
+    // Leave the main screen for a break, if breaks are on. Anything held
+    // or half typed is let go, as refinement waits until the break is over.
+    fn start_break(&mut self) {
+        if !self.station.config.breaks.enabled || self.read_only || self.state != AppState::Main {
+            return;
+        }
+        self.break_until = Some(self.clock.wall() + self.station.config.breaks.length);
+        self.go_to(AppState::Break);
+    }
+
+    /// The screen showing
+    pub fn state(&self) -> AppState {
+        self.state
+    }
+
+    /// Show the screen the observed session is on. An observer only
+    /// watches, so it follows wherever that session goes, without the
+    /// rules or the hooks of moving between screens itself.
+    pub fn observe_state(&mut self, state: AppState) {
+        self.state = state;
+    }
+
+    /// Move to screen `next` if it may follow this one, running the exit
+    /// hook of the screen left and the entry hook of the one reached. The
+    /// error screen runs neither, and only goes back to the screen it
+    /// interrupted. Returns whether the move was made.
+    pub fn go_to(&mut self, next: AppState) -> bool {
+        let from = self.state;
+        let allowed = from.can_go_to(next) && (!from.is_interruption() || next == self.error_return_state);
+        if !allowed {
+            return false;
+        }
+        let hooks = from != next && !from.is_interruption() && !next.is_interruption();
+        if hooks {
+            self.leave_state(from);
+        }
+        self.state = next;
+        if hooks {
+            self.enter_state(next);
+        }
+        true
+    }
+
+    // Tidy up after a screen that is being left
+    fn leave_state(&mut self, state: AppState) {
+        match state {
+            // Anything clicked, held or half typed is let go
+            AppState::Main => {
+                self.last_clicked = None;
+                self.hold = None;
+                self.staged = None;
+                self.button_down = false;
+                self.bin_drag = None;
+                self.key_capture = None;
+                self.aimed_bin = None;
+                self.command_line = None;
+                self.cheat_console = None;
+                self.reset_menu = None;
+                self.context_menu = None;
+                self.inspecting = None;
+                self.ambient = None;
+                self.compliance = None;
+            },
+            AppState::Prize => self.prize_auto_reset_at = None,
+            AppState::Recap => self.recap = None,
+            AppState::Locked => {
+                self.supervisor_entry.clear();
+                self.show_supervisor_error = false;
+            },
+            _ => {},
+        }
+    }
+
+    // Set up a screen that is being reached
+    fn enter_state(&mut self, state: AppState) {
+        match state {
+            AppState::Changelog => self.changelog_scroll = 0,
+            AppState::Locked => {
+                self.supervisor_entry.clear();
+                self.show_supervisor_error = false;
+            },
+            _ => {},
+        }
+    }
+
+    /// Time left on the current break
+    pub fn break_remaining(&self) -> Duration {
+        self.break_until
+            .and_then(|until| until.duration_since(self.clock.wall()).ok())
+            .unwrap_or(Duration::ZERO)
+    }
+    
+    // Add a random value to a random container
+    pub fn add_random(&mut self) {
+        let rng = self.random.stream(Stream::Bins);
+        let container_idx = rng.random_range(0..self.containers.len());
+        let value = rng.random_range(1..=10);
+        
+        self.add_to_container(container_idx, value);
+    }
+    
+    // The bin a capture nobody aimed goes to: the focused one, or an open
+    // bin picked by the configured strategy
+    fn auto_bin(&mut self, temper: Option<Temper>) -> Option<usize> {
+        if self.focused_bin().is_some() {
+            return self.focused_bin();
+        }
+        let open: Vec<usize> = (0..self.containers.len())
+            .filter(|&idx| !self.containers[idx].is_full() && !self.is_locked(idx))
+            .collect();
+        let strategy = self.station.config.bin_strategy;
+        let bin = strategy.choose(&self.containers, &open, temper, self.round_robin, self.random.stream(Stream::Bins));
+        if self.station.config.bin_strategy == BinStrategy::RoundRobin {
+            self.round_robin = bin;
+        }
+        bin
+    }
+
+    /// Put the performance report on the clipboard for pasting elsewhere
+    pub fn copy_report(&mut self) {
+        let text = stats::performance_summary(self);
+        self.copy_text("Report", &text);
+    }
+
+    /// Put `text` on the clipboard and say how it went; `what` names it
+    pub fn copy_text(&mut self, what: &str, text: &str) {
+        let result = match self.station.clipboard.copy(text, &self.station.config.clipboard) {
+            Ok(Method::Terminal) => format!("{} sent to the terminal's clipboard", what),
+            Ok(Method::Native) => format!("{} copied to the clipboard", what),
+            Err(err) => format!("{} could not be copied: {}", what, err),
+        };
+        self.show_toast(result, Some(TOAST_DURATION));
+    }
+
+    /// Whether `bin` is refusing deposits, as it does early in Orientation
+    pub fn is_locked(&self, bin: usize) -> bool {
+        self.orientation.is_some_and(|step| step.is_locked(bin))
+    }
+
+    /// Today's day number in the ledger, in the shift's UTC offset
+    pub fn today(&self) -> i64 {
+        ledger::day_number(self.clock.wall(), self.utc_offset())
+    }
+
+    /// Minutes from UTC of the shift's clock (UTC without a shift)
+    pub fn utc_offset(&self) -> i16 {
+        self.station.config.shift.as_ref().map_or(0, |shift| shift.utc_offset)
+    }
+
+    /// Read the config, content packs and ledger in the background; they
+    /// are taken in by `poll_startup` as they arrive. A damaged ledger is
+    /// reported and history starts afresh. The config and packs are then
+    /// watched for changes.
+    pub fn start_loading(&mut self) {
+        let station = &mut self.station;
+        station.startup = Some(Startup::start(station.config_path.clone(), station.packs_dir.clone(), station.ledger_path.clone()));
+        if self.station.config_path.is_some() || self.station.packs_dir.is_some() {
+            self.station.watcher = Some(Watcher::start(self.station.config_path.clone(), self.station.packs_dir.clone(), WATCH_INTERVAL));
+        }
+    }
+
+    /// Take in a config file or content pack saved since startup. Only
+    /// settings that are safe to change mid-session take effect; a file
+    /// that does not parse is reported and the current settings are kept.
+    pub fn poll_watcher(&mut self) {
+        let Some(watcher) = &self.station.watcher else { return };
+        for loaded in watcher.poll() {
+            match loaded {
+                Loaded::Config(Ok(config)) => {
+                    let reload = self.station.config.reload(*config);
+                    if reload.applied.contains(&"theme") {
+                        self.use_theme();
+                    }
+                    self.announce_reload(&reload);
+                },
+                Loaded::Content(Ok(content)) => {
+                    self.station.content = content;
+                    // Scoring and flavour text wait for the next file
+                    self.use_theme();
+                    self.show_toast("Content packs reloaded", Some(TOAST_DURATION));
+                },
+                Loaded::Config(Err(err)) | Loaded::Content(Err(err)) => {
+                    self.show_toast(format!("Not reloaded: {}", err), Some(TOAST_DURATION));
+                },
+                Loaded::Ledger(_) => {},
+            }
+        }
+    }
+
+    // Say what a config reload changed, and what waits for a restart
+    fn announce_reload(&mut self, reload: &Reload) {
+        if reload.is_empty() {
+            return;
+        }
+        let mut text = if reload.applied.is_empty() {
+            "Config saved".to_string()
+        } else {
+            format!("Config reloaded: {}", reload.applied.join(", "))
+        };
+        if !reload.pending.is_empty() {
+            text.push_str(&format!("; {} after a restart", reload.pending.join(", ")));
+        }
+        self.show_toast(text, Some(TOAST_DURATION));
+    }
+
+    /// Take in whatever startup work has finished
+    pub fn poll_startup(&mut self) {
+        let Some(startup) = &mut self.station.startup else { return };
+        let loaded = startup.poll();
+        self.use_loaded(loaded);
+    }
+
+    // Block until `task` is in, where nothing can go on without it
+    fn wait_for_startup(&mut self, task: Option<Task>) {
+        let Some(startup) = &mut self.station.startup else { return };
+        let loaded = match task {
+            Some(task) => startup.wait_for(task),
+            None => startup.wait(),
+        };
+        self.use_loaded(loaded);
+    }
+
+    fn use_loaded(&mut self, loaded: Vec<Loaded>) {
+        for loaded in loaded {
+            match loaded {
+                Loaded::Config(Ok(config)) => {
+                    self.use_config(*config);
+                    // Only once per launch; reloads leave the check alone
+                    self.station.update_check = UpdateCheck::start(&self.station.config.updates, updates::default_cache_path());
+                },
+                Loaded::Content(Ok(content)) => {
+                    self.station.content = content;
+                    self.use_content();
+                },
+                Loaded::Ledger(Ok(ledger)) => {
+                    self.station.ledger = ledger;
+                    self.offer_recap();
+                },
+                Loaded::Config(Err(err)) | Loaded::Content(Err(err)) => {
+                    self.report_error(RecoverableError::new(FailureKind::Config, err.to_string()));
+                },
+                Loaded::Ledger(Err(err)) => self.report_error(RecoverableError::new(FailureKind::SaveFile, err.to_string())),
+            }
+        }
+        if self.station.startup.as_ref().is_some_and(Startup::is_done) {
+            self.station.startup = None;
+        }
+    }
+
+    pub fn store_ledger(&self) -> crate::error::Result<()> {
+        match &self.station.ledger_path {
+            Some(path) if !self.read_only => self.station.ledger.store(path),
+            _ => Ok(()),
+        }
+    }
+
+    /// On the first launch of a week, open with a recap of the last one if
+    /// anything was refined in it
+    pub fn offer_recap(&mut self) {
+        let today = self.today();
+        let week = ledger::iso_week(today);
+        if self.read_only || self.station.ledger.recap_week == Some(week) {
+            return;
+        }
+        self.station.ledger.recap_week = Some(week);
+
+        let recap = self.station.ledger.recap(ledger::week_start(today) - 7);
+        if !recap.total.is_empty() && self.state == AppState::Login {
+            self.recap = Some(recap);
+            self.go_to(AppState::Recap);
+        }
+    }
+
+    /// Add experience, announcing a promotion if it brings one
+    pub fn gain_xp(&mut self, xp: u64) {
+        let before = ranks::rank_for(self.xp);
+        self.xp = self.xp.saturating_add(xp);
+        let after = ranks::rank_for(self.xp);
+        if after != before {
+            self.show_toast(format!("Promoted to {}. Kier is proud of you.", after.title), Some(TOAST_DURATION));
+        }
+    }
+
+    /// Whether anything on screen is currently changing on its own
+    pub fn is_animating(&self) -> bool {
+        match self.state {
+            AppState::Loading => true,
+            // Dread keeps building while the cursor lingers by a cluster,
+            // a hold's progress ring or the reset gauge fills, a combo's
+            // banner flashes, the completion and bins count up and the grid
+            // slides into a resized window
+            AppState::Main => {
+                self.file_complete_at.is_some()
+                    || self.hold.is_some()
+                    || self.reset_menu.as_ref().is_some_and(|menu| menu.held.is_some())
+                    || self.combo.is_some()
+                    || self.ambient.is_some()
+                    || self.compliance.is_some()
+                    || self.completion.is_moving(self.clock.now())
+                    || self.grid_morph.is_some_and(|morph| morph.is_moving(self.clock.now()))
+                    || self.containers.iter().any(|c| c.fill.is_moving(self.clock.now()) || c.is_celebrating(self.clock.now()))
+                    || self.completion_pulse.is_some()
+                    || self.scary_clusters.iter().any(|c| c.hovered_since.is_some())
+            },
+            AppState::Prize => self.prize_auto_reset_at.is_some(),
+            _ => false,
+        }
+    }
+
+    /// How long the event loop should wait between ticks right now: fast
+    /// while things move or the refiner is busy, slow once they wander off
+    pub fn tick_rate(&self) -> Duration {
+        if self.is_eco() {
+            return ECO_TICK_RATE;
+        }
+
+        let since_input = self.since_input();
+
+        if self.is_animating() || since_input < ACTIVE_WINDOW {
+            ACTIVE_TICK_RATE
+        } else if self.is_idle() {
+            IDLE_TICK_RATE
+        } else {
+            DEFAULT_TICK_RATE
+        }
+    }
+
+    /// Low-power mode, from `--eco` or the config
+    pub fn is_eco(&self) -> bool {
+        self.station.eco_mode || self.station.config.display.eco
+    }
+
+    /// Whether the grid is kept still: in low-power and monochrome modes,
+    /// or when the config asks for reduced motion
+    pub fn reduce_motion(&self) -> bool {
+        self.is_eco() || self.is_mono() || self.station.config.display.reduce_motion
+    }
+
+    /// Monochrome mode, from `--mono` or the terminal: black and white,
+    /// and nothing animates, as an e-ink display smears anything that moves
+    pub fn is_mono(&self) -> bool {
+        matches!(self.palette, Palette::Mono)
+    }
+
+    /// Slow the game's timers down or speed them up a notch
+    pub fn step_game_speed(&mut self, step: isize) {
+        let idx = GAME_SPEEDS.iter().position(|&speed| speed == self.game_speed).unwrap_or(2);
+        let next = (idx as isize + step).clamp(0, GAME_SPEEDS.len() as isize - 1) as usize;
+        self.game_speed = GAME_SPEEDS[next];
+    }
+
+    /// How long `duration` of game time takes at the current speed
+    pub fn game_time(&self, duration: Duration) -> Duration {
+        duration * 100 / self.game_speed.max(1)
+    }
+
+    /// Time since the last key, click or paste
+    pub fn since_input(&self) -> Duration {
+        self.clock.now().duration_since(self.last_input)
+    }
+
+    /// Animation steps since the app started, read off the clock so the
+    /// tick rate never changes how fast things wiggle
+    pub fn animation_step(&self) -> u32 {
+        (self.clock.now().duration_since(self.station.started_at).as_millis() / ANIMATION_STEP.as_millis()) as u32
+    }
+
+    /// The refiner has left the keyboard alone for a while
+    pub fn is_idle(&self) -> bool {
+        self.since_input() >= IDLE_AFTER
+    }
+
+    /// Who the current moment belongs to: the innie while refining with
+    /// the window focused, the outie otherwise
+    pub fn current_profile(&self) -> Profile {
+        if self.station.focused && self.state == AppState::Main && !self.is_idle() {
+            Profile::Innie
+        } else {
+            Profile::Outie
+        }
+    }
+
+    pub fn tick(&mut self) {
+        let now = self.clock.now();
+        self.station.stats.record(&self.username, self.current_profile(), now);
+        self.station.stats.trim(&self.username, self.station.config.budget.refiners);
+        if self.toast.as_ref().and_then(|toast| toast.expires).is_some_and(|at| now >= at) {
+            self.toast = None;
+        }
+        if self.combo.is_some_and(|(_, at)| now.duration_since(at) >= COMBO_BANNER_DURATION) {
+            self.combo = None;
+        }
+        self.check_shift();
+        if self.state == AppState::Main && now.duration_since(self.last_selfcheck) >= SELFCHECK_INTERVAL {
+            self.run_selfcheck(false);
+        }
+        self.update_completion(now);
+        self.ease_bins(now);
+
+        // An observer's state comes from the observed session, not the clock
+        if self.read_only {
+            return;
+        }
+        if self.idle_logout_due() {
+            self.log_out();
+            return;
+        }
+        self.check_autosave(now);
+
+        with_screen!(self.state, screen => screen.tick(self, now));
+    }
+
+    // Lock the main screen outside shift hours and unlock it when the next
+    // shift starts. Other screens are left alone; a refiner who is locked
+    // out will meet the lock screen as soon as they reach the main screen.
+    fn check_shift(&mut self) {
+        let Some(shift) = &self.station.config.shift else { return };
+
+        let open = shift.is_open(self.clock.wall());
+        if open {
+            self.station.shift_override = false;
+        }
+
+        match self.state {
+            AppState::Locked if open => {
+                self.go_to(AppState::Main);
+            },
+            AppState::Main if !open && !self.station.shift_override => {
+                self.go_to(AppState::Locked);
+            },
+            _ => {}
+        }
+    }
+
+    /// Time left before the prize screen resets itself, if it will
+    pub fn prize_countdown(&self) -> Option<Duration> {
+        let at = self.prize_auto_reset_at?;
+        Some(at.saturating_duration_since(self.clock.now()))
+    }
+
+    // Re-read the config file. A broken file leaves the defaults in place
+    // and says so on the error screen rather than refusing to start.
+    pub fn reload_config(&mut self) {
+        let Some(path) = &self.station.config_path else { return };
+        match crate::config::load(path) {
+            Ok(config) => self.use_config(config),
+            Err(err) => {
+                self.report_error(RecoverableError::new(FailureKind::Config, err.to_string()));
+                return;
+            },
+        }
+        self.reload_content();
+    }
+
+    // Switch to a freshly read config: telemetry, speech, goals and the rule script
+    // follow it. Content is left to the caller, as it may be read elsewhere.
+    fn use_config(&mut self, config: Config) {
+        self.station.config = config;
+
+        #[cfg(feature = "telemetry")]
+        match crate::telemetry::Telemetry::start(&self.station.config.telemetry) {
+            Ok(telemetry) => self.station.telemetry = telemetry,
+            Err(err) => self.report_error(RecoverableError::new(FailureKind::Config, err.to_string())),
+        }
+
+        #[cfg(feature = "speech")]
+        match crate::speech::Speech::start(&self.station.config.speech) {
+            Ok(speech) => self.station.speech = speech,
+            Err(err) => {
+                self.station.speech = None;
+                self.report_error(RecoverableError::new(FailureKind::Audio, err.to_string()));
+            },
+        }
+
+        for goal in self.station.config.goals.clone() {
+            self.add_goal(&goal);
+        }
+        self.prefill_username();
+
+        #[cfg(feature = "scripting")]
+        self.reload_rules();
+    }
+
+    /// Re-read the rule script. Part of `reload_config`; a script that does
+    /// not load is reported like a broken config and the built-in rules apply.
+    #[cfg(feature = "scripting")]
+    pub fn reload_rules(&mut self) {
+        let Some(path) = &self.station.rules_path else { return };
+        match crate::scripting::load(path) {
+            Ok(rules) => self.station.rules = rules,
+            Err(err) => {
+                self.station.rules = None;
+                self.report_error(RecoverableError::new(FailureKind::Config, err.to_string()));
+            },
+        }
+    }
+
+    // Ask the rule script, if there is one. A script that fails is switched
+    // off until the config is reloaded, and the built-in rule applies.
+    #[cfg(feature = "scripting")]
+    fn scripted<T>(
+        &mut self,
+        hook: impl FnOnce(&crate::scripting::Rules) -> crate::error::Result<Option<T>>,
+    ) -> Option<T> {
+        let result = hook(self.station.rules.as_ref()?);
+        result.unwrap_or_else(|err| {
+            self.station.rules = None;
+            self.report_error(RecoverableError::new(FailureKind::Config, err.to_string()));
+            None
+        })
+    }
+
+    /// Re-read content packs and apply the configured theme. Part of
+    /// `reload_config`; a broken pack is reported like a broken config.
+    pub fn reload_content(&mut self) {
+        if let Some(dir) = &self.station.packs_dir {
+            match crate::content::load(dir) {
+                Ok(content) => self.station.content = content,
+                Err(err) => self.report_error(RecoverableError::new(FailureKind::Config, err.to_string())),
+            }
+        }
+        self.use_content();
+    }
+
+    // Apply whatever content is loaded: scoring values, flavour text and
+    // the configured theme
+    fn use_content(&mut self) {
+        self.compose_scoring();
+        self.pick_flavour();
+        self.use_theme();
+    }
+
+    // Apply the configured theme, or go back to the detected palette if
+    // a theme was set and no longer is
+    fn use_theme(&mut self) {
+        if self.is_mono() {
+            return;
+        }
+        let Some(name) = &self.station.config.theme else {
+            if matches!(self.palette, Palette::Custom { .. }) {
+                self.palette = crate::theme::detect();
+            }
+            return;
+        };
+        match self.station.content.theme(name) {
+            Some(theme) => self.palette = Palette::Custom { fg: theme.fg, bg: theme.bg },
+            None => {
+                let available: Vec<&str> = self.station.content.themes.iter().map(|t| t.name.as_str()).collect();
+                let mut detail = format!("theme '{}' is not provided by any content pack", name);
+                if !available.is_empty() {
+                    detail.push_str(&format!(" (available: {})", available.join(", ")));
+                }
+                self.report_error(RecoverableError::new(FailureKind::Config, detail));
+            },
+        }
+    }
+
+    // Choose a file to refine and a quote from the current content
+    fn pick_flavour(&mut self) {
+        let rng = self.random.stream(Stream::Loading);
+        if let Some(name) = self.station.content.file_names.get(rng.random_range(0..self.station.content.file_names.len().max(1))) {
+            self.file_name = name.clone();
+        }
+        if let Some(quote) = self.station.content.quotes.get(rng.random_range(0..self.station.content.quotes.len().max(1))) {
+            self.quote = quote.clone();
+        }
+    }
+
+    // Show the error screen, remembering where to return afterwards.
+    // A second failure while the screen is up replaces the first.
+    pub fn report_error(&mut self, error: RecoverableError) {
+        if self.state != AppState::Error {
+            self.error_return_state = self.state;
+        }
+        self.error = Some(error);
+        self.error_buttons = Focus::on(ErrorAction::ALL.to_vec(), ErrorAction::Ignore);
+        self.go_to(AppState::Error);
+    }
+
+    /// Do what an error screen button says
+    pub fn press_error_button(&mut self, action: ErrorAction) {
+        match action {
+            ErrorAction::Retry => {
+                // The failing subsystem picks this up and tries again
+                self.pending_retry = self.error.as_ref().map(|err| err.kind);
+                self.dismiss_error();
+            },
+            ErrorAction::Ignore => self.dismiss_error(),
+            ErrorAction::Quit => self.running = false,
+        }
+    }
+
+    // Leave the error screen and resume whatever was interrupted
+    pub fn dismiss_error(&mut self) {
+        self.error = None;
+        self.go_to(self.error_return_state);
+    }
+
+    // Reset all containers to zero and start on a fresh file
+    pub fn reset_containers(&mut self) {
+        // A fresh file starts with the bins back in order
+        for (slot, container) in self.containers.iter_mut().enumerate() {
+            container.empty();
+            container.slot = slot;
+        }
+        self.bin_drag = None;
+        self.marked.clear();
+        self.staged = None;
+        self.assist_pulse = None;
+        self.reshuffles = 0;
+        self.scatter_sticky();
+        self.compose_scoring();
+        self.pick_flavour();
+    }
+
+    /// The file's completion in percent: the bins' average fill
+    pub fn completion_target(&self) -> f32 {
+        if self.containers.is_empty() {
+            return 0.0;
+        }
+        self.containers.iter().map(|container| container.progress).sum::<f32>() / self.containers.len() as f32
+    }
+
+    // Count the header's completion up toward the bins' fill, pulsing it
+    // each time it passes a quarter. Drops, anything off the main screen
+    // and everything in monochrome mode are shown straight away.
+    fn update_completion(&mut self, now: Instant) {
+        let target = self.completion_target();
+        let eased = self.state == AppState::Main && !self.is_mono();
+        if eased && target > self.completion.target() {
+            self.completion.set(target, now);
+        } else if target != self.completion.target() {
+            self.completion.jump(target);
+        }
+
+        // Checked each tick, so the pulse lands as the count passes
+        let quarters = (self.completion.value(now) / 25.0).floor() as u32;
+        if eased && quarters > self.completion_quarters {
+            self.completion_pulse = Some(now);
+        }
+        self.completion_quarters = quarters;
+        if self.completion_pulse.is_some_and(|at| now.duration_since(at) >= COMPLETION_PULSE) {
+            self.completion_pulse = None;
+        }
+    }
+
+    // Ease each bin's bar up to its new fill; as with the completion,
+    // drops and changes off the main screen are drawn straight away
+    fn ease_bins(&mut self, now: Instant) {
+        let main = self.state == AppState::Main && !self.is_mono();
+        for container in &mut self.containers {
+            if main && container.progress > container.fill.target() {
+                container.fill.set(container.progress, now);
+            } else if container.progress != container.fill.target() {
+                container.fill.jump(container.progress);
+            }
+        }
+    }
+
+    // Check if all containers are 100% full
+    pub fn is_all_complete(&self) -> bool {
+        self.containers.iter().all(|container| container.is_full())
+    }
 
     // Select a random prize for the user
     pub fn select_random_prize(&mut self) {
-        let prizes = [
-            "Waffle Party",
-            "Melon Bar",
-            "Finger Trap",
-            "Caricature Portrait",
-            "Dance Experience",
-            "Music/Dance Experience",
-            "Wellness Session",
-            "Coffee Cozy",
-            "Choice of Desk Toy",
-        ];
-        
-        let mut rng = rng();
-        let prize_idx = rng.random_range(0..prizes.len());
-        self.prize_name = prizes[prize_idx].to_string();
+        self.prize_memo = None;
+        if let Some(prize) = self.forced_prize.take() {
+            self.prize_name = prize;
+        } else {
+            let mut pool = self.station.content.prize_pool(&self.station.config.prize.perks, self.station.config.prize.builtin);
+            if self.founder_visit {
+                pool.push(Prize::new(FOUNDER_PRIZE));
+            }
+            // With the built-in perks turned off and nothing in their place
+            if pool.is_empty() {
+                pool = Content::default().prizes;
+            }
+
+            if let Some(prize) = content::pick_prize(&pool, self.random.stream(Stream::Prizes).random()) {
+                self.prize_name = prize.name.clone();
+            }
+
+            #[cfg(feature = "scripting")]
+            {
+                let prizes: Vec<String> = pool.into_iter().map(|prize| prize.name).collect();
+                if let Some(prize) = self.scripted(|rules| rules.prize(&prizes)) {
+                    self.prize_name = prize;
+                }
+            }
+        }
+
+        #[cfg(feature = "scripting")]
+        {
+            let (username, prize) = (self.username.clone(), self.prize_name.clone());
+            self.prize_memo = self.scripted(|rules| rules.memo(&username, &prize));
+        }
+    }
+
+    // Write out a perk voucher for the prize just won. It is printed on
+    // exit even if the vouchers folder cannot be written.
+    pub fn issue_voucher(&mut self) {
+        let voucher = Voucher {
+            number: self.random.stream(Stream::Prizes).random_range(0..1 << voucher::CODE_BITS),
+            prize: self.prize_name.clone(),
+            employee: self.username.trim().to_string(),
+            file: self.file_name.clone(),
+            day: self.today(),
+        };
+        if let Some(dir) = &self.station.vouchers_dir
+            && let Err(err) = voucher.store(dir)
+        {
+            self.report_error(RecoverableError::new(FailureKind::SaveFile, err.to_string()));
+        }
+        self.vouchers.push(voucher);
+    }
+
+    // Make the completion card for the file just finished in `time`, and
+    // keep a copy in the cards folder
+    pub fn issue_card(&mut self, time: Option<Duration>) {
+        let card = CompletionCard {
+            employee: self.username.trim().to_string(),
+            file: self.file_name.clone(),
+            time,
+            prizes: self.vouchers.iter().map(|voucher| voucher.prize.clone()).collect(),
+            code: self.share_code().to_string(),
+            day: self.today(),
+        };
+        if let Some(dir) = &self.station.cards_dir
+            && let Err(err) = card.store(dir)
+        {
+            self.report_error(RecoverableError::new(FailureKind::SaveFile, err.to_string()));
+        }
+        self.card = Some(card);
+    }
+
+    /// Copy the completion card, fenced for a chat
+    pub fn copy_card(&mut self) {
+        match self.card.as_ref().map(CompletionCard::chat) {
+            Some(text) => self.copy_text("Completion card", &text),
+            None => self.show_toast("No file completed yet", Some(TOAST_DURATION)),
+        }
     }
 }
\ No newline at end of file
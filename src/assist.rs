@@ -0,0 +1,83 @@
+//! The refinement assist: every so often the most valuable cluster on the
+//! grid pulses for a moment, to teach a newcomer what is worth capturing.
+//!
+//! It is on at the relaxed difficulty unless `enabled` in `[assist]` says
+//! otherwise. The better the refiner's streak, the longer it waits between
+//! pulses, so it fades away as they stop needing it.
+
+use crate::animate::Timeline;
+use std::time::{Duration, Instant};
+
+/// Cells along each side of a cluster
+pub const CLUSTER_SIZE: usize = 3;
+/// How long a cluster pulses
+pub const PULSE_LENGTH: Duration = Duration::from_millis(1_500);
+/// How long a pulse stays bright, and then dark, each time it flashes
+const FLASH_EACH: Duration = Duration::from_millis(125);
+/// Time between pulses with no streak
+const BASE_INTERVAL: Duration = Duration::from_secs(10);
+/// Longest time between pulses, however long the streak
+const MAX_INTERVAL: Duration = Duration::from_secs(120);
+
+/// A square of cells on the grid and what capturing it is worth
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cluster {
+    /// Top left cell
+    pub corner: (usize, usize),
+    pub value: u16,
+}
+
+impl Cluster {
+    pub fn contains(&self, (col, row): (usize, usize)) -> bool {
+        let (left, top) = self.corner;
+        (left..left + CLUSTER_SIZE).contains(&col) && (top..top + CLUSTER_SIZE).contains(&row)
+    }
+}
+
+/// A cluster pulsing on the grid
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pulse {
+    pub cluster: Cluster,
+    pub since: Instant,
+}
+
+impl Pulse {
+    /// The pulse's flashes, four a second through `PULSE_LENGTH`: bright
+    /// at 1, dark at 0
+    pub fn flashes() -> Timeline {
+        Timeline::flashing(FLASH_EACH, 6)
+    }
+
+    /// Whether the pulse is bright at `now`
+    pub fn lit(&self, now: Instant) -> bool {
+        Self::flashes().value(now.saturating_duration_since(self.since)) >= 0.5
+    }
+}
+
+/// Time between pulses for a refiner on a streak of `streak` captures
+pub fn interval(streak: u32) -> Duration {
+    BASE_INTERVAL.saturating_mul(streak.saturating_add(1)).min(MAX_INTERVAL)
+}
+
+/// The most valuable cluster on a grid of `digits`, by row, valued by
+/// `value`. Ties go to the cluster nearest the top left. `None` when the
+/// grid is smaller than a cluster.
+pub fn best_cluster(digits: &[Vec<u16>], value: impl Fn(&[u16]) -> u16) -> Option<Cluster> {
+    let rows = digits.len();
+    let cols = digits.iter().map(Vec::len).min().unwrap_or(0);
+    let mut best: Option<Cluster> = None;
+    let mut cells = Vec::with_capacity(CLUSTER_SIZE * CLUSTER_SIZE);
+    for top in 0..(rows + 1).saturating_sub(CLUSTER_SIZE) {
+        for left in 0..(cols + 1).saturating_sub(CLUSTER_SIZE) {
+            cells.clear();
+            for row in &digits[top..top + CLUSTER_SIZE] {
+                cells.extend_from_slice(&row[left..left + CLUSTER_SIZE]);
+            }
+            let value = value(&cells);
+            if best.as_ref().is_none_or(|best| value > best.value) {
+                best = Some(Cluster { corner: (left, top), value });
+            }
+        }
+    }
+    best
+}
@@ -0,0 +1,209 @@
+//! Saving the session in the background, through a write-ahead journal.
+//!
+//! Every `AUTOSAVE_INTERVAL` the session is handed to a writer thread, so
+//! the UI never waits on the disk. Each write goes to a journal next to
+//! the session file first (`mark.save.journal`), with its length and a
+//! checksum, and only then replaces the session file. The journal is
+//! removed once the session file is in place.
+//!
+//! A crash part way leaves one of two things behind. A journal that is
+//! whole holds a save that may not have reached the session file, so it
+//! is replayed onto it at the next launch. One that is cut short never got
+//! as far as the session file, which still holds the save before, so it is
+//! discarded. Either way the loading screen says what was done.
+
+use crate::error::{MdrError, Result};
+use crate::save;
+use std::cell::{Cell, RefCell};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// How often the session is saved while it runs
+pub const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(30);
+
+const JOURNAL_HEADER: &str = "lumon-mdr journal";
+const JOURNAL_VERSION: u32 = 1;
+const JOURNAL_EXTENSION: &str = "journal";
+
+/// What the check at launch did with a journal left by a crash
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Recovery {
+    /// The journal was whole and was written to the session file
+    Replayed(PathBuf),
+    /// The journal was cut short and the session file left as it was
+    Discarded(PathBuf),
+}
+
+impl Recovery {
+    /// A line for the loading screen
+    pub fn notice(&self) -> String {
+        match self {
+            Recovery::Replayed(path) => format!("Recovered an interrupted save: {}", file_name(path)),
+            Recovery::Discarded(path) => format!("Discarded an unfinished save: {}", file_name(path)),
+        }
+    }
+}
+
+/// The journal kept beside a session file
+pub fn journal_path(path: &Path) -> PathBuf {
+    let mut journal = path.as_os_str().to_owned();
+    journal.push(".");
+    journal.push(JOURNAL_EXTENSION);
+    PathBuf::from(journal)
+}
+
+/// Write `contents` to the journal, then to `path`, then drop the journal
+pub fn write_journaled(path: &Path, contents: &str) -> Result<()> {
+    let journal = journal_path(path);
+    let failed = |err: std::io::Error| MdrError::Save(format!("{}: {}", journal.display(), err));
+    let entry = format!(
+        "{} {}\nlength={}\nsum={:016x}\n{}",
+        JOURNAL_HEADER,
+        JOURNAL_VERSION,
+        contents.len(),
+        checksum(contents),
+        contents
+    );
+    File::create(&journal)
+        .and_then(|mut file| file.write_all(entry.as_bytes()).and_then(|()| file.sync_all()))
+        .map_err(failed)?;
+    save::write_atomic(path, contents)?;
+    fs::remove_file(&journal).map_err(failed)
+}
+
+/// Replay or discard every journal left in `dir`
+pub fn recover(dir: &Path) -> Vec<Recovery> {
+    let Ok(entries) = fs::read_dir(dir) else { return Vec::new() };
+    let mut journals: Vec<PathBuf> = entries
+        .filter_map(|entry| Some(entry.ok()?.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == JOURNAL_EXTENSION))
+        .collect();
+    journals.sort();
+
+    journals
+        .into_iter()
+        .map(|journal| {
+            let target = journal.with_extension("");
+            let text = fs::read_to_string(&journal).unwrap_or_default();
+            let recovery = match parse_entry(&text) {
+                Some(contents) if save::write_atomic(&target, contents).is_ok() => Recovery::Replayed(target),
+                _ => Recovery::Discarded(target),
+            };
+            let _ = fs::remove_file(&journal);
+            recovery
+        })
+        .collect()
+}
+
+// Writes are numbered as they are asked for, and one older than the last
+// written is dropped, so a background save that was overtaken by a save
+// at exit can never land after it
+struct Writer {
+    written: Mutex<u64>,
+}
+
+impl Writer {
+    fn write(&self, number: u64, path: &Path, contents: &str) -> Result<()> {
+        let mut written = self.written.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if number < *written {
+            return Ok(());
+        }
+        write_journaled(path, contents)?;
+        *written = number;
+        Ok(())
+    }
+}
+
+struct Job {
+    number: u64,
+    path: PathBuf,
+    contents: String,
+}
+
+/// Handle on the writer thread
+pub struct Autosave {
+    tx: Sender<Job>,
+    results: Receiver<Result<()>>,
+    writer: Arc<Writer>,
+    next: Cell<u64>,
+    /// What was last handed over, to skip saves where nothing changed
+    last: RefCell<String>,
+}
+
+impl Autosave {
+    pub fn start() -> Self {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let (results_tx, results) = mpsc::channel();
+        let writer = Arc::new(Writer { written: Mutex::new(0) });
+        let thread_writer = Arc::clone(&writer);
+        thread::spawn(move || {
+            while let Ok(mut job) = rx.recv() {
+                // Only the newest of a backlog is worth writing
+                while let Ok(newer) = rx.try_recv() {
+                    job = newer;
+                }
+                if results_tx.send(thread_writer.write(job.number, &job.path, &job.contents)).is_err() {
+                    return;
+                }
+            }
+        });
+        Self { tx, results, writer, next: Cell::new(1), last: RefCell::new(String::new()) }
+    }
+
+    /// Hand `contents` to the writer thread, unless they are what was
+    /// handed over last
+    pub fn queue(&self, path: PathBuf, contents: String) {
+        if *self.last.borrow() == contents {
+            return;
+        }
+        self.last.replace(contents.clone());
+        let number = self.take_number();
+        let _ = self.tx.send(Job { number, path, contents });
+    }
+
+    /// Write `contents` now, on this thread, ahead of anything queued
+    pub fn write_now(&self, path: &Path, contents: &str) -> Result<()> {
+        let number = self.take_number();
+        self.writer.write(number, path, contents)?;
+        self.last.replace(contents.to_string());
+        Ok(())
+    }
+
+    /// Failures from the writer thread since the last poll
+    pub fn poll(&self) -> Vec<MdrError> {
+        self.results.try_iter().filter_map(|result| result.err()).collect()
+    }
+
+    fn take_number(&self) -> u64 {
+        let number = self.next.get();
+        self.next.set(number + 1);
+        number
+    }
+}
+
+// FNV-1a, enough to tell a whole entry from a torn one
+fn checksum(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3))
+}
+
+// The saved contents of a journal entry, if it is whole
+fn parse_entry(text: &str) -> Option<&str> {
+    let (header, rest) = text.split_once('\n')?;
+    if header != format!("{} {}", JOURNAL_HEADER, JOURNAL_VERSION) {
+        return None;
+    }
+    let (length, rest) = rest.split_once('\n')?;
+    let (sum, contents) = rest.split_once('\n')?;
+    let length: usize = length.strip_prefix("length=")?.parse().ok()?;
+    let sum = u64::from_str_radix(sum.strip_prefix("sum=")?, 16).ok()?;
+    (contents.len() == length && checksum(contents) == sum).then_some(contents)
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name().map_or_else(|| path.display().to_string(), |name| name.to_string_lossy().into_owned())
+}
@@ -0,0 +1,145 @@
+//! The Lumon logo in each of its sizes, and which one a screen should
+//! draw. Screens ask for a logo by how much room they have instead of
+//! keeping their own copy of the art: the window's layout profile picks the
+//! largest variant that suits it and smaller ones stand in when it does not
+//! fit. The choice can be fixed in `config.toml`:
+//!
+//! ```toml
+//! [branding]
+//! logo = "badge"   # "auto", "globe", "wordmark", "badge" or "text"
+//! ```
+
+use crate::error::{MdrError, Result};
+use crate::ui::LayoutProfile;
+use ratatui::layout::Rect;
+use std::str::FromStr;
+
+/// The globe, as letter art. Each letter stands for how much ink its cell
+/// holds, from A (solid) to Z (barely there); the loading screen shades it.
+const GLOBE: &[&str] = &[
+    "                                               ZTMIKMLKOVSRSY   ZSSTZTNIHHKQX                                          ",
+    "                                         WPNPWZVZ       VP  Y   Z  OX       NSYTNOU                                    ",
+    "                                     XRRUW   TTP  KKMIGN NRSY   ZSSO XGDHHI  ZRY  UWQQU                                ",
+    "                                  ZSLD  EDCBCL CCCCFGD FCCGGGGGGGGGDCC CGGECCCD BCCD  CGMT                             ",
+    "                                TTS                                                      NTT                           ",
+    "                              YWQ VRLGGGFC CGGGGGGGFLUEGGGGGGGGGGGGGGGFI DGGGGGGGGC CFGGMN OTT                         ",
+    "                             VT  RT     ZXTT       Z YY               ZW W        TSZZ   TTN MT                        ",
+    "                             T SWY    TMMSZ    TMMTSMMT   TMLSXQKMT  TMFHHHGMT UNINMLLT    TM NU                       ",
+    "                            WS T      M  M     N  MM  M   M   U   M  M       M O   B  M     TH N                       ",
+    "                            U RX      G  G     H  GG  G   G       G  G  CGE  G J      G      H H                       ",
+    "                            U XS      L  L     M      M   L  U U  L  L  BFB  L O  B   L     YL M                       ",
+    "                            XQ MT     S     Z  TLZ   NS   S   M   V  R       S T  L   S    YV TT                       ",
+    "                             TN OUZ   ZYRRRYZ   ZWUNPRZ   ZYYZ ZYYZ  ZRFCCGLSZ ZYYYVVVZ   TR  X                        ",
+    "                              TO XSSW   T OUU      U NT               WP T       TSZ X  TTM PWY                        ",
+    "                               VUQ  QRRXTTS OTU    UN NU             WS PT     TTM PWQSSS MTT                          ",
+    "                                 WSSQ  VROLRX OQQW  TN NT           TP SW   URRP HJJVT  STT                            ",
+    "                                    URRV   XQKC  TSOPPN NTT       TSM PVTONQK  KST  ZTSSZ                              ",
+    "                                       YROQXX    T    LGD CGGGGGGGC HHNX        ZUPPVZ                                 ",
+    "                                            XQMNTXSOOW                 XTONQPLNUZ                                      ",
+    "                                                    ZXRLIHHGGGGGGGHHKOUY                                               ",
+];
+
+const WORDMARK: &[&str] = &[
+    " _       _    _ __  __  ___  _   _ ",
+    " | |     | |  | |  \\/  |/ _ \\| \\ | |",
+    "| |     | |  | | \\  / | | | |  \\| |",
+    " | |     | |  | | |\\/| | | | | . ` |",
+    "| |___  | |__| | |  | | |_| | |\\  |",
+    " |_____|  \\____/|_|  |_|\\___/|_| \\_|",
+];
+
+const BADGE: &[&str] = &[
+    "╭──────────╮",
+    "│  LUMON   │",
+    "│ INDUSTRY │",
+    "╰──────────╯",
+];
+
+const BADGE_ASCII: &[&str] = &[
+    "+----------+",
+    "|  LUMON   |",
+    "| INDUSTRY |",
+    "+----------+",
+];
+
+const TEXT: &[&str] = &["LUMON INDUSTRIES"];
+
+/// One variant of the logo, largest first
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Logo {
+    /// The full globe, for the loading screen of a full-size window
+    Globe,
+    /// "LUMON" in large letters
+    Wordmark,
+    /// A boxed badge that fits in the title bar
+    Badge,
+    /// The name on one line
+    Text,
+}
+
+impl Logo {
+    pub const ALL: [Logo; 4] = [Logo::Globe, Logo::Wordmark, Logo::Badge, Logo::Text];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Logo::Globe => "globe",
+            Logo::Wordmark => "wordmark",
+            Logo::Badge => "badge",
+            Logo::Text => "text",
+        }
+    }
+
+    /// The art, line by line; `ascii` swaps out box drawing characters
+    pub fn lines(self, ascii: bool) -> &'static [&'static str] {
+        match self {
+            Logo::Globe => GLOBE,
+            Logo::Wordmark => WORDMARK,
+            Logo::Badge if ascii => BADGE_ASCII,
+            Logo::Badge => BADGE,
+            Logo::Text => TEXT,
+        }
+    }
+
+    pub fn width(self) -> u16 {
+        self.lines(false).iter().map(|line| line.chars().count()).max().unwrap_or(0) as u16
+    }
+
+    pub fn height(self) -> u16 {
+        self.lines(false).len() as u16
+    }
+
+    pub fn fits(self, room: Rect) -> bool {
+        self.width() <= room.width && self.height() <= room.height
+    }
+
+    /// The largest logo a window of this profile should show
+    pub fn for_profile(profile: LayoutProfile) -> Self {
+        match profile {
+            LayoutProfile::Full => Logo::Globe,
+            LayoutProfile::Standard => Logo::Wordmark,
+            LayoutProfile::Compact => Logo::Text,
+        }
+    }
+
+    /// The logo to draw in `room`: the configured one, or the profile's,
+    /// stepping down to smaller variants until one fits. None if even the
+    /// text does not.
+    pub fn pick(configured: Option<Logo>, profile: LayoutProfile, room: Rect) -> Option<Self> {
+        let largest = configured.unwrap_or(Logo::for_profile(profile));
+        Logo::ALL
+            .into_iter()
+            .skip_while(|&logo| logo != largest)
+            .find(|&logo| logo.fits(room))
+    }
+}
+
+impl FromStr for Logo {
+    type Err = MdrError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        Logo::ALL
+            .into_iter()
+            .find(|logo| logo.name().eq_ignore_ascii_case(text.trim()))
+            .ok_or_else(|| MdrError::Config(format!("'{}' is not a logo (globe, wordmark, badge or text)", text)))
+    }
+}
@@ -0,0 +1,107 @@
+//! Caps on what the app keeps for the length of a session, so a day-long
+//! shift holds no more in memory than the first hour. Every collection
+//! that grows as the refiner works has a cap here and gives up its oldest
+//! (or least useful) entries once it is full. The caps can be changed in
+//! `config.toml`:
+//!
+//! ```toml
+//! [budget]
+//! replaced_numbers = 4096   # refined cells remembered on the grid
+//! event_log = 50            # session events in the log panel
+//! inbox = 20                # notices in the inbox panel
+//! deposits = 20             # recent deposits kept for each bin
+//! refiners = 16             # work/life balances kept for this session
+//! notes = 200               # lines in the session journal
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+
+/// Largest cap the config may set
+pub const MAX_CAP: usize = 1_000_000;
+
+/// How many entries each collection may hold
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Budget {
+    /// Grid cells holding a new digit since their number was refined
+    pub replaced_numbers: usize,
+    pub event_log: usize,
+    pub inbox: usize,
+    /// Per bin
+    pub deposits: usize,
+    /// Refiners with a work/life balance this session
+    pub refiners: usize,
+    /// Lines in the session journal
+    pub notes: usize,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self {
+            replaced_numbers: 4096,
+            event_log: 50,
+            inbox: 20,
+            deposits: 20,
+            refiners: 16,
+            notes: 200,
+        }
+    }
+}
+
+impl Budget {
+    /// Each cap by its config key
+    pub fn caps_mut(&mut self) -> [(&'static str, &mut usize); 6] {
+        [
+            ("replaced_numbers", &mut self.replaced_numbers),
+            ("event_log", &mut self.event_log),
+            ("inbox", &mut self.inbox),
+            ("deposits", &mut self.deposits),
+            ("refiners", &mut self.refiners),
+            ("notes", &mut self.notes),
+        ]
+    }
+}
+
+/// How full one collection is, for the debug HUD
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Usage {
+    pub name: &'static str,
+    pub used: usize,
+    pub cap: usize,
+}
+
+/// Add `item` at the back, dropping from the front to stay within `cap`
+pub fn push_capped<T>(queue: &mut VecDeque<T>, item: T, cap: usize) {
+    queue.push_back(item);
+    while queue.len() > cap {
+        queue.pop_front();
+    }
+}
+
+/// Bring `cells` down to `cap`. Cells `on_grid` says are not on screen go
+/// first; after them, the ones furthest down and to the right.
+pub fn trim_cells<V>(cells: &mut HashMap<(usize, usize), V>, cap: usize, on_grid: impl Fn((usize, usize)) -> bool) {
+    if cells.len() <= cap {
+        return;
+    }
+    let mut keys: Vec<(usize, usize)> = cells.keys().copied().collect();
+    // Kept cells sort first: on screen, then nearest the top left
+    keys.sort_by_key(|&(col, row)| (!on_grid((col, row)), row, col));
+    for key in keys.drain(cap..) {
+        cells.remove(&key);
+    }
+}
+
+/// Bring `map` down to `cap`, keeping `keep` and otherwise the entries
+/// `worth` ranks highest
+pub fn trim_map<K: Eq + Hash + Clone, V, W: Ord>(map: &mut HashMap<K, V>, cap: usize, keep: &K, worth: impl Fn(&V) -> W) {
+    if map.len() <= cap {
+        return;
+    }
+    let mut keys: Vec<K> = map.keys().filter(|key| *key != keep).cloned().collect();
+    keys.sort_by_key(|key| std::cmp::Reverse(worth(&map[key])));
+    let room = cap.saturating_sub(usize::from(map.contains_key(keep)));
+    for key in keys.drain(room.min(keys.len())..) {
+        map.remove(&key);
+    }
+}
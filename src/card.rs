@@ -0,0 +1,138 @@
+//! Completion cards: a boxed summary of a finished file, for pasting into
+//! a chat. It names the refiner, the file, how long it took, the prizes
+//! won this session and the file's share code, so friends can race it.
+//!
+//! The card is plain text with ANSI colours and knows nothing of the TUI.
+//! `chat` fences it as an `ansi` code block, which Discord shows in
+//! colour; `plain` leaves the colours out for chats that would show the
+//! escape codes instead. A copy is written to the cards folder as each
+//! file is completed.
+
+use crate::error::{MdrError, Result};
+use crate::ledger;
+use crate::race;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Characters between the card's borders
+const CARD_WIDTH: usize = 42;
+
+const BORDER: &str = "\x1b[36m";
+const TITLE: &str = "\x1b[1;37m";
+const LABEL: &str = "\x1b[2m";
+const VALUE: &str = "\x1b[37m";
+const CODE: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+/// What a completion card shows
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompletionCard {
+    pub employee: String,
+    pub file: String,
+    /// How long the file took; `None` for a file resumed from a save
+    pub time: Option<Duration>,
+    /// Prizes won this session, the file's own last
+    pub prizes: Vec<String>,
+    pub code: String,
+    /// Day completed, in days since 1970
+    pub day: i64,
+}
+
+// One line inside the border: a label and its value, or a heading
+enum Row<'a> {
+    Title(&'a str),
+    Field(&'a str, String, &'static str),
+    Blank,
+}
+
+impl CompletionCard {
+    /// The card without colours
+    pub fn plain(&self) -> String {
+        self.render(false)
+    }
+
+    /// The card in ANSI colours
+    pub fn ansi(&self) -> String {
+        self.render(true)
+    }
+
+    /// The coloured card in a code block, ready to paste into a chat
+    pub fn chat(&self) -> String {
+        format!("```ansi\n{}\n```", self.ansi())
+    }
+
+    /// Write the coloured card to `dir` as `<day>-<file>.txt`, returning
+    /// where it went. `cat` shows it in colour.
+    pub fn store(&self, dir: &Path) -> Result<PathBuf> {
+        let slug: String = self
+            .file
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+            .collect();
+        let path = dir.join(format!("{}-{}.txt", ledger::format_day(self.day), slug.trim_matches('-')));
+        fs::create_dir_all(dir)
+            .and_then(|()| fs::write(&path, format!("{}\n", self.ansi())))
+            .map_err(|err| MdrError::Save(format!("{}: {}", path.display(), err)))?;
+        Ok(path)
+    }
+
+    fn rows(&self) -> Vec<Row<'_>> {
+        let time = self.time.map_or_else(|| "untimed (resumed)".to_string(), race::format_time);
+        let prizes = match self.prizes.split_last() {
+            Some((last, [])) => last.clone(),
+            Some((last, earlier)) => format!("{} (+{} earlier)", last, earlier.len()),
+            None => "none".to_string(),
+        };
+        vec![
+            Row::Title("LUMON INDUSTRIES"),
+            Row::Title("FILE COMPLETE"),
+            Row::Blank,
+            Row::Field("Refiner", self.employee.clone(), VALUE),
+            Row::Field("File", self.file.clone(), VALUE),
+            Row::Field("Time", time, VALUE),
+            Row::Field("Prize", prizes, VALUE),
+            Row::Field("Date", ledger::format_day(self.day), VALUE),
+            Row::Blank,
+            Row::Field("Share code", self.code.clone(), CODE),
+        ]
+    }
+
+    fn render(&self, colour: bool) -> String {
+        let paint = |style: &str, text: &str| if colour { format!("{}{}{}", style, text, RESET) } else { text.to_string() };
+        let rule = "═".repeat(CARD_WIDTH);
+        let edge = paint(BORDER, "║");
+
+        let mut lines = vec![paint(BORDER, &format!("╔{}╗", rule))];
+        for row in self.rows() {
+            let inner = match row {
+                Row::Title(text) => paint(TITLE, &format!("{:^width$}", text, width = CARD_WIDTH)),
+                Row::Blank => " ".repeat(CARD_WIDTH),
+                Row::Field(label, value, style) => {
+                    let label = format!("  {:<12}", format!("{}:", label));
+                    let room = CARD_WIDTH - label.chars().count();
+                    let value = fit(&value, room);
+                    let pad = " ".repeat(room - value.chars().count());
+                    format!("{}{}{}", paint(LABEL, &label), paint(style, &value), pad)
+                },
+            };
+            lines.push(format!("{}{}{}", edge, inner, edge));
+        }
+        lines.push(paint(BORDER, &format!("╚{}╝", rule)));
+        lines.join("\n")
+    }
+}
+
+/// Where cards are kept: `cards` in the data directory
+pub fn default_dir() -> Option<PathBuf> {
+    Some(crate::paths::data_dir()?.join("cards"))
+}
+
+// Cut `text` short to `room` characters, ending in "..." if it had to be
+fn fit(text: &str, room: usize) -> String {
+    if text.chars().count() <= room {
+        text.to_string()
+    } else {
+        text.chars().take(room.saturating_sub(3)).chain("...".chars()).collect()
+    }
+}
@@ -0,0 +1,81 @@
+//! The hidden cheat console, for development and demos. The Konami code
+//! opens it on the main screen; each line typed there is one command.
+
+use crate::app::{App, ScaryCluster};
+use crate::error::{MdrError, Result};
+use crate::random::Stream;
+use rand::Rng;
+use std::str::FromStr;
+
+/// Commands and what they do, as listed in the console
+pub const HELP: &[(&str, &str)] = &[
+    ("fill <1-5>", "fill one bin"),
+    ("complete", "fill every bin"),
+    ("cluster", "plant a scary cluster"),
+    ("prize <name>", "award this prize next"),
+];
+
+/// Side of the square a planted cluster covers
+const CLUSTER_SIZE: usize = 3;
+/// Clusters are planted in this corner of the grid, which exists at every
+/// supported window size
+const CLUSTER_AREA: (usize, usize) = (12, 6);
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Cheat {
+    FillBin(usize),
+    CompleteFile,
+    ScaryCluster,
+    Prize(String),
+}
+
+impl FromStr for Cheat {
+    type Err = MdrError;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let line = line.trim();
+        let (name, arg) = line.split_once(' ').map_or((line, ""), |(name, arg)| (name, arg.trim()));
+        match (name, arg) {
+            ("fill", bin) => match bin.parse::<usize>() {
+                Ok(bin @ 1..=5) => Ok(Cheat::FillBin(bin - 1)),
+                _ => Err(MdrError::Config("fill needs a bin from 1 to 5".to_string())),
+            },
+            ("complete", "") => Ok(Cheat::CompleteFile),
+            ("cluster", "") => Ok(Cheat::ScaryCluster),
+            ("prize", "") => Err(MdrError::Config("prize needs a name".to_string())),
+            ("prize", prize) => Ok(Cheat::Prize(prize.to_string())),
+            _ => Err(MdrError::Config(format!("unknown cheat '{}'", line))),
+        }
+    }
+}
+
+/// Carry out a cheat, returning what happened
+pub fn apply(app: &mut App, cheat: Cheat) -> String {
+    match cheat {
+        Cheat::FillBin(bin) => {
+            app.deposit(bin, 100);
+            format!("Bin {:02} filled", bin + 1)
+        },
+        Cheat::CompleteFile => {
+            for bin in 0..app.containers.len() {
+                app.deposit(bin, 100);
+            }
+            format!("{} complete", app.file_name)
+        },
+        Cheat::ScaryCluster => {
+            let rng = app.random.stream(Stream::Grid);
+            let col = rng.random_range(0..=CLUSTER_AREA.0 - CLUSTER_SIZE);
+            let row = rng.random_range(0..=CLUSTER_AREA.1 - CLUSTER_SIZE);
+            let cells = (0..CLUSTER_SIZE)
+                .flat_map(|dy| (0..CLUSTER_SIZE).map(move |dx| (col + dx, row + dy)))
+                .collect();
+            app.scary_clusters.push(ScaryCluster::new(cells));
+            "Something in the grid feels wrong".to_string()
+        },
+        Cheat::Prize(prize) => {
+            let text = format!("Next prize: {}", prize);
+            app.forced_prize = Some(prize);
+            text
+        },
+    }
+}
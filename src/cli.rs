@@ -0,0 +1,113 @@
+use crate::error::{MdrError, Result};
+use crate::race::Target;
+use crate::share::ShareCode;
+use std::path::PathBuf;
+
+/// Subcommands understood by the binary
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Command {
+    /// Launch the TUI (default)
+    #[default]
+    Run,
+    /// Print environment diagnostics and exit
+    Doctor,
+    /// Bundle the config and saved sessions into one file
+    ExportProfile,
+    /// Unpack a bundle written by `export-profile`
+    ImportProfile,
+    /// Check the config file and list its problems, without the TUI
+    ConfigCheck,
+}
+
+/// Parsed command line
+#[derive(Default)]
+pub struct Options {
+    pub command: Command,
+    /// Start with the frame timing overlay visible
+    pub debug_hud: bool,
+    /// Low-power mode: 2 FPS, no wiggle or magnification effects
+    pub eco: bool,
+    /// Hide grid digits except those near the cursor, for screenshots and streams
+    pub redacted: bool,
+    /// Black and white with nothing moving, for e-ink and mono displays
+    pub mono: bool,
+    /// Keep this save file updated with the live session for observers
+    pub publish: Option<PathBuf>,
+    /// Mirror the session published to this save file, read-only
+    pub observe: Option<PathBuf>,
+    /// FIFO or file to read external progress commands from
+    pub inject: Option<PathBuf>,
+    /// Address to serve Prometheus metrics on, e.g. `127.0.0.1:9184`
+    pub metrics: Option<String>,
+    /// Profile bundle to write or read
+    pub bundle: Option<PathBuf>,
+    /// Config file for `config check`, instead of the usual one
+    pub config_file: Option<PathBuf>,
+    /// On import, overwrite differing files (after backing them up)
+    pub replace: bool,
+    /// Refine the file a friend shared instead of a fresh one
+    pub code: Option<ShareCode>,
+    /// Race the file (the shared one, with `--code`) against this time
+    pub race: Option<Target>,
+    /// Write each drawn frame here: an asciicast if it ends in `.cast`,
+    /// otherwise a directory of ANSI text files
+    pub record_frames: Option<PathBuf>,
+    /// Master seed for every random draw, to replay a run exactly
+    pub seed: Option<u64>,
+}
+
+/// Parse command line arguments (without the program name)
+pub fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Options> {
+    let mut options = Options::default();
+    let mut args = args.into_iter().peekable();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "doctor" => options.command = Command::Doctor,
+            "export-profile" | "import-profile" => {
+                options.command = if arg == "export-profile" { Command::ExportProfile } else { Command::ImportProfile };
+                options.bundle = Some(flag_value(&arg, args.next())?.into());
+            },
+            "config" => {
+                if args.next().as_deref() != Some("check") {
+                    return Err(MdrError::Config("usage: config check [path]".to_string()));
+                }
+                options.command = Command::ConfigCheck;
+                options.config_file = args.next_if(|path| !path.starts_with("--")).map(PathBuf::from);
+            },
+            "--replace" => options.replace = true,
+            "--debug-hud" => options.debug_hud = true,
+            "--eco" => options.eco = true,
+            "--redacted" => options.redacted = true,
+            "--mono" => options.mono = true,
+            "--publish" => options.publish = Some(flag_value(&arg, args.next())?.into()),
+            "--observe" => options.observe = Some(flag_value(&arg, args.next())?.into()),
+            "--inject" => options.inject = Some(flag_value(&arg, args.next())?.into()),
+            "--metrics" => options.metrics = Some(flag_value(&arg, args.next())?),
+            "--code" => options.code = Some(flag_value(&arg, args.next())?.parse()?),
+            "--record-frames" => options.record_frames = Some(flag_value(&arg, args.next())?.into()),
+            "--seed" => {
+                let value = flag_value(&arg, args.next())?;
+                options.seed = Some(value.parse().map_err(|_| MdrError::Config(format!("bad seed '{}'", value)))?);
+            },
+            "--race" => options.race = Some(flag_value(&arg, args.next())?.parse()?),
+            other => return Err(MdrError::Config(format!("unrecognized argument '{}'", other))),
+        }
+    }
+
+    if options.replace && options.command != Command::ImportProfile {
+        return Err(MdrError::Config("--replace only applies to import-profile".to_string()));
+    }
+
+    if options.publish.is_some() && options.observe.is_some() {
+        return Err(MdrError::Config("--publish and --observe cannot be used together".to_string()));
+    }
+
+    Ok(options)
+}
+
+fn flag_value(flag: &str, value: Option<String>) -> Result<String> {
+    value
+        .filter(|v| !v.starts_with("--"))
+        .ok_or_else(|| MdrError::Config(format!("{} needs a value", flag)))
+}
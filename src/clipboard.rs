@@ -0,0 +1,173 @@
+//! Copying text out of the app for pasting elsewhere: the performance
+//! report, the file's seed, or the whole screen as text.
+//!
+//! Text is sent to the terminal in an OSC 52 sequence, and the terminal
+//! puts it on the clipboard of the machine it runs on. That works over SSH
+//! and through tmux or screen, where no clipboard library can reach the
+//! refiner's desktop. Terminals known to ignore OSC 52 are skipped, and so
+//! is text too large for terminals to accept; builds with the `clipboard`
+//! feature then write the system clipboard directly instead.
+
+use crate::config::ClipboardConfig;
+use crate::environment::{self, Environment, Multiplexer};
+use ratatui::buffer::Buffer;
+use std::fmt;
+use std::io::{self, Write};
+
+/// Largest OSC 52 sequence sent by default. Terminals cap what they will
+/// take (xterm and hterm at about 100 kB) and drop anything longer.
+pub const DEFAULT_MAX_BYTES: usize = 100_000;
+
+/// How text reached the clipboard
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    /// Handed to the terminal with OSC 52
+    Terminal,
+    /// Written to the system clipboard
+    Native,
+}
+
+/// Why text could not be copied
+#[derive(Debug)]
+pub enum CopyError {
+    /// The OSC 52 sequence would be longer than the terminal takes
+    TooLarge { bytes: usize, max: usize },
+    /// The terminal does not take OSC 52 and there is no other way
+    Unsupported,
+    /// Writing to the terminal failed
+    Io(io::Error),
+}
+
+impl fmt::Display for CopyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CopyError::TooLarge { bytes, max } => write!(f, "{} bytes is over the terminal's {}", bytes, max),
+            CopyError::Unsupported => write!(f, "this terminal has no clipboard to copy to"),
+            CopyError::Io(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+pub struct Clipboard {
+    multiplexer: Option<Multiplexer>,
+    /// Where OSC 52 sequences are written; nowhere until `to_terminal`
+    out: Option<Box<dyn Write>>,
+    /// Whether the terminal was detected as taking OSC 52
+    osc52: bool,
+    #[cfg(feature = "clipboard")]
+    // Kept open: on X11 the copied text goes when its owner does
+    native: Option<arboard::Clipboard>,
+    /// The last text copied
+    pub last: Option<String>,
+}
+
+// Writes nowhere, as if to a terminal that takes everything
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self {
+            multiplexer: None,
+            out: None,
+            osc52: true,
+            #[cfg(feature = "clipboard")]
+            native: None,
+            last: None,
+        }
+    }
+}
+
+impl Clipboard {
+    /// Copy through the terminal on `out`, as far as `env` allows
+    pub fn to_terminal(out: Box<dyn Write>, env: &Environment) -> Self {
+        Self { multiplexer: env.multiplexer, out: Some(out), osc52: takes_osc52(env), ..Self::default() }
+    }
+
+    /// Whether `config` has text sent through the terminal, the detected
+    /// answer unless it says otherwise
+    pub fn uses_osc52(&self, config: &ClipboardConfig) -> bool {
+        config.osc52.unwrap_or(self.osc52)
+    }
+
+    /// Put `text` on the clipboard: through the terminal if it will take
+    /// it, otherwise natively where the build allows
+    pub fn copy(&mut self, text: &str, config: &ClipboardConfig) -> Result<Method, CopyError> {
+        let sequence = osc52(text);
+        let fits = sequence.len() <= config.max_bytes;
+        let method = if self.uses_osc52(config) && fits {
+            if let Some(out) = &mut self.out {
+                out.write_all(environment::passthrough(self.multiplexer, &sequence).as_bytes())
+                    .and_then(|()| out.flush())
+                    .map_err(CopyError::Io)?;
+            }
+            Method::Terminal
+        } else if self.copy_native(text) {
+            Method::Native
+        } else if self.uses_osc52(config) {
+            return Err(CopyError::TooLarge { bytes: sequence.len(), max: config.max_bytes });
+        } else {
+            return Err(CopyError::Unsupported);
+        };
+        self.last = Some(text.to_string());
+        Ok(method)
+    }
+
+    #[cfg(feature = "clipboard")]
+    fn copy_native(&mut self, text: &str) -> bool {
+        if self.native.is_none() {
+            self.native = arboard::Clipboard::new().ok();
+        }
+        self.native.as_mut().is_some_and(|native| native.set_text(text).is_ok())
+    }
+
+    #[cfg(not(feature = "clipboard"))]
+    fn copy_native(&mut self, _text: &str) -> bool {
+        false
+    }
+}
+
+/// Whether the terminal in `env` is expected to act on OSC 52. Most do;
+/// the ones that are known not to are ruled out.
+pub fn takes_osc52(env: &Environment) -> bool {
+    // The Linux console and dumb terminals have no clipboard at all
+    let plain = matches!(env.term.as_str(), "" | "dumb" | "linux");
+    // Terminal.app ignores the sequence
+    let ignores = env.term_program == "Apple_Terminal";
+    !plain && !ignores
+}
+
+/// The OSC 52 sequence that sets the clipboard to `text`
+pub fn osc52(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+/// Standard base64 with padding
+pub fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], chunk.get(1).copied().unwrap_or(0), chunk.get(2).copied().unwrap_or(0)];
+        let group = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for idx in 0..4 {
+            if idx <= chunk.len() {
+                encoded.push(ALPHABET[(group >> (18 - 6 * idx) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// A drawn screen as plain text, one line per row with trailing blanks cut
+pub fn screen_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut lines = Vec::with_capacity(area.height as usize);
+    for y in area.y..area.y + area.height {
+        let row: String = (area.x..area.x + area.width).map(|x| buffer.get(x, y).symbol.as_str()).collect();
+        lines.push(row.trim_end().to_string());
+    }
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
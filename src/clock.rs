@@ -0,0 +1,69 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Source of time for the event loop and every duration-based rule
+pub trait Clock {
+    /// Monotonic time, used for ticks and timers
+    fn now(&self) -> Instant;
+
+    /// Wall-clock time, used for anything shown to or scheduled by the user
+    fn wall(&self) -> SystemTime;
+}
+
+/// The real clock
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn wall(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that only moves when told to. Clones share the same time, so a
+/// test can keep one handle and give another to the app.
+#[derive(Clone)]
+pub struct MockClock {
+    start: Instant,
+    wall_start: SystemTime,
+    elapsed: Rc<Cell<Duration>>,
+}
+
+impl MockClock {
+    pub fn new() -> Self {
+        Self {
+            start: Instant::now(),
+            wall_start: SystemTime::now(),
+            elapsed: Rc::new(Cell::new(Duration::ZERO)),
+        }
+    }
+
+    /// Start the wall clock at a specific time
+    pub fn at(wall_start: SystemTime) -> Self {
+        Self { wall_start, ..Self::new() }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        self.elapsed.set(self.elapsed.get() + by);
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.start + self.elapsed.get()
+    }
+
+    fn wall(&self) -> SystemTime {
+        self.wall_start + self.elapsed.get()
+    }
+}
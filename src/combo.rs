@@ -0,0 +1,52 @@
+//! Combos: a single capture holding three or more of the same digit earns
+//! the bin a bonus and a banner across the grid.
+
+use std::fmt;
+
+/// Fewest matching digits that count as a combo
+pub const COMBO_MIN: usize = 3;
+/// Extra progress for each matching digit past the second
+pub const COMBO_BONUS: u16 = 5;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Combo {
+    pub digit: u16,
+    pub count: usize,
+}
+
+impl Combo {
+    /// The biggest group of equal digits in one capture, if it is big
+    /// enough. Higher digits win ties.
+    pub fn find(digits: &[u16]) -> Option<Combo> {
+        Combo::find_at_least(digits, COMBO_MIN)
+    }
+
+    /// As `find`, with groups of `min` or more counting
+    pub fn find_at_least(digits: &[u16], min: usize) -> Option<Combo> {
+        let mut counts = [0usize; 10];
+        for &digit in digits.iter().filter(|&&d| d < 10) {
+            counts[digit as usize] += 1;
+        }
+        (0..10u16)
+            .map(|digit| Combo { digit, count: counts[digit as usize] })
+            .filter(|combo| combo.count >= min.max(1))
+            .max_by_key(|combo| (combo.count, combo.digit))
+    }
+
+    /// Progress added on top of the captured numbers' sum
+    pub fn bonus(&self) -> u16 {
+        (self.count - 2) as u16 * COMBO_BONUS
+    }
+}
+
+/// The banner text, e.g. "TRIPLE 7s"
+impl fmt::Display for Combo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.count {
+            3 => write!(f, "TRIPLE {}s", self.digit),
+            4 => write!(f, "QUADRUPLE {}s", self.digit),
+            5 => write!(f, "QUINTUPLE {}s", self.digit),
+            count => write!(f, "{}x {}s", count, self.digit),
+        }
+    }
+}
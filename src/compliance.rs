@@ -0,0 +1,86 @@
+//! Compliance checks: once in a long while, refinement stops and the
+//! refiner is asked to retype a line from the handbook before the time
+//! runs out.
+//!
+//! A check retyped in time adds `BONUS` to a bin; one left unfinished,
+//! refused with Esc or submitted wrong costs `STREAK_PENALTY` from the
+//! streak. Checks come at random, most of an hour apart on average, and
+//! are off unless `enabled` in `[compliance]` turns them on.
+
+use crate::random;
+use rand::Rng;
+use std::time::{Duration, Instant};
+
+/// How long the refiner has to retype the phrase
+pub const TIME_LIMIT: Duration = Duration::from_secs(20);
+/// Progress added to a bin for a check passed
+pub const BONUS: u16 = 10;
+/// Captures taken off the streak for a check failed
+pub const STREAK_PENALTY: u32 = 5;
+/// Average wait between checks
+const MEAN_GAP: Duration = Duration::from_secs(40 * 60);
+/// Shortest wait between checks
+const MIN_GAP: Duration = Duration::from_secs(10 * 60);
+/// Longest wait between checks
+const MAX_GAP: Duration = Duration::from_secs(2 * 3600);
+
+const PHRASES: &[&str] = &[
+    "The work is mysterious and important",
+    "Render not unto others",
+    "I am grateful for my work",
+    "Tame the tempers of the numbers",
+    "Every refiner is a credit to Lumon",
+    "Come now children of my industry",
+];
+
+/// A wait before the next check
+pub fn gap(rng: &mut impl Rng) -> Duration {
+    random::exponential(rng, MEAN_GAP, (MIN_GAP, MAX_GAP))
+}
+
+/// A check under way
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComplianceCheck {
+    pub phrase: &'static str,
+    pub typed: String,
+    pub since: Instant,
+}
+
+impl ComplianceCheck {
+    /// A check on a phrase picked with `rng`, starting at `now`
+    pub fn new(rng: &mut impl Rng, now: Instant) -> Self {
+        Self { phrase: PHRASES[rng.random_range(0..PHRASES.len())], typed: String::new(), since: now }
+    }
+
+    /// Type `c`, unless the line is already longer than the phrase could
+    /// need
+    pub fn push(&mut self, c: char) {
+        if self.typed.chars().count() < self.phrase.chars().count() + 8 {
+            self.typed.push(c);
+        }
+    }
+
+    pub fn pop(&mut self) {
+        self.typed.pop();
+    }
+
+    /// Whether what is typed so far could still become the phrase
+    pub fn on_track(&self) -> bool {
+        self.phrase.to_lowercase().starts_with(&self.typed.to_lowercase())
+    }
+
+    /// Whether the phrase has been typed in full. Case is forgiven, and
+    /// so are spaces at either end.
+    pub fn is_typed(&self) -> bool {
+        self.typed.trim().eq_ignore_ascii_case(self.phrase)
+    }
+
+    /// Time left at `now`
+    pub fn remaining(&self, now: Instant) -> Duration {
+        TIME_LIMIT.saturating_sub(now.duration_since(self.since))
+    }
+
+    pub fn is_expired(&self, now: Instant) -> bool {
+        self.remaining(now).is_zero()
+    }
+}
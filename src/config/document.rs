@@ -0,0 +1,316 @@
+//! A small TOML-subset reader shared by the config file and data files.
+//!
+//! Supported: comments, `[table]` and `[[array.of.tables]]` headers, and
+//! `key = value` pairs where a value is a boolean, integer, float, quoted
+//! string, or array of those (which may span several lines). Every value
+//! remembers the line it starts on so callers can point at the exact spot
+//! when validation fails.
+
+use crate::error::{MdrError, Result};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    Str(String),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    /// Human-readable type name for error messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::Bool(_) => "boolean",
+            Value::Integer(_) => "integer",
+            Value::Float(_) => "float",
+            Value::Str(_) => "string",
+            Value::Array(_) => "array",
+        }
+    }
+}
+
+/// One `key = value` line
+#[derive(Clone, Debug)]
+pub struct Entry {
+    pub key: String,
+    pub value: Value,
+    pub line: usize,
+}
+
+/// A `[table]` or one element of a `[[table]]` array, with its entries.
+/// Keys before the first header live in a table with an empty name.
+#[derive(Clone, Debug)]
+pub struct Table {
+    pub name: String,
+    pub is_array_element: bool,
+    pub line: usize,
+    pub entries: Vec<Entry>,
+}
+
+impl Table {
+    pub fn get(&self, key: &str) -> Option<&Entry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Document {
+    pub tables: Vec<Table>,
+}
+
+impl Document {
+    pub fn parse(source: &str) -> Result<Document> {
+        let mut tables = vec![Table {
+            name: String::new(),
+            is_array_element: false,
+            line: 0,
+            entries: Vec::new(),
+        }];
+
+        let mut lines = source.lines().enumerate();
+        while let Some((idx, raw)) = lines.next() {
+            let line_no = idx + 1;
+            let mut line = strip_comment(raw).trim().to_string();
+            if line.is_empty() {
+                continue;
+            }
+
+            // An array left open continues on the following lines
+            if line.contains('=') {
+                while open_brackets(&line) > 0 {
+                    let (_, next) = lines.next().ok_or_else(|| error_at(line_no, "array is never closed"))?;
+                    line.push(' ');
+                    line.push_str(strip_comment(next).trim());
+                }
+            }
+            let line = line.as_str();
+
+            if let Some(name) = line.strip_prefix("[[").and_then(|l| l.strip_suffix("]]")) {
+                tables.push(Table {
+                    name: parse_table_name(name, line_no)?,
+                    is_array_element: true,
+                    line: line_no,
+                    entries: Vec::new(),
+                });
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let name = parse_table_name(name, line_no)?;
+                if tables.iter().any(|t| t.name == name && !t.is_array_element) {
+                    return Err(error_at(line_no, format!("table [{}] is defined twice", name)));
+                }
+                tables.push(Table {
+                    name,
+                    is_array_element: false,
+                    line: line_no,
+                    entries: Vec::new(),
+                });
+                continue;
+            }
+
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| error_at(line_no, format!("expected `key = value`, found '{}'", line)))?;
+            let key = key.trim();
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+                return Err(error_at(line_no, format!("invalid key '{}'", key)));
+            }
+
+            let value = parse_value(value.trim(), line_no)?;
+            let table = tables.last_mut().expect("root table");
+            if table.get(key).is_some() {
+                return Err(error_at(line_no, format!("key '{}' is defined twice", key)));
+            }
+            table.entries.push(Entry { key: key.to_string(), value, line: line_no });
+        }
+
+        Ok(Document { tables })
+    }
+
+    /// The plain table with the given name (empty string for the root)
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.iter().find(|t| t.name == name && !t.is_array_element)
+    }
+
+    /// Every element of a `[[name]]` array, in file order
+    pub fn array_tables<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Table> + 'a {
+        self.tables.iter().filter(move |t| t.name == name && t.is_array_element)
+    }
+}
+
+/// Build a config error that points at a line
+pub fn error_at(line: usize, message: impl AsRef<str>) -> MdrError {
+    MdrError::Config(format!("line {}: {}", line, message.as_ref()))
+}
+
+fn parse_table_name(name: &str, line: usize) -> Result<String> {
+    let name = name.trim();
+    let valid = !name.is_empty()
+        && name.split('.').all(|part| {
+            !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        });
+    if valid {
+        Ok(name.to_string())
+    } else {
+        Err(error_at(line, format!("invalid table name '{}'", name)))
+    }
+}
+
+// Drop a trailing `# comment`, ignoring any `#` inside a quoted string
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (idx, c) in line.char_indices() {
+        match c {
+            '\\' if in_string && !escaped => {
+                escaped = true;
+                continue;
+            },
+            '"' if !escaped => in_string = !in_string,
+            '#' if !in_string => return &line[..idx],
+            _ => {},
+        }
+        escaped = false;
+    }
+    line
+}
+
+// How many `[` are still unclosed, ignoring brackets inside strings
+fn open_brackets(line: &str) -> i32 {
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in line.chars() {
+        match c {
+            '\\' if in_string && !escaped => {
+                escaped = true;
+                continue;
+            },
+            '"' if !escaped => in_string = !in_string,
+            '[' if !in_string => depth += 1,
+            ']' if !in_string => depth -= 1,
+            _ => {},
+        }
+        escaped = false;
+    }
+    depth
+}
+
+fn parse_value(raw: &str, line: usize) -> Result<Value> {
+    let (value, rest) = parse_value_prefix(raw, line)?;
+    if !rest.trim().is_empty() {
+        return Err(error_at(line, format!("unexpected '{}' after value", rest.trim())));
+    }
+    Ok(value)
+}
+
+// Parse one value from the start of `raw`, returning it and the unparsed rest
+fn parse_value_prefix(raw: &str, line: usize) -> Result<(Value, &str)> {
+    let raw = raw.trim_start();
+
+    if let Some(rest) = raw.strip_prefix('"') {
+        let mut out = String::new();
+        let mut chars = rest.char_indices();
+        while let Some((idx, c)) = chars.next() {
+            match c {
+                '"' => return Ok((Value::Str(out), &rest[idx + 1..])),
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => out.push('\n'),
+                    Some((_, 't')) => out.push('\t'),
+                    Some((_, '"')) => out.push('"'),
+                    Some((_, '\\')) => out.push('\\'),
+                    _ => return Err(error_at(line, "invalid escape in string")),
+                },
+                c => out.push(c),
+            }
+        }
+        return Err(error_at(line, "unterminated string"));
+    }
+
+    if let Some(mut rest) = raw.strip_prefix('[') {
+        let mut items = Vec::new();
+        loop {
+            rest = rest.trim_start();
+            if let Some(after) = rest.strip_prefix(']') {
+                return Ok((Value::Array(items), after));
+            }
+            let (item, after) = parse_value_prefix(rest, line)?;
+            items.push(item);
+            rest = after.trim_start();
+            if let Some(after) = rest.strip_prefix(',') {
+                rest = after;
+            } else if !rest.starts_with(']') {
+                return Err(error_at(line, "expected ',' or ']' in array"));
+            }
+        }
+    }
+
+    // Bare token: runs until a delimiter
+    let end = raw.find([',', ']']).unwrap_or(raw.len());
+    let (token, rest) = raw.split_at(end);
+    let token = token.trim();
+
+    let value = match token {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => {
+            let digits = token.replace('_', "");
+            if let Ok(n) = digits.parse::<i64>() {
+                Value::Integer(n)
+            } else if let Ok(f) = digits.parse::<f64>() {
+                Value::Float(f)
+            } else if token.is_empty() {
+                return Err(error_at(line, "missing value"));
+            } else {
+                return Err(error_at(line, format!("'{}' is not a valid value (strings need quotes)", token)));
+            }
+        },
+    };
+    Ok((value, rest))
+}
+
+impl Entry {
+    pub fn as_bool(&self) -> Result<bool> {
+        match self.value {
+            Value::Bool(b) => Ok(b),
+            ref other => Err(self.type_error("boolean", other)),
+        }
+    }
+
+    pub fn as_integer(&self) -> Result<i64> {
+        match self.value {
+            Value::Integer(n) => Ok(n),
+            ref other => Err(self.type_error("integer", other)),
+        }
+    }
+
+    pub fn as_str(&self) -> Result<&str> {
+        match &self.value {
+            Value::Str(s) => Ok(s),
+            other => Err(self.type_error("string", other)),
+        }
+    }
+
+    pub fn as_str_array(&self) -> Result<Vec<&str>> {
+        let items = match &self.value {
+            Value::Array(items) => items,
+            other => return Err(self.type_error("array of strings", other)),
+        };
+        items
+            .iter()
+            .map(|item| match item {
+                Value::Str(s) => Ok(s.as_str()),
+                other => Err(error_at(
+                    self.line,
+                    format!("'{}' should only hold strings, found {}", self.key, other.type_name()),
+                )),
+            })
+            .collect()
+    }
+
+    fn type_error(&self, expected: &str, found: &Value) -> MdrError {
+        error_at(self.line, format!("'{}' should be a {}, found {}", self.key, expected, found.type_name()))
+    }
+}
@@ -0,0 +1,661 @@
+//! Workstation settings read from `config.toml`.
+//!
+//! Every setting is optional; a missing file or key falls back to the
+//! defaults below, so a fresh install behaves exactly as before.
+
+pub mod document;
+pub mod schema;
+
+use crate::branding::Logo;
+use crate::budget::{self, Budget};
+use crate::content::{self, Prize};
+use crate::crypto;
+use crate::error::{MdrError, Result};
+use crate::scoring::Difficulty;
+use crate::shift::{self, DAY_NAMES, Shift};
+use crate::strategy::BinStrategy;
+use document::{Document, Table, Value, error_at};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Settings for the prize screen
+#[derive(Clone, Debug, PartialEq)]
+pub struct PrizeConfig {
+    /// Return to a fresh file automatically after the prize is shown
+    pub auto_reset: bool,
+    /// How long the auto-reset countdown runs
+    pub auto_reset_after: Duration,
+    /// Draw from the built-in Severance perks as well as any others
+    pub builtin: bool,
+    /// The refiner's own prizes, from `[[perk]]` tables
+    pub perks: Vec<Prize>,
+}
+
+impl Default for PrizeConfig {
+    fn default() -> Self {
+        Self {
+            auto_reset: false,
+            auto_reset_after: Duration::from_secs(30),
+            builtin: true,
+            perks: Vec::new(),
+        }
+    }
+}
+
+/// Where session events are published (needs the `telemetry` feature)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TelemetryConfig {
+    /// `http://host[:port]/path` to POST JSON events to
+    pub webhook: Option<String>,
+    /// `mqtt://host[:port]/topic` to publish JSON events on
+    pub mqtt: Option<String>,
+}
+
+/// Milestones read out loud (needs the `speech` feature)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SpeechConfig {
+    pub enabled: bool,
+    /// Program to speak with, given the text as its last argument;
+    /// without one the system's own engine is used
+    pub command: Option<String>,
+}
+
+/// How sessions are kept between runs
+#[derive(Clone, Debug, PartialEq)]
+pub struct SaveConfig {
+    /// Seal session files with a passphrase asked for at login
+    pub encrypt: bool,
+    /// Key derivation rounds for newly sealed sessions; more is slower to
+    /// log in and slower to guess
+    pub kdf_iterations: u32,
+}
+
+impl Default for SaveConfig {
+    fn default() -> Self {
+        Self {
+            encrypt: false,
+            kdf_iterations: crypto::DEFAULT_ITERATIONS,
+        }
+    }
+}
+
+/// Whether to look for newer releases at startup
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdatesConfig {
+    /// Off unless asked for: the app makes no network requests by default
+    pub check: bool,
+    /// Changelog to read releases from: a URL or a local file
+    pub feed: String,
+}
+
+impl Default for UpdatesConfig {
+    fn default() -> Self {
+        Self {
+            check: false,
+            feed: crate::updates::DEFAULT_FEED.to_string(),
+        }
+    }
+}
+
+/// How text is copied out of the app
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClipboardConfig {
+    /// Send copies through the terminal with OSC 52; `None` detects
+    /// whether the terminal takes it
+    pub osc52: Option<bool>,
+    /// Longest OSC 52 sequence to send, in bytes
+    pub max_bytes: usize,
+}
+
+impl Default for ClipboardConfig {
+    fn default() -> Self {
+        Self {
+            osc52: None,
+            max_bytes: crate::clipboard::DEFAULT_MAX_BYTES,
+        }
+    }
+}
+
+/// How much the screen moves
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DisplayConfig {
+    /// Low-power mode, as with `--eco`: 2 FPS and a still grid
+    pub eco: bool,
+    /// Keep the grid still and skip hover magnification, at the usual
+    /// frame rate
+    pub reduce_motion: bool,
+    /// Lay the main screen out right to left: bin 01 on the right and
+    /// the side panel on the left
+    pub mirror: bool,
+}
+
+/// When a slow terminal thins out the grid's animation
+#[derive(Clone, Debug, PartialEq)]
+pub struct LagConfig {
+    /// A frame whose output takes longer than this to write is slow
+    pub slow_write: Duration,
+    /// Slow frames in a row before the animation thins out a step, and
+    /// four times as many quick ones before it fills back in
+    pub frames: usize,
+    /// Fewest cells animating at once: one in every `max_stride`. 1 never
+    /// thins the grid.
+    pub max_stride: usize,
+}
+
+impl Default for LagConfig {
+    fn default() -> Self {
+        Self {
+            slow_write: Duration::from_millis(40),
+            frames: 10,
+            max_stride: 4,
+        }
+    }
+}
+
+/// How the refiner gets around
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputConfig {
+    /// Hover and click on the grid; off leaves the terminal's own text
+    /// selection alone
+    pub mouse: bool,
+    /// Capture with the right button and open a bin's details with the
+    /// left, for left-handed mice
+    pub swap_buttons: bool,
+    /// Show each capture at the foot of the grid for a moment before it is
+    /// deposited, so Esc can call it off
+    pub review_captures: bool,
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        Self { mouse: true, swap_buttons: false, review_captures: false }
+    }
+}
+
+/// Breaks earned by filling a bin, for using the app as a focus timer
+#[derive(Clone, Debug, PartialEq)]
+pub struct BreaksConfig {
+    /// Each bin filled starts a break that refinement waits out
+    pub enabled: bool,
+    pub length: Duration,
+}
+
+impl Default for BreaksConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            length: Duration::from_secs(5 * 60),
+        }
+    }
+}
+
+/// How the reset menu makes sure
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConfirmStyle {
+    /// Ask yes or no
+    #[default]
+    Modal,
+    /// Hold Y until the gauge fills
+    Hold,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    pub prize: PrizeConfig,
+    pub save: SaveConfig,
+    /// Working hours; without a `[shift]` table the floor never closes
+    pub shift: Option<Shift>,
+    pub telemetry: TelemetryConfig,
+    pub speech: SpeechConfig,
+    pub updates: UpdatesConfig,
+    /// Name of a content pack theme to use instead of the detected palette
+    pub theme: Option<String>,
+    /// Logo to show instead of the one the window size picks
+    pub logo: Option<Logo>,
+    /// Session goals to start every session with
+    pub goals: Vec<String>,
+    /// How forgiving capture scoring is
+    pub difficulty: Difficulty,
+    /// How resets are confirmed
+    pub reset_confirm: ConfirmStyle,
+    /// How a capture nobody aimed picks its bin
+    pub bin_strategy: BinStrategy,
+    /// Pulse the best cluster now and then; `None` leaves it to the
+    /// difficulty, on only when relaxed
+    pub assist: Option<bool>,
+    /// Now and then flicker the lights, chime a door, deliver a memo or
+    /// put up a PSA
+    pub ambience: bool,
+    /// Now and then stop for a compliance check
+    pub compliance: bool,
+    /// How much each history and cache may hold
+    pub budget: Budget,
+    pub clipboard: ClipboardConfig,
+    pub display: DisplayConfig,
+    pub lag: LagConfig,
+    pub input: InputConfig,
+    pub breaks: BreaksConfig,
+    /// Name filled in on the login screen
+    pub login_name: Option<String>,
+    /// Save and go back to the login screen after this long without
+    /// input; `None` stays logged in
+    pub idle_logout: Option<Duration>,
+}
+
+/// What reloading the config changed, by table
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Reload {
+    /// Changed and already in effect
+    pub applied: Vec<&'static str>,
+    /// Changed, but only read at startup
+    pub pending: Vec<&'static str>,
+}
+
+impl Reload {
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty() && self.pending.is_empty()
+    }
+}
+
+impl Config {
+    /// Take the settings from `new` that are safe to change while running.
+    /// The rest are left as they are until the next launch: they start
+    /// threads, unlock saves, or would change a file part way through.
+    pub fn reload(&mut self, new: Config) -> Reload {
+        let mut reload = Reload::default();
+        let mut hot = |table, changed: bool| {
+            if changed {
+                reload.applied.push(table);
+            }
+        };
+        hot("theme", self.theme != new.theme);
+        hot("branding", self.logo != new.logo);
+        hot("display", self.display != new.display);
+        hot("lag", self.lag != new.lag);
+        hot("prize", self.prize != new.prize);
+        hot("reset", self.reset_confirm != new.reset_confirm);
+        hot("bins", self.bin_strategy != new.bin_strategy);
+        hot("assist", self.assist != new.assist);
+        hot("ambience", self.ambience != new.ambience);
+        hot("compliance", self.compliance != new.compliance);
+        hot("clipboard", self.clipboard != new.clipboard);
+        hot("budget", self.budget != new.budget);
+        hot("input", self.input != new.input);
+        hot("breaks", self.breaks != new.breaks);
+        hot("login", self.login_name != new.login_name || self.idle_logout != new.idle_logout);
+        self.theme = new.theme;
+        self.logo = new.logo;
+        self.display = new.display;
+        self.lag = new.lag;
+        self.prize = new.prize;
+        self.reset_confirm = new.reset_confirm;
+        self.bin_strategy = new.bin_strategy;
+        self.assist = new.assist;
+        self.ambience = new.ambience;
+        self.compliance = new.compliance;
+        self.clipboard = new.clipboard;
+        self.budget = new.budget;
+        self.input = new.input;
+        self.breaks = new.breaks;
+        self.login_name = new.login_name;
+        self.idle_logout = new.idle_logout;
+
+        let cold = [
+            ("save", self.save != new.save),
+            ("shift", self.shift != new.shift),
+            ("telemetry", self.telemetry != new.telemetry),
+            ("speech", self.speech != new.speech),
+            ("updates", self.updates != new.updates),
+            ("goals", self.goals != new.goals),
+            ("scoring", self.difficulty != new.difficulty),
+        ];
+        reload.pending = cold.iter().filter(|(_, changed)| *changed).map(|(table, _)| *table).collect();
+        reload
+    }
+}
+
+impl FromStr for Config {
+    type Err = MdrError;
+
+    fn from_str(source: &str) -> Result<Self> {
+        let doc = Document::parse(source)?;
+        let problems = schema::check(&doc);
+        if let Some(first) = problems.first() {
+            let mut message = first.message.clone();
+            if problems.len() > 1 {
+                message.push_str(&format!(" (and {} more; see `lumon-mdr config check`)", problems.len() - 1));
+            }
+            return Err(error_at(first.line, message));
+        }
+        let mut config = Config::default();
+
+        if let Some(prize) = doc.table("prize") {
+            if let Some(entry) = prize.get("auto_reset") {
+                config.prize.auto_reset = entry.as_bool()?;
+            }
+            if let Some(entry) = prize.get("auto_reset_seconds") {
+                let secs = entry.as_integer()?;
+                if !(1..=3600).contains(&secs) {
+                    return Err(error_at(entry.line, "'auto_reset_seconds' must be between 1 and 3600"));
+                }
+                config.prize.auto_reset_after = Duration::from_secs(secs as u64);
+            }
+            if let Some(entry) = prize.get("builtin") {
+                config.prize.builtin = entry.as_bool()?;
+            }
+        }
+        for table in doc.array_tables("perk") {
+            content::add_prize(&mut config.prize.perks, content::parse_perk(table)?, table.line)?;
+        }
+
+        if let Some(table) = doc.table("save") {
+            if let Some(entry) = table.get("encrypt") {
+                config.save.encrypt = entry.as_bool()?;
+            }
+            if let Some(entry) = table.get("kdf_iterations") {
+                let rounds = entry.as_integer()?;
+                if !(100_000..=10_000_000).contains(&rounds) {
+                    return Err(error_at(entry.line, "'kdf_iterations' must be between 100000 and 10000000"));
+                }
+                config.save.kdf_iterations = rounds as u32;
+            }
+        }
+
+        if let Some(table) = doc.table("shift") {
+            config.shift = Some(parse_shift(table)?);
+        }
+
+        if let Some(table) = doc.table("telemetry") {
+            if let Some(entry) = table.get("webhook") {
+                config.telemetry.webhook = Some(entry.as_str()?.to_string());
+            }
+            if let Some(entry) = table.get("mqtt") {
+                config.telemetry.mqtt = Some(entry.as_str()?.to_string());
+            }
+        }
+
+        if let Some(table) = doc.table("speech") {
+            if let Some(entry) = table.get("enabled") {
+                config.speech.enabled = entry.as_bool()?;
+            }
+            if let Some(entry) = table.get("command") {
+                let command = entry.as_str()?.trim();
+                if command.is_empty() {
+                    return Err(error_at(entry.line, "'command' must not be empty"));
+                }
+                config.speech.command = Some(command.to_string());
+            }
+        }
+
+        if let Some(table) = doc.table("theme")
+            && let Some(entry) = table.get("name")
+        {
+            config.theme = Some(entry.as_str()?.to_string());
+        }
+
+        if let Some(table) = doc.table("branding")
+            && let Some(entry) = table.get("logo")
+        {
+            config.logo = match entry.as_str()? {
+                "auto" => None,
+                name => Some(name.parse().map_err(|_| {
+                    error_at(entry.line, "'logo' must be \"auto\", \"globe\", \"wordmark\", \"badge\" or \"text\"")
+                })?),
+            };
+        }
+
+        if let Some(table) = doc.table("goals")
+            && let Some(entry) = table.get("items")
+        {
+            config.goals = entry.as_str_array()?.into_iter().map(str::to_string).collect();
+        }
+
+        if let Some(table) = doc.table("scoring")
+            && let Some(entry) = table.get("difficulty")
+        {
+            config.difficulty = entry
+                .as_str()?
+                .parse()
+                .map_err(|_| error_at(entry.line, "'difficulty' must be \"relaxed\", \"standard\" or \"exacting\""))?;
+        }
+
+        if let Some(table) = doc.table("reset")
+            && let Some(entry) = table.get("confirm")
+        {
+            config.reset_confirm = match entry.as_str()? {
+                "modal" => ConfirmStyle::Modal,
+                "hold" => ConfirmStyle::Hold,
+                _ => return Err(error_at(entry.line, "'confirm' must be \"modal\" or \"hold\"")),
+            };
+        }
+
+        if let Some(table) = doc.table("bins")
+            && let Some(entry) = table.get("strategy")
+        {
+            config.bin_strategy = entry.as_str()?.parse().map_err(|_| {
+                error_at(entry.line, "'strategy' must be \"random\", \"least-full\", \"round-robin\" or \"temper\"")
+            })?;
+        }
+
+        if let Some(table) = doc.table("assist")
+            && let Some(entry) = table.get("enabled")
+        {
+            config.assist = Some(entry.as_bool()?);
+        }
+
+        if let Some(table) = doc.table("ambience")
+            && let Some(entry) = table.get("enabled")
+        {
+            config.ambience = entry.as_bool()?;
+        }
+
+        if let Some(table) = doc.table("compliance")
+            && let Some(entry) = table.get("enabled")
+        {
+            config.compliance = entry.as_bool()?;
+        }
+
+        if let Some(table) = doc.table("budget") {
+            for (key, cap) in config.budget.caps_mut() {
+                if let Some(entry) = table.get(key) {
+                    let value = entry.as_integer()?;
+                    if !(1..=budget::MAX_CAP as i64).contains(&value) {
+                        return Err(error_at(entry.line, format!("'{}' must be between 1 and {}", key, budget::MAX_CAP)));
+                    }
+                    *cap = value as usize;
+                }
+            }
+        }
+
+        if let Some(table) = doc.table("clipboard") {
+            if let Some(entry) = table.get("osc52") {
+                config.clipboard.osc52 = match &entry.value {
+                    Value::Str(mode) if mode == "auto" => None,
+                    _ => Some(entry.as_bool().map_err(|_| error_at(entry.line, "'osc52' must be true, false or \"auto\""))?),
+                };
+            }
+            if let Some(entry) = table.get("max_bytes") {
+                let bytes = entry.as_integer()?;
+                if !(64..=100_000_000).contains(&bytes) {
+                    return Err(error_at(entry.line, "'max_bytes' must be between 64 and 100000000"));
+                }
+                config.clipboard.max_bytes = bytes as usize;
+            }
+        }
+
+        if let Some(table) = doc.table("display") {
+            if let Some(entry) = table.get("eco") {
+                config.display.eco = entry.as_bool()?;
+            }
+            if let Some(entry) = table.get("reduce_motion") {
+                config.display.reduce_motion = entry.as_bool()?;
+            }
+            if let Some(entry) = table.get("mirror") {
+                config.display.mirror = entry.as_bool()?;
+            }
+        }
+
+        if let Some(table) = doc.table("lag") {
+            if let Some(entry) = table.get("slow_write_ms") {
+                let ms = entry.as_integer()?;
+                if !(1..=10_000).contains(&ms) {
+                    return Err(error_at(entry.line, "'slow_write_ms' must be between 1 and 10000"));
+                }
+                config.lag.slow_write = Duration::from_millis(ms as u64);
+            }
+            if let Some(entry) = table.get("frames") {
+                let frames = entry.as_integer()?;
+                if !(1..=1_000).contains(&frames) {
+                    return Err(error_at(entry.line, "'frames' must be between 1 and 1000"));
+                }
+                config.lag.frames = frames as usize;
+            }
+            if let Some(entry) = table.get("max_stride") {
+                let stride = entry.as_integer()?;
+                if !(1..=16).contains(&stride) {
+                    return Err(error_at(entry.line, "'max_stride' must be between 1 and 16"));
+                }
+                config.lag.max_stride = stride as usize;
+            }
+        }
+
+        if let Some(table) = doc.table("input") {
+            if let Some(entry) = table.get("mouse") {
+                config.input.mouse = entry.as_bool()?;
+            }
+            if let Some(entry) = table.get("swap_buttons") {
+                config.input.swap_buttons = entry.as_bool()?;
+            }
+            if let Some(entry) = table.get("review_captures") {
+                config.input.review_captures = entry.as_bool()?;
+            }
+        }
+
+        if let Some(table) = doc.table("breaks") {
+            if let Some(entry) = table.get("enabled") {
+                config.breaks.enabled = entry.as_bool()?;
+            }
+            if let Some(entry) = table.get("minutes") {
+                let minutes = entry.as_integer()?;
+                if !(1..=120).contains(&minutes) {
+                    return Err(error_at(entry.line, "'minutes' must be between 1 and 120"));
+                }
+                config.breaks.length = Duration::from_secs(minutes as u64 * 60);
+            }
+        }
+
+        if let Some(table) = doc.table("login") {
+            if let Some(entry) = table.get("name") {
+                let name = entry.as_str()?.trim();
+                config.login_name = Some(name.to_string()).filter(|name| !name.is_empty());
+            }
+            if let Some(entry) = table.get("idle_logout_minutes") {
+                let minutes = entry.as_integer()?;
+                if !(0..=1440).contains(&minutes) {
+                    return Err(error_at(entry.line, "'idle_logout_minutes' must be between 0 and 1440"));
+                }
+                config.idle_logout = Some(Duration::from_secs(minutes as u64 * 60)).filter(|after| !after.is_zero());
+            }
+        }
+
+        if let Some(table) = doc.table("updates") {
+            if let Some(entry) = table.get("check") {
+                config.updates.check = entry.as_bool()?;
+            }
+            if let Some(entry) = table.get("feed") {
+                config.updates.feed = entry.as_str()?.to_string();
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+fn parse_shift(table: &Table) -> Result<Shift> {
+    let time = |key: &str| -> Result<u16> {
+        let entry = table
+            .get(key)
+            .ok_or_else(|| error_at(table.line, format!("[shift] needs a '{}' time", key)))?;
+        shift::parse_time(entry.as_str()?)
+            .ok_or_else(|| error_at(entry.line, format!("'{}' should look like \"09:00\"", key)))
+    };
+
+    let mut shift = Shift {
+        start: time("start")?,
+        end: time("end")?,
+        days: [true; 7],
+        utc_offset: 0,
+        supervisor_code: "kier".to_string(),
+    };
+
+    if let Some(entry) = table.get("days") {
+        let Value::Array(days) = &entry.value else {
+            return Err(error_at(entry.line, format!("'days' should be an array, found {}", entry.value.type_name())));
+        };
+        shift.days = [false; 7];
+        for day in days {
+            let idx = match day {
+                Value::Str(name) => DAY_NAMES.iter().position(|d| name.eq_ignore_ascii_case(d)),
+                _ => None,
+            };
+            let idx = idx.ok_or_else(|| error_at(entry.line, "'days' entries should be names like \"mon\""))?;
+            shift.days[idx] = true;
+        }
+    }
+
+    if let Some(entry) = table.get("utc_offset") {
+        shift.utc_offset = shift::parse_offset(entry.as_str()?)
+            .ok_or_else(|| error_at(entry.line, "'utc_offset' should look like \"+02:00\""))?;
+    }
+
+    if let Some(entry) = table.get("supervisor_code") {
+        let code = entry.as_str()?;
+        if code.is_empty() {
+            return Err(error_at(entry.line, "'supervisor_code' cannot be empty"));
+        }
+        shift.supervisor_code = code.to_string();
+    }
+
+    Ok(shift)
+}
+
+/// Every problem with a config file's text, each starting with its line,
+/// for `lumon-mdr config check`. Empty if the file is fine.
+pub fn check(source: &str) -> Vec<String> {
+    let described = |err: MdrError| match err {
+        MdrError::Config(msg) => msg,
+        other => other.to_string(),
+    };
+    let doc = match Document::parse(source) {
+        Ok(doc) => doc,
+        Err(err) => return vec![described(err)],
+    };
+    let problems = schema::check(&doc);
+    if !problems.is_empty() {
+        return problems.iter().map(ToString::to_string).collect();
+    }
+    // The shape is right, so only a value can be wrong
+    source.parse::<Config>().err().map(described).into_iter().collect()
+}
+
+/// Where the config file lives: `config.toml` in the config directory,
+/// e.g. `~/.config/lumon-mdr/config.toml`
+pub fn default_path() -> Option<PathBuf> {
+    Some(crate::paths::config_dir()?.join("config.toml"))
+}
+
+/// Read and parse the config file; a file that does not exist yet is
+/// treated as empty
+pub fn load(path: &Path) -> Result<Config> {
+    match std::fs::read_to_string(path) {
+        Ok(source) => source
+            .parse()
+            .map_err(|err| match err {
+                MdrError::Config(msg) => MdrError::Config(format!("{}: {}", path.display(), msg)),
+                other => other,
+            }),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+        Err(err) => Err(MdrError::Io(err)),
+    }
+}
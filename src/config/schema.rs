@@ -0,0 +1,190 @@
+//! Every table and key `config.toml` understands, and the type each key
+//! takes. The file is checked against it before any setting is read, so a
+//! misspelt key is reported with its line instead of quietly falling
+//! back to the default, and `lumon-mdr config check` can list every
+//! problem at once.
+
+use super::document::{Document, Entry, Value};
+use std::fmt;
+
+/// The type a key takes
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Bool,
+    Integer,
+    Str,
+    StrArray,
+    /// `true`, `false` or a word like `"auto"`
+    BoolOrStr,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Bool => "a boolean",
+            Kind::Integer => "an integer",
+            Kind::Str => "a string",
+            Kind::StrArray => "an array of strings",
+            Kind::BoolOrStr => "a boolean or string",
+        }
+    }
+
+    fn accepts(self, value: &Value) -> bool {
+        match (self, value) {
+            (Kind::Bool | Kind::BoolOrStr, Value::Bool(_)) => true,
+            (Kind::Str | Kind::BoolOrStr, Value::Str(_)) => true,
+            (Kind::Integer, Value::Integer(_)) => true,
+            (Kind::StrArray, Value::Array(items)) => items.iter().all(|item| matches!(item, Value::Str(_))),
+            _ => false,
+        }
+    }
+}
+
+/// Each table with its keys
+pub const SCHEMA: &[(&str, &[(&str, Kind)])] = &[
+    ("prize", &[("auto_reset", Kind::Bool), ("auto_reset_seconds", Kind::Integer), ("builtin", Kind::Bool)]),
+    ("save", &[("encrypt", Kind::Bool), ("kdf_iterations", Kind::Integer)]),
+    ("shift", &[
+        ("start", Kind::Str),
+        ("end", Kind::Str),
+        ("days", Kind::StrArray),
+        ("utc_offset", Kind::Str),
+        ("supervisor_code", Kind::Str),
+    ]),
+    ("telemetry", &[("webhook", Kind::Str), ("mqtt", Kind::Str)]),
+    ("speech", &[("enabled", Kind::Bool), ("command", Kind::Str)]),
+    ("theme", &[("name", Kind::Str)]),
+    ("branding", &[("logo", Kind::Str)]),
+    ("goals", &[("items", Kind::StrArray)]),
+    ("scoring", &[("difficulty", Kind::Str)]),
+    ("reset", &[("confirm", Kind::Str)]),
+    ("bins", &[("strategy", Kind::Str)]),
+    ("assist", &[("enabled", Kind::Bool)]),
+    ("ambience", &[("enabled", Kind::Bool)]),
+    ("compliance", &[("enabled", Kind::Bool)]),
+    ("budget", &[
+        ("replaced_numbers", Kind::Integer),
+        ("event_log", Kind::Integer),
+        ("inbox", Kind::Integer),
+        ("deposits", Kind::Integer),
+        ("refiners", Kind::Integer),
+        ("notes", Kind::Integer),
+    ]),
+    ("clipboard", &[("osc52", Kind::BoolOrStr), ("max_bytes", Kind::Integer)]),
+    ("display", &[("eco", Kind::Bool), ("reduce_motion", Kind::Bool), ("mirror", Kind::Bool)]),
+    ("lag", &[("slow_write_ms", Kind::Integer), ("frames", Kind::Integer), ("max_stride", Kind::Integer)]),
+    ("input", &[("mouse", Kind::Bool), ("swap_buttons", Kind::Bool), ("review_captures", Kind::Bool)]),
+    ("breaks", &[("enabled", Kind::Bool), ("minutes", Kind::Integer)]),
+    ("login", &[("name", Kind::Str), ("idle_logout_minutes", Kind::Integer)]),
+    ("updates", &[("check", Kind::Bool), ("feed", Kind::Str)]),
+];
+
+/// Tables written `[[name]]`, once for each item, with their keys
+pub const ARRAY_SCHEMA: &[(&str, &[(&str, Kind)])] = &[("perk", &[("name", Kind::Str), ("weight", Kind::Integer)])];
+
+/// Something in the file the schema does not allow
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Every problem with `doc`'s shape, in file order. Values are only
+/// checked for type here; ranges and spellings are left to the parser.
+pub fn check(doc: &Document) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    for table in &doc.tables {
+        if table.name.is_empty() {
+            for entry in &table.entries {
+                let message = format!("'{}' must go under a table, like [prize]", entry.key);
+                problems.push(Problem { line: entry.line, message });
+            }
+            continue;
+        }
+
+        if let Some(&(_, keys)) = ARRAY_SCHEMA.iter().find(|(name, _)| *name == table.name) {
+            if !table.is_array_element {
+                let message = format!("[{}] should be written [[{}]], once for each item", table.name, table.name);
+                problems.push(Problem { line: table.line, message });
+                continue;
+            }
+            for entry in &table.entries {
+                if let Some(problem) = check_entry(&table.name, keys, entry) {
+                    problems.push(problem);
+                }
+            }
+            continue;
+        }
+
+        let Some(&(_, keys)) = SCHEMA.iter().find(|(name, _)| *name == table.name) else {
+            let names = SCHEMA.iter().chain(ARRAY_SCHEMA).map(|(name, _)| *name);
+            let message = match closest(&table.name, names) {
+                Some(near) => format!("[{}] is not a config table; did you mean [{}]?", table.name, near),
+                None => format!("[{}] is not a config table", table.name),
+            };
+            problems.push(Problem { line: table.line, message });
+            continue;
+        };
+        if table.is_array_element {
+            let message = format!("[[{}]] should be a single [{}] table", table.name, table.name);
+            problems.push(Problem { line: table.line, message });
+            continue;
+        }
+
+        for entry in &table.entries {
+            if let Some(problem) = check_entry(&table.name, keys, entry) {
+                problems.push(problem);
+            }
+        }
+    }
+    problems.sort_by_key(|problem| problem.line);
+    problems
+}
+
+fn check_entry(table: &str, keys: &[(&str, Kind)], entry: &Entry) -> Option<Problem> {
+    let path = format!("{}.{}", table, entry.key);
+    let message = match keys.iter().find(|(key, _)| *key == entry.key) {
+        Some((_, kind)) if kind.accepts(&entry.value) => return None,
+        Some((_, Kind::StrArray)) if matches!(entry.value, Value::Array(_)) => {
+            format!("'{}' should only hold strings", entry.key)
+        },
+        Some((_, kind)) => format!("'{}' should be {}, found {}", entry.key, kind.name(), entry.value.type_name()),
+        None => match closest(&entry.key, keys.iter().map(|(key, _)| *key)) {
+            Some(near) => format!("{} is not a setting; did you mean {}.{}?", path, table, near),
+            None => format!("{} is not a setting", path),
+        },
+    };
+    Some(Problem { line: entry.line, message })
+}
+
+// The candidate a typo most likely meant: at most two edits away, or a
+// third of the word for longer names
+fn closest<'a>(word: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let allowed = (word.chars().count() / 3).max(2);
+    candidates
+        .map(|candidate| (edit_distance(word, candidate), candidate))
+        .filter(|&(distance, _)| distance <= allowed)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitute = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitute.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
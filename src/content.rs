@@ -0,0 +1,422 @@
+//! Data-driven text and colours: prizes, loading messages, quotes, file
+//! names and themes.
+//!
+//! The built-in set below can be extended by content packs, TOML files in
+//! the `packs` folder of the config directory, e.g. `~/.config/lumon-mdr/packs/`:
+//!
+//! ```toml
+//! [pack]
+//! name = "Optics and Design"
+//!
+//! [content]
+//! prizes = ["Egg Bar", "Defiant Jazz Dance Experience"]
+//! loading_messages = ["Calibrating the Break Room"]
+//! quotes = ["Render not unto the world what is the world's."]
+//! file_names = ["Allentown", "Jesse"]
+//!
+//! [[theme]]
+//! name = "green-room"
+//! fg = "#5fbf8f"
+//! bg = "#0b2015"
+//!
+//! [[perk]]
+//! name = "Walk outside"
+//! weight = 3
+//!
+//! [scoring]
+//! combo_bonus = 8
+//! ```
+//!
+//! Packs only ever add to the registry, apart from `[scoring]` values
+//! (see [`crate::scoring`]), where the last pack in name order wins.
+//! Every file is checked at startup, and a mistake is reported with the
+//! file and line it is on.
+//!
+//! Prizes in `[content]` are drawn as often as each other; a `[[perk]]`
+//! has a weight, so a weight of 3 comes up three times as often. A prize
+//! may only be in the pool once.
+
+use crate::config::document::{Document, Table, error_at};
+use crate::error::{MdrError, Result};
+use crate::scoring::ScoringValues;
+use ratatui::style::Color;
+use std::path::{Path, PathBuf};
+
+const PRIZES: &[&str] = &[
+    "Waffle Party",
+    "Melon Bar",
+    "Finger Trap",
+    "Caricature Portrait",
+    "Dance Experience",
+    "Music/Dance Experience",
+    "Wellness Session",
+    "Coffee Cozy",
+    "Choice of Desk Toy",
+];
+
+const LOADING_MESSAGES: &[&str] = &[
+    "Initializing MDR protocol",
+    "Checking refinement quotas",
+    "Verifying department credentials",
+    "Preparing macrodata bins",
+    "Establishing connection to Lumon mainframe",
+    "Running compliance check",
+    "Validating severance chip",
+    "Please enjoy all amenities equally",
+];
+
+const QUOTES: &[&str] = &[
+    "Let not weakness live in your veins.",
+    "The remembered man does not decay.",
+    "Tame in me the tempers four.",
+    "Be content in the work you do for Lumon.",
+];
+
+const FILE_NAMES: &[&str] = &[
+    "Cold Harbor",
+    "Siena",
+    "Tumwater",
+    "Dranesville",
+    "Moonbeam",
+    "Allentown",
+    "Labrador",
+];
+
+/// Heaviest weight a prize may have
+pub const MAX_PRIZE_WEIGHT: u32 = 1000;
+
+/// A prize and how often it is drawn compared with the others
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Prize {
+    pub name: String,
+    pub weight: u32,
+}
+
+impl Prize {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), weight: 1 }
+    }
+}
+
+/// The prize `roll` lands on, counting each prize `weight` times. Rolls
+/// past the total wrap around.
+pub fn pick_prize(pool: &[Prize], roll: u64) -> Option<&Prize> {
+    let total: u64 = pool.iter().map(|prize| u64::from(prize.weight)).sum();
+    if total == 0 {
+        return None;
+    }
+    let mut roll = roll % total;
+    pool.iter().find(|prize| {
+        let weight = u64::from(prize.weight);
+        if roll < weight {
+            return true;
+        }
+        roll -= weight;
+        false
+    })
+}
+
+/// A colour scheme a pack can provide
+#[derive(Clone, Debug, PartialEq)]
+pub struct Theme {
+    pub name: String,
+    pub fg: Color,
+    pub bg: Color,
+}
+
+/// Everything the screens draw their flavour text from
+#[derive(Clone, Debug, PartialEq)]
+pub struct Content {
+    /// The built-in perks, then each pack's
+    pub prizes: Vec<Prize>,
+    /// Shown in order as the loading bar fills
+    pub loading_messages: Vec<String>,
+    pub quotes: Vec<String>,
+    pub file_names: Vec<String>,
+    pub themes: Vec<Theme>,
+    /// What the scoring rules are built from
+    pub scoring: ScoringValues,
+    /// Names of the packs that were merged in
+    pub packs: Vec<String>,
+}
+
+impl Default for Content {
+    fn default() -> Self {
+        let owned = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        Self {
+            prizes: PRIZES.iter().map(|&name| Prize::new(name)).collect(),
+            loading_messages: owned(LOADING_MESSAGES),
+            quotes: owned(QUOTES),
+            file_names: owned(FILE_NAMES),
+            themes: Vec::new(),
+            scoring: ScoringValues::default(),
+            packs: Vec::new(),
+        }
+    }
+}
+
+impl Content {
+    /// Add one pack's content. `source` names the file in error messages.
+    pub fn merge(&mut self, pack_text: &str, source: &str) -> Result<()> {
+        let located = |err: MdrError| match err {
+            MdrError::Config(msg) => MdrError::Config(format!("{}: {}", source, msg)),
+            other => other,
+        };
+        let pack = Pack::parse(pack_text).map_err(located)?;
+
+        for theme in &pack.themes {
+            if self.themes.iter().any(|t| t.name == theme.name) {
+                return Err(MdrError::Config(format!(
+                    "{}: theme '{}' is already provided by another pack",
+                    source, theme.name
+                )));
+            }
+        }
+        for prize in &pack.prizes {
+            if self.prizes.iter().any(|p| p.name == prize.name) {
+                return Err(MdrError::Config(format!(
+                    "{}: prize '{}' is already in the pool",
+                    source, prize.name
+                )));
+            }
+        }
+
+        self.prizes.extend(pack.prizes);
+        self.loading_messages.extend(pack.loading_messages);
+        self.quotes.extend(pack.quotes);
+        self.file_names.extend(pack.file_names);
+        self.themes.extend(pack.themes);
+        for (key, value) in pack.scoring {
+            set_scoring(&mut self.scoring, &key, value);
+        }
+        self.packs.push(pack.name);
+        Ok(())
+    }
+
+    pub fn theme(&self, name: &str) -> Option<&Theme> {
+        self.themes.iter().find(|t| t.name == name)
+    }
+
+    /// The prizes a completed file draws from: these, without the built-in
+    /// perks unless `builtin`, and then the config's `custom` prizes. A
+    /// custom prize already in the pool takes the custom weight.
+    pub fn prize_pool(&self, custom: &[Prize], builtin: bool) -> Vec<Prize> {
+        let mut pool: Vec<Prize> = self
+            .prizes
+            .iter()
+            .filter(|prize| builtin || !PRIZES.contains(&prize.name.as_str()))
+            .filter(|prize| custom.iter().all(|own| own.name != prize.name))
+            .cloned()
+            .collect();
+        pool.extend(custom.iter().cloned());
+        pool
+    }
+}
+
+/// The built-in content plus every `*.toml` pack in `dir`, in name order.
+/// A missing directory just means no packs.
+pub fn load(dir: &Path) -> Result<Content> {
+    let mut content = Content::default();
+
+    let mut paths: Vec<PathBuf> = match std::fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+            .collect(),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(content),
+        Err(err) => return Err(MdrError::Io(err)),
+    };
+    paths.sort();
+
+    for path in paths {
+        let text = std::fs::read_to_string(&path)?;
+        content.merge(&text, &path.display().to_string())?;
+    }
+    Ok(content)
+}
+
+/// Where packs live: a `packs` folder next to the config file
+pub fn default_dir() -> Option<PathBuf> {
+    Some(crate::paths::config_dir()?.join("packs"))
+}
+
+struct Pack {
+    name: String,
+    prizes: Vec<Prize>,
+    loading_messages: Vec<String>,
+    quotes: Vec<String>,
+    file_names: Vec<String>,
+    themes: Vec<Theme>,
+    scoring: Vec<(String, u32)>,
+}
+
+/// Scoring values a pack may set, with the range each must fall in
+const SCORING_KEYS: &[(&str, u32, u32)] = &[
+    ("combo_min", 2, 9),
+    ("combo_bonus", 0, 100),
+    ("streak_from", 1, 100),
+    ("streak_step", 0, 100),
+    ("streak_max", 100, 1000),
+    ("misclick_penalty", 0, 100),
+];
+
+impl Pack {
+    fn parse(text: &str) -> Result<Pack> {
+        let doc = Document::parse(text)?;
+
+        for table in &doc.tables {
+            let known = match (table.name.as_str(), table.is_array_element) {
+                ("", false) => table.entries.is_empty(),
+                ("pack" | "content" | "scoring", false) | ("theme" | "perk", true) => true,
+                _ => false,
+            };
+            if !known {
+                let line = table.entries.first().map_or(table.line, |e| e.line);
+                return Err(error_at(line, "packs may only contain [pack], [content], [scoring], [[theme]] and [[perk]]"));
+            }
+        }
+
+        let info = doc
+            .table("pack")
+            .ok_or_else(|| MdrError::Config("a pack needs a [pack] table with a name".to_string()))?;
+        check_keys(info, &["name"])?;
+        let name = info
+            .get("name")
+            .ok_or_else(|| error_at(info.line, "[pack] needs a 'name'"))?
+            .as_str()?
+            .trim()
+            .to_string();
+        if name.is_empty() {
+            return Err(error_at(info.line, "the pack name cannot be empty"));
+        }
+
+        let mut pack = Pack {
+            name,
+            prizes: Vec::new(),
+            loading_messages: Vec::new(),
+            quotes: Vec::new(),
+            file_names: Vec::new(),
+            themes: Vec::new(),
+            scoring: Vec::new(),
+        };
+
+        if let Some(table) = doc.table("content") {
+            check_keys(table, &["prizes", "loading_messages", "quotes", "file_names"])?;
+            for entry in &table.entries {
+                let items = entry.as_str_array()?;
+                if items.iter().any(|item| item.trim().is_empty()) {
+                    return Err(error_at(entry.line, format!("'{}' cannot contain empty text", entry.key)));
+                }
+                let items = items.into_iter().map(str::to_string);
+                match entry.key.as_str() {
+                    "prizes" => {
+                        for name in items {
+                            add_prize(&mut pack.prizes, Prize::new(name), entry.line)?;
+                        }
+                    },
+                    "loading_messages" => pack.loading_messages.extend(items),
+                    "quotes" => pack.quotes.extend(items),
+                    _ => pack.file_names.extend(items),
+                }
+            }
+        }
+
+        for table in doc.array_tables("theme") {
+            check_keys(table, &["name", "fg", "bg"])?;
+            let field = |key: &str| {
+                table.get(key).ok_or_else(|| error_at(table.line, format!("[[theme]] needs '{}'", key)))
+            };
+            let color = |key: &str| -> Result<Color> {
+                let entry = field(key)?;
+                parse_color(entry.as_str()?)
+                    .ok_or_else(|| error_at(entry.line, format!("'{}' should be a colour like \"#1a2b3c\"", key)))
+            };
+
+            let name = field("name")?.as_str()?.to_string();
+            if pack.themes.iter().any(|t| t.name == name) {
+                return Err(error_at(table.line, format!("theme '{}' is defined twice", name)));
+            }
+            pack.themes.push(Theme { name, fg: color("fg")?, bg: color("bg")? });
+        }
+
+        for table in doc.array_tables("perk") {
+            check_keys(table, &["name", "weight"])?;
+            add_prize(&mut pack.prizes, parse_perk(table)?, table.line)?;
+        }
+
+        if let Some(table) = doc.table("scoring") {
+            let keys: Vec<&str> = SCORING_KEYS.iter().map(|&(key, _, _)| key).collect();
+            check_keys(table, &keys)?;
+            for entry in &table.entries {
+                let &(key, low, high) = SCORING_KEYS.iter().find(|&&(key, _, _)| key == entry.key).unwrap();
+                let value = entry.as_integer()?;
+                if !(low as i64..=high as i64).contains(&value) {
+                    return Err(error_at(entry.line, format!("'{}' must be between {} and {}", key, low, high)));
+                }
+                pack.scoring.push((key.to_string(), value as u32));
+            }
+        }
+
+        Ok(pack)
+    }
+}
+
+/// Read a `[[perk]]` table: a name and an optional weight, 1 by default
+pub fn parse_perk(table: &Table) -> Result<Prize> {
+    let entry = table.get("name").ok_or_else(|| error_at(table.line, "[[perk]] needs 'name'"))?;
+    let name = entry.as_str()?.trim();
+    if name.is_empty() {
+        return Err(error_at(entry.line, "the perk name cannot be empty"));
+    }
+    let mut prize = Prize::new(name);
+    if let Some(entry) = table.get("weight") {
+        let weight = entry.as_integer()?;
+        if !(1..=i64::from(MAX_PRIZE_WEIGHT)).contains(&weight) {
+            return Err(error_at(entry.line, format!("'weight' must be between 1 and {}", MAX_PRIZE_WEIGHT)));
+        }
+        prize.weight = weight as u32;
+    }
+    Ok(prize)
+}
+
+/// Add `prize` to `prizes`, unless one by that name is already there
+pub fn add_prize(prizes: &mut Vec<Prize>, prize: Prize, line: usize) -> Result<()> {
+    if prizes.iter().any(|p| p.name == prize.name) {
+        return Err(error_at(line, format!("prize '{}' is listed twice", prize.name)));
+    }
+    prizes.push(prize);
+    Ok(())
+}
+
+fn set_scoring(values: &mut ScoringValues, key: &str, value: u32) {
+    match key {
+        "combo_min" => values.combo_min = value as usize,
+        "combo_bonus" => values.combo_bonus = value as u16,
+        "streak_from" => values.streak_from = value,
+        "streak_step" => values.streak_step = value,
+        "streak_max" => values.streak_max = value,
+        _ => values.misclick_penalty = value as u16,
+    }
+}
+
+// Reject keys that are probably typos, suggesting the closest real one
+fn check_keys(table: &Table, allowed: &[&str]) -> Result<()> {
+    for entry in &table.entries {
+        if !allowed.contains(&entry.key.as_str()) {
+            let hint = allowed
+                .iter()
+                .find(|key| key.starts_with(&entry.key[..entry.key.len().min(4)]))
+                .map(|key| format!("; did you mean '{}'?", key))
+                .unwrap_or_default();
+            return Err(error_at(entry.line, format!("unknown key '{}'{}", entry.key, hint)));
+        }
+    }
+    Ok(())
+}
+
+/// Parse `#rrggbb`
+pub fn parse_color(text: &str) -> Option<Color> {
+    let hex = text.strip_prefix('#').filter(|h| h.len() == 6 && h.is_ascii())?;
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?))
+}
@@ -0,0 +1,91 @@
+//! The small menu a right click opens by a bin or on the grid, with what
+//! can be done to whatever was clicked.
+//!
+//! The menu is anchored at the click: it opens below and to the right of
+//! it, and flips above or to the left where the screen runs out. Where it
+//! lands is worked out here so drawing it and hit-testing clicks on it
+//! agree.
+
+use ratatui::layout::Rect;
+
+/// What the menu was opened on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuTarget {
+    /// A bin, by index
+    Bin(usize),
+    /// A grid cell, by column and row
+    Cell((usize, usize)),
+}
+
+/// Something the menu offers
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MenuItem {
+    /// Drop a few numbers in the bin, as a click on it does
+    Deposit,
+    /// List the bin's recent deposits
+    Inspect,
+    /// Open the bin's side panel
+    BinDetails,
+    /// Capture the numbers at the click, as a click there does
+    Refine,
+    /// Flag the cells around the click as a cluster to come back to
+    MarkCluster,
+    UnmarkCluster,
+}
+
+impl MenuItem {
+    pub fn label(self) -> &'static str {
+        match self {
+            MenuItem::Deposit => "Deposit here",
+            MenuItem::Inspect => "Inspect",
+            MenuItem::BinDetails => "Bin details",
+            MenuItem::Refine => "Refine here",
+            MenuItem::MarkCluster => "Mark cluster",
+            MenuItem::UnmarkCluster => "Unmark cluster",
+        }
+    }
+}
+
+/// An open context menu
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ContextMenu {
+    /// The screen cell that was clicked
+    pub anchor: (u16, u16),
+    pub target: MenuTarget,
+    pub items: Vec<MenuItem>,
+    pub selected: usize,
+}
+
+impl ContextMenu {
+    pub fn new(anchor: (u16, u16), target: MenuTarget, items: Vec<MenuItem>) -> Self {
+        Self { anchor, target, items, selected: 0 }
+    }
+
+    /// Where the menu is drawn on `screen`, borders included
+    pub fn area(&self, screen: Rect) -> Rect {
+        let longest = self.items.iter().map(|item| item.label().chars().count()).max().unwrap_or(0) as u16;
+        let width = (longest + 4).min(screen.width);
+        let height = (self.items.len() as u16 + 2).min(screen.height);
+        let (x, y) = self.anchor;
+
+        let x = if x + 1 + width <= screen.x + screen.width { x + 1 } else { x.saturating_sub(width).max(screen.x) };
+        let y = if y + 1 + height <= screen.y + screen.height { y + 1 } else { y.saturating_sub(height).max(screen.y) };
+        Rect::new(x, y, width, height)
+    }
+
+    /// The item drawn at a screen cell, if any
+    pub fn item_at(&self, screen: Rect, column: u16, row: u16) -> Option<usize> {
+        let area = self.area(screen);
+        let inside = column > area.x && column + 1 < area.x + area.width && row > area.y && row + 1 < area.y + area.height;
+        inside.then(|| usize::from(row - area.y - 1)).filter(|&idx| idx < self.items.len())
+    }
+
+    /// Move the highlight, wrapping around the ends
+    pub fn select(&mut self, delta: isize) {
+        self.selected = (self.selected as isize + delta).rem_euclid(self.items.len().max(1) as isize) as usize;
+    }
+
+    pub fn chosen(&self) -> Option<MenuItem> {
+        self.items.get(self.selected).copied()
+    }
+}
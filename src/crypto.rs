@@ -0,0 +1,90 @@
+//! Passphrase-based sealing for save files: PBKDF2-HMAC-SHA256 for the key
+//! and ChaCha20-Poly1305 (RFC 8439) for authenticated encryption, both from
+//! the RustCrypto crates.
+
+use chacha20poly1305::ChaCha20Poly1305;
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use rand::{RngCore, rng};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Instant;
+
+pub const KEY_LEN: usize = 32;
+pub const NONCE_LEN: usize = 12;
+pub const SALT_LEN: usize = 16;
+pub const TAG_LEN: usize = 16;
+
+/// PBKDF2 rounds for new keys (OWASP's 2023 guidance for SHA-256)
+pub const DEFAULT_ITERATIONS: u32 = 600_000;
+
+/// A derived key together with what is needed to derive it again
+#[derive(Clone)]
+pub struct SealKey {
+    pub key: [u8; KEY_LEN],
+    pub salt: [u8; SALT_LEN],
+    pub iterations: u32,
+}
+
+impl SealKey {
+    /// Derive a key for a new file with a fresh random salt
+    pub fn generate(passphrase: &str, iterations: u32) -> Self {
+        Self::derive(passphrase, random_salt(), iterations)
+    }
+
+    pub fn derive(passphrase: &str, salt: [u8; SALT_LEN], iterations: u32) -> Self {
+        let mut key = [0u8; KEY_LEN];
+        pbkdf2::pbkdf2_hmac::<sha2::Sha256>(passphrase.as_bytes(), &salt, iterations, &mut key);
+        Self { key, salt, iterations }
+    }
+}
+
+/// A key being derived on a worker thread, so the slow rounds leave the
+/// screen free to say what is going on
+pub struct KeyDerivation {
+    rx: Receiver<SealKey>,
+    /// When it started, for showing how long it has taken
+    pub since: Instant,
+}
+
+impl KeyDerivation {
+    pub fn start(passphrase: String, salt: [u8; SALT_LEN], iterations: u32, now: Instant) -> Self {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(SealKey::derive(&passphrase, salt, iterations));
+        });
+        Self { rx, since: now }
+    }
+
+    /// The key, once it is ready
+    pub fn poll(&self) -> Option<SealKey> {
+        self.rx.try_recv().ok()
+    }
+}
+
+/// Encrypt and authenticate `plaintext`; returns ciphertext followed by the tag
+pub fn seal(key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN], aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+    ChaCha20Poly1305::new(key.into())
+        .encrypt(nonce.into(), Payload { msg: plaintext, aad })
+        .expect("a save file is far inside ChaCha20's length limit")
+}
+
+/// Check and decrypt the output of [`seal`]. `None` means the key is wrong
+/// or the data was tampered with; the two cannot be told apart.
+pub fn open(key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN], aad: &[u8], sealed: &[u8]) -> Option<Vec<u8>> {
+    ChaCha20Poly1305::new(key.into()).decrypt(nonce.into(), Payload { msg: sealed, aad }).ok()
+}
+
+/// A fresh random salt, from the OS rather than `RngService`, whose seeded
+/// streams a replay could predict
+pub fn random_salt() -> [u8; SALT_LEN] {
+    let mut salt = [0u8; SALT_LEN];
+    rng().fill_bytes(&mut salt);
+    salt
+}
+
+/// A fresh random nonce, from the OS like the salt
+pub fn random_nonce() -> [u8; NONCE_LEN] {
+    let mut nonce = [0u8; NONCE_LEN];
+    rng().fill_bytes(&mut nonce);
+    nonce
+}
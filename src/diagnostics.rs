@@ -0,0 +1,201 @@
+use crate::config::LagConfig;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// Global allocator wrapper that counts allocations for the debug HUD.
+/// Installed by the binary with `#[global_allocator]`; when it is not
+/// installed the counter simply stays at zero.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// Total number of allocations made since startup
+pub fn allocation_count() -> usize {
+    ALLOCATIONS.load(Ordering::Relaxed)
+}
+
+/// A frame that takes longer than this to draw is treated as stuck
+pub const STUCK_FRAME: Duration = Duration::from_millis(250);
+
+/// How much the renderer draws. Each stuck frame steps it down a level for
+/// the rest of the session, so a blocked terminal or a huge grid slows the
+/// screen down rather than freezing it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Quality {
+    /// Half the grid's rows and columns, and nothing moving
+    Sparse,
+    /// The grid holds still and numbers are not magnified
+    Still,
+    #[default]
+    Full,
+}
+
+impl Quality {
+    pub fn name(self) -> &'static str {
+        match self {
+            Quality::Sparse => "sparse",
+            Quality::Still => "still",
+            Quality::Full => "full",
+        }
+    }
+
+    /// What dropping to this level turns off
+    pub fn describe(self) -> &'static str {
+        match self {
+            Quality::Sparse => "The grid has been thinned out",
+            Quality::Still => "Grid animation is off",
+            Quality::Full => "Full quality",
+        }
+    }
+
+    fn lower(self) -> Option<Quality> {
+        match self {
+            Quality::Full => Some(Quality::Still),
+            Quality::Still => Some(Quality::Sparse),
+            Quality::Sparse => None,
+        }
+    }
+}
+
+/// Per-frame measurements shown in the debug HUD
+#[derive(Default)]
+pub struct FrameStats {
+    pub fps: f32,
+    pub frames_drawn: usize,
+    pub last_render: Duration,
+    pub queue_depth: usize,
+    pub allocations: usize,
+    /// Mouse motion events merged into a later one (running total)
+    pub moves_coalesced: usize,
+    /// Repeat clicks dropped by the debounce (running total)
+    pub clicks_debounced: usize,
+    /// Set by the watchdog as frames get stuck
+    pub quality: Quality,
+    /// Frames that took longer than `STUCK_FRAME` (running total)
+    pub stuck_frames: usize,
+    /// How long the last frame's output took to write to the terminal
+    pub last_write: Duration,
+    /// Steps the grid's animation has been thinned by for a slow terminal
+    thinned: usize,
+    slow_writes: usize,
+    quick_writes: usize,
+    /// Cells that differed from the frame before, which is all the
+    /// terminal is sent; counted only while the HUD is up
+    pub cells_changed: usize,
+    /// How many of those were in the number grid
+    pub grid_changed: usize,
+    /// Cells on screen, what a full repaint would send
+    pub cells_drawn: usize,
+    /// Changed cells over every frame diffed (running total)
+    pub cells_sent: usize,
+    previous: Option<Buffer>,
+}
+
+impl FrameStats {
+    /// Record a finished frame. `frame_interval` is the time since the
+    /// previous frame started and is smoothed so the FPS readout is stable.
+    /// Returns the quality a stuck frame dropped to, if it dropped.
+    pub fn record_frame(&mut self, frame_interval: Duration, render_time: Duration, allocations: usize) -> Option<Quality> {
+        let secs = frame_interval.as_secs_f32();
+        if secs > 0.0 {
+            let instant_fps = 1.0 / secs;
+            self.fps = if self.fps == 0.0 {
+                instant_fps
+            } else {
+                self.fps * 0.9 + instant_fps * 0.1
+            };
+        }
+        self.frames_drawn += 1;
+        self.last_render = render_time;
+        self.allocations = allocations;
+
+        if render_time < STUCK_FRAME {
+            return None;
+        }
+        self.stuck_frames += 1;
+        let lower = self.quality.lower()?;
+        self.quality = lower;
+        Some(lower)
+    }
+
+    /// Record how long a frame's output took to write. A run of slow
+    /// writes, as over a laggy SSH link, thins the grid's animation a
+    /// step; a longer run of quick ones fills it back in.
+    pub fn record_write(&mut self, write_time: Duration, lag: &LagConfig) {
+        self.last_write = write_time;
+        self.thinned = self.thinned.min(lag.max_stride.saturating_sub(1));
+        if write_time >= lag.slow_write {
+            self.quick_writes = 0;
+            self.slow_writes += 1;
+            if self.slow_writes >= lag.frames {
+                self.slow_writes = 0;
+                self.thinned = (self.thinned + 1).min(lag.max_stride.saturating_sub(1));
+            }
+        } else if write_time < lag.slow_write / 2 {
+            self.slow_writes = 0;
+            self.quick_writes += 1;
+            if self.quick_writes >= lag.frames * 4 {
+                self.quick_writes = 0;
+                self.thinned = self.thinned.saturating_sub(1);
+            }
+        } else {
+            // In between breaks a run either way
+            self.slow_writes = 0;
+            self.quick_writes = 0;
+        }
+    }
+
+    /// Compare a drawn frame with the one before, cell by cell, as the
+    /// terminal does before writing. `grid` is where the number grid was
+    /// drawn, if it was.
+    pub fn record_diff(&mut self, buffer: &Buffer, grid: Option<Rect>) {
+        self.cells_drawn = buffer.content.len();
+        let updates = match &self.previous {
+            Some(previous) if previous.area == buffer.area => previous.diff(buffer),
+            // A new size is repainted in full
+            _ => Buffer::empty(buffer.area).diff(buffer),
+        };
+        self.cells_changed = updates.len();
+        self.cells_sent += updates.len();
+        self.grid_changed = grid.map_or(0, |grid| {
+            updates
+                .iter()
+                .filter(|&&(x, y, _)| x >= grid.x && x < grid.x + grid.width && y >= grid.y && y < grid.y + grid.height)
+                .count()
+        });
+        match &mut self.previous {
+            Some(previous) if previous.area == buffer.area => previous.content.clone_from(&buffer.content),
+            previous => *previous = Some(buffer.clone()),
+        }
+    }
+
+    /// Stop diffing frames, e.g. once the HUD is closed
+    pub fn forget_frames(&mut self) {
+        self.previous = None;
+    }
+
+    /// The grid animates one cell in every `stride` each frame, taking
+    /// turns, so a slow terminal is sent fewer changes
+    pub fn stride(&self) -> usize {
+        self.thinned + 1
+    }
+}
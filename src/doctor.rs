@@ -0,0 +1,56 @@
+use crate::clipboard;
+use crate::environment::{Environment, Multiplexer};
+use crate::paths;
+use crate::theme;
+
+/// Build the human-readable report printed by `lumon-mdr doctor`
+pub fn report(env: &Environment) -> String {
+    let mut lines = vec![
+        "Lumon Industries terminal diagnostics".to_string(),
+        String::new(),
+        format!("TERM:        {}", or_unset(&env.term)),
+        format!("COLORTERM:   {}", or_unset(&env.colorterm)),
+        format!("Palette:     {}", theme::detect().name()),
+        format!("Multiplexer: {}", env.multiplexer.map_or("none", |m| m.name())),
+        format!("Charts:      {}", if env.unicode { "block elements" } else { "ASCII (locale is not UTF-8)" }),
+        format!("Clipboard:   {}", if clipboard::takes_osc52(env) { "OSC 52 through the terminal" } else { "none (terminal ignores OSC 52)" }),
+    ];
+
+    if let Ok((width, height)) = crossterm::terminal::size() {
+        lines.push(format!("Size:        {}x{}", width, height));
+    }
+    match paths::dirs() {
+        Some(dirs) => {
+            lines.push(format!("Config:      {}", dirs.config.display()));
+            lines.push(format!("Data:        {}", dirs.data.display()));
+            lines.push(format!("Cache:       {}", dirs.cache.display()));
+        },
+        None => lines.push(format!("Files:       nowhere to keep them; set HOME or {}", paths::HOME_VAR)),
+    }
+
+    // Mouse support is the part most likely to degrade inside a multiplexer
+    match env.multiplexer {
+        Some(Multiplexer::Tmux) => {
+            lines.push(String::new());
+            lines.push("Notes:".to_string());
+            lines.push("  - Window resizing is skipped; resize the tmux pane instead.".to_string());
+            lines.push("  - Mouse hover and clicks need `set -g mouse on` in tmux.conf,".to_string());
+            lines.push("    otherwise tmux keeps the mouse for itself.".to_string());
+            lines.push("  - Hover magnification may lag: tmux re-encodes every motion event.".to_string());
+        },
+        Some(Multiplexer::Screen) => {
+            lines.push(String::new());
+            lines.push("Notes:".to_string());
+            lines.push("  - Window resizing is skipped; resize the screen region instead.".to_string());
+            lines.push("  - GNU screen does not forward mouse motion, so numbers will not".to_string());
+            lines.push("    magnify on hover. Clicks need `mousetrack on` in .screenrc.".to_string());
+        },
+        None => {},
+    }
+
+    lines.join("\n")
+}
+
+fn or_unset(value: &str) -> &str {
+    if value.is_empty() { "(unset)" } else { value }
+}
@@ -0,0 +1,88 @@
+use std::env;
+
+/// Terminal multiplexers that sit between the app and the real terminal
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Multiplexer {
+    Tmux,
+    Screen,
+}
+
+impl Multiplexer {
+    pub fn name(self) -> &'static str {
+        match self {
+            Multiplexer::Tmux => "tmux",
+            Multiplexer::Screen => "GNU screen",
+        }
+    }
+}
+
+/// Snapshot of the environment the app was launched in
+pub struct Environment {
+    pub term: String,
+    pub colorterm: String,
+    /// The terminal application, where it says (`TERM_PROGRAM`)
+    pub term_program: String,
+    pub multiplexer: Option<Multiplexer>,
+    /// Whether the locale allows UTF-8, so block elements will render.
+    /// An unset locale is given the benefit of the doubt.
+    pub unicode: bool,
+}
+
+/// Detect the current environment from the process variables
+pub fn detect() -> Environment {
+    let term = env::var("TERM").unwrap_or_default();
+    let colorterm = env::var("COLORTERM").unwrap_or_default();
+    let term_program = env::var("TERM_PROGRAM").unwrap_or_default();
+
+    // tmux always exports TMUX, screen always exports STY. Fall back to TERM
+    // for nested sessions where the variables were scrubbed (e.g. sudo).
+    let multiplexer = if env::var_os("TMUX").is_some() || term.starts_with("tmux") {
+        Some(Multiplexer::Tmux)
+    } else if env::var_os("STY").is_some() || term.starts_with("screen") {
+        Some(Multiplexer::Screen)
+    } else {
+        None
+    };
+
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| env::var(name).ok())
+        .find(|value| !value.is_empty());
+    let unicode = locale.is_none_or(|locale| {
+        let locale = locale.to_lowercase();
+        locale.contains("utf-8") || locale.contains("utf8")
+    });
+
+    Environment { term, colorterm, term_program, multiplexer, unicode }
+}
+
+impl Environment {
+    /// Whether resizing the window with `SetSize` is worth attempting.
+    /// Multiplexers own the pane size, so the request is either ignored or
+    /// resizes the outer terminal out from under the other panes.
+    pub fn can_resize(&self) -> bool {
+        self.multiplexer.is_none()
+    }
+
+    /// Wrap a raw escape sequence so it reaches the outer terminal
+    pub fn passthrough(&self, sequence: &str) -> String {
+        passthrough(self.multiplexer, sequence)
+    }
+
+    /// Escape sequence that sets the outer terminal's window title
+    pub fn window_title_sequence(&self, title: &str) -> String {
+        self.passthrough(&format!("\x1b]0;{}\x07", title))
+    }
+}
+
+/// Wrap a raw escape sequence so it gets through `multiplexer`
+pub fn passthrough(multiplexer: Option<Multiplexer>, sequence: &str) -> String {
+    match multiplexer {
+        // tmux requires every ESC inside the payload to be doubled
+        Some(Multiplexer::Tmux) => {
+            format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+        },
+        Some(Multiplexer::Screen) => format!("\x1bP{}\x1b\\", sequence),
+        None => sequence.to_string(),
+    }
+}
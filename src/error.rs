@@ -0,0 +1,97 @@
+use std::fmt;
+use std::io;
+
+/// Errors returned by the library's public APIs
+#[derive(Debug)]
+pub enum MdrError {
+    /// Drawing to or reading from the terminal failed
+    Terminal(io::Error),
+    /// Any other filesystem or OS-level failure
+    Io(io::Error),
+    /// Invalid command line, config file, or script
+    Config(String),
+    /// A saved session could not be read or written
+    Save(String),
+    /// Network communication failed
+    Net(String),
+}
+
+pub type Result<T> = std::result::Result<T, MdrError>;
+
+impl fmt::Display for MdrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MdrError::Terminal(err) => write!(f, "terminal error: {}", err),
+            MdrError::Io(err) => write!(f, "I/O error: {}", err),
+            MdrError::Config(msg) => write!(f, "configuration error: {}", msg),
+            MdrError::Save(msg) => write!(f, "save file error: {}", msg),
+            MdrError::Net(msg) => write!(f, "network error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for MdrError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MdrError::Terminal(err) | MdrError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for MdrError {
+    fn from(err: io::Error) -> Self {
+        MdrError::Io(err)
+    }
+}
+
+/// Subsystems that can fail without ending the session
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FailureKind {
+    Terminal,
+    SaveFile,
+    Config,
+    Audio,
+    Injection,
+    /// A self-check found the session's state contradicting itself
+    Integrity,
+}
+
+impl FailureKind {
+    /// Headline shown on the error screen, in Lumon's house style
+    pub fn headline(self) -> &'static str {
+        match self {
+            FailureKind::Terminal => "Your terminal has experienced an unscheduled pause.",
+            FailureKind::SaveFile => "Your refinement records could not be verified.",
+            FailureKind::Config => "Your workstation settings are out of compliance.",
+            FailureKind::Audio => "The break room speakers are currently unavailable.",
+            FailureKind::Injection => "Contributions from outside the severed floor have stopped arriving.",
+            FailureKind::Integrity => "Your refinement records do not agree with one another.",
+        }
+    }
+
+    /// Reassurance line shown under the headline
+    pub fn reassurance(self) -> &'static str {
+        match self {
+            FailureKind::Terminal => "Please keep your hands on the desk while the connection is restored.",
+            FailureKind::SaveFile => "Your innie's work is safe in the hands of the Board.",
+            FailureKind::Config => "Default settings have been provided for your comfort.",
+            FailureKind::Audio => "Silence is also a gift from Kier.",
+            FailureKind::Injection => "Your own refinement remains unaffected and appreciated.",
+            FailureKind::Integrity => "The discrepancy has been noted in your inbox for Optics and Design.",
+        }
+    }
+}
+
+/// A failure the refiner can retry, ignore, or quit over
+#[derive(Clone, Debug)]
+pub struct RecoverableError {
+    pub kind: FailureKind,
+    pub detail: String,
+}
+
+impl RecoverableError {
+    pub fn new(kind: FailureKind, detail: impl Into<String>) -> Self {
+        Self { kind, detail: detail.into() }
+    }
+}
@@ -0,0 +1,42 @@
+//! Things that happened during a session, for whoever wants to react.
+//!
+//! The app queues events in `App::outbox` as they happen; the event loop
+//! hands them to the outputs (telemetry and friends) once per frame.
+
+/// A notable moment in a refinement session
+#[derive(Clone, Debug, PartialEq)]
+pub enum AppEvent {
+    /// Numbers were captured from the grid
+    NumbersRefined { count: usize },
+    /// A bin reached 100%. `bin` starts at 1, as shown on screen.
+    BinFilled { bin: usize },
+    /// Every bin is full and a prize has been awarded
+    FileCompleted { prize: String },
+}
+
+impl AppEvent {
+    /// Short machine-readable name, e.g. for webhook payloads
+    pub fn name(&self) -> &'static str {
+        match self {
+            AppEvent::NumbersRefined { .. } => "numbers_refined",
+            AppEvent::BinFilled { .. } => "bin_filled",
+            AppEvent::FileCompleted { .. } => "file_complete",
+        }
+    }
+
+    /// One line for people, e.g. in the main screen's log panel
+    pub fn describe(&self) -> String {
+        match self {
+            AppEvent::NumbersRefined { count: 1 } => "Refined 1 number".to_string(),
+            AppEvent::NumbersRefined { count } => format!("Refined {} numbers", count),
+            AppEvent::BinFilled { bin } => format!("Bin 0{} filled", bin),
+            AppEvent::FileCompleted { prize } => format!("File complete: {}", prize),
+        }
+    }
+
+    /// Whether the event is worth telling the outside world about on its
+    /// own, rather than only being counted
+    pub fn is_milestone(&self) -> bool {
+        !matches!(self, AppEvent::NumbersRefined { .. })
+    }
+}
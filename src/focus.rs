@@ -0,0 +1,124 @@
+//! Which of a screen's widgets the keyboard is on.
+//!
+//! A screen with more than one thing to press or type into registers them
+//! in tab order with a `Focus`. Tab and Shift-Tab move along the order,
+//! wrapping at the ends, and `route` hands every other key to the focused
+//! widget, which the screen draws highlighted (`ui::widgets::focus_style`).
+//! The focus follows its widget rather than a position, so reordering the
+//! widgets, as when bins are dragged along the floor, leaves it in place.
+
+use crossterm::event::KeyCode;
+
+/// Where `Focus::route` sent a key
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Routed<T> {
+    /// Tab or Shift-Tab moved the focus onto this widget
+    Moved(T),
+    /// The key is for the focused widget
+    To(T, KeyCode),
+    /// Nothing is focused, so the key is the screen's own
+    Unfocused(KeyCode),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Focus<T> {
+    order: Vec<T>,
+    focused: Option<T>,
+}
+
+impl<T> Default for Focus<T> {
+    fn default() -> Self {
+        Self { order: Vec::new(), focused: None }
+    }
+}
+
+impl<T: Copy + PartialEq> Focus<T> {
+    /// `order` in tab order, with nothing focused
+    pub fn new(order: Vec<T>) -> Self {
+        Self { order, focused: None }
+    }
+
+    /// `order` in tab order, starting on `widget`
+    pub fn on(order: Vec<T>, widget: T) -> Self {
+        let mut focus = Self::new(order);
+        focus.focus(widget);
+        focus
+    }
+
+    /// Add `widget` at the end of the tab order
+    pub fn register(&mut self, widget: T) {
+        if !self.order.contains(&widget) {
+            self.order.push(widget);
+        }
+    }
+
+    /// Replace the tab order. The focus stays on its widget if that is
+    /// still in the order, and is dropped otherwise.
+    pub fn set_order(&mut self, order: Vec<T>) {
+        self.order = order;
+        self.focused = self.focused.filter(|widget| self.order.contains(widget));
+    }
+
+    pub fn order(&self) -> &[T] {
+        &self.order
+    }
+
+    pub fn focused(&self) -> Option<T> {
+        self.focused
+    }
+
+    pub fn is_focused(&self, widget: T) -> bool {
+        self.focused == Some(widget)
+    }
+
+    /// Put the focus on `widget`; `false`, leaving it where it was, if
+    /// `widget` is not registered
+    pub fn focus(&mut self, widget: T) -> bool {
+        let known = self.order.contains(&widget);
+        if known {
+            self.focused = Some(widget);
+        }
+        known
+    }
+
+    /// Take the focus off every widget
+    pub fn blur(&mut self) {
+        self.focused = None;
+    }
+
+    /// Move the focus `step` widgets along the order, wrapping at the ends.
+    /// With nothing focused, stepping forwards lands on the first widget
+    /// and backwards on the last.
+    pub fn cycle(&mut self, step: isize) -> Option<T> {
+        if self.order.is_empty() {
+            return None;
+        }
+        let len = self.order.len() as isize;
+        let next = match self.focused.and_then(|widget| self.order.iter().position(|&w| w == widget)) {
+            Some(idx) => (idx as isize + step).rem_euclid(len),
+            None if step > 0 => (step - 1).rem_euclid(len),
+            None => (len + step).rem_euclid(len),
+        };
+        self.focused = Some(self.order[next as usize]);
+        self.focused
+    }
+
+    /// Tab and Shift-Tab move the focus; any other key goes to the focused
+    /// widget, if there is one
+    pub fn route(&mut self, key: KeyCode) -> Routed<T> {
+        let step = match key {
+            KeyCode::Tab => 1,
+            KeyCode::BackTab => -1,
+            _ => {
+                return match self.focused {
+                    Some(widget) => Routed::To(widget, key),
+                    None => Routed::Unfocused(key),
+                };
+            },
+        };
+        match self.cycle(step) {
+            Some(widget) => Routed::Moved(widget),
+            None => Routed::Unfocused(key),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Session goals: a short checklist set in `config.toml` or with
+//! `:goal add`, ticked off as the session's events come in.
+//!
+//! A goal's text decides what ticks it:
+//!
+//! - "Fill bin 3" (or "Bin 3 full") when that bin reaches 100%
+//! - "Refine 200 numbers" once that many numbers are captured
+//! - "Complete a file" / "Complete 2 files" once that many files are done
+//!
+//! Anything else is ticked by hand with `:goal done <n>`.
+
+use crate::events::AppEvent;
+use std::fmt;
+
+/// What ticks a goal
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// This bin (from 1) fills up
+    BinFull(usize),
+    /// This many numbers are refined in the session
+    Numbers(u64),
+    /// This many files are completed in the session
+    Files(u64),
+    /// Only `:goal done`
+    Manual,
+}
+
+impl Target {
+    /// Read the target out of a goal's wording
+    pub fn parse(text: &str) -> Target {
+        let lower = text.to_lowercase();
+        let words: Vec<&str> = lower.split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()).collect();
+        let count = |word: &str| match word {
+            "a" | "an" | "one" => Some(1),
+            word => word.parse::<u64>().ok(),
+        };
+
+        for (idx, pair) in words.windows(2).enumerate() {
+            let next = words.get(idx + 2).copied();
+            match (pair[0], pair[1]) {
+                ("bin", n) if n.parse::<usize>().is_ok() => {
+                    let filling = words[..idx].contains(&"fill") || matches!(next, Some("full" | "filled"));
+                    if filling {
+                        return Target::BinFull(n.parse().unwrap());
+                    }
+                },
+                (n, "number" | "numbers") if count(n).is_some() => return Target::Numbers(count(n).unwrap()),
+                (n, "file" | "files") if count(n).is_some() => return Target::Files(count(n).unwrap()),
+                _ => {},
+            }
+        }
+        Target::Manual
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Goal {
+    pub text: String,
+    pub target: Target,
+    pub done: bool,
+}
+
+impl Goal {
+    pub fn new(text: &str) -> Self {
+        let text = text.trim().trim_matches('"').trim().to_string();
+        Goal { target: Target::parse(&text), text, done: false }
+    }
+}
+
+/// The session's checklist and the running totals that tick it
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Goals {
+    pub items: Vec<Goal>,
+    /// Numbers refined since the session started
+    pub numbers: u64,
+    /// Files completed since the session started
+    pub files: u64,
+}
+
+impl Goals {
+    /// Add a goal unless one with the same wording is already listed
+    pub fn add(&mut self, text: &str) -> bool {
+        let goal = Goal::new(text);
+        if goal.text.is_empty() || self.items.iter().any(|g| g.text.eq_ignore_ascii_case(&goal.text)) {
+            return false;
+        }
+        self.items.push(goal);
+        true
+    }
+
+    /// Count `event` and tick whatever it completes, returning the newly
+    /// met goals
+    pub fn observe(&mut self, event: &AppEvent) -> Vec<String> {
+        match event {
+            AppEvent::NumbersRefined { count } => self.numbers += *count as u64,
+            AppEvent::FileCompleted { .. } => self.files += 1,
+            AppEvent::BinFilled { .. } => {},
+        }
+        let (numbers, files) = (self.numbers, self.files);
+
+        let mut met = Vec::new();
+        for goal in self.items.iter_mut().filter(|goal| !goal.done) {
+            goal.done = match goal.target {
+                Target::BinFull(bin) => *event == AppEvent::BinFilled { bin },
+                Target::Numbers(target) => numbers >= target,
+                Target::Files(target) => files >= target,
+                Target::Manual => false,
+            };
+            if goal.done {
+                met.push(goal.text.clone());
+            }
+        }
+        met
+    }
+
+    /// How far along a counted goal is, e.g. `(54, 200)`
+    pub fn progress(&self, goal: &Goal) -> Option<(u64, u64)> {
+        match goal.target {
+            Target::Numbers(target) => Some((self.numbers.min(target), target)),
+            Target::Files(target) => Some((self.files.min(target), target)),
+            _ => None,
+        }
+    }
+
+    pub fn met(&self) -> usize {
+        self.items.iter().filter(|goal| goal.done).count()
+    }
+
+    pub fn all_met(&self) -> bool {
+        !self.items.is_empty() && self.met() == self.items.len()
+    }
+}
+
+/// The end-of-session summary printed once the screen is restored
+impl fmt::Display for Goals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let heading = if self.all_met() {
+            "Every session goal met. The Board is pleased."
+        } else {
+            "Session goals"
+        };
+        writeln!(f, "{} ({} of {})", heading, self.met(), self.items.len())?;
+        for goal in &self.items {
+            writeln!(f, "  [{}] {}", if goal.done { 'x' } else { ' ' }, goal.text)?;
+        }
+        write!(f, "Numbers refined: {}   Files completed: {}", self.numbers, self.files)
+    }
+}
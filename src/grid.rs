@@ -0,0 +1,173 @@
+//! The cells of the number grid that have had their number refined, and
+//! the digit that took its place.
+//!
+//! A replacement is keyed by the cell it sits in, so it only means
+//! something while that cell is on screen. Each frame tells the model how
+//! many columns and rows fit. Cells that no longer fit after a resize are
+//! recycled, and their replacements expire with them. Nothing is replaced
+//! outside the grid in the first place.
+//!
+//! A saved file is different: it may come back in a window of another
+//! size, so its cells are scaled onto the new grid instead (`GridSnapshot`).
+
+use crate::budget;
+use crate::temper::Temper;
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GridModel {
+    /// Columns and rows last drawn; unknown until the first frame
+    size: Option<(usize, usize)>,
+    replaced: HashMap<(usize, usize), u16>,
+}
+
+impl GridModel {
+    /// Columns and rows the grid was last drawn with
+    pub fn size(&self) -> Option<(usize, usize)> {
+        self.size
+    }
+
+    /// The temper of the quadrant `cell` is in, once the grid's size is
+    /// known
+    pub fn temper_at(&self, cell: (usize, usize)) -> Option<Temper> {
+        self.size.map(|size| Temper::of_cell(cell, size))
+    }
+
+    /// Whether `cell` is on the grid; before the first frame every cell is
+    pub fn contains(&self, (col, row): (usize, usize)) -> bool {
+        self.size.is_none_or(|(cols, rows)| col < cols && row < rows)
+    }
+
+    /// The grid is now `cols` by `rows`: cells past its edges are recycled.
+    /// Returns how many replacements expired.
+    pub fn resize(&mut self, cols: usize, rows: usize) -> usize {
+        if self.size == Some((cols, rows)) {
+            return 0;
+        }
+        self.size = Some((cols, rows));
+        let before = self.replaced.len();
+        self.replaced.retain(|&(col, row), _| col < cols && row < rows);
+        before - self.replaced.len()
+    }
+
+    /// Put `digit` in `cell`, keeping at most `cap` replacements. A cell
+    /// off the grid is left alone; returns whether it was replaced.
+    pub fn replace(&mut self, cell: (usize, usize), digit: u16, cap: usize) -> bool {
+        if !self.contains(cell) {
+            return false;
+        }
+        self.replaced.insert(cell, digit);
+        budget::trim_cells(&mut self.replaced, cap, |_| true);
+        true
+    }
+
+    /// The digit that replaced `cell`'s number, if it was refined
+    pub fn replaced(&self, cell: (usize, usize)) -> Option<u16> {
+        self.replaced.get(&cell).copied()
+    }
+
+    /// What `cell` shows, given the digit the file's seed put there
+    pub fn digit(&self, cell: (usize, usize), seeded: u16) -> u16 {
+        self.replaced(cell).unwrap_or(seeded)
+    }
+
+    /// The digit in each cell of a grid drawn from `seed`, row by row, as
+    /// the grid is drawn: each row takes `stride` digits from the seed,
+    /// however many columns fit. Empty before the grid's size is known.
+    pub fn digits(&self, seed: u64, stride: usize) -> Vec<Vec<u16>> {
+        let Some((cols, rows)) = self.size else { return Vec::new() };
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..rows)
+            .map(|row| {
+                let mut digits: Vec<u16> =
+                    (0..stride.max(cols)).map(|col| self.digit((col, row), rng.random_range(0..=9))).collect();
+                digits.truncate(cols);
+                digits
+            })
+            .collect()
+    }
+
+    /// Every replaced cell
+    pub fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.replaced.keys().copied()
+    }
+
+    pub fn len(&self) -> usize {
+        self.replaced.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.replaced.is_empty()
+    }
+
+    /// Forget every replacement, e.g. for a new file
+    pub fn clear(&mut self) {
+        self.replaced.clear();
+    }
+}
+
+/// A file's refined cells, scary clusters and cursor, in the grid they
+/// were saved on
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GridSnapshot {
+    /// Columns and rows
+    pub size: (usize, usize),
+    pub replaced: Vec<((usize, usize), u16)>,
+    pub clusters: Vec<Vec<(usize, usize)>>,
+    /// The cell under the cursor
+    pub cursor: Option<(usize, usize)>,
+}
+
+impl GridSnapshot {
+    /// The same snapshot on a `cols` by `rows` grid, everything moved to the
+    /// same relative place. A cluster keeps its share of the grid, so it
+    /// grows on a bigger one; replaced numbers that land on one cell keep
+    /// the last digit.
+    pub fn remap(&self, cols: usize, rows: usize) -> GridSnapshot {
+        let to = (cols.max(1), rows.max(1));
+        if self.size == to {
+            return self.clone();
+        }
+        let mut replaced: Vec<((usize, usize), u16)> = Vec::new();
+        for &(cell, digit) in &self.replaced {
+            let cell = scale_cell(cell, self.size, to);
+            replaced.retain(|&(other, _)| other != cell);
+            replaced.push((cell, digit));
+        }
+        GridSnapshot {
+            size: to,
+            replaced,
+            clusters: self.clusters.iter().map(|cells| scale_region(cells, self.size, to)).collect(),
+            cursor: self.cursor.map(|cell| scale_cell(cell, self.size, to)),
+        }
+    }
+}
+
+/// Where `cell` of a `from` grid falls on a `to` grid, going by its centre
+pub fn scale_cell((col, row): (usize, usize), from: (usize, usize), to: (usize, usize)) -> (usize, usize) {
+    let axis = |at: usize, from: usize, to: usize| ((2 * at + 1) * to / (2 * from.max(1))).min(to - 1);
+    (axis(col, from.0, to.0), axis(row, from.1, to.1))
+}
+
+// The cells of a `to` grid covering `cells` of a `from` grid: those whose
+// centre falls inside one of them, plus one for each so none are lost
+// when shrinking
+fn scale_region(cells: &[(usize, usize)], from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
+    let mut region: Vec<(usize, usize)> = cells.iter().map(|&cell| scale_cell(cell, from, to)).collect();
+    let span = |lo: usize, hi: usize, from: usize, to: usize| (lo * to / from.max(1))..((hi + 1) * to).div_ceil(from.max(1)).min(to);
+    let (Some(left), Some(right)) = (cells.iter().map(|c| c.0).min(), cells.iter().map(|c| c.0).max()) else {
+        return region;
+    };
+    let (top, bottom) = (cells.iter().map(|c| c.1).min().unwrap_or(0), cells.iter().map(|c| c.1).max().unwrap_or(0));
+    for col in span(left, right, from.0, to.0) {
+        for row in span(top, bottom, from.1, to.1) {
+            if cells.contains(&scale_cell((col, row), to, from)) {
+                region.push((col, row));
+            }
+        }
+    }
+    region.sort_unstable();
+    region.dedup();
+    region
+}
@@ -0,0 +1,208 @@
+//! Progress contributed from outside the app.
+//!
+//! A watcher thread reads one JSON object per line from a FIFO or a
+//! regular file, e.g. `{"bin": 2, "add": 5}`, and hands validated
+//! contributions to the event loop. A FIFO is reopened whenever its writer
+//! goes away; a regular file is followed like `tail -f`, starting from its
+//! current end.
+
+use crate::app::{App, AppState};
+use crate::error::{MdrError, Result};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+use std::time::Duration;
+
+/// How often a regular file is checked for new lines
+const FOLLOW_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Most a single contribution may add: one full bin
+pub const MAX_CONTRIBUTION: u16 = 100;
+
+/// One validated request to deposit into a bin
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Contribution {
+    /// Bin number as the refiner sees it, starting at 1
+    pub bin: usize,
+    pub add: u16,
+}
+
+impl FromStr for Contribution {
+    type Err = MdrError;
+
+    fn from_str(line: &str) -> Result<Self> {
+        let fields = parse_flat_object(line)?;
+
+        let mut bin = None;
+        let mut add = None;
+        for (key, value) in fields {
+            match key.as_str() {
+                "bin" => bin = Some(value),
+                "add" => add = Some(value),
+                other => return Err(invalid(format!("unknown field \"{}\"", other))),
+            }
+        }
+
+        let bin = bin.ok_or_else(|| invalid("missing \"bin\""))?;
+        let add = add.ok_or_else(|| invalid("missing \"add\""))?;
+        if !(1..=5).contains(&bin) {
+            return Err(invalid(format!("\"bin\" must be 1 to 5, got {}", bin)));
+        }
+        if !(1..=MAX_CONTRIBUTION as i64).contains(&add) {
+            return Err(invalid(format!("\"add\" must be 1 to {}, got {}", MAX_CONTRIBUTION, add)));
+        }
+
+        Ok(Contribution { bin: bin as usize, add: add as u16 })
+    }
+}
+
+enum Message {
+    Contribution(Contribution),
+    Rejected(String),
+    Failed(String),
+}
+
+/// Receives contributions from the watcher thread
+pub struct Injector {
+    pub path: PathBuf,
+    rx: Receiver<Message>,
+    pub applied: usize,
+    pub rejected: usize,
+    pub last_rejection: Option<String>,
+}
+
+impl Injector {
+    /// Start watching `path` on a background thread
+    pub fn watch(path: PathBuf) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let watched = path.clone();
+        thread::spawn(move || {
+            if let Err(err) = watch(&watched, &tx) {
+                let _ = tx.send(Message::Failed(format!("{}: {}", watched.display(), err)));
+            }
+        });
+        Self { path, rx, applied: 0, rejected: 0, last_rejection: None }
+    }
+
+    /// Start a fresh watcher on the same path, keeping the counts
+    pub fn restart(&mut self) {
+        let fresh = Injector::watch(self.path.clone());
+        self.rx = fresh.rx;
+    }
+
+    /// Deposit whatever has arrived. Contributions wait while the refiner
+    /// is away from the main screen. Returns an error if the watcher died.
+    pub fn apply(&mut self, app: &mut App) -> Result<()> {
+        while app.state() == AppState::Main && !app.read_only {
+            match self.rx.try_recv() {
+                Ok(Message::Contribution(c)) => {
+                    // Straight into the bin; a pending click must survive
+                    app.deposit(c.bin - 1, c.add);
+                    self.applied += 1;
+                },
+                Ok(Message::Rejected(reason)) => {
+                    self.rejected += 1;
+                    self.last_rejection = Some(reason);
+                },
+                Ok(Message::Failed(reason)) => return Err(MdrError::Io(io::Error::other(reason))),
+                Err(TryRecvError::Empty | TryRecvError::Disconnected) => break,
+            }
+        }
+        Ok(())
+    }
+}
+
+// Runs until the file can no longer be read or the app stops listening
+fn watch(path: &Path, tx: &Sender<Message>) -> io::Result<()> {
+    let is_fifo = is_fifo(path)?;
+
+    loop {
+        // Opening a FIFO blocks until a writer shows up
+        let mut file = File::open(path)?;
+        if !is_fifo {
+            file.seek(SeekFrom::End(0))?;
+        }
+        let mut reader = BufReader::new(file);
+        let mut line = String::new();
+
+        loop {
+            let read = reader.read_line(&mut line)?;
+            if read == 0 {
+                if is_fifo {
+                    // Writer closed; wait for the next one
+                    break;
+                }
+                // A truncated file starts over from the top
+                let pos = reader.stream_position()?;
+                if reader.get_ref().metadata()?.len() < pos {
+                    reader.seek(SeekFrom::Start(0))?;
+                }
+                thread::sleep(FOLLOW_INTERVAL);
+                continue;
+            }
+
+            // Wait for the rest of a line that is still being written
+            if !line.ends_with('\n') && !is_fifo {
+                continue;
+            }
+
+            let text = line.trim();
+            if !text.is_empty() {
+                let message = match text.parse::<Contribution>() {
+                    Ok(c) => Message::Contribution(c),
+                    Err(err) => Message::Rejected(err.to_string()),
+                };
+                if tx.send(message).is_err() {
+                    return Ok(());
+                }
+            }
+            line.clear();
+        }
+    }
+}
+
+#[cfg(unix)]
+fn is_fifo(path: &Path) -> io::Result<bool> {
+    use std::os::unix::fs::FileTypeExt;
+    Ok(std::fs::metadata(path)?.file_type().is_fifo())
+}
+
+#[cfg(not(unix))]
+fn is_fifo(path: &Path) -> io::Result<bool> {
+    std::fs::metadata(path).map(|_| false)
+}
+
+fn invalid(message: impl Into<String>) -> MdrError {
+    MdrError::Config(format!("injected command: {}", message.into()))
+}
+
+// Just enough JSON for `{"key": integer, ...}`
+fn parse_flat_object(text: &str) -> Result<Vec<(String, i64)>> {
+    let body = text
+        .trim()
+        .strip_prefix('{')
+        .and_then(|t| t.strip_suffix('}'))
+        .ok_or_else(|| invalid("expected a JSON object"))?;
+
+    let mut fields = Vec::new();
+    for pair in body.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let (key, value) = pair.split_once(':').ok_or_else(|| invalid(format!("expected \"key\": value, got {}", pair)))?;
+        let key = key
+            .trim()
+            .strip_prefix('"')
+            .and_then(|k| k.strip_suffix('"'))
+            .ok_or_else(|| invalid(format!("field names need quotes: {}", key.trim())))?;
+        let value = value
+            .trim()
+            .parse::<i64>()
+            .map_err(|_| invalid(format!("\"{}\" must be a whole number", key)))?;
+        if fields.iter().any(|(k, _)| k == key) {
+            return Err(invalid(format!("\"{}\" given twice", key)));
+        }
+        fields.push((key.to_string(), value));
+    }
+    Ok(fields)
+}
@@ -1,79 +1,325 @@
-use crate::{app::App, ui};
-use crossterm::event::{self, Event};
-use crossterm::terminal;
+use crate::{app::{App, AppState, ECO_TICK_RATE, TOAST_DURATION}, clipboard, diagnostics, ranks, ui};
+use crate::error::{FailureKind, MdrError, RecoverableError};
+use crossterm::event::{self, Event, MouseEventKind};
+use std::io;
 use std::time::{Duration, Instant};
 use ratatui::Terminal;
 
+/// Consecutive input failures tolerated before giving up on the terminal
+const MAX_INPUT_FAILURES: u32 = 3;
+
+/// Most events handled between two frames; the rest wait for the next one
+/// so a flood of mouse motion cannot starve ticks and redraws
+const MAX_EVENTS_PER_FRAME: usize = 64;
+
+/// Repeat clicks on the same cell within this window are treated as bounce
+const CLICK_DEBOUNCE: Duration = Duration::from_millis(80);
+
 // Define the desired window size
 pub const DESIRED_WIDTH: u16 = 120;
 pub const DESIRED_HEIGHT: u16 = 40;
 
-pub fn event_loop<B: ratatui::backend::Backend>(
+/// Where the event loop gets its input from
+pub trait EventSource {
+    /// Wait up to `timeout` for an event to become available
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool>;
+
+    /// Read the next event; only called after `poll` returned true
+    fn read(&mut self) -> io::Result<Event>;
+
+    /// Whether the source has run dry and the loop should stop
+    fn is_finished(&self) -> bool {
+        false
+    }
+
+    /// Start or stop reporting the mouse, which takes the terminal's own
+    /// text selection while on
+    fn set_mouse_capture(&mut self, _on: bool) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Reads events from the real terminal
+pub struct CrosstermEvents;
+
+impl EventSource for CrosstermEvents {
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        event::poll(timeout)
+    }
+
+    fn read(&mut self) -> io::Result<Event> {
+        event::read()
+    }
+
+    fn set_mouse_capture(&mut self, on: bool) -> io::Result<()> {
+        if on {
+            crossterm::execute!(io::stdout(), event::EnableMouseCapture)
+        } else {
+            crossterm::execute!(io::stdout(), event::DisableMouseCapture)
+        }
+    }
+}
+
+pub fn event_loop<B: ratatui::backend::Backend, E: EventSource>(
     terminal: &mut Terminal<B>,
     app: &mut App,
-) -> anyhow::Result<()> {
-    // Enable mouse capture when the app starts
-    crossterm::execute!(
-        std::io::stdout(),
-        crossterm::event::EnableMouseCapture
-    )?;
-    
+    events: &mut E,
+) -> crate::error::Result<()> {
     // Set size warning flag
     let mut has_shown_size_warning = false;
     
-    // For consistent timing - extremely slow rate for barely perceptible animation
-    let tick_rate = Duration::from_millis(300);  // Increased from 100ms
-    let mut last_tick = Instant::now();
+    let mut last_tick = app.clock.now();
+    let mut last_frame = Instant::now();
+    let mut last_draw: Option<Instant> = None;
+    let mut input_failures = 0;
+    let mut last_click: Option<(Instant, u16, u16)> = None;
+    // The terminal starts out reporting the mouse
+    let mut mouse_capture = true;
     
     // Check window size and update app status
-    check_window_size(app);
+    let size = terminal.size().map_err(MdrError::Terminal)?;
+    app.on_resize(size.width, size.height);
     
-    while app.running {
-        // Calculate time until next tick
+    while app.running && !events.is_finished() {
+        // Calculate time until next tick; the rate adapts to activity
+        let tick_rate = app.tick_rate();
         let timeout = tick_rate
-            .checked_sub(last_tick.elapsed())
+            .checked_sub(app.clock.now().duration_since(last_tick))
             .unwrap_or(Duration::from_millis(0));
             
-        // Draw UI
-        terminal.draw(|frame| ui::draw(frame, app))?;
+        // Eco mode only redraws at its own slow cadence, however much input arrives
+        let now = app.clock.now();
+        let frame_due = !app.is_eco()
+            || last_draw.is_none_or(|at| now.duration_since(at) >= ECO_TICK_RATE);
+
+        if frame_due {
+            let size = terminal.size().map_err(MdrError::Terminal)?;
+            ui::layout(app, size);
+            // Draw UI, measuring how long the frame takes for the debug HUD.
+            // These are real measurements, so they bypass the app clock.
+            let frame_start = Instant::now();
+            let allocations_before = diagnostics::allocation_count();
+            // Whatever drawing does not account for is the backend writing
+            // the frame out
+            let mut drawing = Duration::ZERO;
+            let frame = terminal
+                .draw(|frame| {
+                    let start = Instant::now();
+                    ui::draw(frame, app);
+                    drawing = start.elapsed();
+                })
+                .map_err(MdrError::Terminal)?;
+            let write_time = frame_start.elapsed().saturating_sub(drawing);
+            if app.station.show_debug_hud {
+                let grid = app.grid_geometry.filter(|_| app.state() == AppState::Main).map(|geometry| geometry.area);
+                app.station.frame_stats.record_diff(frame.buffer, grid);
+            } else {
+                app.station.frame_stats.forget_frames();
+            }
+            if std::mem::take(&mut app.copy_screen) {
+                app.copy_text("Screen", &clipboard::screen_text(frame.buffer));
+            }
+            if let Some(recorder) = &mut app.station.recorder
+                && let Err(err) = recorder.record(frame.buffer, now)
+            {
+                app.station.recorder = None;
+                app.show_toast(format!("Recording stopped: {}", err), Some(TOAST_DURATION));
+            }
+            let allocations = diagnostics::allocation_count() - allocations_before;
+            let render_time = frame_start.elapsed();
+            if let Some(quality) = app.station.frame_stats.record_frame(frame_start - last_frame, render_time, allocations) {
+                app.on_stuck_frame(render_time, quality);
+            }
+            app.station.frame_stats.record_write(write_time, &app.station.config.lag);
+            last_frame = frame_start;
+            last_draw = Some(now);
+        }
         
         // Show size warning if needed (only once)
-        if app.window_size_warning && !has_shown_size_warning {
+        if app.station.window_size_warning && !has_shown_size_warning {
             app.show_size_warning = true;
             has_shown_size_warning = true;
         }
         
-        // Poll for events with timeout
-        if event::poll(timeout)? {
-            match event::read()? {
-                Event::Key(key) => app.on_key(key.code),
-                Event::Mouse(mouse) => app.on_mouse(mouse),
-                Event::Resize(_, _) => check_window_size(app),
-                _ => {}
-            }
+        // A hiccup reading input goes to the error screen rather than tearing
+        // the session down; only a terminal that keeps failing is fatal
+        if app.pending_retry == Some(FailureKind::Terminal) {
+            app.pending_retry = None;
+            input_failures = 0;
+        }
+        if app.pending_retry == Some(FailureKind::Config) {
+            app.pending_retry = None;
+            app.reload_config();
+        }
+        if app.pending_retry == Some(FailureKind::Integrity) {
+            app.pending_retry = None;
+            app.run_selfcheck(true);
+        }
+        let mut queue_depth = 0;
+        match pump_events(app, events, timeout, &mut queue_depth, &mut last_click) {
+            Ok(()) => input_failures = 0,
+            Err(err) => {
+                input_failures += 1;
+                if input_failures >= MAX_INPUT_FAILURES {
+                    return Err(MdrError::Terminal(err));
+                }
+                app.report_error(RecoverableError::new(FailureKind::Terminal, err.to_string()));
+            },
+        }
+        app.station.frame_stats.queue_depth = queue_depth;
+        apply_injected(app);
+        app.poll_startup();
+        app.poll_key_derivation();
+        app.poll_watcher();
+        app.poll_updates();
+        dispatch_events(app);
+        if app.station.config.input.mouse != mouse_capture && events.set_mouse_capture(app.station.config.input.mouse).is_ok() {
+            mouse_capture = app.station.config.input.mouse;
         }
         
-        // Update app state at a fixed tick rate
-        if last_tick.elapsed() >= tick_rate {
+        // Update app state once the tick interval has passed
+        let now = app.clock.now();
+        if now.duration_since(last_tick) >= app.tick_rate() {
             app.tick();
-            last_tick = Instant::now();
+            last_tick = now;
+            sync_session(app);
         }
     }
     
-    // Disable mouse capture when the app exits
-    crossterm::execute!(
-        std::io::stdout(),
-        crossterm::event::DisableMouseCapture
-    )?;
-    
     Ok(())
 }
 
-// Check if window size matches desired size
-fn check_window_size(app: &mut App) {
-    if let Ok((width, height)) = terminal::size() {
-        app.window_size_warning = width < DESIRED_WIDTH || height < DESIRED_HEIGHT;
-        app.current_width = width;
-        app.current_height = height;
+// Publish to or pull from a shared session file. A failure pauses the
+// link and goes to the error screen; retrying resumes it.
+fn sync_session(app: &mut App) {
+    let Some(mut link) = app.session_link.take() else { return };
+
+    if app.pending_retry == Some(FailureKind::SaveFile) {
+        app.pending_retry = None;
+        link.paused = false;
+    }
+    if !link.paused && let Err(err) = link.sync(app) {
+        link.paused = true;
+        app.report_error(RecoverableError::new(FailureKind::SaveFile, err.to_string()));
+    }
+
+    app.session_link = Some(link);
+}
+
+// Deposit progress from external tools. If the watcher dies, say so on the
+// error screen; retrying starts a new one.
+fn apply_injected(app: &mut App) {
+    let Some(mut injector) = app.station.injector.take() else { return };
+
+    if app.pending_retry == Some(FailureKind::Injection) {
+        app.pending_retry = None;
+        injector.restart();
+    }
+    if let Err(err) = injector.apply(app) {
+        app.report_error(RecoverableError::new(FailureKind::Injection, err.to_string()));
+    }
+
+    app.station.injector = Some(injector);
+}
+
+// Hand whatever happened since the last frame to the outputs
+fn dispatch_events(app: &mut App) {
+    let earned = app.station.outbox.iter().map(ranks::xp_for).sum();
+    app.gain_xp(earned);
+
+    app.log_events();
+    app.check_goals();
+    app.check_lessons();
+    let today = app.today();
+    for event in &app.station.outbox {
+        app.station.ledger.record(today, event);
+    }
+
+    if let Some(metrics) = &app.station.metrics {
+        for event in &app.station.outbox {
+            metrics.record(event);
+        }
+    }
+    #[cfg(feature = "telemetry")]
+    if let Some(telemetry) = &app.station.telemetry {
+        for event in &app.station.outbox {
+            telemetry.send(&app.username, event);
+        }
+    }
+    #[cfg(feature = "speech")]
+    if let Some(speech) = &app.station.speech {
+        let now = app.clock.now();
+        for event in &app.station.outbox {
+            speech.announce(event, now);
+        }
+    }
+    app.station.outbox.clear();
+}
+
+// Poll for events with timeout, then drain whatever else is queued (up to
+// the per-frame cap) so a burst of input is handled before the next redraw
+fn pump_events<E: EventSource>(
+    app: &mut App,
+    events: &mut E,
+    timeout: Duration,
+    queue_depth: &mut usize,
+    last_click: &mut Option<(Instant, u16, u16)>,
+) -> io::Result<()> {
+    let mut batch: Vec<Event> = Vec::new();
+    let result = read_batch(events, timeout, &mut batch, queue_depth, &mut app.station.frame_stats.moves_coalesced);
+
+    // Apply whatever was read even if the source failed part way through
+    for event in batch {
+        if let Event::Mouse(mouse) = &event
+            && let MouseEventKind::Down(_) = mouse.kind
+        {
+            let now = app.clock.now();
+            let bounced = last_click.is_some_and(|(at, x, y)| {
+                x == mouse.column && y == mouse.row && now.duration_since(at) < CLICK_DEBOUNCE
+            });
+            if bounced {
+                app.station.frame_stats.clicks_debounced += 1;
+                continue;
+            }
+            *last_click = Some((now, mouse.column, mouse.row));
+        }
+        app.update(event);
+    }
+
+    result
+}
+
+// Read queued events into `batch`, merging runs of mouse motion into the
+// latest position since only the final one affects the next frame
+fn read_batch<E: EventSource>(
+    events: &mut E,
+    timeout: Duration,
+    batch: &mut Vec<Event>,
+    queue_depth: &mut usize,
+    moves_coalesced: &mut usize,
+) -> io::Result<()> {
+    if !events.poll(timeout)? {
+        return Ok(());
     }
-}
\ No newline at end of file
+
+    loop {
+        let event = events.read()?;
+        *queue_depth += 1;
+
+        match batch.last_mut() {
+            Some(previous) if is_motion(previous) && is_motion(&event) => {
+                *previous = event;
+                *moves_coalesced += 1;
+            },
+            _ => batch.push(event),
+        }
+
+        if *queue_depth >= MAX_EVENTS_PER_FRAME || !events.poll(Duration::ZERO)? {
+            return Ok(());
+        }
+    }
+}
+
+fn is_motion(event: &Event) -> bool {
+    matches!(event, Event::Mouse(mouse) if mouse.kind == MouseEventKind::Moved)
+}
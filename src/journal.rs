@@ -0,0 +1,66 @@
+//! The refiner's journal: notes typed with `:note` during a session, so
+//! there is a record of what they were actually working on while the
+//! numbers were being refined. Notes are kept with the session save,
+//! listed on the work/life balance report and printed on exit.
+
+use crate::budget;
+use std::collections::VecDeque;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// One line in the journal
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Note {
+    /// Seconds since 1970-01-01 UTC
+    pub at: u64,
+    pub text: String,
+}
+
+impl Note {
+    pub fn new(wall: SystemTime, text: &str) -> Self {
+        let at = wall.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        Note { at, text: text.trim().to_string() }
+    }
+
+    /// `HH:MM` in a clock `utc_offset` minutes from UTC
+    pub fn stamp(&self, utc_offset: i16) -> String {
+        let minutes = (self.at as i64 / 60 + utc_offset as i64).rem_euclid(24 * 60);
+        crate::shift::format_time(minutes as u16)
+    }
+}
+
+/// The session's notes, oldest first
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Journal {
+    pub notes: VecDeque<Note>,
+}
+
+impl Journal {
+    /// Write `text` down at `wall`, keeping at most `cap` notes. Blank
+    /// text is not a note; returns whether one was added.
+    pub fn add(&mut self, wall: SystemTime, text: &str, cap: usize) -> bool {
+        let note = Note::new(wall, text);
+        if note.text.is_empty() {
+            return false;
+        }
+        budget::push_capped(&mut self.notes, note, cap);
+        true
+    }
+
+    pub fn len(&self) -> usize {
+        self.notes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// The end-of-session listing printed once the screen is restored
+    pub fn summary(&self, utc_offset: i16) -> String {
+        let plural = if self.notes.len() == 1 { "" } else { "s" };
+        let mut text = format!("Session journal ({} note{})", self.notes.len(), plural);
+        for note in &self.notes {
+            text.push_str(&format!("\n  {}  {}", note.stamp(utc_offset), note.text));
+        }
+        text
+    }
+}
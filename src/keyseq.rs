@@ -0,0 +1,50 @@
+//! Recognising a run of keys typed in order, such as the Konami code.
+
+use crossterm::event::KeyCode;
+use std::collections::VecDeque;
+
+/// Up, up, down, down, left, right, left, right, B, A
+pub const KONAMI: &[KeyCode] = &[
+    KeyCode::Up,
+    KeyCode::Up,
+    KeyCode::Down,
+    KeyCode::Down,
+    KeyCode::Left,
+    KeyCode::Right,
+    KeyCode::Left,
+    KeyCode::Right,
+    KeyCode::Char('b'),
+    KeyCode::Char('a'),
+];
+
+/// Watches key presses for one fixed sequence
+#[derive(Clone, Debug)]
+pub struct KeySequence {
+    pattern: &'static [KeyCode],
+    recent: VecDeque<KeyCode>,
+}
+
+impl KeySequence {
+    pub fn new(pattern: &'static [KeyCode]) -> Self {
+        Self { pattern, recent: VecDeque::with_capacity(pattern.len()) }
+    }
+
+    /// Record a key press; true when it completes the sequence. Stray keys
+    /// in between start the sequence over.
+    pub fn push(&mut self, key: KeyCode) -> bool {
+        if self.recent.len() == self.pattern.len() {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(key);
+
+        let complete = self.recent.iter().eq(self.pattern.iter());
+        if complete {
+            self.recent.clear();
+        }
+        complete
+    }
+
+    pub fn reset(&mut self) {
+        self.recent.clear();
+    }
+}
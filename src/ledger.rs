@@ -0,0 +1,379 @@
+//! A day-by-day record of refinement on this terminal, for recaps and
+//! history.
+//!
+//! ```text
+//! lumon-mdr ledger 1
+//! recap=2025-W10
+//! learned=hover,refine
+//! best=272100
+//! race=2025-03-04 HUSH-49B4996-10 target=300000 time=272100
+//! 2025-03-03 numbers=340 bins=11 files=2
+//! 2025-03-04 numbers=95 bins=2 files=0
+//! ```
+//!
+//! Days are calendar days in the shift's UTC offset (UTC without a shift).
+//! Times are in milliseconds.
+//! Unknown keys are ignored so older builds can read newer files.
+
+use crate::error::{MdrError, Result};
+use crate::events::AppEvent;
+use crate::onboarding::Lesson;
+use crate::race::RaceResult;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Format version written on the first line
+pub const LEDGER_VERSION: u32 = 1;
+
+const HEADER: &str = "lumon-mdr ledger";
+
+/// Races kept; older ones are dropped as new ones finish
+pub const RACES_KEPT: usize = 100;
+
+/// Weekday names, Monday first
+pub const WEEKDAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+
+/// What was refined on one day
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DayTotals {
+    pub numbers: u64,
+    pub bins: u64,
+    pub files: u64,
+}
+
+impl DayTotals {
+    pub fn is_empty(&self) -> bool {
+        *self == DayTotals::default()
+    }
+
+    fn add(&mut self, other: DayTotals) {
+        self.numbers += other.numbers;
+        self.bins += other.bins;
+        self.files += other.files;
+    }
+}
+
+/// An ISO 8601 week, e.g. 2025-W10
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Week {
+    pub year: i32,
+    pub week: u32,
+}
+
+impl fmt::Display for Week {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-W{:02}", self.year, self.week)
+    }
+}
+
+impl FromStr for Week {
+    type Err = MdrError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let (year, week) = text
+            .split_once("-W")
+            .and_then(|(y, w)| Some((y.parse().ok()?, w.parse().ok()?)))
+            .filter(|&(_, w)| (1..=53).contains(&w))
+            .ok_or_else(|| MdrError::Save(format!("ledger: '{}' is not a week like 2025-W10", text)))?;
+        Ok(Week { year, week })
+    }
+}
+
+/// One week of the ledger, summed up
+#[derive(Clone, Debug, PartialEq)]
+pub struct Recap {
+    pub week: Week,
+    /// Day number of the week's Monday
+    pub monday: i64,
+    /// Monday first
+    pub days: [DayTotals; 7],
+    pub total: DayTotals,
+    /// Weekday with the most files completed (numbers refined break ties)
+    pub best_day: Option<usize>,
+    /// Longest run of consecutive days with any refinement
+    pub streak: u32,
+}
+
+/// Refinement totals by day
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Ledger {
+    /// Keyed by days since 1970-01-01
+    pub days: BTreeMap<i64, DayTotals>,
+    /// The last week a recap was offered for
+    pub recap_week: Option<Week>,
+    /// What the refiner has done at least once, so is not coached on again
+    pub learned: BTreeSet<Lesson>,
+    /// The fastest file completed from a fresh board
+    pub best: Option<Duration>,
+    /// Finished races, oldest first
+    pub races: Vec<RaceResult>,
+}
+
+impl Ledger {
+    /// Count an event towards `day`
+    pub fn record(&mut self, day: i64, event: &AppEvent) {
+        let totals = self.days.entry(day).or_default();
+        match event {
+            AppEvent::NumbersRefined { count } => totals.numbers += *count as u64,
+            AppEvent::BinFilled { .. } => totals.bins += 1,
+            AppEvent::FileCompleted { .. } => totals.files += 1,
+        }
+    }
+
+    /// Keep `result`, dropping the oldest race past `RACES_KEPT`
+    pub fn record_race(&mut self, result: RaceResult) {
+        self.races.push(result);
+        let excess = self.races.len().saturating_sub(RACES_KEPT);
+        self.races.drain(..excess);
+    }
+
+    /// Totals for each day from `first` up to (not including) `end`
+    pub fn daily(&self, first: i64, end: i64) -> Vec<DayTotals> {
+        (first..end).map(|day| self.days.get(&day).copied().unwrap_or_default()).collect()
+    }
+
+    /// The week starting on the Monday `monday`
+    pub fn recap(&self, monday: i64) -> Recap {
+        let mut days = [DayTotals::default(); 7];
+        days.copy_from_slice(&self.daily(monday, monday + 7));
+
+        let mut total = DayTotals::default();
+        let (mut streak, mut run) = (0, 0);
+        for day in &days {
+            total.add(*day);
+            run = if day.is_empty() { 0 } else { run + 1 };
+            streak = streak.max(run);
+        }
+        let best_day = (0..7)
+            .filter(|&idx| !days[idx].is_empty())
+            .max_by_key(|&idx| (days[idx].files, days[idx].numbers, std::cmp::Reverse(idx)));
+
+        Recap { week: iso_week(monday), monday, days, total, best_day, streak }
+    }
+
+    pub fn load(path: &Path) -> Result<Ledger> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => text.parse(),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Ledger::default()),
+            Err(err) => Err(MdrError::Save(format!("{}: {}", path.display(), err))),
+        }
+    }
+
+    pub fn store(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        crate::save::write_atomic(path, &self.to_string())
+    }
+}
+
+impl fmt::Display for Ledger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", HEADER, LEDGER_VERSION)?;
+        if let Some(week) = self.recap_week {
+            writeln!(f, "recap={}", week)?;
+        }
+        if !self.learned.is_empty() {
+            let keys: Vec<&str> = self.learned.iter().map(|lesson| lesson.key()).collect();
+            writeln!(f, "learned={}", keys.join(","))?;
+        }
+        if let Some(best) = self.best {
+            writeln!(f, "best={}", best.as_millis())?;
+        }
+        for race in &self.races {
+            writeln!(
+                f,
+                "race={} {} target={} time={}",
+                format_day(race.day),
+                race.code,
+                race.target.as_millis(),
+                race.time.as_millis()
+            )?;
+        }
+        for (&day, totals) in &self.days {
+            writeln!(
+                f,
+                "{} numbers={} bins={} files={}",
+                format_day(day),
+                totals.numbers,
+                totals.bins,
+                totals.files
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Ledger {
+    type Err = MdrError;
+
+    fn from_str(source: &str) -> Result<Self> {
+        let mut lines = source.lines();
+        let version = lines
+            .next()
+            .and_then(|line| line.strip_prefix(HEADER))
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .ok_or_else(|| MdrError::Save("not a lumon-mdr ledger".to_string()))?;
+        if version > LEDGER_VERSION {
+            return Err(MdrError::Save(format!("ledger version {} is newer than this build", version)));
+        }
+
+        let mut ledger = Ledger::default();
+        for (idx, line) in lines.enumerate() {
+            let line_no = idx + 2;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if let Some(week) = line.strip_prefix("recap=") {
+                ledger.recap_week = Some(week.parse()?);
+                continue;
+            }
+            if let Some(keys) = line.strip_prefix("learned=") {
+                // Lessons from a newer build are skipped, like unknown keys
+                ledger.learned = keys.split(',').filter_map(|key| key.parse().ok()).collect();
+                continue;
+            }
+            if let Some(millis) = line.strip_prefix("best=") {
+                let millis = millis.parse().map_err(|_| invalid(line_no, "times must be whole milliseconds"))?;
+                ledger.best = Some(Duration::from_millis(millis));
+                continue;
+            }
+            if let Some(race) = line.strip_prefix("race=") {
+                ledger.races.push(parse_race(line_no, race)?);
+                continue;
+            }
+
+            let mut fields = line.split_whitespace();
+            let day = fields
+                .next()
+                .and_then(parse_day)
+                .ok_or_else(|| invalid(line_no, "expected a date like 2025-03-03"))?;
+            let mut totals = DayTotals::default();
+            for field in fields {
+                let Some((key, value)) = field.split_once('=') else {
+                    return Err(invalid(line_no, "expected key=value"));
+                };
+                let value: u64 = value.parse().map_err(|_| invalid(line_no, "counts must be whole numbers"))?;
+                match key {
+                    "numbers" => totals.numbers = value,
+                    "bins" => totals.bins = value,
+                    "files" => totals.files = value,
+                    _ => {},
+                }
+            }
+            ledger.days.insert(day, totals);
+        }
+        Ok(ledger)
+    }
+}
+
+/// Where the ledger is kept: in the data directory, next to the sessions
+/// folder
+pub fn default_path() -> Option<PathBuf> {
+    Some(crate::paths::data_dir()?.join("ledger"))
+}
+
+/// Days since 1970-01-01 at `wall`, in a clock `utc_offset` minutes from UTC
+pub fn day_number(wall: SystemTime, utc_offset: i16) -> i64 {
+    let secs = wall.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+    (secs / 60 + utc_offset as i64).div_euclid(24 * 60)
+}
+
+/// 0 for Monday through 6 for Sunday
+pub fn weekday(day: i64) -> usize {
+    // 1970-01-01 was a Thursday
+    (day + 3).rem_euclid(7) as usize
+}
+
+/// The Monday on or before `day`
+pub fn week_start(day: i64) -> i64 {
+    day - weekday(day) as i64
+}
+
+pub fn iso_week(day: i64) -> Week {
+    // The week belongs to the year its Thursday falls in
+    let thursday = week_start(day) + 3;
+    let (year, _, _) = civil(thursday);
+    let week = (thursday - days_from_civil(year, 1, 1)) / 7 + 1;
+    Week { year, week: week as u32 }
+}
+
+/// `YYYY-MM-DD`
+pub fn format_day(day: i64) -> String {
+    let (year, month, date) = civil(day);
+    format!("{:04}-{:02}-{:02}", year, month, date)
+}
+
+pub fn parse_day(text: &str) -> Option<i64> {
+    let mut parts = text.split('-');
+    let year = parts.next()?.parse().ok()?;
+    let month = parts.next()?.parse().ok().filter(|m| (1..=12).contains(m))?;
+    let date = parts.next()?.parse().ok().filter(|d| (1..=31).contains(d))?;
+    if parts.next().is_some() {
+        return None;
+    }
+    let day = days_from_civil(year, month, date);
+    // Reject dates like 02-30 that roll over into the next month
+    (civil(day) == (year, month, date)).then_some(day)
+}
+
+// Calendar conversions after Howard Hinnant's public-domain algorithms
+fn days_from_civil(year: i32, month: u32, date: u32) -> i64 {
+    let year = if month <= 2 { year as i64 - 1 } else { year as i64 };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + date as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+fn civil(day: i64) -> (i32, u32, u32) {
+    let z = day + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let date = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year as i32, month, date)
+}
+
+// `2025-03-04 HUSH-49B4996-10 target=300000 time=272100`
+fn parse_race(line_no: usize, text: &str) -> Result<RaceResult> {
+    let mut fields = text.split_whitespace();
+    let day = fields
+        .next()
+        .and_then(parse_day)
+        .ok_or_else(|| invalid(line_no, "expected a date like 2025-03-03"))?;
+    let code = fields
+        .next()
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| invalid(line_no, "expected a share code"))?;
+    let (mut target, mut time) = (None, None);
+    for field in fields {
+        let Some((key, value)) = field.split_once('=') else {
+            return Err(invalid(line_no, "expected key=value"));
+        };
+        let value = value.parse().map_err(|_| invalid(line_no, "times must be whole milliseconds"))?;
+        match key {
+            "target" => target = Some(Duration::from_millis(value)),
+            "time" => time = Some(Duration::from_millis(value)),
+            _ => {},
+        }
+    }
+    let (Some(target), Some(time)) = (target, time) else {
+        return Err(invalid(line_no, "a race needs a target and a time"));
+    };
+    Ok(RaceResult { day, code, target, time })
+}
+
+fn invalid(line: usize, message: &str) -> MdrError {
+    MdrError::Save(format!("ledger line {}: {}", line, message))
+}
@@ -1,5 +1,67 @@
+pub mod ambience;
+pub mod animate;
 pub mod app;
+pub mod assist;
+pub mod autosave;
+pub mod branding;
+pub mod budget;
+pub mod card;
+pub mod cheats;
+pub mod cli;
+pub mod clipboard;
+pub mod clock;
+pub mod combo;
+pub mod compliance;
+pub mod config;
+pub mod content;
+pub mod context_menu;
+pub mod crypto;
+pub mod diagnostics;
+pub mod doctor;
+pub mod environment;
+pub mod error;
+pub mod events;
+pub mod focus;
+pub mod goals;
+pub mod grid;
+pub mod inject;
 pub mod input;
+pub mod journal;
+pub mod keyseq;
+pub mod ledger;
+pub mod metrics;
+pub mod observe;
+pub mod paths;
+pub mod onboarding;
+pub mod orientation;
+pub mod profile;
+pub mod race;
+pub mod random;
+pub mod ranks;
+pub mod recording;
+pub mod save;
+pub mod scoring;
+pub mod screen;
+#[cfg(feature = "scripting")]
+pub mod scripting;
+pub mod selfcheck;
+pub mod setup;
+pub mod share;
+pub mod shift;
+pub mod slots;
+#[cfg(feature = "speech")]
+pub mod speech;
+pub mod startup;
+pub mod state;
+pub mod stats;
+pub mod strategy;
+#[cfg(feature = "telemetry")]
+pub mod telemetry;
+pub mod temper;
+pub mod testing;
 pub mod theme;
 pub mod ui;
+pub mod updates;
+pub mod voucher;
+pub mod watch;
 //pub mod ssh;
\ No newline at end of file
@@ -1,39 +1,200 @@
-use lumon_mdr::{app::App, input, theme};
+use lumon_mdr::{
+    app::{App, AppState}, autosave::Autosave, card, cli::{self, Command}, clipboard::Clipboard, clock::SystemClock, config, content, diagnostics::CountingAllocator, doctor, environment, input,
+    inject::Injector, ledger, metrics::Metrics, observe::SessionLink, profile, random::RngService, recording::Recorder,
+    save, theme, voucher,
+};
 use ratatui::backend::CrosstermBackend;
 use crossterm::{
-    execute, 
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, SetSize}, 
-    event::DisableMouseCapture
+    execute,
+    terminal::{EnterAlternateScreen, LeaveAlternateScreen, SetSize},
+    event::{
+        EnableBracketedPaste, DisableBracketedPaste, EnableFocusChange, DisableFocusChange,
+        EnableMouseCapture, DisableMouseCapture,
+        KeyboardEnhancementFlags, PushKeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
+    }
 };
-use std::io;
+use std::io::{self, Write};
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
 
 fn main() -> anyhow::Result<()> {
+    let options = cli::parse(std::env::args().skip(1))?;
+    let env = environment::detect();
+
+    let locations = profile::Locations { config: config::default_path(), sessions: save::default_dir() };
+    match (options.command, &options.bundle) {
+        (Command::Doctor, _) => {
+            println!("{}", doctor::report(&env));
+            return Ok(());
+        },
+        (Command::ConfigCheck, _) => {
+            let Some(path) = options.config_file.clone().or_else(config::default_path) else {
+                anyhow::bail!("no config directory; pass the file to check");
+            };
+            let source = match std::fs::read_to_string(&path) {
+                Ok(source) => source,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    println!("{}: no config file, so the defaults are in use", path.display());
+                    return Ok(());
+                },
+                Err(err) => return Err(err.into()),
+            };
+            let problems = config::check(&source);
+            if problems.is_empty() {
+                println!("{}: OK", path.display());
+                return Ok(());
+            }
+            for problem in &problems {
+                println!("{}: {}", path.display(), problem);
+            }
+            anyhow::bail!("{} problem(s) in {}", problems.len(), path.display());
+        },
+        (Command::ExportProfile, Some(out)) => {
+            let bundle = profile::export(&locations, out)?;
+            println!("Exported {} file(s) to {}", bundle.entries.len(), out.display());
+            return Ok(());
+        },
+        (Command::ImportProfile, Some(file)) => {
+            let report = profile::import(&locations, file, options.replace)?;
+            for (label, names) in [
+                ("Imported", &report.written),
+                ("Replaced (old copy kept as .bak)", &report.replaced),
+                ("Already up to date", &report.unchanged),
+                ("Kept this machine's copy", &report.kept),
+            ] {
+                for name in names {
+                    println!("{:<34} {}", label, name);
+                }
+            }
+            if !report.kept.is_empty() {
+                println!("Run again with --replace to take the imported copies instead.");
+            }
+            return Ok(());
+        },
+        _ => {},
+    }
+
+    // Bind the metrics listener before touching the terminal, so a busy
+    // port is reported on a normal screen
+    let metrics = match &options.metrics {
+        Some(addr) => {
+            let metrics = Arc::new(Metrics::new());
+            metrics.serve(addr)?;
+            Some(metrics)
+        },
+        None => None,
+    };
+
+    // Likewise start the recording, so a bad path never gets as far as the TUI
+    let recorder = options.record_frames.as_deref().map(Recorder::create).transpose()?;
+
     // Set desired window size (columns, rows)
     let desired_width = 120;
     let desired_height = 40;
-    
-    // Try to set terminal size (this works in many but not all terminals)
-    let _ = execute!(io::stdout(), SetSize(desired_width, desired_height));
-    
+
+    // Try to set terminal size (this works in many but not all terminals).
+    // Inside tmux/screen the pane size belongs to the multiplexer, so leave it alone.
+    if env.can_resize() {
+        let _ = execute!(io::stdout(), SetSize(desired_width, desired_height));
+    }
+
+    // Title the outer terminal window, tunnelling through any multiplexer
+    let _ = write!(io::stdout(), "{}", env.window_title_sequence("Lumon Industries - Macrodata Refinement"));
+
     // terminal bootstrap
     crossterm::terminal::enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture, EnableBracketedPaste, EnableFocusChange)?;
+    // Ask for key releases where the terminal can report them, so held keys
+    // let go the moment they are released
+    let key_releases = crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false);
+    if key_releases {
+        let _ = execute!(stdout, PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES));
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = ratatui::Terminal::new(backend)?;
 
     // run the TUI
-    let mut app = App::new(theme::detect());
-    let result = input::event_loop(&mut terminal, &mut app);
-    
+    let palette = if options.mono { theme::Palette::Mono } else { theme::detect() };
+    let random = options.seed.map_or_else(RngService::from_entropy, RngService::new);
+    let mut app = App::with_random(palette, Rc::new(SystemClock), random);
+    app.station.show_debug_hud = options.debug_hud;
+    app.station.eco_mode = options.eco;
+    app.station.redacted = options.redacted;
+    app.pending_code = options.code;
+    app.pending_race = options.race;
+    app.station.ascii_only = !env.unicode;
+    app.station.config_path = config::default_path();
+    app.station.clipboard = Clipboard::to_terminal(Box::new(io::stdout()), &env);
+    app.station.packs_dir = content::default_dir();
+    #[cfg(feature = "scripting")]
+    {
+        app.station.rules_path = lumon_mdr::scripting::default_path();
+    }
+    if let Some(path) = options.publish {
+        app.session_link = Some(SessionLink::publish(path));
+    }
+    app.station.metrics = metrics;
+    app.station.recorder = recorder;
+    if let Some(path) = options.inject {
+        app.station.injector = Some(Injector::watch(path));
+    }
+    if let Some(path) = options.observe {
+        app.read_only = true;
+        app.observe_state(AppState::Main);
+        app.session_link = Some(SessionLink::observe(path));
+    } else {
+        app.station.save_dir = save::default_dir();
+        app.station.autosave = Some(Autosave::start());
+        app.recover_sessions();
+        app.station.ledger_path = ledger::default_path();
+        app.station.vouchers_dir = voucher::default_dir();
+        app.station.cards_dir = card::default_dir();
+    }
+    // Read the config, packs and ledger off the UI thread
+    app.start_loading();
+    app.offer_setup();
+    let result = input::event_loop(&mut terminal, &mut app, &mut input::CrosstermEvents);
+    let stored = app.store_session().and(app.store_ledger());
+
     // restore tty
+    if key_releases {
+        let _ = execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags);
+    }
     crossterm::terminal::disable_raw_mode()?;
     execute!(
-        terminal.backend_mut(), 
+        terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableBracketedPaste,
+        DisableFocusChange
     )?;
-    
+
+    // The screen is back to normal, so a failed save can be read
+    if let Err(err) = stored {
+        eprintln!("Your session could not be saved: {}", err);
+    }
+    if !app.goals.items.is_empty() {
+        println!("{}", app.goals);
+    }
+    if !app.journal.is_empty() {
+        println!("{}", app.journal.summary(app.utc_offset()));
+    }
+    for voucher in &app.vouchers {
+        println!("{}\n", voucher.card());
+    }
+    if let Some(dir) = &app.station.vouchers_dir
+        && !app.vouchers.is_empty()
+    {
+        println!("Your vouchers are kept in {}", dir.display());
+    }
+    if let (Some(recorder), Some(path)) = (&app.station.recorder, &options.record_frames) {
+        println!("Recorded {} frames to {}", recorder.frames, path.display());
+    }
+
     // Return any error that might have occurred
-    result
-}
\ No newline at end of file
+    Ok(result?)
+}
@@ -0,0 +1,127 @@
+//! Prometheus metrics served over plain HTTP at `/metrics`.
+//!
+//! Counters are updated from the event loop and read by a listener thread,
+//! so they live in atomics behind an `Arc`. The exporter speaks just enough
+//! HTTP/1.1 for a Prometheus scraper or `curl`.
+
+use crate::events::AppEvent;
+use crate::error::{MdrError, Result};
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Running totals for the current session
+pub struct Metrics {
+    numbers_refined: AtomicU64,
+    bins_completed: AtomicU64,
+    files_completed: AtomicU64,
+    started: Instant,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            numbers_refined: AtomicU64::new(0),
+            bins_completed: AtomicU64::new(0),
+            files_completed: AtomicU64::new(0),
+            started: Instant::now(),
+        }
+    }
+
+    /// Count an event
+    pub fn record(&self, event: &AppEvent) {
+        match event {
+            AppEvent::NumbersRefined { count } => {
+                self.numbers_refined.fetch_add(*count as u64, Ordering::Relaxed);
+            },
+            AppEvent::BinFilled { .. } => {
+                self.bins_completed.fetch_add(1, Ordering::Relaxed);
+            },
+            AppEvent::FileCompleted { .. } => {
+                self.files_completed.fetch_add(1, Ordering::Relaxed);
+            },
+        }
+    }
+
+    /// The metrics in Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let mut metric = |name: &str, kind: &str, help: &str, value: String| {
+            out.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n{} {}\n", name, help, name, kind, name, value));
+        };
+
+        metric(
+            "numbers_refined_total", "counter", "Numbers captured from the grid.",
+            self.numbers_refined.load(Ordering::Relaxed).to_string(),
+        );
+        metric(
+            "bins_completed_total", "counter", "Bins filled to 100%.",
+            self.bins_completed.load(Ordering::Relaxed).to_string(),
+        );
+        metric(
+            "files_completed_total", "counter", "Files completed with every bin full.",
+            self.files_completed.load(Ordering::Relaxed).to_string(),
+        );
+        metric(
+            "session_seconds", "gauge", "Seconds since the session started.",
+            format!("{:.3}", self.started.elapsed().as_secs_f64()),
+        );
+        out
+    }
+
+    /// Serve `/metrics` on `addr` from a background thread. Returns the
+    /// address actually bound, which matters when asking for port 0.
+    pub fn serve(self: &Arc<Self>, addr: &str) -> Result<std::net::SocketAddr> {
+        let listener = TcpListener::bind(addr)
+            .map_err(|err| MdrError::Net(format!("cannot listen on {}: {}", addr, err)))?;
+        let bound = listener.local_addr()?;
+
+        let metrics = Arc::clone(self);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // A misbehaving client only loses its own response
+                let _ = respond(&metrics, stream);
+            }
+        });
+        Ok(bound)
+    }
+}
+
+fn respond(metrics: &Metrics, mut stream: TcpStream) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Drain the headers so the client sees a clean close
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", metrics.render()),
+        (Some("GET"), _) => ("404 Not Found", "Try /metrics\n".to_string()),
+        _ => ("405 Method Not Allowed", "Only GET is supported\n".to_string()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, body.len(), body
+    )
+}
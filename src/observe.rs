@@ -0,0 +1,65 @@
+//! Sharing a live session with read-only observers.
+//!
+//! The refining session publishes its state to a save file after every
+//! change; an observer polls the same file and mirrors it on screen. The
+//! file is replaced atomically, so an observer never reads half a write.
+
+use crate::app::App;
+use crate::error::{MdrError, Result};
+use crate::save::{self, SaveState};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+pub enum LinkMode {
+    /// Write this session's state whenever it changes
+    Publish,
+    /// Mirror another session's state
+    Observe,
+}
+
+pub struct SessionLink {
+    pub mode: LinkMode,
+    pub path: PathBuf,
+    /// Stop syncing after a failure until the refiner asks to retry
+    pub paused: bool,
+    // Last contents written or read, to skip work when nothing changed
+    last: String,
+}
+
+impl SessionLink {
+    pub fn publish(path: PathBuf) -> Self {
+        Self { mode: LinkMode::Publish, path, paused: false, last: String::new() }
+    }
+
+    pub fn observe(path: PathBuf) -> Self {
+        Self { mode: LinkMode::Observe, path, paused: false, last: String::new() }
+    }
+
+    /// Publish `app`'s state, or pull the observed session's state into it
+    pub fn sync(&mut self, app: &mut App) -> Result<()> {
+        match self.mode {
+            LinkMode::Publish => {
+                let text = SaveState::capture(app).to_string();
+                if text != self.last {
+                    save::write_atomic(&self.path, &text)?;
+                    self.last = text;
+                }
+            },
+            LinkMode::Observe => {
+                let text = match fs::read_to_string(&self.path) {
+                    Ok(text) => text,
+                    // The session being observed has not published yet
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+                    Err(err) => return Err(MdrError::Save(format!("{}: {}", self.path.display(), err))),
+                };
+                if text != self.last {
+                    let screen = text.parse::<SaveState>()?.apply(app);
+                    app.observe_state(screen);
+                    self.last = text;
+                }
+            },
+        }
+        Ok(())
+    }
+}
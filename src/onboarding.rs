@@ -0,0 +1,96 @@
+//! Coach marks for a new refiner. During their first file, a pause in
+//! their work brings up a hint for the next thing they have yet to do:
+//! hover near the numbers, refine some, fill the bins. Once a lesson has
+//! been done it is never coached again; the ledger keeps the lessons
+//! learned so the marks stay gone across launches.
+
+use crate::error::{MdrError, Result};
+use std::collections::BTreeSet;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Quiet time before a coach mark appears
+pub const COACH_AFTER: Duration = Duration::from_secs(6);
+
+/// Something a new refiner is coached to do, in the order they are coached
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Lesson {
+    /// Bring the mouse over the grid
+    Hover,
+    /// Capture numbers as they grow
+    Refine,
+    /// Complete a file
+    FillBins,
+}
+
+impl Lesson {
+    pub const ALL: [Lesson; 3] = [Lesson::Hover, Lesson::Refine, Lesson::FillBins];
+
+    /// Name in the ledger
+    pub fn key(self) -> &'static str {
+        match self {
+            Lesson::Hover => "hover",
+            Lesson::Refine => "refine",
+            Lesson::FillBins => "fill",
+        }
+    }
+
+    /// What the coach mark says
+    pub fn hint(self) -> &'static str {
+        match self {
+            Lesson::Hover => "Move your mouse near the numbers",
+            Lesson::Refine => "Click when they grow",
+            Lesson::FillBins => "Fill all five bins",
+        }
+    }
+
+    /// The first lesson not in `learned`
+    pub fn next(learned: &BTreeSet<Lesson>) -> Option<Lesson> {
+        Lesson::ALL.into_iter().find(|lesson| !learned.contains(lesson))
+    }
+}
+
+impl FromStr for Lesson {
+    type Err = MdrError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        Lesson::ALL
+            .into_iter()
+            .find(|lesson| lesson.key() == text)
+            .ok_or_else(|| MdrError::Save(format!("ledger: '{}' is not a lesson", text)))
+    }
+}
+
+/// Where onboarding has got to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Onboarding {
+    /// Waiting for the refiner to pause
+    #[default]
+    Watching,
+    /// A coach mark is up until the refiner does something
+    Showing(Lesson),
+    /// Nothing left to coach this session
+    Finished,
+}
+
+impl Onboarding {
+    /// Move on, given how long the refiner has been quiet, the next lesson
+    /// they have yet to learn and whether they are still on their first
+    /// file. Returns the coach mark to show, if any.
+    pub fn step(&mut self, quiet: Duration, next: Option<Lesson>, first_file: bool) -> Option<Lesson> {
+        *self = match (*self, next) {
+            (Onboarding::Finished, _) | (_, None) => Onboarding::Finished,
+            _ if !first_file => Onboarding::Finished,
+            (_, Some(lesson)) if quiet >= COACH_AFTER => Onboarding::Showing(lesson),
+            _ => Onboarding::Watching,
+        };
+        self.showing()
+    }
+
+    pub fn showing(self) -> Option<Lesson> {
+        match self {
+            Onboarding::Showing(lesson) => Some(lesson),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,112 @@
+//! The Orientation file: a guided file for new refiners, started with
+//! `:orientation`. It walks through a fixed sequence of steps, each with
+//! an instruction over the grid, and moves on as soon as the refiner has
+//! done what the step asks. Steps set the file up as they begin: one plants
+//! a scary cluster, and the bins stay locked until the refiner is told to
+//! fill them.
+
+use crate::app::{App, ScaryCluster, TOAST_DURATION};
+
+/// The file name shown while orienting
+pub const ORIENTATION_FILE: &str = "Orientation";
+/// Seeds the grid, so every refiner is oriented on the same numbers
+pub const ORIENTATION_SEED: u64 = 0x4f52_4945_4e54; // "ORIENT"
+/// Where the cluster is planted: near the top left, on the grid at every
+/// supported window size
+pub const ORIENTATION_CLUSTER: [(usize, usize); 4] = [(3, 2), (4, 2), (3, 3), (4, 3)];
+/// How much dread counts as having felt the cluster
+const FELT_DREAD: f32 = 0.5;
+
+/// One step of orientation, in order
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    /// Bring the cursor over the grid
+    Look,
+    /// Linger near the planted cluster
+    Sense,
+    /// Refine the cluster
+    Refine,
+    /// Fill the one open bin
+    FirstBin,
+    /// Fill the rest
+    AllBins,
+}
+
+impl Step {
+    pub fn instruction(self) -> &'static str {
+        match self {
+            Step::Look => "Welcome to Orientation. Move your cursor over the numbers.",
+            Step::Sense => "Some numbers feel wrong. Find them and let the feeling build.",
+            Step::Refine => "Click the numbers that feel wrong to refine them.",
+            Step::FirstBin => "Fill bin 01. The other bins stay locked for now.",
+            Step::AllBins => "Every bin is open. Fill them all to complete the file.",
+        }
+    }
+
+    /// The step after this one; `None` after the last
+    pub fn next(self) -> Option<Step> {
+        match self {
+            Step::Look => Some(Step::Sense),
+            Step::Sense => Some(Step::Refine),
+            Step::Refine => Some(Step::FirstBin),
+            Step::FirstBin => Some(Step::AllBins),
+            Step::AllBins => None,
+        }
+    }
+
+    /// Whether `bin` refuses deposits during this step
+    pub fn is_locked(self, bin: usize) -> bool {
+        self != Step::AllBins && bin != 0
+    }
+
+    // Whether the refiner has done what the step asks
+    fn is_done(self, app: &App) -> bool {
+        match self {
+            Step::Look => app
+                .mouse_position
+                .zip(app.grid_geometry)
+                .is_some_and(|((x, y), geometry)| geometry.cell_at(x, y).is_some()),
+            Step::Sense => app.dread() >= FELT_DREAD,
+            Step::Refine => !app
+                .scary_clusters
+                .iter()
+                .any(|cluster| cluster.cells.iter().any(|cell| ORIENTATION_CLUSTER.contains(cell))),
+            Step::FirstBin => app.containers.first().is_some_and(|bin| bin.is_full()),
+            Step::AllBins => app.is_all_complete(),
+        }
+    }
+
+    // Set the file up for the step as it begins
+    fn begin(self, app: &mut App) {
+        if self == Step::Sense {
+            app.scary_clusters.push(ScaryCluster::new(ORIENTATION_CLUSTER.to_vec()));
+        }
+    }
+}
+
+/// Put the refiner on a fresh Orientation file at its first step
+pub fn start(app: &mut App) {
+    app.new_file();
+    app.grid_seed = ORIENTATION_SEED;
+    app.file_name = ORIENTATION_FILE.to_string();
+    // Not timed: nobody sets a personal best while being shown around
+    app.file_started = None;
+    // Nothing sticky to trip over on the way
+    app.sticky.clear();
+    app.orientation = Some(Step::Look);
+    Step::Look.begin(app);
+}
+
+/// Move through every step the refiner has finished since the last call
+pub fn advance(app: &mut App) {
+    while let Some(step) = app.orientation {
+        if !step.is_done(app) {
+            return;
+        }
+        app.orientation = step.next();
+        match app.orientation {
+            Some(next) => next.begin(app),
+            None => app.show_toast("Orientation complete. Welcome to Macrodata Refinement.", Some(TOAST_DURATION)),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Where lumon-mdr keeps its files on each platform.
+//!
+//! | | Linux and other Unix | macOS | Windows |
+//! |---|---|---|---|
+//! | config | `$XDG_CONFIG_HOME/lumon-mdr` | `~/Library/Application Support/lumon-mdr` | `%APPDATA%\lumon-mdr` |
+//! | data | `$XDG_DATA_HOME/lumon-mdr` | `~/Library/Application Support/lumon-mdr` | `%APPDATA%\lumon-mdr` |
+//! | cache | `$XDG_CACHE_HOME/lumon-mdr` | `~/Library/Caches/lumon-mdr` | `%LOCALAPPDATA%\lumon-mdr\cache` |
+//!
+//! The XDG variables fall back to `~/.config`, `~/.local/share` and
+//! `~/.cache`. Setting `LUMON_MDR_HOME` puts everything in its `config`,
+//! `data` and `cache` folders instead, for a portable install. On macOS
+//! and Windows, files already in the XDG folders older builds used are
+//! kept there until the new folder exists.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// The folder named after the app inside each base directory
+pub const APP_DIR: &str = "lumon-mdr";
+/// Overrides every directory, for portable installs
+pub const HOME_VAR: &str = "LUMON_MDR_HOME";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Platform {
+    /// Linux and the other Unixes, following the XDG base directories
+    Xdg,
+    MacOs,
+    Windows,
+}
+
+impl Platform {
+    pub fn current() -> Self {
+        if cfg!(target_os = "macos") {
+            Platform::MacOs
+        } else if cfg!(windows) {
+            Platform::Windows
+        } else {
+            Platform::Xdg
+        }
+    }
+}
+
+/// The three places files go
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dirs {
+    /// `config.toml`, content packs and rules
+    pub config: PathBuf,
+    /// Sessions and the ledger
+    pub data: PathBuf,
+    /// Anything that can be fetched again, like the release notes
+    pub cache: PathBuf,
+}
+
+/// The directories for `platform`, reading environment variables through
+/// `var` (which should treat empty values as unset) and checking for
+/// older folders with `exists`. `None` if there is no home to put them in.
+pub fn resolve(platform: Platform, var: impl Fn(&str) -> Option<OsString>, exists: impl Fn(&Path) -> bool) -> Option<Dirs> {
+    if let Some(home) = var(HOME_VAR).map(PathBuf::from) {
+        return Some(Dirs { config: home.join("config"), data: home.join("data"), cache: home.join("cache") });
+    }
+
+    let home = var("HOME").or_else(|| var("USERPROFILE")).map(PathBuf::from);
+    let xdg = |name: &str, fallback: &[&str]| -> Option<PathBuf> {
+        let base = var(name).map(PathBuf::from).or_else(|| Some(fallback.iter().fold(home.clone()?, |p, part| p.join(part))))?;
+        Some(base.join(APP_DIR))
+    };
+    let xdg_dirs = || -> Option<Dirs> {
+        Some(Dirs {
+            config: xdg("XDG_CONFIG_HOME", &[".config"])?,
+            data: xdg("XDG_DATA_HOME", &[".local", "share"])?,
+            cache: xdg("XDG_CACHE_HOME", &[".cache"])?,
+        })
+    };
+
+    let native = match platform {
+        Platform::Xdg => return xdg_dirs(),
+        Platform::MacOs => {
+            let library = home.as_ref()?.join("Library");
+            let support = library.join("Application Support").join(APP_DIR);
+            Dirs { config: support.clone(), data: support, cache: library.join("Caches").join(APP_DIR) }
+        },
+        Platform::Windows => {
+            let roaming = PathBuf::from(var("APPDATA")?).join(APP_DIR);
+            let local = var("LOCALAPPDATA").map_or_else(|| roaming.clone(), |dir| PathBuf::from(dir).join(APP_DIR));
+            Dirs { config: roaming.clone(), data: roaming, cache: local.join("cache") }
+        },
+    };
+
+    // Keep using the folders an older build wrote to, until moved
+    let legacy = xdg_dirs();
+    let keep = |new: PathBuf, old: Option<PathBuf>| match old {
+        Some(old) if !exists(&new) && exists(&old) => old,
+        _ => new,
+    };
+    Some(Dirs {
+        config: keep(native.config, legacy.as_ref().map(|dirs| dirs.config.clone())),
+        data: keep(native.data, legacy.as_ref().map(|dirs| dirs.data.clone())),
+        cache: keep(native.cache, legacy.map(|dirs| dirs.cache)),
+    })
+}
+
+/// The directories for this machine
+pub fn dirs() -> Option<Dirs> {
+    resolve(Platform::current(), |name| std::env::var_os(name).filter(|value| !value.is_empty()), Path::exists)
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    dirs().map(|dirs| dirs.config)
+}
+
+pub fn data_dir() -> Option<PathBuf> {
+    dirs().map(|dirs| dirs.data)
+}
+
+pub fn cache_dir() -> Option<PathBuf> {
+    dirs().map(|dirs| dirs.cache)
+}
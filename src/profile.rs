@@ -0,0 +1,214 @@
+//! Portable refiner profiles: the config file and saved sessions bundled
+//! into one file, for `export-profile` and `import-profile`.
+//!
+//! ```text
+//! lumon-mdr profile 1
+//! entry config.toml 52
+//! <52 bytes>
+//! entry sessions/mark.save 118
+//! <118 bytes>
+//! ```
+//!
+//! Entries are stored byte-for-byte, so sealed sessions stay sealed and
+//! need the same passphrase on the new machine.
+
+use crate::error::{MdrError, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Format version written on the first line
+pub const PROFILE_VERSION: u32 = 1;
+
+const HEADER: &str = "lumon-mdr profile";
+const CONFIG_ENTRY: &str = "config.toml";
+const SESSIONS_PREFIX: &str = "sessions/";
+
+/// Where a profile lives on this machine
+pub struct Locations {
+    pub config: Option<PathBuf>,
+    pub sessions: Option<PathBuf>,
+}
+
+impl Locations {
+    // Map a bundle entry name to a path; `None` for names that do not
+    // belong in a profile, which keeps a hostile bundle inside its folders
+    fn resolve(&self, name: &str) -> Option<PathBuf> {
+        if name == CONFIG_ENTRY {
+            return self.config.clone();
+        }
+        let file = name.strip_prefix(SESSIONS_PREFIX)?;
+        let safe = file.ends_with(".save")
+            && file.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            && !file.starts_with('.');
+        safe.then(|| self.sessions.as_ref().map(|dir| dir.join(file))).flatten()
+    }
+}
+
+/// The files making up a profile, by entry name
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Bundle {
+    pub entries: Vec<(String, Vec<u8>)>,
+}
+
+/// What an import did with each entry
+#[derive(Debug, Default, PartialEq)]
+pub struct ImportReport {
+    pub written: Vec<String>,
+    /// Already present with the same contents
+    pub unchanged: Vec<String>,
+    /// Present with different contents and left alone
+    pub kept: Vec<String>,
+    /// Present with different contents, replaced after a `.bak` copy
+    pub replaced: Vec<String>,
+}
+
+impl Bundle {
+    /// Gather the config file and every saved session that exist
+    pub fn collect(locations: &Locations) -> Result<Self> {
+        let mut bundle = Bundle::default();
+
+        if let Some(path) = &locations.config
+            && let Some(bytes) = read_optional(path)?
+        {
+            bundle.entries.push((CONFIG_ENTRY.to_string(), bytes));
+        }
+
+        if let Some(dir) = &locations.sessions {
+            let mut names = match fs::read_dir(dir) {
+                Ok(entries) => entries
+                    .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+                    .filter(|name| name.ends_with(".save"))
+                    .collect::<Vec<_>>(),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+                Err(err) => return Err(MdrError::Io(err)),
+            };
+            names.sort();
+            for name in names {
+                let bytes = fs::read(dir.join(&name))?;
+                bundle.entries.push((format!("{}{}", SESSIONS_PREFIX, name), bytes));
+            }
+        }
+
+        Ok(bundle)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = format!("{} {}\n", HEADER, PROFILE_VERSION).into_bytes();
+        for (name, bytes) in &self.entries {
+            out.extend_from_slice(format!("entry {} {}\n", name, bytes.len()).as_bytes());
+            out.extend_from_slice(bytes);
+            out.push(b'\n');
+        }
+        out
+    }
+
+    pub fn from_bytes(mut data: &[u8]) -> Result<Self> {
+        let header = take_line(&mut data).ok_or_else(|| invalid("not a lumon-mdr profile"))?;
+        let version = header
+            .strip_prefix(HEADER)
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .ok_or_else(|| invalid("not a lumon-mdr profile"))?;
+        if version > PROFILE_VERSION {
+            return Err(invalid(&format!("profile version {} is newer than this build", version)));
+        }
+
+        let mut bundle = Bundle::default();
+        while let Some(line) = take_line(&mut data) {
+            if line.is_empty() {
+                continue;
+            }
+            let mut parts = line.split(' ');
+            let (Some("entry"), Some(name), Some(len), None) = (parts.next(), parts.next(), parts.next(), parts.next())
+            else {
+                return Err(invalid(&format!("expected an entry, found '{}'", line)));
+            };
+            let len: usize = len.parse().map_err(|_| invalid(&format!("bad length for {}", name)))?;
+            if data.len() < len + 1 || data[len] != b'\n' {
+                return Err(invalid(&format!("{} is cut short", name)));
+            }
+            if bundle.entries.iter().any(|(existing, _)| existing == name) {
+                return Err(invalid(&format!("{} appears twice", name)));
+            }
+            bundle.entries.push((name.to_string(), data[..len].to_vec()));
+            data = &data[len + 1..];
+        }
+        Ok(bundle)
+    }
+
+    /// Write the bundle's files into place. Files that already exist with
+    /// different contents are kept unless `replace` is set, in which case
+    /// the old file is first copied to `<name>.bak`.
+    pub fn install(&self, locations: &Locations, replace: bool) -> Result<ImportReport> {
+        let mut report = ImportReport::default();
+
+        // Check every name before touching anything
+        let paths = self
+            .entries
+            .iter()
+            .map(|(name, _)| {
+                locations
+                    .resolve(name)
+                    .ok_or_else(|| invalid(&format!("{} has no place in a profile", name)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        for ((name, bytes), path) in self.entries.iter().zip(paths) {
+            match read_optional(&path)? {
+                Some(existing) if existing == *bytes => report.unchanged.push(name.clone()),
+                Some(_) if !replace => report.kept.push(name.clone()),
+                existing => {
+                    if let Some(dir) = path.parent() {
+                        fs::create_dir_all(dir)?;
+                    }
+                    if let Some(existing) = existing {
+                        let mut backup = path.clone().into_os_string();
+                        backup.push(".bak");
+                        fs::write(backup, existing)?;
+                        report.replaced.push(name.clone());
+                    } else {
+                        report.written.push(name.clone());
+                    }
+                    fs::write(&path, bytes)?;
+                },
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Bundle this machine's profile into `out`
+pub fn export(locations: &Locations, out: &Path) -> Result<Bundle> {
+    let bundle = Bundle::collect(locations)?;
+    fs::write(out, bundle.to_bytes())?;
+    Ok(bundle)
+}
+
+/// Unpack the profile in `file` onto this machine
+pub fn import(locations: &Locations, file: &Path, replace: bool) -> Result<ImportReport> {
+    let data = fs::read(file)?;
+    Bundle::from_bytes(&data)?.install(locations, replace)
+}
+
+fn read_optional(path: &Path) -> Result<Option<Vec<u8>>> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(MdrError::Io(err)),
+    }
+}
+
+// Split off one `\n`-terminated line of text
+fn take_line(data: &mut &[u8]) -> Option<String> {
+    if data.is_empty() {
+        return None;
+    }
+    let end = data.iter().position(|&b| b == b'\n').unwrap_or(data.len());
+    let line = String::from_utf8_lossy(&data[..end]).into_owned();
+    *data = &data[(end + 1).min(data.len())..];
+    Some(line)
+}
+
+fn invalid(message: &str) -> MdrError {
+    MdrError::Save(format!("profile: {}", message))
+}
@@ -0,0 +1,138 @@
+//! Race mode: refining a board against a target time, either a friend's
+//! time on the board their share code describes or the refiner's own
+//! best. The header shows how far ahead or behind the refiner is as the
+//! bins fill, and the finish is written to the ledger.
+
+use crate::error::{MdrError, Result};
+use crate::share::ShareCode;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// What to race against, as typed after `:race` or `--race`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Target {
+    /// A time to beat, e.g. `12:34`
+    Time(Duration),
+    /// The fastest file in the ledger
+    PersonalBest,
+}
+
+impl FromStr for Target {
+    type Err = MdrError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        match text.trim() {
+            "pb" => Ok(Target::PersonalBest),
+            time => parse_time(time)
+                .filter(|time| !time.is_zero())
+                .map(Target::Time)
+                .ok_or_else(|| MdrError::Config(format!("'{}' is not a race time like 12:34 or pb", text.trim()))),
+        }
+    }
+}
+
+/// A race under way on the current file
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Race {
+    /// The board being raced on
+    pub code: ShareCode,
+    pub target: Duration,
+}
+
+impl Race {
+    /// How the refiner stands `elapsed` into the file with it `completion`
+    /// percent done, against a refiner who finishes on the target at an
+    /// even pace
+    pub fn pace(&self, elapsed: Duration, completion: f32) -> Pace {
+        let expected = self.target.mul_f32(completion.clamp(0.0, 100.0) / 100.0);
+        if elapsed < expected { Pace::Ahead(expected - elapsed) } else { Pace::Behind(elapsed - expected) }
+    }
+}
+
+/// How far from the target a refiner is
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pace {
+    Ahead(Duration),
+    Behind(Duration),
+}
+
+impl Pace {
+    pub fn is_ahead(self) -> bool {
+        matches!(self, Pace::Ahead(_))
+    }
+
+    pub fn margin(self) -> Duration {
+        match self {
+            Pace::Ahead(margin) | Pace::Behind(margin) => margin,
+        }
+    }
+}
+
+/// `-0:12.3` when ahead, `+0:12.3` when behind
+impl fmt::Display for Pace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.is_ahead() { '-' } else { '+' };
+        write!(f, "{}{}", sign, format_time(self.margin()))
+    }
+}
+
+/// A finished race, as kept in the ledger
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RaceResult {
+    /// Days since 1970-01-01
+    pub day: i64,
+    pub code: ShareCode,
+    pub target: Duration,
+    /// How long the file took
+    pub time: Duration,
+}
+
+impl RaceResult {
+    /// A tie goes to the refiner
+    pub fn won(&self) -> bool {
+        self.time <= self.target
+    }
+
+    /// The toast shown at the finish
+    pub fn verdict(&self) -> String {
+        let margin = format_time(self.time.abs_diff(self.target));
+        if self.won() { format!("Race won by {}", margin) } else { format!("Race lost by {}", margin) }
+    }
+}
+
+/// `m:ss.t`, or `h:mm:ss.t` from an hour up
+pub fn format_time(time: Duration) -> String {
+    let tenths = time.as_millis() / 100;
+    let (secs, tenth) = (tenths / 10, tenths % 10);
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}:{:02}:{:02}.{}", hours, minutes, seconds, tenth)
+    } else {
+        format!("{}:{:02}.{}", minutes, seconds, tenth)
+    }
+}
+
+/// Read `12:34`, `1:02:03`, `95` or any of them with a fraction of a
+/// second, e.g. `4:32.5`
+pub fn parse_time(text: &str) -> Option<Duration> {
+    let parts: Vec<&str> = text.split(':').collect();
+    if parts.len() > 3 {
+        return None;
+    }
+    let (whole, last) = parts.split_at(parts.len() - 1);
+    let seconds: f64 = last[0].parse().ok().filter(|s: &f64| s.is_finite() && *s >= 0.0)?;
+    // Below the largest unit, seconds and minutes stop at 59
+    if !whole.is_empty() && seconds >= 60.0 {
+        return None;
+    }
+    let mut total = 0u64;
+    for (idx, part) in whole.iter().enumerate() {
+        let value: u64 = part.parse().ok()?;
+        if idx > 0 && value >= 60 {
+            return None;
+        }
+        total = total.checked_mul(60)?.checked_add(value)?;
+    }
+    Duration::try_from_secs_f64(total as f64 * 60.0 + seconds).ok()
+}
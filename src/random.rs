@@ -0,0 +1,94 @@
+//! Every random draw the app makes, split into named streams.
+//!
+//! One master seed is picked at launch (or given with `--seed`), and each
+//! `Stream` gets its own generator seeded from it and the stream's name.
+//! A stream only ever advances for its own draws, so a run replayed from
+//! the same seed shuffles the same grids and hands out the same prizes
+//! even after some unrelated effect starts drawing numbers of its own. A
+//! new kind of randomness gets a new stream rather than borrowing one.
+//!
+//! The crypto salts and nonces are the one exception: they must never be
+//! predictable, so `crypto.rs` keeps drawing them from the OS.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng, rng};
+use std::time::Duration;
+
+/// What a draw is for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Stream {
+    /// Grid seeds, replacement digits, sticky numbers and planted clusters
+    Grid,
+    /// Prizes and voucher numbers
+    Prizes,
+    /// The loading bar, and the file name and quote it introduces
+    Loading,
+    /// Tempers and the bin a capture lands in
+    Bins,
+    /// When compliance checks come and the phrases they ask for
+    Audits,
+    /// Anything that only changes how the board looks
+    Effects,
+}
+
+impl Stream {
+    pub const ALL: [Stream; 6] = [Stream::Grid, Stream::Prizes, Stream::Loading, Stream::Bins, Stream::Audits, Stream::Effects];
+
+    /// The name the stream's seed is derived from. Changing it changes
+    /// every draw the stream makes, so it must stay as it is.
+    pub fn name(self) -> &'static str {
+        match self {
+            Stream::Grid => "grid",
+            Stream::Prizes => "prizes",
+            Stream::Loading => "loading",
+            Stream::Bins => "bins",
+            Stream::Audits => "audits",
+            Stream::Effects => "effects",
+        }
+    }
+}
+
+/// One generator per stream, all derived from a master seed
+#[derive(Clone, Debug)]
+pub struct RngService {
+    seed: u64,
+    streams: [StdRng; Stream::ALL.len()],
+}
+
+impl RngService {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, streams: Stream::ALL.map(|stream| StdRng::seed_from_u64(derive(seed, stream.name()))) }
+    }
+
+    /// A service on a master seed picked by the OS
+    pub fn from_entropy() -> Self {
+        Self::new(rng().random())
+    }
+
+    /// The master seed, for replaying this run with `--seed`
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The generator for `stream`
+    pub fn stream(&mut self, stream: Stream) -> &mut StdRng {
+        &mut self.streams[stream as usize]
+    }
+}
+
+/// A wait exponentially distributed around `mean`, as between arrivals
+/// that come independently of each other, kept within `bounds`
+pub fn exponential(rng: &mut impl Rng, mean: Duration, bounds: (Duration, Duration)) -> Duration {
+    let draw: f64 = rng.random();
+    mean.mul_f64(-(1.0 - draw).ln()).clamp(bounds.0, bounds.1)
+}
+
+// The master seed mixed with an FNV-1a hash of the stream's name, then
+// through the splitmix64 finalizer so neighbouring seeds land far apart
+fn derive(seed: u64, name: &str) -> u64 {
+    let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3));
+    let mut z = seed ^ hash;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
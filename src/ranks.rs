@@ -0,0 +1,41 @@
+//! Refiner ranks, earned with lifetime experience (XP).
+//!
+//! Every captured number is worth 1 XP, a filled bin 10 and a completed
+//! file 100. XP is kept with the refiner's saved session.
+
+use crate::events::AppEvent;
+
+/// A title and the lifetime XP it takes to earn it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rank {
+    pub title: &'static str,
+    pub xp: u64,
+}
+
+/// Every rank, lowest first
+pub const RANKS: &[Rank] = &[
+    Rank { title: "Refiner I", xp: 0 },
+    Rank { title: "Refiner II", xp: 500 },
+    Rank { title: "Refiner III", xp: 1_500 },
+    Rank { title: "Senior Refiner", xp: 4_000 },
+    Rank { title: "Department Head", xp: 10_000 },
+];
+
+/// XP earned for one event
+pub fn xp_for(event: &AppEvent) -> u64 {
+    match event {
+        AppEvent::NumbersRefined { count } => *count as u64,
+        AppEvent::BinFilled { .. } => 10,
+        AppEvent::FileCompleted { .. } => 100,
+    }
+}
+
+/// The highest rank `xp` qualifies for
+pub fn rank_for(xp: u64) -> Rank {
+    RANKS.iter().rev().find(|rank| xp >= rank.xp).copied().unwrap_or(RANKS[0])
+}
+
+/// The rank after the one `xp` holds, if there is one
+pub fn next_rank(xp: u64) -> Option<Rank> {
+    RANKS.iter().find(|rank| rank.xp > xp).copied()
+}
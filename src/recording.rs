@@ -0,0 +1,186 @@
+//! Recording the session frame by frame (`--record-frames`), so it can be
+//! turned into a GIF or an asciicast without a screen recorder.
+//!
+//! A path ending in `.cast` gets one asciinema v2 file holding every
+//! frame with its timing; `asciinema play` replays it and `agg` turns it
+//! into a GIF. Any other path is a directory that gets one ANSI text file
+//! per frame, `frame-000001.ans` onwards, which `cat` draws back in a
+//! terminal. Frames the same as the one before are skipped.
+
+use crate::error::{MdrError, Result};
+use ratatui::buffer::Buffer;
+use ratatui::style::{Color, Modifier};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+enum Sink {
+    /// One `.ans` file per frame in this directory
+    Frames(PathBuf),
+    /// An asciicast, with the size of the last frame written to it
+    Cast { out: BufWriter<File>, size: Option<(u16, u16)> },
+}
+
+pub struct Recorder {
+    sink: Sink,
+    /// When the first frame was recorded
+    started: Option<Instant>,
+    /// Frames written so far
+    pub frames: u64,
+    // The last frame written, to skip repeats
+    last: String,
+}
+
+impl Recorder {
+    /// Record to `path`: an asciicast if it ends in `.cast`, otherwise a
+    /// directory of frames, created if it does not exist
+    pub fn create(path: &Path) -> Result<Self> {
+        let failed = |err: io::Error| MdrError::Config(format!("--record-frames {}: {}", path.display(), err));
+        let sink = if path.extension().is_some_and(|ext| ext == "cast") {
+            Sink::Cast { out: BufWriter::new(File::create(path).map_err(failed)?), size: None }
+        } else {
+            fs::create_dir_all(path).map_err(failed)?;
+            Sink::Frames(path.to_path_buf())
+        };
+        Ok(Self { sink, started: None, frames: 0, last: String::new() })
+    }
+
+    /// Write the frame drawn into `buffer` at `now`, unless nothing on
+    /// screen changed since the last one
+    pub fn record(&mut self, buffer: &Buffer, now: Instant) -> io::Result<()> {
+        let rows = ansi_rows(buffer);
+        let text = rows.join("\r\n");
+        if text == self.last {
+            return Ok(());
+        }
+        let started = *self.started.get_or_insert(now);
+        let size = (buffer.area.width, buffer.area.height);
+
+        match &mut self.sink {
+            Sink::Frames(dir) => {
+                let path = dir.join(format!("frame-{:06}.ans", self.frames + 1));
+                // Cleared first, so `cat` draws it over whatever was there
+                fs::write(path, format!("\x1b[H\x1b[2J{}\n", rows.join("\n")))?;
+            },
+            Sink::Cast { out, size: last_size } => {
+                let at = now.duration_since(started);
+                match *last_size {
+                    None => writeln!(out, "{}", cast_header(size, SystemTime::now()))?,
+                    Some(last) if last != size => {
+                        writeln!(out, "{}", cast_event(at, "r", &format!("{}x{}", size.0, size.1)))?
+                    },
+                    Some(_) => {},
+                }
+                *last_size = Some(size);
+                writeln!(out, "{}", cast_event(at, "o", &format!("\x1b[H\x1b[2J{}", text)))?;
+                out.flush()?;
+            },
+        }
+        self.frames += 1;
+        self.last = text;
+        Ok(())
+    }
+}
+
+/// The asciicast v2 header for a `size` terminal recorded from `wall`
+pub fn cast_header(size: (u16, u16), wall: SystemTime) -> String {
+    let timestamp = wall.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    format!(
+        "{{\"version\": 2, \"width\": {}, \"height\": {}, \"timestamp\": {}, \"title\": \"Lumon Industries - Macrodata Refinement\"}}",
+        size.0, size.1, timestamp
+    )
+}
+
+/// One asciicast event line: `data` of kind `code` (`o` for output, `r`
+/// for a resize) `at` into the recording
+pub fn cast_event(at: Duration, code: &str, data: &str) -> String {
+    format!("[{:.6}, \"{}\", {}]", at.as_secs_f64(), code, json_string(data))
+}
+
+/// Each row of `buffer` as text with ANSI colours, ending with the style
+/// reset so rows can be joined with any line break
+pub fn ansi_rows(buffer: &Buffer) -> Vec<String> {
+    let area = buffer.area;
+    let mut rows = Vec::with_capacity(area.height as usize);
+    for y in area.y..area.y + area.height {
+        let mut row = String::new();
+        let mut style = None;
+        for x in area.x..area.x + area.width {
+            let cell = buffer.get(x, y);
+            let cell_style = (cell.fg, cell.bg, cell.modifier);
+            if style != Some(cell_style) {
+                row.push_str(&sgr(cell.fg, cell.bg, cell.modifier));
+                style = Some(cell_style);
+            }
+            row.push_str(&cell.symbol);
+        }
+        row.push_str("\x1b[0m");
+        rows.push(row);
+    }
+    rows
+}
+
+// The escape that sets a cell's style, starting from a reset
+fn sgr(fg: Color, bg: Color, modifier: Modifier) -> String {
+    const MODIFIERS: [(Modifier, u8); 8] = [
+        (Modifier::BOLD, 1),
+        (Modifier::DIM, 2),
+        (Modifier::ITALIC, 3),
+        (Modifier::UNDERLINED, 4),
+        (Modifier::SLOW_BLINK, 5),
+        (Modifier::REVERSED, 7),
+        (Modifier::HIDDEN, 8),
+        (Modifier::CROSSED_OUT, 9),
+    ];
+    let mut codes = vec!["0".to_string()];
+    codes.extend(MODIFIERS.iter().filter(|(m, _)| modifier.contains(*m)).map(|(_, code)| code.to_string()));
+    codes.extend(colour(fg, 30));
+    codes.extend(colour(bg, 40));
+    format!("\x1b[{}m", codes.join(";"))
+}
+
+// SGR parameters for `color`, `base` being 30 for foreground and 40 for
+// background; nothing for the terminal's default
+fn colour(color: Color, base: u8) -> Option<String> {
+    let basic = |idx: u8| Some((base + idx).to_string());
+    let bright = |idx: u8| Some((base + 60 + idx).to_string());
+    match color {
+        Color::Reset => None,
+        Color::Black => basic(0),
+        Color::Red => basic(1),
+        Color::Green => basic(2),
+        Color::Yellow => basic(3),
+        Color::Blue => basic(4),
+        Color::Magenta => basic(5),
+        Color::Cyan => basic(6),
+        Color::Gray => basic(7),
+        Color::DarkGray => bright(0),
+        Color::LightRed => bright(1),
+        Color::LightGreen => bright(2),
+        Color::LightYellow => bright(3),
+        Color::LightBlue => bright(4),
+        Color::LightMagenta => bright(5),
+        Color::LightCyan => bright(6),
+        Color::White => bright(7),
+        Color::Indexed(idx) => Some(format!("{};5;{}", base + 8, idx)),
+        Color::Rgb(r, g, b) => Some(format!("{};2;{};{};{}", base + 8, r, g, b)),
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
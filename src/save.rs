@@ -0,0 +1,547 @@
+//! Session state as a small line-based text file.
+//!
+//! ```text
+//! lumon-mdr save 1
+//! state=main
+//! username=mark
+//! file=Cold Harbor
+//! bins=10,20,30,40,50
+//! seed=42
+//! xp=1200
+//! code=HUSH-49B4996-10
+//! note=1741000000 Expense report for Q3
+//! grid=58x19
+//! cell=3,4 7
+//! cluster=10,2 11,2 10,3
+//! cursor=12,5
+//! break=1741003900
+//! reshuffles=1
+//! ```
+//!
+//! Besides the screen, the refiner, the bins, the seed and experience,
+//! a save records:
+//!
+//! - `file`, the name of the file being refined, shown when choosing
+//!   between save slots
+//! - `code`, the share code the file was opened from, if it was
+//! - a `note=` line for each journal note: when it was written, in
+//!   seconds since 1970, then its text
+//! - `grid`, the size in cells the file was last drawn at, followed by a
+//!   `cell=` line for each refined number and its digit, a `cluster=`
+//!   line for each scary cluster and the cell under the `cursor`; they
+//!   are scaled to fit when the file comes back at another size
+//! - `break`, when a break in progress ends, in seconds since 1970
+//! - `reshuffles`, how often the file's grid was reshuffled
+//!
+//! Unknown keys are ignored so older builds can read newer files.
+//!
+//! A refiner may choose to seal their session with a passphrase, so their
+//! innie's work is unreadable outside the terminal:
+//!
+//! ```text
+//! lumon-mdr sealed 1
+//! salt=<hex>
+//! iterations=600000
+//! nonce=<hex>
+//! data=<hex ChaCha20-Poly1305 ciphertext of the plain save>
+//! ```
+
+use crate::app::{App, AppState, DEFAULT_GRID_SEED};
+use crate::crypto::{self, NONCE_LEN, SALT_LEN, SealKey};
+use crate::error::{MdrError, Result};
+use crate::grid::GridSnapshot;
+use crate::journal::Note;
+use crate::share::ShareCode;
+use crate::stats::Balance;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Format version written on the first line
+pub const SAVE_VERSION: u32 = 1;
+
+const HEADER: &str = "lumon-mdr save";
+const SEALED_HEADER: &str = "lumon-mdr sealed";
+const SEALED_VERSION: u32 = 1;
+
+/// Everything needed to put a session back on screen
+#[derive(Clone, Debug, PartialEq)]
+pub struct SaveState {
+    pub state: AppState,
+    pub username: String,
+    /// Name of the file being refined
+    pub file_name: String,
+    pub bins: Vec<u16>,
+    /// Bin numbers (from 1) left to right
+    pub order: Vec<usize>,
+    /// The file's grid; saves from before files had seeds get the old grid
+    pub seed: u64,
+    pub loading_progress: f32,
+    pub prize_name: String,
+    pub balance: Balance,
+    /// Lifetime experience
+    pub xp: u64,
+    /// The share code the file was opened from, if it was
+    pub code: Option<ShareCode>,
+    /// The session journal, oldest first
+    pub notes: Vec<Note>,
+    /// What is on the grid, if it was ever drawn
+    pub grid: Option<GridSnapshot>,
+    /// When the break in progress ends, in seconds since 1970
+    pub break_until: Option<u64>,
+    /// Times the file's grid was reshuffled
+    pub reshuffles: u32,
+}
+
+impl SaveState {
+    pub fn capture(app: &App) -> Self {
+        // The error screen is transient; record what it interrupted
+        let state = match app.state() {
+            AppState::Error => app.error_return_state,
+            state => state,
+        };
+
+        Self {
+            state,
+            username: app.username.clone(),
+            file_name: app.file_name.clone(),
+            bins: app.containers.iter().map(|c| c.count).collect(),
+            order: app.bin_order().iter().map(|bin| bin + 1).collect(),
+            seed: app.grid_seed,
+            loading_progress: app.progress_percentage,
+            prize_name: app.prize_name.clone(),
+            balance: app.station.stats.balance(&app.username),
+            xp: app.xp,
+            code: app.shared,
+            notes: app.journal.notes.iter().cloned().collect(),
+            grid: app.grid_snapshot(),
+            break_until: app.break_until.and_then(|until| until.duration_since(UNIX_EPOCH).ok()).map(|since| since.as_secs()),
+            reshuffles: app.reshuffles,
+        }
+    }
+
+    /// Copy the saved state into `app`, returning the screen it was saved
+    /// on for the caller to decide whether to go to
+    pub fn apply(&self, app: &mut App) -> AppState {
+        app.username = self.username.clone();
+        app.username_cursor = app.username.chars().count();
+        // Older saves did not name the file; it keeps the one it was given
+        if !self.file_name.is_empty() {
+            app.file_name = self.file_name.clone();
+        }
+        for (container, &count) in app.containers.iter_mut().zip(&self.bins) {
+            container.count = 0;
+            container.add(count);
+        }
+        // Only a full arrangement of this app's bins is applied
+        let mut numbers = self.order.clone();
+        numbers.sort_unstable();
+        if numbers.iter().copied().eq(1..=app.containers.len()) {
+            for (slot, &number) in self.order.iter().enumerate() {
+                app.containers[number - 1].slot = slot;
+            }
+        } else {
+            for (slot, container) in app.containers.iter_mut().enumerate() {
+                container.slot = slot;
+            }
+        }
+        app.grid_seed = self.seed;
+        app.shared = self.code;
+        app.progress_percentage = self.loading_progress.clamp(0.0, 100.0);
+        app.prize_name = self.prize_name.clone();
+        app.station.stats.set_balance(&self.username, self.balance);
+        app.xp = self.xp;
+        app.journal.notes = self.notes.iter().cloned().collect();
+        // Laid out once the grid is drawn and its size known
+        app.pending_grid = self.grid.clone();
+        app.break_until = self.break_until.map(|secs| UNIX_EPOCH + Duration::from_secs(secs));
+        app.reshuffles = self.reshuffles;
+        self.state
+    }
+}
+
+impl fmt::Display for SaveState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bins: Vec<String> = self.bins.iter().map(|b| b.to_string()).collect();
+        let order: Vec<String> = self.order.iter().map(|n| n.to_string()).collect();
+
+        writeln!(f, "{} {}", HEADER, SAVE_VERSION)?;
+        writeln!(f, "state={}", state_name(self.state))?;
+        writeln!(f, "username={}", self.username)?;
+        writeln!(f, "file={}", self.file_name)?;
+        writeln!(f, "bins={}", bins.join(","))?;
+        writeln!(f, "order={}", order.join(","))?;
+        writeln!(f, "seed={}", self.seed)?;
+        writeln!(f, "loading={}", self.loading_progress)?;
+        writeln!(f, "prize={}", self.prize_name)?;
+        writeln!(f, "innie_ms={}", self.balance.innie.as_millis())?;
+        writeln!(f, "outie_ms={}", self.balance.outie.as_millis())?;
+        writeln!(f, "xp={}", self.xp)?;
+        if let Some(code) = &self.code {
+            writeln!(f, "code={}", code)?;
+        }
+        for note in &self.notes {
+            writeln!(f, "note={} {}", note.at, note.text)?;
+        }
+        if let Some(grid) = &self.grid {
+            writeln!(f, "grid={}x{}", grid.size.0, grid.size.1)?;
+            for ((col, row), digit) in &grid.replaced {
+                writeln!(f, "cell={},{} {}", col, row, digit)?;
+            }
+            for cells in &grid.clusters {
+                let cells: Vec<String> = cells.iter().map(|(col, row)| format!("{},{}", col, row)).collect();
+                writeln!(f, "cluster={}", cells.join(" "))?;
+            }
+            if let Some((col, row)) = grid.cursor {
+                writeln!(f, "cursor={},{}", col, row)?;
+            }
+        }
+        if let Some(until) = self.break_until {
+            writeln!(f, "break={}", until)?;
+        }
+        if self.reshuffles > 0 {
+            writeln!(f, "reshuffles={}", self.reshuffles)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SaveState {
+    type Err = MdrError;
+
+    fn from_str(source: &str) -> Result<Self> {
+        let mut lines = source.lines();
+
+        let version = lines
+            .next()
+            .and_then(|line| line.strip_prefix(HEADER))
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .ok_or_else(|| MdrError::Save("not a lumon-mdr save file".to_string()))?;
+        if version > SAVE_VERSION {
+            return Err(MdrError::Save(format!("save file version {} is newer than this build", version)));
+        }
+
+        let mut save = SaveState {
+            state: AppState::Main,
+            username: String::new(),
+            file_name: String::new(),
+            bins: Vec::new(),
+            order: Vec::new(),
+            seed: DEFAULT_GRID_SEED,
+            loading_progress: 0.0,
+            prize_name: String::new(),
+            balance: Balance::default(),
+            xp: 0,
+            code: None,
+            notes: Vec::new(),
+            grid: None,
+            break_until: None,
+            reshuffles: 0,
+        };
+        let mut grid = GridSnapshot::default();
+
+        for (idx, line) in lines.enumerate() {
+            let line_no = idx + 2;
+            let Some((key, value)) = line.split_once('=') else {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                return Err(invalid(line_no, "expected key=value"));
+            };
+
+            match key {
+                "state" => save.state = parse_state(value).ok_or_else(|| invalid(line_no, "unknown state"))?,
+                "username" => save.username = value.to_string(),
+                "file" => save.file_name = value.to_string(),
+                "bins" => {
+                    save.bins = value
+                        .split(',')
+                        .filter(|b| !b.is_empty())
+                        .map(|b| b.parse::<u16>().ok().filter(|&n| n <= 100))
+                        .collect::<Option<_>>()
+                        .ok_or_else(|| invalid(line_no, "bins must be numbers from 0 to 100"))?;
+                },
+                "order" => {
+                    save.order = value
+                        .split(',')
+                        .filter(|n| !n.is_empty())
+                        .map(|n| n.parse::<usize>().ok().filter(|&n| n >= 1))
+                        .collect::<Option<_>>()
+                        .ok_or_else(|| invalid(line_no, "order must list bin numbers from 1"))?;
+                },
+                "seed" => save.seed = value.parse().map_err(|_| invalid(line_no, "bad seed"))?,
+                "loading" => save.loading_progress = value.parse().map_err(|_| invalid(line_no, "bad loading progress"))?,
+                "prize" => save.prize_name = value.to_string(),
+                "innie_ms" => save.balance.innie = parse_millis(value).ok_or_else(|| invalid(line_no, "bad duration"))?,
+                "outie_ms" => save.balance.outie = parse_millis(value).ok_or_else(|| invalid(line_no, "bad duration"))?,
+                "xp" => save.xp = value.parse().map_err(|_| invalid(line_no, "bad xp"))?,
+                "code" => save.code = Some(value.parse().map_err(|_| invalid(line_no, "bad share code"))?),
+                "note" => {
+                    let (at, text) = value.split_once(' ').unwrap_or((value, ""));
+                    let at = at.parse().map_err(|_| invalid(line_no, "a note starts with when it was written"))?;
+                    save.notes.push(Note { at, text: text.to_string() });
+                },
+                "grid" => {
+                    grid.size = value
+                        .split_once('x')
+                        .and_then(|(cols, rows)| Some((cols.parse().ok()?, rows.parse().ok()?)))
+                        .filter(|&(cols, rows)| cols > 0 && rows > 0)
+                        .ok_or_else(|| invalid(line_no, "grid must look like 58x19"))?;
+                },
+                "cell" => {
+                    let (cell, digit) = value.split_once(' ').unwrap_or((value, ""));
+                    let cell = parse_cell(cell).ok_or_else(|| invalid(line_no, "a cell starts with its column and row"))?;
+                    let digit = digit.parse().ok().filter(|&d| d <= 9).ok_or_else(|| invalid(line_no, "bad digit"))?;
+                    grid.replaced.push((cell, digit));
+                },
+                "cluster" => {
+                    let cells = value.split_whitespace().map(parse_cell).collect::<Option<Vec<_>>>();
+                    grid.clusters.push(cells.ok_or_else(|| invalid(line_no, "a cluster lists cells like 3,4"))?);
+                },
+                "break" => save.break_until = Some(value.parse().map_err(|_| invalid(line_no, "bad break end"))?),
+                "reshuffles" => save.reshuffles = value.parse().map_err(|_| invalid(line_no, "bad reshuffle count"))?,
+                "cursor" => grid.cursor = Some(parse_cell(value).ok_or_else(|| invalid(line_no, "bad cursor cell"))?),
+                _ => {},
+            }
+        }
+
+        // Cells only mean something on the grid they were saved on
+        let (cols, rows) = grid.size;
+        let on_grid = |&(col, row): &(usize, usize)| col < cols && row < rows;
+        grid.replaced.retain(|(cell, _)| on_grid(cell));
+        for cells in &mut grid.clusters {
+            cells.retain(on_grid);
+        }
+        grid.clusters.retain(|cells| !cells.is_empty());
+        grid.cursor = grid.cursor.filter(on_grid);
+        if cols > 0 {
+            save.grid = Some(grid);
+        }
+
+        Ok(save)
+    }
+}
+
+/// A save file encrypted with a passphrase
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sealed {
+    pub salt: [u8; SALT_LEN],
+    pub iterations: u32,
+    nonce: [u8; NONCE_LEN],
+    data: Vec<u8>,
+}
+
+impl Sealed {
+    /// Encrypt `save` under `key` with a fresh nonce
+    pub fn seal(save: &SaveState, key: &SealKey) -> Self {
+        let nonce = crypto::random_nonce();
+        let data = crypto::seal(&key.key, &nonce, sealed_header().as_bytes(), save.to_string().as_bytes());
+        Self { salt: key.salt, iterations: key.iterations, nonce, data }
+    }
+
+    /// Derive the key this file was sealed with from a passphrase. Slow on
+    /// purpose.
+    pub fn key_for(&self, passphrase: &str) -> SealKey {
+        SealKey::derive(passphrase, self.salt, self.iterations)
+    }
+
+    /// Decrypt with `key`; a wrong passphrase and a damaged file look the same
+    pub fn open(&self, key: &SealKey) -> Result<SaveState> {
+        let plain = crypto::open(&key.key, &self.nonce, sealed_header().as_bytes(), &self.data)
+            .ok_or_else(|| MdrError::Save("wrong passphrase or damaged file".to_string()))?;
+        String::from_utf8(plain)
+            .map_err(|_| MdrError::Save("sealed save is not text".to_string()))?
+            .parse()
+    }
+}
+
+impl fmt::Display for Sealed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", sealed_header())?;
+        writeln!(f, "salt={}", to_hex(&self.salt))?;
+        writeln!(f, "iterations={}", self.iterations)?;
+        writeln!(f, "nonce={}", to_hex(&self.nonce))?;
+        writeln!(f, "data={}", to_hex(&self.data))
+    }
+}
+
+impl FromStr for Sealed {
+    type Err = MdrError;
+
+    fn from_str(source: &str) -> Result<Self> {
+        let mut lines = source.lines();
+
+        let version = lines
+            .next()
+            .and_then(|line| line.strip_prefix(SEALED_HEADER))
+            .and_then(|v| v.trim().parse::<u32>().ok())
+            .ok_or_else(|| MdrError::Save("not a sealed lumon-mdr save".to_string()))?;
+        if version > SEALED_VERSION {
+            return Err(MdrError::Save(format!("sealed save version {} is newer than this build", version)));
+        }
+
+        let (mut salt, mut iterations, mut nonce, mut data) = (None, None, None, None);
+        for (idx, line) in lines.enumerate() {
+            let line_no = idx + 2;
+            let Some((key, value)) = line.split_once('=') else {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                return Err(invalid(line_no, "expected key=value"));
+            };
+
+            let hex = || from_hex(value).ok_or_else(|| invalid(line_no, "expected hexadecimal bytes"));
+            match key {
+                "salt" => salt = Some(hex()?.try_into().map_err(|_| invalid(line_no, "salt has the wrong length"))?),
+                "iterations" => {
+                    iterations = Some(value.parse().ok().filter(|&n| n > 0).ok_or_else(|| invalid(line_no, "bad iteration count"))?)
+                },
+                "nonce" => nonce = Some(hex()?.try_into().map_err(|_| invalid(line_no, "nonce has the wrong length"))?),
+                "data" => data = Some(hex()?),
+                _ => {},
+            }
+        }
+
+        let missing = |key: &str| MdrError::Save(format!("sealed save is missing '{}'", key));
+        Ok(Sealed {
+            salt: salt.ok_or_else(|| missing("salt"))?,
+            iterations: iterations.ok_or_else(|| missing("iterations"))?,
+            nonce: nonce.ok_or_else(|| missing("nonce"))?,
+            data: data.ok_or_else(|| missing("data"))?,
+        })
+    }
+}
+
+/// What a session file on disk turned out to hold
+pub enum Stored {
+    Plain(Box<SaveState>),
+    Sealed(Sealed),
+}
+
+/// Read a session file that may or may not be sealed. `Ok(None)` means
+/// there is no file yet.
+pub fn read_stored(path: &Path) -> Result<Option<Stored>> {
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(MdrError::Save(format!("{}: {}", path.display(), err))),
+    };
+
+    let stored = if source.starts_with(SEALED_HEADER) {
+        Stored::Sealed(source.parse()?)
+    } else {
+        Stored::Plain(Box::new(source.parse()?))
+    };
+    Ok(Some(stored))
+}
+
+/// Where sessions are kept: `sessions` in the data directory, e.g.
+/// `~/.local/share/lumon-mdr/sessions`
+pub fn default_dir() -> Option<PathBuf> {
+    Some(crate::paths::data_dir()?.join("sessions"))
+}
+
+/// The session file for an employee. Names are folded to something every
+/// filesystem accepts, so "Mark S." and "mark_s_" share a file.
+pub fn session_path(dir: &Path, username: &str) -> PathBuf {
+    slot_path(dir, username, 1)
+}
+
+/// The file for one of an employee's save slots, numbered from 1. The
+/// first is the plain session file, so saves from before slots carry on
+/// in it; the others are `<name>.<slot>.save`.
+pub fn slot_path(dir: &Path, username: &str, slot: usize) -> PathBuf {
+    let name: String = username
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    match slot {
+        1 => dir.join(format!("{}.save", name)),
+        slot => dir.join(format!("{}.{}.save", name, slot)),
+    }
+}
+
+/// Read and parse a save file
+pub fn load(path: &Path) -> Result<SaveState> {
+    let source = fs::read_to_string(path).map_err(|err| MdrError::Save(format!("{}: {}", path.display(), err)))?;
+    source.parse()
+}
+
+/// Replace `path` with `contents` in one step, so a reader never sees a
+/// half-written file
+pub fn write_atomic(path: &Path, contents: &str) -> Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(".tmp");
+    fs::write(&tmp, contents)
+        .and_then(|_| fs::rename(&tmp, path))
+        .map_err(|err| MdrError::Save(format!("{}: {}", path.display(), err)))
+}
+
+fn sealed_header() -> String {
+    format!("{} {}", SEALED_HEADER, SEALED_VERSION)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(text: &str) -> Option<Vec<u8>> {
+    if !text.is_ascii() || !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).ok())
+        .collect()
+}
+
+fn invalid(line: usize, message: &str) -> MdrError {
+    MdrError::Save(format!("line {}: {}", line, message))
+}
+
+fn parse_cell(value: &str) -> Option<(usize, usize)> {
+    let (col, row) = value.split_once(',')?;
+    Some((col.parse().ok()?, row.parse().ok()?))
+}
+
+fn parse_millis(value: &str) -> Option<Duration> {
+    value.parse().ok().map(Duration::from_millis)
+}
+
+fn state_name(state: AppState) -> &'static str {
+    match state {
+        AppState::Login => "login",
+        AppState::Loading => "loading",
+        AppState::Main => "main",
+        AppState::Prize => "prize",
+        AppState::Error => "error",
+        AppState::Report => "report",
+        AppState::Locked => "locked",
+        AppState::Changelog => "changelog",
+        AppState::Recap => "recap",
+        AppState::Setup => "setup",
+        AppState::Break => "break",
+    }
+}
+
+fn parse_state(name: &str) -> Option<AppState> {
+    Some(match name {
+        "login" => AppState::Login,
+        "loading" => AppState::Loading,
+        "main" => AppState::Main,
+        "prize" => AppState::Prize,
+        "report" => AppState::Report,
+        "locked" => AppState::Locked,
+        "changelog" => AppState::Changelog,
+        "recap" => AppState::Recap,
+        "break" => AppState::Break,
+        // The questions were answered into the config, if at all
+        "setup" => AppState::Login,
+        // A saved error screen has nothing to show; resume work instead
+        "error" => AppState::Main,
+        _ => return None,
+    })
+}
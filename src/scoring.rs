@@ -0,0 +1,213 @@
+//! What a capture is worth: the numbers' sum, run through a stack of
+//! rules. The stack is composed for each file from the difficulty set in
+//! `config.toml`, and content packs can tweak the values in it:
+//!
+//! ```toml
+//! [scoring]
+//! combo_min = 3          # equal digits that make a combo
+//! combo_bonus = 5        # per matching digit past combo_min - 1
+//! streak_from = 5        # clean captures before the multiplier starts
+//! streak_step = 10       # percent added per capture after that
+//! streak_max = 200       # percent the multiplier tops out at
+//! misclick_penalty = 2   # taken off the next capture per empty click
+//! ```
+
+use crate::combo::Combo;
+use crate::error::{MdrError, Result};
+use std::fmt;
+use std::str::FromStr;
+
+/// One capture, and what led up to it
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Capture {
+    pub digits: Vec<u16>,
+    /// Captures in a row before this one without a misclick
+    pub streak: u32,
+    /// Clicks on the grid that caught nothing since the last capture
+    pub misclicks: u32,
+}
+
+/// One step of working out a capture's value. Rules run in order, each
+/// taking the value so far.
+pub trait ScoringRule {
+    fn name(&self) -> &'static str;
+    fn apply(&self, capture: &Capture, value: i32) -> i32;
+}
+
+/// The captured digits, summed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BaseValue;
+
+impl ScoringRule for BaseValue {
+    fn name(&self) -> &'static str {
+        "base value"
+    }
+
+    fn apply(&self, capture: &Capture, _value: i32) -> i32 {
+        capture.digits.iter().map(|&digit| digit as i32).sum()
+    }
+}
+
+/// A growing multiplier for clean captures in a row
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StreakMultiplier {
+    /// Streak the multiplier starts at
+    pub from: u32,
+    /// Percent added for each capture past `from`
+    pub step: u32,
+    /// Percent it tops out at
+    pub max: u32,
+}
+
+impl ScoringRule for StreakMultiplier {
+    fn name(&self) -> &'static str {
+        "streak multiplier"
+    }
+
+    fn apply(&self, capture: &Capture, value: i32) -> i32 {
+        if capture.streak < self.from {
+            return value;
+        }
+        let percent = (100 + (capture.streak - self.from + 1) * self.step).min(self.max.max(100));
+        value * percent as i32 / 100
+    }
+}
+
+/// Extra for a group of equal digits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ComboBonus {
+    /// Equal digits needed
+    pub min: usize,
+    /// Added for each one past `min - 1`
+    pub per_digit: u16,
+}
+
+impl ScoringRule for ComboBonus {
+    fn name(&self) -> &'static str {
+        "combo bonus"
+    }
+
+    fn apply(&self, capture: &Capture, value: i32) -> i32 {
+        match Combo::find_at_least(&capture.digits, self.min) {
+            Some(combo) => value + (combo.count + 1 - self.min) as i32 * self.per_digit as i32,
+            None => value,
+        }
+    }
+}
+
+/// Taken off for clicks that caught nothing
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MisclickPenalty {
+    pub per_misclick: u16,
+}
+
+impl ScoringRule for MisclickPenalty {
+    fn name(&self) -> &'static str {
+        "misclick penalty"
+    }
+
+    fn apply(&self, capture: &Capture, value: i32) -> i32 {
+        value - capture.misclicks as i32 * self.per_misclick as i32
+    }
+}
+
+/// The numbers the rules are built from; content packs may change any
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScoringValues {
+    pub combo_min: usize,
+    pub combo_bonus: u16,
+    pub streak_from: u32,
+    pub streak_step: u32,
+    pub streak_max: u32,
+    pub misclick_penalty: u16,
+}
+
+impl Default for ScoringValues {
+    fn default() -> Self {
+        Self {
+            combo_min: crate::combo::COMBO_MIN,
+            combo_bonus: crate::combo::COMBO_BONUS,
+            streak_from: 5,
+            streak_step: 10,
+            streak_max: 200,
+            misclick_penalty: 2,
+        }
+    }
+}
+
+/// How forgiving the scoring is
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Difficulty {
+    /// No streaks, no penalties
+    Relaxed,
+    #[default]
+    Standard,
+    /// Misclicks cost double and streaks build half as fast
+    Exacting,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Relaxed, Difficulty::Standard, Difficulty::Exacting];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Relaxed => "relaxed",
+            Difficulty::Standard => "standard",
+            Difficulty::Exacting => "exacting",
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = MdrError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.name().eq_ignore_ascii_case(text.trim()))
+            .ok_or_else(|| MdrError::Config(format!("'{}' is not a difficulty (relaxed, standard or exacting)", text)))
+    }
+}
+
+/// A stack of rules that values a capture
+pub struct Scoring {
+    pub rules: Vec<Box<dyn ScoringRule>>,
+}
+
+impl Scoring {
+    /// The rules for one file at `difficulty`
+    pub fn compose(difficulty: Difficulty, values: &ScoringValues) -> Self {
+        let combo = ComboBonus { min: values.combo_min, per_digit: values.combo_bonus };
+        let streak = StreakMultiplier { from: values.streak_from, step: values.streak_step, max: values.streak_max };
+        let penalty = MisclickPenalty { per_misclick: values.misclick_penalty };
+
+        let rules: Vec<Box<dyn ScoringRule>> = match difficulty {
+            Difficulty::Relaxed => vec![Box::new(BaseValue), Box::new(combo)],
+            Difficulty::Standard => vec![Box::new(BaseValue), Box::new(combo), Box::new(streak), Box::new(penalty)],
+            Difficulty::Exacting => {
+                let streak = StreakMultiplier { step: streak.step / 2, ..streak };
+                let penalty = MisclickPenalty { per_misclick: penalty.per_misclick * 2 };
+                vec![Box::new(BaseValue), Box::new(combo), Box::new(streak), Box::new(penalty)]
+            },
+        };
+        Scoring { rules }
+    }
+
+    /// What `capture` puts into a bin; never less than nothing
+    pub fn score(&self, capture: &Capture) -> u16 {
+        let value = self.rules.iter().fold(0, |value, rule| rule.apply(capture, value));
+        value.clamp(0, u16::MAX as i32) as u16
+    }
+}
+
+impl Default for Scoring {
+    fn default() -> Self {
+        Scoring::compose(Difficulty::default(), &ScoringValues::default())
+    }
+}
+
+impl fmt::Debug for Scoring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.rules.iter().map(|rule| rule.name())).finish()
+    }
+}
@@ -0,0 +1,57 @@
+//! What each screen does with the keys, the mouse and the clock, and how
+//! it draws itself.
+//!
+//! Every `AppState` has one `Screen`, kept in the screen's module under
+//! `ui`. `App::on_key`, `App::on_mouse` and `App::tick` do what applies
+//! on any screen, such as the debug overlay's key or the idle logout,
+//! and hand the rest to the screen shown; `ui::layout` and `ui::draw`
+//! do the same for drawing. Drawing only reads the app: anything a
+//! screen needs to remember about where things landed, such as the
+//! grid's geometry, is worked out in `layout`, which runs before every
+//! frame. A new screen needs its variant in `state.rs`, an
+//! implementation of this trait and an arm in `with_screen!`.
+
+use crate::app::App;
+use crossterm::event::{KeyCode, MouseEvent};
+use ratatui::{Frame, backend::Backend, layout::Rect};
+use std::time::Instant;
+
+pub trait Screen {
+    /// A key pressed while the screen is shown
+    fn handle_key(&self, app: &mut App, key: KeyCode);
+
+    /// A mouse event while the screen is shown. Most screens ignore them.
+    fn handle_mouse(&self, _app: &mut App, _event: MouseEvent) {}
+
+    /// Called every tick while the screen is shown
+    fn tick(&self, _app: &mut App, _now: Instant) {}
+
+    /// Called before each frame is drawn into `area`, to record where
+    /// things will be for the input handlers to hit-test against
+    fn layout(&self, _app: &mut App, _area: Rect) {}
+
+    fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, app: &App);
+}
+
+/// Runs `$body` with `$screen` bound to the `Screen` for `$state`. Drawing
+/// is generic over the backend, so screens are picked by this rather than
+/// looked up as trait objects.
+macro_rules! with_screen {
+    ($state:expr, $screen:ident => $body:expr) => {
+        match $state {
+            $crate::state::AppState::Login => { let $screen = $crate::ui::LoginScreen; $body },
+            $crate::state::AppState::Loading => { let $screen = $crate::ui::LoadingScreen; $body },
+            $crate::state::AppState::Main => { let $screen = $crate::ui::MainScreen; $body },
+            $crate::state::AppState::Prize => { let $screen = $crate::ui::PrizeScreen; $body },
+            $crate::state::AppState::Error => { let $screen = $crate::ui::ErrorScreen; $body },
+            $crate::state::AppState::Report => { let $screen = $crate::ui::ReportScreen; $body },
+            $crate::state::AppState::Locked => { let $screen = $crate::ui::LockScreen; $body },
+            $crate::state::AppState::Changelog => { let $screen = $crate::ui::ChangelogScreen; $body },
+            $crate::state::AppState::Recap => { let $screen = $crate::ui::RecapScreen; $body },
+            $crate::state::AppState::Setup => { let $screen = $crate::ui::SetupScreen; $body },
+            $crate::state::AppState::Break => { let $screen = $crate::ui::BreakScreen; $body },
+        }
+    };
+}
+
+pub(crate) use with_screen;
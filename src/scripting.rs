@@ -0,0 +1,153 @@
+//! Rule scripts: a Lua file that can change how the floor behaves, for
+//! builds with the `scripting` feature.
+//!
+//! The script lives next to the config file as `rules.lua`. It may define
+//! any of these global functions; a missing function, or one that returns
+//! `nil`, leaves the built-in rule in place:
+//!
+//! ```lua
+//! function digit(col, row) return 7 end                  -- a refreshed grid digit, 0-9
+//! function deposit(bin, value) return value * 2 end      -- what a deposit adds to a bin (1-5)
+//! function prize(prizes) return prizes[1] end            -- the prize for a completed file
+//! function memo(user, prize) return "Well done, " .. user end  -- a note under the prize
+//! ```
+//!
+//! Scripts run in a sandbox: only the `string`, `table`, `math` and `utf8`
+//! libraries are available, there is no file, OS or module access, memory
+//! is capped, and a hook that runs too long is stopped. Errors cannot be
+//! caught, so a stopped script stays stopped.
+
+use crate::error::{MdrError, Result};
+use mlua::{FromLua, HookTriggers, IntoLuaMulti, Lua, LuaOptions, StdLib, Value};
+use std::cell::Cell;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Memory a script may hold
+const MEMORY_LIMIT: usize = 16 * 1024 * 1024;
+/// How often the instruction budget is checked
+const CHECK_EVERY: u32 = 1000;
+/// Budget checks allowed per hook call (about a million instructions)
+const MAX_CHECKS: u32 = 1000;
+
+/// Base library functions a rule script has no business calling. `pcall`
+/// and `xpcall` would let a script catch the error that stops it for
+/// running too long, and carry on regardless.
+const REMOVED_GLOBALS: &[&str] =
+    &["dofile", "loadfile", "load", "require", "collectgarbage", "print", "pcall", "xpcall"];
+
+/// A loaded rule script
+pub struct Rules {
+    lua: Lua,
+    /// File name used in error messages
+    name: String,
+    checks: Rc<Cell<u32>>,
+}
+
+impl Rules {
+    /// Run `source` once to define its hooks. `name` identifies it in errors.
+    pub fn load(source: &str, name: &str) -> Result<Self> {
+        let libs = StdLib::STRING | StdLib::TABLE | StdLib::MATH | StdLib::UTF8;
+        let lua = Lua::new_with(libs, LuaOptions::new()).map_err(|err| failure(name, &err))?;
+        lua.set_memory_limit(MEMORY_LIMIT).map_err(|err| failure(name, &err))?;
+
+        let globals = lua.globals();
+        for global in REMOVED_GLOBALS {
+            globals.set(*global, Value::Nil).map_err(|err| failure(name, &err))?;
+        }
+        // Compiled chunks could bypass the sandbox if ever loaded back
+        let string: mlua::Table = globals.get("string").map_err(|err| failure(name, &err))?;
+        string.set("dump", Value::Nil).map_err(|err| failure(name, &err))?;
+        drop(string);
+        drop(globals);
+
+        let checks = Rc::new(Cell::new(0));
+        let counter = Rc::clone(&checks);
+        lua.set_hook(HookTriggers::new().every_nth_instruction(CHECK_EVERY), move |_, _| {
+            counter.set(counter.get() + 1);
+            if counter.get() > MAX_CHECKS {
+                return Err(mlua::Error::RuntimeError("script took too long and was stopped".to_string()));
+            }
+            Ok(())
+        });
+
+        lua.load(source).set_name(name).exec().map_err(|err| failure(name, &err))?;
+        Ok(Rules { lua, name: name.to_string(), checks })
+    }
+
+    /// The digit to show at a refreshed grid cell, counted from 1
+    pub fn digit(&self, col: usize, row: usize) -> Result<Option<u16>> {
+        let Some(digit) = self.call::<_, i64>("digit", (col + 1, row + 1))? else { return Ok(None) };
+        match u16::try_from(digit) {
+            Ok(digit) if digit <= 9 => Ok(Some(digit)),
+            _ => Err(self.bad_return("digit", &format!("a digit from 0 to 9, got {}", digit))),
+        }
+    }
+
+    /// How much a deposit of `value` into bin `bin` (counted from 0) adds
+    pub fn deposit(&self, bin: usize, value: u16) -> Result<Option<u16>> {
+        let Some(value) = self.call::<_, i64>("deposit", (bin + 1, value))? else { return Ok(None) };
+        match u16::try_from(value) {
+            Ok(value) if value <= 100 => Ok(Some(value)),
+            _ => Err(self.bad_return("deposit", &format!("a number from 0 to 100, got {}", value))),
+        }
+    }
+
+    /// The prize for a completed file, chosen from `prizes` or made up
+    pub fn prize(&self, prizes: &[String]) -> Result<Option<String>> {
+        let prize = self.call::<_, String>("prize", prizes.to_vec())?;
+        match prize {
+            Some(prize) if prize.trim().is_empty() => Err(self.bad_return("prize", "a prize name, got empty text")),
+            prize => Ok(prize),
+        }
+    }
+
+    /// A note shown under the prize
+    pub fn memo(&self, username: &str, prize: &str) -> Result<Option<String>> {
+        self.call("memo", (username, prize))
+    }
+
+    // Call the global function `hook`, if the script defines one, with a
+    // fresh instruction budget
+    fn call<'lua, A, R>(&'lua self, hook: &str, args: A) -> Result<Option<R>>
+    where
+        A: IntoLuaMulti<'lua>,
+        R: FromLua<'lua>,
+    {
+        let function = match self.lua.globals().get::<_, Value>(hook) {
+            Ok(Value::Nil) => return Ok(None),
+            Ok(Value::Function(function)) => function,
+            Ok(other) => return Err(self.bad_return(hook, &format!("a function, found {}", other.type_name()))),
+            Err(err) => return Err(failure(&self.name, &err)),
+        };
+        self.checks.set(0);
+        function.call::<_, Option<R>>(args).map_err(|err| failure(&self.name, &err))
+    }
+
+    fn bad_return(&self, hook: &str, expected: &str) -> MdrError {
+        MdrError::Config(format!("{}: '{}' should return {}", self.name, hook, expected))
+    }
+}
+
+/// Load the script at `path`. A missing file just means no rules.
+pub fn load(path: &Path) -> Result<Option<Rules>> {
+    let source = match std::fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(MdrError::Io(err)),
+    };
+    let name = path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().into_owned());
+    Rules::load(&source, &name).map(Some)
+}
+
+/// Where the rule script lives: `rules.lua` next to the config file
+pub fn default_path() -> Option<PathBuf> {
+    Some(crate::paths::config_dir()?.join("rules.lua"))
+}
+
+// Lua errors carry a traceback; the first line is what matters on screen
+fn failure(name: &str, err: &mlua::Error) -> MdrError {
+    let text = err.to_string();
+    let first = text.lines().next().unwrap_or_default();
+    MdrError::Config(format!("{}: {}", name, first.trim_start_matches("runtime error: ")))
+}
@@ -0,0 +1,111 @@
+//! Checks that a session's state still hangs together, to catch state
+//! corruption during marathon sessions. `:selfcheck` runs them on demand,
+//! and the app runs them on its own every `SELFCHECK_INTERVAL`.
+//!
+//! Each check is an invariant the rest of the app relies on: bins hold no
+//! more than their deposits added up to, the grid only remembers cells on
+//! it, and every capped collection is within its cap.
+
+use crate::app::{App, STICKY_CELLS};
+use std::fmt;
+use std::time::Duration;
+
+/// How often the app checks itself in the background
+pub const SELFCHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// An invariant that does not hold
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Discrepancy {
+    /// Which part of the state, e.g. `bins`
+    pub area: &'static str,
+    pub detail: String,
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.area, self.detail)
+    }
+}
+
+/// What a self-check found
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Invariants looked at
+    pub checked: usize,
+    pub discrepancies: Vec<Discrepancy>,
+}
+
+impl Report {
+    pub fn is_clean(&self) -> bool {
+        self.discrepancies.is_empty()
+    }
+
+    fn expect(&mut self, area: &'static str, holds: bool, detail: impl FnOnce() -> String) {
+        self.checked += 1;
+        if !holds {
+            self.discrepancies.push(Discrepancy { area, detail: detail() });
+        }
+    }
+}
+
+/// Check every invariant against `app` as it is now
+pub fn run(app: &App) -> Report {
+    let mut report = Report::default();
+    check_bins(app, &mut report);
+    check_grid(app, &mut report);
+    check_caps(app, &mut report);
+    report
+}
+
+fn check_bins(app: &App, report: &mut Report) {
+    let now = app.clock.now();
+    for (idx, bin) in app.containers.iter().enumerate() {
+        let number = idx + 1;
+        report.expect("bins", bin.count <= 100, || format!("bin {} holds {}, past 100", number, bin.count));
+        report.expect("bins", bin.progress == f32::from(bin.count), || {
+            format!("bin {} shows {} but holds {}", number, bin.progress, bin.count)
+        });
+        // Restored bins come back without their history, so the history
+        // can only fall short of the count
+        let history: u32 = bin.tempers.iter().map(|&value| u32::from(value)).sum();
+        report.expect("bins", history <= u32::from(bin.count), || {
+            format!("bin {} holds {} but its deposits add up to {}", number, bin.count, history)
+        });
+        let recent: u32 = bin.deposits.iter().map(|deposit| u32::from(deposit.value)).sum();
+        report.expect("bins", recent <= history, || {
+            format!("bin {}'s recent deposits add up to {}, more than all {} deposited", number, recent, history)
+        });
+        let fill = bin.fill.value(now);
+        report.expect("animation", fill.is_finite() && (0.0..=100.0).contains(&fill), || {
+            format!("bin {}'s bar is drawn at {}", number, fill)
+        });
+    }
+
+    let mut slots: Vec<usize> = app.containers.iter().map(|bin| bin.slot).collect();
+    slots.sort_unstable();
+    report.expect("bins", slots.iter().copied().eq(0..app.containers.len()), || {
+        format!("bins stand in slots {:?}", slots)
+    });
+}
+
+fn check_grid(app: &App, report: &mut Report) {
+    let off_grid = app.grid.cells().filter(|&cell| !app.grid.contains(cell)).count();
+    report.expect("grid", off_grid == 0, || format!("{} refined cells are off the grid", off_grid));
+    let unmarkable = app.marked.iter().filter(|&&cell| !app.grid.contains(cell)).count();
+    report.expect("grid", unmarkable == 0, || format!("{} marked cells are off the grid", unmarkable));
+    for (idx, cluster) in app.scary_clusters.iter().enumerate() {
+        let number = idx + 1;
+        report.expect("grid", !cluster.cells.is_empty(), || format!("scary cluster {} has no cells", number));
+        let outside = cluster.cells.iter().filter(|&&cell| !app.grid.contains(cell)).count();
+        report.expect("grid", outside == 0, || format!("scary cluster {} has {} cells off the grid", number, outside));
+    }
+    report.expect("animation", app.sticky.len() <= STICKY_CELLS, || {
+        format!("{} sticky cells, more than {}", app.sticky.len(), STICKY_CELLS)
+    });
+}
+
+fn check_caps(app: &App, report: &mut Report) {
+    for usage in app.budget_usage() {
+        report.expect("caps", usage.used <= usage.cap, || format!("{} holds {} of {}", usage.name, usage.used, usage.cap));
+    }
+}
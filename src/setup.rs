@@ -0,0 +1,168 @@
+//! The first-run setup: a few questions asked before the first login when
+//! there is no `config.toml` yet, answered into one so the refiner starts
+//! with their own choices instead of silent defaults.
+
+use crate::scoring::Difficulty;
+
+/// The questions, in the order they are asked
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SetupStep {
+    Theme,
+    /// Not a setting: a chance to size the window before the first file
+    Terminal,
+    Mouse,
+    Difficulty,
+    Name,
+}
+
+impl SetupStep {
+    pub const ALL: [SetupStep; 5] =
+        [SetupStep::Theme, SetupStep::Terminal, SetupStep::Mouse, SetupStep::Difficulty, SetupStep::Name];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            SetupStep::Theme => "Choose a colour theme",
+            SetupStep::Terminal => "Check the terminal size",
+            SetupStep::Mouse => "Use the mouse?",
+            SetupStep::Difficulty => "Pick a scoring difficulty",
+            SetupStep::Name => "Your employee identification name",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        SetupStep::ALL.iter().position(|&step| step == self).unwrap_or(0)
+    }
+
+    fn next(self) -> Option<SetupStep> {
+        SetupStep::ALL.get(self.index() + 1).copied()
+    }
+
+    fn previous(self) -> Option<SetupStep> {
+        self.index().checked_sub(1).map(|idx| SetupStep::ALL[idx])
+    }
+}
+
+/// The answers so far
+#[derive(Clone, Debug, PartialEq)]
+pub struct Setup {
+    pub step: SetupStep,
+    /// Highlighted row of the current step's list
+    pub selected: usize,
+    /// A content pack theme, or `None` for the detected palette
+    pub theme: Option<String>,
+    pub mouse: bool,
+    pub difficulty: Difficulty,
+    /// Filled in at login; may be left empty
+    pub name: String,
+}
+
+impl Default for Setup {
+    fn default() -> Self {
+        Self {
+            step: SetupStep::Theme,
+            selected: 0,
+            theme: None,
+            mouse: true,
+            difficulty: Difficulty::default(),
+            name: String::new(),
+        }
+    }
+}
+
+impl Setup {
+    /// The rows to choose from on the current step, given the themes the
+    /// content packs provide; empty for steps that are not a list
+    pub fn choices(&self, themes: &[&str]) -> Vec<String> {
+        match self.step {
+            SetupStep::Theme => {
+                let mut choices = vec!["Lumon standard (matches your terminal)".to_string()];
+                choices.extend(themes.iter().map(|name| name.to_string()));
+                choices
+            },
+            SetupStep::Mouse => vec!["Yes, hover and click".to_string(), "No, keyboard only".to_string()],
+            SetupStep::Difficulty => Difficulty::ALL
+                .iter()
+                .map(|difficulty| match difficulty {
+                    Difficulty::Relaxed => "Relaxed: no streaks, no penalties",
+                    Difficulty::Standard => "Standard",
+                    Difficulty::Exacting => "Exacting: misclicks cost double",
+                })
+                .map(str::to_string)
+                .collect(),
+            SetupStep::Terminal | SetupStep::Name => Vec::new(),
+        }
+    }
+
+    /// Move the highlight on a list step, staying within its `count` rows
+    pub fn select(&mut self, delta: isize, count: usize) {
+        if count > 0 {
+            self.selected = self.selected.saturating_add_signed(delta).min(count - 1);
+        }
+    }
+
+    /// Take the highlighted row as the answer and go on. `false` once the
+    /// last question is answered.
+    pub fn advance(&mut self, themes: &[&str]) -> bool {
+        match self.step {
+            SetupStep::Theme => {
+                self.theme = self.selected.checked_sub(1).and_then(|idx| themes.get(idx)).map(|name| name.to_string());
+            },
+            SetupStep::Mouse => self.mouse = self.selected == 0,
+            SetupStep::Difficulty => self.difficulty = Difficulty::ALL[self.selected.min(2)],
+            SetupStep::Terminal | SetupStep::Name => {},
+        }
+        match self.step.next() {
+            Some(step) => {
+                self.step = step;
+                self.selected = self.current_row(themes);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Go back a question, keeping the answers. `false` on the first one.
+    pub fn back(&mut self, themes: &[&str]) -> bool {
+        match self.step.previous() {
+            Some(step) => {
+                self.step = step;
+                self.selected = self.current_row(themes);
+                true
+            },
+            None => false,
+        }
+    }
+
+    // The row matching the answer already given for the current step
+    fn current_row(&self, themes: &[&str]) -> usize {
+        match self.step {
+            SetupStep::Theme => self
+                .theme
+                .as_ref()
+                .and_then(|theme| themes.iter().position(|name| name == theme))
+                .map_or(0, |idx| idx + 1),
+            SetupStep::Mouse => usize::from(!self.mouse),
+            SetupStep::Difficulty => Difficulty::ALL.iter().position(|&d| d == self.difficulty).unwrap_or(1),
+            SetupStep::Terminal | SetupStep::Name => 0,
+        }
+    }
+
+    /// The answers as a `config.toml`
+    pub fn to_toml(&self) -> String {
+        let mut out = String::from("# Written by the first-run setup. Every setting is described in the README.\n");
+        if let Some(theme) = &self.theme {
+            out.push_str(&format!("\n[theme]\nname = {}\n", quoted(theme)));
+        }
+        out.push_str(&format!("\n[input]\nmouse = {}\n", self.mouse));
+        out.push_str(&format!("\n[scoring]\ndifficulty = {}\n", quoted(self.difficulty.name())));
+        let name = self.name.trim();
+        if !name.is_empty() {
+            out.push_str(&format!("\n[login]\nname = {}\n", quoted(name)));
+        }
+        out
+    }
+}
+
+fn quoted(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
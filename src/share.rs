@@ -0,0 +1,126 @@
+//! Share codes: a file's grid written short enough to read out, so
+//! friends can refine the same board, e.g. `HUSH-49B4996-10`.
+//!
+//! A code packs the grid seed, the scoring difficulty and the grid's
+//! columns and rows into one number. Its lowest bits pick the word at the
+//! front, the rest are written in Crockford base32, and the two digits at
+//! the end are a checksum, so a mistyped code is caught rather than
+//! opening some other file.
+
+use crate::error::{MdrError, Result};
+use crate::scoring::Difficulty;
+use std::fmt;
+use std::str::FromStr;
+
+/// The first part of a code, picked by its lowest five bits
+const WORDS: [&str; 32] = [
+    "COLD", "BAIT", "BEAM", "BELL", "BOLT", "CALM", "CORE", "DESK", "DOOR", "DUSK", "EDGE", "FILE", "GLOW", "GOAT",
+    "HALL", "HUSH", "IRIS", "JADE", "KIER", "LAMP", "LOOP", "MILK", "MINT", "NODE", "OATH", "PALE", "REEF", "SEAL",
+    "TIDE", "VEIL", "WING", "ZINC",
+];
+const WORD_BITS: u32 = 5;
+
+/// Crockford base32: no I, L, O or U to misread
+pub(crate) const DIGITS: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// The middle part is padded to at least this many characters
+const MIN_BODY: usize = 4;
+
+/// Columns and rows each get six bits
+pub const MAX_GRID_SIDE: usize = 63;
+const SIDE_BITS: u32 = 6;
+const DIFFICULTY_BITS: u32 = 2;
+
+/// Everything needed to put the same board in front of someone else
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ShareCode {
+    pub seed: u64,
+    pub difficulty: Difficulty,
+    /// Columns and rows of the grid; `(0, 0)` if it was not yet drawn
+    pub grid: (usize, usize),
+}
+
+impl ShareCode {
+    /// The grid size to hold the board to, if the code has one
+    pub fn pinned_grid(&self) -> Option<(usize, usize)> {
+        (self.grid.0 > 0 && self.grid.1 > 0).then_some(self.grid)
+    }
+
+    fn pack(&self) -> u128 {
+        let difficulty = Difficulty::ALL.iter().position(|&d| d == self.difficulty).unwrap_or(0) as u128;
+        let (cols, rows) = (self.grid.0.min(MAX_GRID_SIDE) as u128, self.grid.1.min(MAX_GRID_SIDE) as u128);
+        let mut packed = self.seed as u128;
+        packed = packed << DIFFICULTY_BITS | difficulty;
+        packed = packed << SIDE_BITS | cols;
+        packed << SIDE_BITS | rows
+    }
+
+    fn unpack(mut packed: u128) -> Option<Self> {
+        let side = (1 << SIDE_BITS) - 1;
+        let rows = (packed & side) as usize;
+        packed >>= SIDE_BITS;
+        let cols = (packed & side) as usize;
+        packed >>= SIDE_BITS;
+        let difficulty = *Difficulty::ALL.get((packed & ((1 << DIFFICULTY_BITS) - 1)) as usize)?;
+        packed >>= DIFFICULTY_BITS;
+        let seed = u64::try_from(packed).ok()?;
+        Some(ShareCode { seed, difficulty, grid: (cols, rows) })
+    }
+}
+
+// Catches any one mistyped character and most swapped pairs
+fn checksum(packed: u128) -> u32 {
+    (packed % 97) as u32
+}
+
+impl fmt::Display for ShareCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let packed = self.pack();
+        let word = WORDS[(packed & ((1 << WORD_BITS) - 1)) as usize];
+
+        let mut body = Vec::new();
+        let mut rest = packed >> WORD_BITS;
+        while rest > 0 || body.len() < MIN_BODY {
+            body.push(DIGITS[(rest & 31) as usize] as char);
+            rest >>= 5;
+        }
+        let body: String = body.into_iter().rev().collect();
+        write!(f, "{}-{}-{:02}", word, body, checksum(packed))
+    }
+}
+
+impl FromStr for ShareCode {
+    type Err = MdrError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let invalid = |why: &str| MdrError::Config(format!("'{}' is not a share code: {}", text, why));
+
+        let upper = text.trim().to_uppercase();
+        let mut parts = upper.split('-');
+        let (Some(word), Some(body), Some(check), None) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+            return Err(invalid("expected three parts, like HUSH-49B4996-10"));
+        };
+
+        let word = WORDS.iter().position(|&w| w == word).ok_or_else(|| invalid("unknown word"))? as u128;
+        let mut rest: u128 = 0;
+        for c in body.chars() {
+            // Letters that look like digits are read as those digits
+            let c = match c {
+                'O' => '0',
+                'I' | 'L' => '1',
+                c => c,
+            };
+            let digit = DIGITS.iter().position(|&d| d as char == c).ok_or_else(|| invalid("unexpected character"))?;
+            rest = rest.checked_mul(32).ok_or_else(|| invalid("too long"))? + digit as u128;
+        }
+        let packed = rest.checked_mul(1 << WORD_BITS).ok_or_else(|| invalid("too long"))? | word;
+
+        let check: u32 = match check.len() {
+            2 => check.parse().map_err(|_| invalid("the last part should be two digits"))?,
+            _ => return Err(invalid("the last part should be two digits")),
+        };
+        if check != checksum(packed) {
+            return Err(invalid("the checksum does not match; check for a typo"));
+        }
+        ShareCode::unpack(packed).ok_or_else(|| invalid("too long"))
+    }
+}
@@ -0,0 +1,91 @@
+//! Shift hours for the severed floor.
+//!
+//! Times are minutes past midnight in the configured UTC offset; the app
+//! has no timezone database, so the offset is part of the config.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// Day names as written in the config, Monday first
+pub const DAY_NAMES: [&str; 7] = ["mon", "tue", "wed", "thu", "fri", "sat", "sun"];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shift {
+    /// Minutes past midnight the shift starts
+    pub start: u16,
+    /// Minutes past midnight the shift ends; earlier than `start` for an
+    /// overnight shift
+    pub end: u16,
+    /// Which days a shift starts on, Monday first
+    pub days: [bool; 7],
+    /// Offset of the refiner's clock from UTC, in minutes
+    pub utc_offset: i16,
+    /// Code a supervisor types on the lock screen to open the floor early
+    pub supervisor_code: String,
+}
+
+impl Shift {
+    /// Whether refinement is permitted at `wall`
+    pub fn is_open(&self, wall: SystemTime) -> bool {
+        let (day, minute) = self.local(wall);
+        let (start, end) = (self.start as i64, self.end as i64);
+
+        if start <= end {
+            self.days[day] && (start..end).contains(&minute)
+        } else {
+            // Overnight: the early hours belong to the previous day's shift
+            let yesterday = (day + 6) % 7;
+            (self.days[day] && minute >= start) || (self.days[yesterday] && minute < end)
+        }
+    }
+
+    /// Minutes until the next shift starts (0 while one is running)
+    pub fn minutes_until_open(&self, wall: SystemTime) -> u32 {
+        if self.is_open(wall) || !self.days.contains(&true) {
+            return 0;
+        }
+        let (day, minute) = self.local(wall);
+        (0..8)
+            .map(|offset| ((day + offset) % 7, offset as i64 * MINUTES_PER_DAY + self.start as i64 - minute))
+            .find(|&(d, wait)| self.days[d] && wait > 0)
+            .map_or(0, |(_, wait)| wait as u32)
+    }
+
+    // Local weekday (0 = Monday) and minute of the day
+    fn local(&self, wall: SystemTime) -> (usize, i64) {
+        let secs = wall.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs() as i64);
+        let minutes = secs / 60 + self.utc_offset as i64;
+        let days = minutes.div_euclid(MINUTES_PER_DAY);
+        // 1970-01-01 was a Thursday
+        let weekday = (days + 3).rem_euclid(7) as usize;
+        (weekday, minutes.rem_euclid(MINUTES_PER_DAY))
+    }
+}
+
+/// Format minutes past midnight as `HH:MM`
+pub fn format_time(minutes: u16) -> String {
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+/// Parse `HH:MM` into minutes past midnight
+pub fn parse_time(text: &str) -> Option<u16> {
+    let (hours, minutes) = text.split_once(':')?;
+    if minutes.len() != 2 {
+        return None;
+    }
+    let hours: u16 = hours.parse().ok()?;
+    let minutes: u16 = minutes.parse().ok()?;
+    (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+/// Parse a UTC offset such as `+02:00` or `-05:30` into minutes
+pub fn parse_offset(text: &str) -> Option<i16> {
+    let (sign, rest) = match text.as_bytes().first()? {
+        b'+' => (1, &text[1..]),
+        b'-' => (-1, &text[1..]),
+        _ => return None,
+    };
+    let minutes = parse_time(rest)?;
+    (minutes <= 14 * 60).then_some(sign * minutes as i16)
+}
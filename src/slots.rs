@@ -0,0 +1,122 @@
+//! Save slots: an employee may keep several files in progress, each in a
+//! session file of its own, and picks one to continue at login.
+//!
+//! Slot 1 is the plain `<name>.save` every session used before slots, so
+//! nothing already saved goes missing. What a slot shows in the picker is
+//! read from its file: the file name and how full its bins are, or just
+//! that it is sealed, and when it was last written.
+
+use crate::save::{self, Stored};
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+/// Most slots an employee may keep
+pub const MAX_SLOTS: usize = 5;
+
+/// What a slot's file says about it
+#[derive(Clone, Debug, PartialEq)]
+pub enum SlotDetail {
+    Progress {
+        file: String,
+        /// Average fill of the bins
+        percent: f32,
+    },
+    /// Needs the passphrase to say more
+    Sealed,
+    /// There, but not a save this build can read
+    Unreadable,
+}
+
+/// One saved slot
+#[derive(Clone, Debug, PartialEq)]
+pub struct Slot {
+    /// From 1
+    pub number: usize,
+    /// When the file was last written
+    pub last_played: Option<SystemTime>,
+    pub detail: SlotDetail,
+}
+
+/// The slots `username` has saved, by number
+pub fn list(dir: &Path, username: &str) -> Vec<Slot> {
+    (1..=MAX_SLOTS)
+        .filter_map(|number| {
+            let path = save::slot_path(dir, username, number);
+            let detail = match save::read_stored(&path) {
+                Ok(None) => return None,
+                Ok(Some(Stored::Plain(save))) => {
+                    let percent = save.bins.iter().map(|&count| f32::from(count)).sum::<f32>() / save.bins.len().max(1) as f32;
+                    SlotDetail::Progress { file: save.file_name, percent }
+                },
+                Ok(Some(Stored::Sealed(_))) => SlotDetail::Sealed,
+                Err(_) => SlotDetail::Unreadable,
+            };
+            let last_played = path.metadata().and_then(|meta| meta.modified()).ok();
+            Some(Slot { number, last_played, detail })
+        })
+        .collect()
+}
+
+/// The lowest slot number not in `slots`, if there is room for another
+pub fn free_slot(slots: &[Slot]) -> Option<usize> {
+    (1..=MAX_SLOTS).find(|&number| slots.iter().all(|slot| slot.number != number))
+}
+
+/// How long ago `at` was, in words
+pub fn last_played_label(at: SystemTime, now: SystemTime) -> String {
+    let ago = now.duration_since(at).unwrap_or(Duration::ZERO).as_secs();
+    let plural = |count: u64, unit: &str| format!("{} {}{} ago", count, unit, if count == 1 { "" } else { "s" });
+    match ago {
+        0..60 => "just now".to_string(),
+        60..3_600 => plural(ago / 60, "minute"),
+        3_600..86_400 => plural(ago / 3_600, "hour"),
+        _ => plural(ago / 86_400, "day"),
+    }
+}
+
+/// The slot picker shown after the name at login, while open
+#[derive(Clone, Debug, PartialEq)]
+pub struct SlotPicker {
+    pub slots: Vec<Slot>,
+    /// Highlighted row: a slot, or past the last slot, a new file
+    pub selected: usize,
+    /// Delete was pressed once on the highlighted slot
+    pub confirm_delete: bool,
+}
+
+impl SlotPicker {
+    /// Open on the slot played most recently
+    pub fn new(slots: Vec<Slot>) -> Self {
+        let selected = slots
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, slot)| slot.last_played)
+            .map_or(0, |(idx, _)| idx);
+        Self { slots, selected, confirm_delete: false }
+    }
+
+    /// Rows to choose from: each slot, then a new file if there is room
+    pub fn rows(&self) -> usize {
+        self.slots.len() + usize::from(free_slot(&self.slots).is_some())
+    }
+
+    /// Move the highlight, staying on the list
+    pub fn select(&mut self, delta: isize) {
+        self.confirm_delete = false;
+        self.selected = self.selected.saturating_add_signed(delta).min(self.rows().saturating_sub(1));
+    }
+
+    /// The highlighted slot, unless the new file row is
+    pub fn highlighted(&self) -> Option<&Slot> {
+        self.slots.get(self.selected)
+    }
+
+    /// The slot number to play in: the highlighted slot, or a free one for
+    /// a new file
+    pub fn chosen(&self) -> Option<usize> {
+        match self.highlighted() {
+            Some(slot) => Some(slot.number),
+            None => free_slot(&self.slots),
+        }
+    }
+}
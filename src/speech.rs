@@ -0,0 +1,143 @@
+//! Spoken announcements for milestones ("Bin three is full. Please enjoy
+//! each number equally."), read out by the operating system's own speech
+//! engine: `say` on macOS, the speech synthesizer through PowerShell on
+//! Windows, and `spd-say` or espeak elsewhere.
+//!
+//! Speaking happens on a background thread, one announcement at a time,
+//! so the UI never waits on it. Announcements closer together than
+//! `MIN_GAP` are dropped rather than queued, as a burst of bins filling
+//! would otherwise be read out long after it happened.
+
+use crate::config::SpeechConfig;
+use crate::error::{MdrError, Result};
+use crate::events::AppEvent;
+use std::cell::Cell;
+use std::env;
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Shortest time between two announcements
+pub const MIN_GAP: Duration = Duration::from_secs(5);
+/// Announcements waiting to be spoken before new ones are dropped
+const QUEUE_LIMIT: usize = 2;
+/// Engines tried in turn where there is no built-in one
+const LINUX_ENGINES: &[&[&str]] = &[&["spd-say", "--wait"], &["espeak-ng"], &["espeak"]];
+
+/// How text is turned into speech
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Engine {
+    /// A program given the text as its last argument
+    Program(Vec<String>),
+    /// The Windows speech synthesizer, driven through PowerShell
+    PowerShell,
+}
+
+impl Engine {
+    /// The configured command, or the first engine this system has
+    pub fn find(config: &SpeechConfig) -> Option<Engine> {
+        if let Some(command) = &config.command {
+            let words: Vec<String> = command.split_whitespace().map(str::to_string).collect();
+            return (!words.is_empty()).then_some(Engine::Program(words));
+        }
+        if cfg!(target_os = "macos") {
+            return Some(Engine::Program(vec!["say".to_string()]));
+        }
+        if cfg!(windows) {
+            return Some(Engine::PowerShell);
+        }
+        LINUX_ENGINES
+            .iter()
+            .find(|words| on_path(words[0]))
+            .map(|words| Engine::Program(words.iter().map(|word| word.to_string()).collect()))
+    }
+
+    fn command(&self, text: &str) -> Command {
+        let mut command = match self {
+            Engine::Program(words) => {
+                let mut command = Command::new(&words[0]);
+                command.args(&words[1..]).arg(text);
+                command
+            },
+            Engine::PowerShell => {
+                let script = format!(
+                    "Add-Type -AssemblyName System.Speech; (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak('{}')",
+                    text.replace('\'', "''")
+                );
+                let mut command = Command::new("powershell");
+                command.args(["-NoProfile", "-Command", &script]);
+                command
+            },
+        };
+        // The engine must not write over the TUI
+        command.stdin(Stdio::null()).stdout(Stdio::null()).stderr(Stdio::null());
+        command
+    }
+}
+
+/// Handle for queueing announcements; speaking runs on its own thread
+pub struct Speech {
+    tx: SyncSender<String>,
+    last: Cell<Option<Instant>>,
+}
+
+impl Speech {
+    /// Start the speaking thread, or return `None` when announcements are
+    /// off. Fails when they are on but there is no engine to speak with.
+    pub fn start(config: &SpeechConfig) -> Result<Option<Speech>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+        let engine = Engine::find(config)
+            .ok_or_else(|| MdrError::Config("no speech engine found; set [speech] command".to_string()))?;
+
+        let (tx, rx) = mpsc::sync_channel(QUEUE_LIMIT);
+        thread::spawn(move || speak_all(rx, engine));
+        Ok(Some(Speech { tx, last: Cell::new(None) }))
+    }
+
+    /// Queue the announcement for a milestone, unless one was made less
+    /// than `MIN_GAP` before `now`. Returns whether it was queued; never
+    /// blocks.
+    pub fn announce(&self, event: &AppEvent, now: Instant) -> bool {
+        let Some(text) = announcement(event) else { return false };
+        if self.last.get().is_some_and(|last| now.duration_since(last) < MIN_GAP) {
+            return false;
+        }
+        let queued = self.tx.try_send(text).is_ok();
+        if queued {
+            self.last.set(Some(now));
+        }
+        queued
+    }
+}
+
+/// What is said for an event, if anything
+pub fn announcement(event: &AppEvent) -> Option<String> {
+    match event {
+        AppEvent::NumbersRefined { .. } => None,
+        AppEvent::BinFilled { bin } => Some(format!("Bin {} is full. Please enjoy each number equally.", number_word(*bin))),
+        AppEvent::FileCompleted { prize } => Some(format!("File complete. Please enjoy your {}.", prize)),
+    }
+}
+
+fn speak_all(rx: Receiver<String>, engine: Engine) {
+    for text in rx {
+        // An engine that fails only costs this announcement
+        let _ = engine.command(&text).status();
+    }
+}
+
+// Spelled out, as some engines read "03" as "oh three"
+fn number_word(number: usize) -> String {
+    const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+    match number.checked_sub(1).and_then(|idx| WORDS.get(idx)) {
+        Some(word) => word.to_string(),
+        None => number.to_string(),
+    }
+}
+
+fn on_path(program: &str) -> bool {
+    env::var_os("PATH").is_some_and(|paths| env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+}
@@ -0,0 +1,165 @@
+//! Startup work that reads from disk: the config, the content packs and
+//! the ledger. Each is read on its own thread so a large ledger or a slow
+//! disk never holds up the first frame, and the results are taken in as
+//! they arrive. The loading screen lists the tasks still out and waits for
+//! all of them before handing over to the main screen.
+
+use crate::config::{self, Config};
+use crate::content::{self, Content};
+use crate::error::Result;
+use crate::ledger::Ledger;
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+/// One piece of startup work
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Task {
+    Config,
+    Content,
+    Ledger,
+}
+
+impl Task {
+    pub fn label(self) -> &'static str {
+        match self {
+            Task::Config => "config",
+            Task::Content => "content packs",
+            Task::Ledger => "ledger",
+        }
+    }
+}
+
+/// Where a task has got to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Running,
+    Done,
+    Failed,
+}
+
+/// What a task read
+#[derive(Debug)]
+pub enum Loaded {
+    // Boxed, as a config is several times the size of the others
+    Config(Result<Box<Config>>),
+    Content(Result<Content>),
+    Ledger(Result<Ledger>),
+}
+
+impl Loaded {
+    pub fn task(&self) -> Task {
+        match self {
+            Loaded::Config(_) => Task::Config,
+            Loaded::Content(_) => Task::Content,
+            Loaded::Ledger(_) => Task::Ledger,
+        }
+    }
+
+    fn succeeded(&self) -> bool {
+        match self {
+            Loaded::Config(result) => result.is_ok(),
+            Loaded::Content(result) => result.is_ok(),
+            Loaded::Ledger(result) => result.is_ok(),
+        }
+    }
+}
+
+/// The startup tasks under way
+pub struct Startup {
+    rx: Receiver<Loaded>,
+    pub tasks: Vec<(Task, Status)>,
+    // Packs name the theme the config picks, so they wait for the config
+    held: Option<Loaded>,
+}
+
+impl Startup {
+    /// Start reading whichever of the three have a path
+    pub fn start(config: Option<PathBuf>, packs: Option<PathBuf>, ledger: Option<PathBuf>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let mut tasks = Vec::new();
+
+        if let Some(path) = config {
+            tasks.push((Task::Config, Status::Running));
+            spawn(&tx, move || Loaded::Config(config::load(&path).map(Box::new)));
+        }
+        if let Some(dir) = packs {
+            tasks.push((Task::Content, Status::Running));
+            spawn(&tx, move || Loaded::Content(content::load(&dir)));
+        }
+        if let Some(path) = ledger {
+            tasks.push((Task::Ledger, Status::Running));
+            spawn(&tx, move || Loaded::Ledger(Ledger::load(&path)));
+        }
+        Self { rx, tasks, held: None }
+    }
+
+    /// Whatever has arrived since the last call, config first
+    pub fn poll(&mut self) -> Vec<Loaded> {
+        let mut ready = Vec::new();
+        while let Ok(loaded) = self.rx.try_recv() {
+            self.take(loaded, &mut ready);
+        }
+        ready
+    }
+
+    /// Block until `task` is in, returning it and anything that came first
+    pub fn wait_for(&mut self, task: Task) -> Vec<Loaded> {
+        let mut ready = self.poll();
+        while self.status(task) == Some(Status::Running) {
+            match self.rx.recv() {
+                Ok(loaded) => self.take(loaded, &mut ready),
+                // A task that panicked never answers
+                Err(_) => break,
+            }
+        }
+        ready
+    }
+
+    /// Block until every task is in
+    pub fn wait(&mut self) -> Vec<Loaded> {
+        let mut ready = self.poll();
+        while !self.is_done() {
+            match self.rx.recv() {
+                Ok(loaded) => self.take(loaded, &mut ready),
+                Err(_) => break,
+            }
+        }
+        ready
+    }
+
+    pub fn status(&self, task: Task) -> Option<Status> {
+        self.tasks.iter().find(|(t, _)| *t == task).map(|&(_, status)| status)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.tasks.iter().all(|&(_, status)| status != Status::Running)
+    }
+
+    fn take(&mut self, loaded: Loaded, ready: &mut Vec<Loaded>) {
+        let task = loaded.task();
+        if task == Task::Content && self.status(Task::Config) == Some(Status::Running) {
+            self.held = Some(loaded);
+            return;
+        }
+
+        let status = if loaded.succeeded() { Status::Done } else { Status::Failed };
+        if let Some(entry) = self.tasks.iter_mut().find(|(t, _)| *t == task) {
+            entry.1 = status;
+        }
+        ready.push(loaded);
+
+        if task == Task::Config
+            && let Some(held) = self.held.take()
+        {
+            self.take(held, ready);
+        }
+    }
+}
+
+fn spawn(tx: &Sender<Loaded>, work: impl FnOnce() -> Loaded + Send + 'static) {
+    let tx = tx.clone();
+    thread::spawn(move || {
+        let _ = tx.send(work());
+    });
+}
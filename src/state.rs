@@ -0,0 +1,57 @@
+//! The screens the app moves between, and which may follow which.
+//!
+//! The app changes screen through `App::go_to`, which checks the move
+//! against `AppState::can_go_to` and refuses one the table does not
+//! allow, so a stray key or timer cannot, say, take a refiner from the
+//! login straight to the prize. Going to a screen runs its entry hook and
+//! leaving one its exit hook, so what a screen sets up or leaves behind
+//! is tidied in one place rather than at every move. A new screen needs a
+//! variant here, its moves in the table, any hooks it wants and a
+//! `Screen` (see `screen.rs`).
+//!
+//! The error screen is an interruption rather than a screen of its own:
+//! any screen may be interrupted by it, it goes back to whatever it
+//! interrupted, and neither way runs a hook, so nothing is lost to it.
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AppState {
+    Login,
+    Loading,
+    Main,
+    Prize,
+    Error,
+    Report,
+    Locked,
+    Changelog,
+    /// Last week's numbers, shown before login on the first launch of a week
+    Recap,
+    /// The first-run questions, before the first login
+    Setup,
+    /// A break earned by filling a bin, counting down before refinement
+    /// may go on
+    Break,
+}
+
+impl AppState {
+    /// Whether the app may move from this screen to `next`
+    pub fn can_go_to(self, next: AppState) -> bool {
+        use AppState::*;
+        match (self, next) {
+            _ if self == next => true,
+            (_, Error) | (Error, _) => true,
+            (Setup | Recap, Login) => true,
+            // New and restored sessions alike are loaded from the login
+            (Login, Setup | Recap | Loading) => true,
+            // A saved break is sat out before refinement goes on
+            (Loading, Main | Break) => true,
+            (Main, Prize | Report | Changelog | Locked | Break) => true,
+            (Prize | Report | Changelog | Locked | Break, Main) => true,
+            _ => false,
+        }
+    }
+
+    /// The error screen, which interrupts rather than replaces
+    pub fn is_interruption(self) -> bool {
+        self == AppState::Error
+    }
+}
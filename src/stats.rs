@@ -0,0 +1,140 @@
+//! Time accounting for the work/life balance report.
+//!
+//! Every refiner has two profiles: the innie, who is credited with time
+//! spent actively refining, and the outie, who gets everything else the
+//! app was open for (idle, unfocused, or off the main screen).
+
+use crate::app::{App, AppState};
+use crate::ranks;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// Which half of the refiner a stretch of time belongs to
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Profile {
+    Innie,
+    Outie,
+}
+
+/// Accumulated time for one refiner
+#[derive(Clone, Copy, Default, Debug, PartialEq)]
+pub struct Balance {
+    pub innie: Duration,
+    pub outie: Duration,
+}
+
+impl Balance {
+    pub fn total(&self) -> Duration {
+        self.innie + self.outie
+    }
+
+    /// Share of the session the innie worked, from 0.0 to 1.0
+    pub fn innie_share(&self) -> f32 {
+        let total = self.total().as_secs_f32();
+        if total > 0.0 {
+            self.innie.as_secs_f32() / total
+        } else {
+            0.0
+        }
+    }
+
+    /// Management's assessment of the split
+    pub fn verdict(&self) -> &'static str {
+        match self.innie_share() {
+            s if self.total().is_zero() || s == 0.0 => "No work has been recorded. Your outie is thriving.",
+            s if s >= 0.9 => "Exemplary. Your outie may not recognise you.",
+            s if s >= 0.6 => "A healthy balance, as defined by the Board.",
+            s if s >= 0.3 => "Your outie has been noted. Please refine accordingly.",
+            _ => "Concerning. A visit to the Wellness Center has been scheduled.",
+        }
+    }
+}
+
+/// Per-user balances for the current session
+pub struct Stats {
+    balances: HashMap<String, Balance>,
+    last_sample: Instant,
+}
+
+impl Stats {
+    pub fn new(now: Instant) -> Self {
+        Self {
+            balances: HashMap::new(),
+            last_sample: now,
+        }
+    }
+
+    /// Credit the time since the last sample to `user`'s innie or outie
+    pub fn record(&mut self, user: &str, profile: Profile, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_sample);
+        self.last_sample = now;
+
+        let balance = self.balances.entry(user.to_string()).or_default();
+        match profile {
+            Profile::Innie => balance.innie += elapsed,
+            Profile::Outie => balance.outie += elapsed,
+        }
+    }
+
+    pub fn balance(&self, user: &str) -> Balance {
+        self.balances.get(user).copied().unwrap_or_default()
+    }
+
+    /// Replace `user`'s totals, e.g. with ones read from a save file
+    pub fn set_balance(&mut self, user: &str, balance: Balance) {
+        self.balances.insert(user.to_string(), balance);
+    }
+
+    /// Refiners with a balance this session
+    pub fn refiners(&self) -> usize {
+        self.balances.len()
+    }
+
+    /// Keep at most `cap` balances: `user`'s, then those with the most time
+    pub fn trim(&mut self, user: &str, cap: usize) {
+        crate::budget::trim_map(&mut self.balances, cap, &user.to_string(), Balance::total);
+    }
+}
+
+/// Format a duration as `1h 02m 03s`, dropping leading zero units
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, minutes, seconds) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}h {:02}m {:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {:02}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// The performance report as plain text, for pasting elsewhere
+pub fn performance_summary(app: &App) -> String {
+    let balance = app.station.stats.balance(&app.username);
+    let mut lines = vec![
+        "Lumon Industries - Macrodata Refinement".to_string(),
+        format!("Performance report: {} ({})", app.username, ranks::rank_for(app.xp).title),
+        format!("File: {} ({:.0}% complete)", app.file_name, app.completion_target()),
+        format!("Share code: {}", app.share_code()),
+        format!("Numbers refined: {}   Files completed: {}", app.goals.numbers, app.goals.files),
+        format!(
+            "Innie: {}   Outie: {}   Innie share: {:.0}%",
+            format_duration(balance.innie),
+            format_duration(balance.outie),
+            balance.innie_share() * 100.0
+        ),
+        balance.verdict().to_string(),
+    ];
+    if app.reshuffles > 0 {
+        let times = if app.reshuffles == 1 { "once".to_string() } else { format!("{} times", app.reshuffles) };
+        lines.push(format!("Grid reshuffled {}", times));
+    }
+    if app.state() == AppState::Prize {
+        lines.push(format!("Awarded: {}", app.prize_name));
+    }
+    if !app.journal.is_empty() {
+        lines.push(app.journal.summary(app.utc_offset()));
+    }
+    lines.join("\n")
+}
@@ -0,0 +1,87 @@
+//! How a capture nobody aimed picks its bin. The strategy is set with
+//! `strategy` in `[bins]` or switched while playing with `:bins <name>`.
+//!
+//! Every strategy chooses among the open bins only, those neither full nor
+//! locked, and a focused bin still takes every capture whatever the
+//! strategy.
+
+use crate::app::DataContainer;
+use crate::error::{MdrError, Result};
+use crate::temper::Temper;
+use rand::Rng;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BinStrategy {
+    /// Any open bin, with even odds
+    #[default]
+    Random,
+    /// The emptiest bin, so the bins fill together
+    LeastFull,
+    /// Each open bin in turn, by number
+    RoundRobin,
+    /// The bin holding the least of the capture's temper, so each bin's
+    /// tempers even out
+    TemperMatched,
+}
+
+impl BinStrategy {
+    pub const ALL: [BinStrategy; 4] =
+        [BinStrategy::Random, BinStrategy::LeastFull, BinStrategy::RoundRobin, BinStrategy::TemperMatched];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BinStrategy::Random => "random",
+            BinStrategy::LeastFull => "least-full",
+            BinStrategy::RoundRobin => "round-robin",
+            BinStrategy::TemperMatched => "temper",
+        }
+    }
+
+    /// Pick one of the `open` bins, by index into `bins`, for a capture
+    /// sorted under `temper`. `last` is the bin round-robin picked last.
+    /// Ties go to the bin further left.
+    pub fn choose(
+        self,
+        bins: &[DataContainer],
+        open: &[usize],
+        temper: Option<Temper>,
+        last: Option<usize>,
+        rng: &mut impl Rng,
+    ) -> Option<usize> {
+        if open.is_empty() {
+            return None;
+        }
+        let count = |idx: usize| bins.get(idx).map_or(0, |bin| bin.count);
+        match (self, temper) {
+            (BinStrategy::Random, _) => Some(open[rng.random_range(0..open.len())]),
+            (BinStrategy::LeastFull, _) | (BinStrategy::TemperMatched, None) => {
+                open.iter().copied().min_by_key(|&idx| (count(idx), idx))
+            },
+            (BinStrategy::RoundRobin, _) => {
+                let next = last.and_then(|last| open.iter().copied().filter(|&idx| idx > last).min());
+                next.or_else(|| open.iter().copied().min())
+            },
+            (BinStrategy::TemperMatched, Some(temper)) => open.iter().copied().min_by_key(|&idx| {
+                let held = bins.get(idx).map_or(0, |bin| bin.tempers[temper.index()]);
+                (held, count(idx), idx)
+            }),
+        }
+    }
+}
+
+impl FromStr for BinStrategy {
+    type Err = MdrError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        BinStrategy::ALL
+            .into_iter()
+            .find(|strategy| strategy.name().eq_ignore_ascii_case(text.trim()))
+            .ok_or_else(|| {
+                MdrError::Config(format!(
+                    "'{}' is not a bin strategy (random, least-full, round-robin or temper)",
+                    text.trim()
+                ))
+            })
+    }
+}
@@ -0,0 +1,228 @@
+//! Publishes session events to a webhook and/or an MQTT topic, so a file
+//! completing can flash the office lights.
+//!
+//! Delivery happens on a background thread and never blocks the UI. Events
+//! that cannot be delivered after a few attempts are dropped, as are events
+//! that arrive while the queue is full.
+//!
+//! Only plain `http://` webhooks and unencrypted MQTT 3.1.1 are spoken,
+//! which is what home-automation hubs on the local network expect.
+
+use crate::config::TelemetryConfig;
+use crate::error::{MdrError, Result};
+use crate::events::AppEvent;
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread;
+use std::time::Duration;
+
+/// Events waiting for delivery before new ones are dropped
+const QUEUE_LIMIT: usize = 64;
+/// Delivery attempts per event and sink
+const MAX_ATTEMPTS: u32 = 4;
+/// Wait before the first retry; doubles after every failure
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Where events go
+#[derive(Clone, Debug, PartialEq)]
+pub enum Sink {
+    Webhook { host: String, port: u16, path: String },
+    Mqtt { host: String, port: u16, topic: String },
+}
+
+impl Sink {
+    /// Parse `http://host[:port]/path`
+    pub fn webhook(url: &str) -> Result<Sink> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| MdrError::Config(format!("webhook '{}' must start with http://", url)))?;
+        let (host, port, path) = split_url(rest, 80);
+        Ok(Sink::Webhook { host, port, path })
+    }
+
+    /// Parse `mqtt://host[:port]/topic/name`
+    pub fn mqtt(url: &str) -> Result<Sink> {
+        let rest = url
+            .strip_prefix("mqtt://")
+            .ok_or_else(|| MdrError::Config(format!("MQTT broker '{}' must start with mqtt://", url)))?;
+        let (host, port, path) = split_url(rest, 1883);
+        let topic = path.trim_start_matches('/').to_string();
+        if topic.is_empty() {
+            return Err(MdrError::Config(format!("MQTT URL '{}' needs a topic", url)));
+        }
+        Ok(Sink::Mqtt { host, port, topic })
+    }
+
+    fn deliver(&self, payload: &str) -> io::Result<()> {
+        match self {
+            Sink::Webhook { host, port, path } => post(host, *port, path, payload),
+            Sink::Mqtt { host, port, topic } => publish(host, *port, topic, payload),
+        }
+    }
+}
+
+/// Handle for queueing events; delivery runs on its own thread
+pub struct Telemetry {
+    tx: SyncSender<String>,
+}
+
+impl Telemetry {
+    /// Start the delivery thread, or return `None` when nothing is configured
+    pub fn start(config: &TelemetryConfig) -> Result<Option<Telemetry>> {
+        let mut sinks = Vec::new();
+        if let Some(url) = &config.webhook {
+            sinks.push(Sink::webhook(url)?);
+        }
+        if let Some(url) = &config.mqtt {
+            sinks.push(Sink::mqtt(url)?);
+        }
+        if sinks.is_empty() {
+            return Ok(None);
+        }
+
+        let (tx, rx) = mpsc::sync_channel(QUEUE_LIMIT);
+        thread::spawn(move || deliver_all(rx, sinks));
+        Ok(Some(Telemetry { tx }))
+    }
+
+    /// Queue a milestone event; never blocks
+    pub fn send(&self, user: &str, event: &AppEvent) {
+        if event.is_milestone() {
+            let _ = self.tx.try_send(payload(user, event));
+        }
+    }
+}
+
+/// The JSON body sent for an event
+pub fn payload(user: &str, event: &AppEvent) -> String {
+    let detail = match event {
+        AppEvent::NumbersRefined { count } => format!(",\"count\":{}", count),
+        AppEvent::BinFilled { bin } => format!(",\"bin\":{}", bin),
+        AppEvent::FileCompleted { prize } => format!(",\"prize\":{}", json_string(prize)),
+    };
+    format!("{{\"event\":\"{}\",\"user\":{}{}}}", event.name(), json_string(user), detail)
+}
+
+fn deliver_all(rx: Receiver<String>, sinks: Vec<Sink>) {
+    for payload in rx {
+        for sink in &sinks {
+            let mut backoff = INITIAL_BACKOFF;
+            for attempt in 1..=MAX_ATTEMPTS {
+                if sink.deliver(&payload).is_ok() {
+                    break;
+                }
+                if attempt < MAX_ATTEMPTS {
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                }
+            }
+        }
+    }
+}
+
+// Split `host[:port]/path` into its parts
+fn split_url(rest: &str, default_port: u16) -> (String, u16, String) {
+    let (authority, path) = match rest.find('/') {
+        Some(idx) => (&rest[..idx], &rest[idx..]),
+        None => (rest, "/"),
+    };
+    match authority.rsplit_once(':').and_then(|(h, p)| Some((h, p.parse().ok()?))) {
+        Some((host, port)) => (host.to_string(), port, path.to_string()),
+        None => (authority.to_string(), default_port, path.to_string()),
+    }
+}
+
+fn connect(host: &str, port: u16) -> io::Result<TcpStream> {
+    let addr = (host, port)
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("cannot resolve {}", host)))?;
+    let stream = TcpStream::connect_timeout(&addr, NETWORK_TIMEOUT)?;
+    stream.set_read_timeout(Some(NETWORK_TIMEOUT))?;
+    stream.set_write_timeout(Some(NETWORK_TIMEOUT))?;
+    Ok(stream)
+}
+
+fn post(host: &str, port: u16, path: &str, body: &str) -> io::Result<()> {
+    let mut stream = connect(host, port)?;
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path, host, body.len(), body
+    )?;
+
+    // Only the status line matters
+    let mut response = [0u8; 12];
+    stream.read_exact(&mut response)?;
+    match &response[9..10] {
+        b"2" => Ok(()),
+        _ => Err(io::Error::other(format!(
+            "webhook answered {}",
+            String::from_utf8_lossy(&response[9..12])
+        ))),
+    }
+}
+
+fn publish(host: &str, port: u16, topic: &str, payload: &str) -> io::Result<()> {
+    let mut stream = connect(host, port)?;
+
+    // CONNECT: protocol "MQTT" level 4, clean session, 60s keep-alive
+    let mut connect = vec![0, 4, b'M', b'Q', b'T', b'T', 4, 0x02, 0, 60];
+    push_str(&mut connect, "lumon-mdr");
+    stream.write_all(&packet(0x10, &connect))?;
+
+    let mut connack = [0u8; 4];
+    stream.read_exact(&mut connack)?;
+    if connack[0] != 0x20 || connack[3] != 0 {
+        return Err(io::Error::other(format!("broker refused connection (code {})", connack[3])));
+    }
+
+    // PUBLISH at QoS 0, then say goodbye
+    let mut body = Vec::new();
+    push_str(&mut body, topic);
+    body.extend_from_slice(payload.as_bytes());
+    stream.write_all(&packet(0x30, &body))?;
+    stream.write_all(&[0xE0, 0])?;
+    Ok(())
+}
+
+// Fixed header plus variable-length "remaining length"
+fn packet(kind: u8, body: &[u8]) -> Vec<u8> {
+    let mut out = vec![kind];
+    let mut len = body.len();
+    loop {
+        let mut byte = (len % 128) as u8;
+        len /= 128;
+        if len > 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if len == 0 {
+            break;
+        }
+    }
+    out.extend_from_slice(body);
+    out
+}
+
+fn push_str(out: &mut Vec<u8>, text: &str) {
+    out.extend_from_slice(&(text.len() as u16).to_be_bytes());
+    out.extend_from_slice(text.as_bytes());
+}
+
+fn json_string(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
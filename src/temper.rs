@@ -0,0 +1,99 @@
+//! The four tempers a refined number can carry: woe, frolic, dread and
+//! malice. Each deposit into a bin is sorted into one of them.
+//!
+//! A number's temper comes from the quadrant of the grid it was refined
+//! in: woe top left, frolic top right, dread bottom left and malice bottom
+//! right. A capture is sorted by the tempers of the numbers in it, and
+//! only a deposit made straight into a bin, with no numbers behind it, is
+//! sorted at random.
+
+use rand::Rng;
+use std::time::Instant;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Temper {
+    Woe,
+    Frolic,
+    Dread,
+    Malice,
+}
+
+impl Temper {
+    /// Every temper, in the order the bins list them
+    pub const ALL: [Temper; 4] = [Temper::Woe, Temper::Frolic, Temper::Dread, Temper::Malice];
+
+    /// The two-letter code from the bin labels
+    pub fn code(self) -> &'static str {
+        match self {
+            Temper::Woe => "WO",
+            Temper::Frolic => "FC",
+            Temper::Dread => "DR",
+            Temper::Malice => "MA",
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Temper::Woe => "Woe",
+            Temper::Frolic => "Frolic",
+            Temper::Dread => "Dread",
+            Temper::Malice => "Malice",
+        }
+    }
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn random(rng: &mut impl Rng) -> Temper {
+        Temper::ALL[rng.random_range(0..Temper::ALL.len())]
+    }
+
+    /// The temper of the quadrant `cell` lies in, on a grid `cols` by `rows`
+    pub fn of_cell((col, row): (usize, usize), (cols, rows): (usize, usize)) -> Temper {
+        let right = col * 2 >= cols;
+        let lower = row * 2 >= rows;
+        Temper::ALL[usize::from(lower) * 2 + usize::from(right)]
+    }
+}
+
+/// Where one captured number came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Provenance {
+    pub cell: (usize, usize),
+    pub digit: u16,
+    /// Its quadrant's temper; unknown before the grid is first drawn
+    pub temper: Option<Temper>,
+}
+
+/// The temper a capture is sorted into: the one whose numbers add up to
+/// the most, then the one with more numbers, then the earliest in `ALL`.
+/// `None` when no number's temper is known.
+pub fn attribute(origins: &[Provenance]) -> Option<Temper> {
+    let mut weights = [(0u32, 0usize); 4];
+    for origin in origins {
+        if let Some(temper) = origin.temper {
+            let weight = &mut weights[temper.index()];
+            weight.0 += u32::from(origin.digit);
+            weight.1 += 1;
+        }
+    }
+    Temper::ALL
+        .iter()
+        .rev()
+        .filter(|temper| weights[temper.index()].1 > 0)
+        .max_by_key(|temper| weights[temper.index()])
+        .copied()
+}
+
+/// One deposit into a bin, for its recent deposits log
+#[derive(Clone, Debug, PartialEq)]
+pub struct Deposit {
+    /// What the bin actually took, after capping at 100
+    pub value: u16,
+    pub temper: Temper,
+    pub at: Instant,
+    /// The numbers behind it and where they were refined; empty for
+    /// deposits made straight into the bin
+    pub origins: Vec<Provenance>,
+}
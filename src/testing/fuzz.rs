@@ -0,0 +1,221 @@
+//! Randomized event storms for shaking out panics and broken invariants.
+//!
+//! Every step feeds one arbitrary event to [`App::update`], occasionally
+//! ticks the clock, and renders a frame at the current terminal size, then
+//! checks that the app state still makes sense.
+
+use crate::app::{App, AppState, MAX_USERNAME_LEN};
+use crate::clock::MockClock;
+use crate::error::{FailureKind, RecoverableError};
+use crate::theme::Palette;
+use crate::ui;
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use ratatui::{Terminal, backend::TestBackend};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// A reproducible failure found by [`run`]
+pub struct FuzzFailure {
+    pub seed: u64,
+    pub step: usize,
+    pub event: String,
+    pub message: String,
+}
+
+impl fmt::Display for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "seed {} step {}: {} (after {})",
+            self.seed, self.step, self.message, self.event
+        )
+    }
+}
+
+impl fmt::Debug for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Throw `steps` random events at a fresh app. The same seed always
+/// produces the same event sequence.
+pub fn run(seed: u64, steps: usize) -> Result<(), FuzzFailure> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let clock = MockClock::new();
+    let mut app = App::with_clock(Palette::Ansi, Rc::new(clock.clone()));
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).expect("test backend");
+
+    // Start from a random screen and bin levels so every state gets
+    // coverage, each reached the way the app would reach it
+    let route: &[AppState] = match rng.random_range(0..9) {
+        0 => &[],
+        1 => &[AppState::Loading],
+        2 => &[AppState::Loading, AppState::Main],
+        3 => &[AppState::Loading, AppState::Main, AppState::Prize],
+        4 => &[AppState::Loading, AppState::Main, AppState::Report],
+        5 => &[AppState::Loading, AppState::Main, AppState::Locked],
+        6 => &[AppState::Loading, AppState::Main, AppState::Changelog],
+        7 => {
+            app.recap = Some(app.station.ledger.recap(0));
+            &[AppState::Recap]
+        },
+        _ => {
+            app.report_error(RecoverableError::new(FailureKind::Config, "fuzzed failure"));
+            &[]
+        },
+    };
+    for &state in route {
+        app.go_to(state);
+    }
+    for container in &mut app.containers {
+        container.add(rng.random_range(0..=100));
+    }
+
+    for step in 0..steps {
+        let event = random_event(&mut rng);
+        let description = format!("{:?}", event);
+        let fail = |message: String| FuzzFailure { seed, step, event: description.clone(), message };
+
+        let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+            if let Event::Resize(width, height) = event {
+                terminal.backend_mut().resize(width, height);
+            }
+            app.update(event);
+
+            if rng.random_bool(0.3) {
+                clock.advance(Duration::from_millis(300));
+                app.tick();
+            }
+
+            ui::layout(&mut app, terminal.size()?);
+            terminal.draw(|frame| ui::draw(frame, &app)).map(|_| ())
+        }));
+
+        match outcome {
+            Ok(Ok(())) => {},
+            Ok(Err(err)) => return Err(fail(format!("draw failed: {}", err))),
+            Err(payload) => return Err(fail(format!("panicked: {}", panic_message(&payload)))),
+        }
+
+        check_invariants(&app).map_err(fail)?;
+
+        // Quitting is legal; start over so the rest of the run still counts
+        if !app.running {
+            app.running = true;
+            app.go_to(AppState::Main);
+        }
+    }
+
+    Ok(())
+}
+
+/// Check the properties that must hold after any sequence of events
+pub fn check_invariants(app: &App) -> Result<(), String> {
+    for (idx, container) in app.containers.iter().enumerate() {
+        if container.count > 100 {
+            return Err(format!("bin {} holds {} (max 100)", idx + 1, container.count));
+        }
+        if container.progress != container.count as f32 {
+            return Err(format!(
+                "bin {} progress {} does not match count {}",
+                idx + 1, container.progress, container.count
+            ));
+        }
+    }
+
+    let name_len = app.username.chars().count();
+    if app.username_cursor > name_len {
+        return Err(format!("username cursor {} past end of {:?}", app.username_cursor, app.username));
+    }
+    if name_len > MAX_USERNAME_LEN {
+        return Err(format!("username {:?} longer than {} characters", app.username, MAX_USERNAME_LEN));
+    }
+
+    if let Some(cell) = app.grid.cells().find(|&cell| !app.grid.contains(cell)) {
+        return Err(format!("replaced cell {:?} is off the {:?} grid", cell, app.grid.size()));
+    }
+
+    if !(0.0..=100.0).contains(&app.progress_percentage) {
+        return Err(format!("loading progress {} out of range", app.progress_percentage));
+    }
+
+    // And everything the self-check looks at during long sessions
+    match crate::selfcheck::run(app).discrepancies.first() {
+        Some(discrepancy) => Err(discrepancy.to_string()),
+        None => Ok(()),
+    }
+}
+
+/// Generate one arbitrary key, mouse, paste, focus, or resize event
+pub fn random_event(rng: &mut StdRng) -> Event {
+    match rng.random_range(0..10) {
+        0..=3 => Event::Key(KeyEvent::new(random_key(rng), KeyModifiers::NONE)),
+        4..=7 => {
+            let kind = match rng.random_range(0..6) {
+                0 => MouseEventKind::Down(MouseButton::Left),
+                1 => MouseEventKind::Up(MouseButton::Left),
+                2 => MouseEventKind::Drag(MouseButton::Left),
+                3 => MouseEventKind::ScrollDown,
+                _ => MouseEventKind::Moved,
+            };
+            // Deliberately include positions outside the screen
+            Event::Mouse(MouseEvent {
+                kind,
+                column: rng.random_range(0..140),
+                row: rng.random_range(0..50),
+                modifiers: KeyModifiers::NONE,
+            })
+        },
+        8 => match rng.random_range(0..4) {
+            0 => Event::FocusLost,
+            1 => Event::FocusGained,
+            _ => Event::Paste(random_text(rng)),
+        },
+        _ => Event::Resize(rng.random_range(1..=160), rng.random_range(1..=60)),
+    }
+}
+
+fn random_key(rng: &mut StdRng) -> KeyCode {
+    match rng.random_range(0..14) {
+        0 => KeyCode::Enter,
+        1 => KeyCode::Esc,
+        2 => KeyCode::Backspace,
+        3 => KeyCode::Delete,
+        4 => KeyCode::Left,
+        5 => KeyCode::Right,
+        6 => KeyCode::Tab,
+        7 => KeyCode::F(rng.random_range(1..=12)),
+        // Rarely quit, so runs reach deep states
+        8 if rng.random_bool(0.1) => KeyCode::Char('q'),
+        _ => KeyCode::Char(random_char(rng)),
+    }
+}
+
+fn random_text(rng: &mut StdRng) -> String {
+    let len = rng.random_range(0..40);
+    (0..len).map(|_| random_char(rng)).collect()
+}
+
+fn random_char(rng: &mut StdRng) -> char {
+    // Mix ASCII with multi-byte characters and control codes
+    const SPECIALS: &[char] = &['é', 'ß', '漢', '😀', '\n', '\t', '\u{7f}', ' ', 'r'];
+    if rng.random_bool(0.3) {
+        SPECIALS[rng.random_range(0..SPECIALS.len())]
+    } else {
+        rng.random_range(b'0'..=b'z') as char
+    }
+}
+
+fn panic_message(payload: &Box<dyn std::any::Any + Send>) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
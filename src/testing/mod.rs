@@ -0,0 +1,6 @@
+//! Public helpers for exercising the app without a real terminal.
+
+pub mod fuzz;
+mod script;
+
+pub use script::{ScriptedEvents, find_text};
@@ -0,0 +1,285 @@
+//! Scripted input for driving the real event loop.
+//!
+//! A script is a `;`-separated list of steps:
+//!
+//! - `type:<text>` types each character of `<text>`
+//! - `enter`, `esc`, `space`, `backspace`, `delete`, `tab`, `backtab`,
+//!   `left`, `right`, `up`, `down`, `f12` press the named key
+//! - `key:<c>` presses a single character key; `key:ctrl+<c>` and
+//!   `key:alt+<c>` press it with a modifier
+//! - `repeat:<c>` is the auto-repeat of a held character key, and
+//!   `keyup:<c>` lets go of it, as terminals that report both do
+//! - `click:<x>,<y>` clicks the left mouse button at a cell, and
+//!   `rclick:<x>,<y>` the right one
+//! - `move:<x>,<y>` moves the mouse to a cell
+//! - `press:<x>,<y>`, `drag:<x>,<y>`, `release:<x>,<y>` press the left
+//!   button, move with it held, and let go, for drag and drop
+//! - `resize:<w>,<h>` reports a terminal resize
+//! - `blur`, `focus` report the terminal window losing or regaining focus
+//! - `wait:<n>` lets `n` ticks pass without input
+//! - `pause:<ms>` lets at least `ms` milliseconds pass without input; since
+//!   the tick rate adapts to activity, this is the step to use when timing
+//!   matters
+//!
+//! Waits sleep for real unless the script is given a `MockClock` with
+//! [`ScriptedEvents::with_clock`], in which case time is fast-forwarded.
+
+use crate::clock::MockClock;
+use crate::error::{MdrError, Result};
+use crate::input::EventSource;
+use crossterm::event::{
+    Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::buffer::Buffer;
+use std::collections::VecDeque;
+use std::io;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+enum Step {
+    Event(Event),
+    Wait(u32),
+    Pause(Duration),
+}
+
+/// An `EventSource` that replays a fixed script and then stops the loop
+pub struct ScriptedEvents {
+    steps: VecDeque<Step>,
+    finished: bool,
+    clock: Option<MockClock>,
+}
+
+impl ScriptedEvents {
+    fn pass_time(&self, duration: Duration) {
+        match &self.clock {
+            Some(clock) => clock.advance(duration),
+            None => thread::sleep(duration),
+        }
+    }
+
+    /// Advance `clock` instead of sleeping during waits. The app under
+    /// test must share the same clock.
+    pub fn with_clock(mut self, clock: MockClock) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+}
+
+impl FromStr for ScriptedEvents {
+    type Err = MdrError;
+
+    fn from_str(script: &str) -> Result<Self> {
+        let mut steps = VecDeque::new();
+
+        for raw in script.split(';') {
+            let step = raw.trim();
+            if step.is_empty() {
+                continue;
+            }
+
+            let (name, arg) = match step.split_once(':') {
+                Some((name, arg)) => (name, Some(arg)),
+                None => (step, None),
+            };
+
+            match (name, arg) {
+                ("type", Some(text)) => {
+                    for c in text.chars() {
+                        steps.push_back(Step::Event(key(KeyCode::Char(c))));
+                    }
+                },
+                ("key", Some(text)) if text.chars().count() == 1 => {
+                    let c = text.chars().next().unwrap();
+                    steps.push_back(Step::Event(key(KeyCode::Char(c))));
+                },
+                ("key", Some(text)) if chord(text).is_some() => {
+                    let (modifiers, c) = chord(text).unwrap();
+                    steps.push_back(Step::Event(Event::Key(KeyEvent::new(KeyCode::Char(c), modifiers))));
+                },
+                ("repeat" | "keyup", Some(text)) if text.chars().count() == 1 => {
+                    let c = text.chars().next().unwrap();
+                    let mut event = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+                    event.kind = if name == "repeat" { KeyEventKind::Repeat } else { KeyEventKind::Release };
+                    steps.push_back(Step::Event(Event::Key(event)));
+                },
+                ("click", Some(pos)) => {
+                    let (x, y) = parse_pair(step, pos)?;
+                    steps.push_back(Step::Event(mouse(MouseEventKind::Down(MouseButton::Left), x, y)));
+                    steps.push_back(Step::Event(mouse(MouseEventKind::Up(MouseButton::Left), x, y)));
+                },
+                ("rclick", Some(pos)) => {
+                    let (x, y) = parse_pair(step, pos)?;
+                    steps.push_back(Step::Event(mouse(MouseEventKind::Down(MouseButton::Right), x, y)));
+                    steps.push_back(Step::Event(mouse(MouseEventKind::Up(MouseButton::Right), x, y)));
+                },
+                ("press", Some(pos)) => {
+                    let (x, y) = parse_pair(step, pos)?;
+                    steps.push_back(Step::Event(mouse(MouseEventKind::Down(MouseButton::Left), x, y)));
+                },
+                ("drag", Some(pos)) => {
+                    let (x, y) = parse_pair(step, pos)?;
+                    steps.push_back(Step::Event(mouse(MouseEventKind::Drag(MouseButton::Left), x, y)));
+                },
+                ("release", Some(pos)) => {
+                    let (x, y) = parse_pair(step, pos)?;
+                    steps.push_back(Step::Event(mouse(MouseEventKind::Up(MouseButton::Left), x, y)));
+                },
+                ("move", Some(pos)) => {
+                    let (x, y) = parse_pair(step, pos)?;
+                    steps.push_back(Step::Event(mouse(MouseEventKind::Moved, x, y)));
+                },
+                ("resize", Some(size)) => {
+                    let (w, h) = parse_pair(step, size)?;
+                    steps.push_back(Step::Event(Event::Resize(w, h)));
+                },
+                ("wait", Some(ticks)) => {
+                    let ticks = ticks
+                        .parse()
+                        .map_err(|_| invalid(format!("invalid tick count in '{}'", step)))?;
+                    steps.push_back(Step::Wait(ticks));
+                },
+                ("pause", Some(ms)) => {
+                    let ms = ms
+                        .parse()
+                        .map_err(|_| invalid(format!("invalid duration in '{}'", step)))?;
+                    steps.push_back(Step::Pause(Duration::from_millis(ms)));
+                },
+                ("blur", None) => steps.push_back(Step::Event(Event::FocusLost)),
+                ("focus", None) => steps.push_back(Step::Event(Event::FocusGained)),
+                (name, None) => {
+                    let code = named_key(name)
+                        .ok_or_else(|| invalid(format!("unknown script step '{}'", step)))?;
+                    steps.push_back(Step::Event(key(code)));
+                },
+                _ => return Err(invalid(format!("unknown script step '{}'", step))),
+            }
+        }
+
+        Ok(Self { steps, finished: false, clock: None })
+    }
+}
+
+impl EventSource for ScriptedEvents {
+    fn poll(&mut self, timeout: Duration) -> io::Result<bool> {
+        match self.steps.front_mut() {
+            Some(Step::Event(_)) => Ok(true),
+            Some(Step::Wait(ticks)) => {
+                // Zero-timeout polls only drain queued input; they must not
+                // consume the wait, or it would pass without a tick
+                if !timeout.is_zero() {
+                    *ticks = ticks.saturating_sub(1);
+                    if *ticks == 0 {
+                        self.steps.pop_front();
+                    }
+                    self.pass_time(timeout);
+                }
+                Ok(false)
+            },
+            Some(Step::Pause(remaining)) => {
+                if !timeout.is_zero() {
+                    let step = timeout.min(*remaining);
+                    *remaining -= step;
+                    if remaining.is_zero() {
+                        self.steps.pop_front();
+                    }
+                    self.pass_time(step);
+                }
+                Ok(false)
+            },
+            None => {
+                // Stop only once a frame has been drawn after the last event,
+                // so clicks are processed by the renderer before the loop ends
+                if !timeout.is_zero() {
+                    self.finished = true;
+                }
+                Ok(false)
+            },
+        }
+    }
+
+    fn read(&mut self) -> io::Result<Event> {
+        match self.steps.pop_front() {
+            Some(Step::Event(event)) => Ok(event),
+            _ => Err(io::Error::new(io::ErrorKind::UnexpectedEof, "no scripted event ready")),
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.finished
+    }
+}
+
+/// Find the first cell where `text` appears in a rendered buffer
+pub fn find_text(buffer: &Buffer, text: &str) -> Option<(u16, u16)> {
+    let area = buffer.area;
+    let needle: Vec<String> = text.chars().map(|c| c.to_string()).collect();
+
+    for y in area.y..area.y + area.height {
+        for x in area.x..area.x + area.width {
+            let fits = needle.iter().enumerate().all(|(i, symbol)| {
+                let cx = x + i as u16;
+                cx < area.x + area.width && buffer.get(cx, y).symbol == *symbol
+            });
+            if fits {
+                return Some((x, y));
+            }
+        }
+    }
+    None
+}
+
+fn key(code: KeyCode) -> Event {
+    Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+// `ctrl+c` or `alt+2`
+fn chord(text: &str) -> Option<(KeyModifiers, char)> {
+    let (modifier, key) = text.split_once('+')?;
+    let modifiers = match modifier {
+        "ctrl" => KeyModifiers::CONTROL,
+        "alt" => KeyModifiers::ALT,
+        _ => return None,
+    };
+    let mut chars = key.chars();
+    let c = chars.next()?;
+    chars.next().is_none().then_some((modifiers, c))
+}
+
+fn mouse(kind: MouseEventKind, column: u16, row: u16) -> Event {
+    Event::Mouse(MouseEvent { kind, column, row, modifiers: KeyModifiers::NONE })
+}
+
+fn named_key(name: &str) -> Option<KeyCode> {
+    Some(match name {
+        "enter" => KeyCode::Enter,
+        "esc" => KeyCode::Esc,
+        "space" => KeyCode::Char(' '),
+        "backspace" => KeyCode::Backspace,
+        "delete" => KeyCode::Delete,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "f12" => KeyCode::F(12),
+        _ => return None,
+    })
+}
+
+fn invalid(message: String) -> MdrError {
+    MdrError::Config(message)
+}
+
+fn parse_pair(step: &str, pair: &str) -> Result<(u16, u16)> {
+    let (a, b) = pair
+        .split_once(',')
+        .ok_or_else(|| invalid(format!("expected two comma-separated numbers in '{}'", step)))?;
+    let parse = |v: &str| {
+        v.trim()
+            .parse::<u16>()
+            .map_err(|_| invalid(format!("invalid number in '{}'", step)))
+    };
+    Ok((parse(a)?, parse(b)?))
+}
@@ -6,27 +6,54 @@ pub enum Palette {
     True,
     X256,
     Ansi,
+    /// Colours from a content pack theme
+    Custom { fg: Color, bg: Color },
+    /// The terminal's own black and white, for e-ink and mono displays
+    Mono,
 }
 
 pub fn detect() -> Palette {
+    if is_mono_term(&env::var("TERM").unwrap_or_default()) {
+        return Palette::Mono;
+    }
     let colorterm = env::var("COLORTERM").unwrap_or_default().to_lowercase();
     if colorterm.contains("truecolor") {
         return Palette::True;
     }
     let term = env::var("TERM").unwrap_or_default();
     if term.contains("256") {
-        return Palette::X256;
+        Palette::X256
     } else {
-        return Palette::Ansi;
+        Palette::Ansi
     }
 }
 
+/// Whether a `TERM` names a display without colour, e.g. `vt100-mono`,
+/// `xterm-m` or an e-ink reader's terminal
+pub fn is_mono_term(term: &str) -> bool {
+    let term = term.to_lowercase();
+    term.ends_with("-m") || term.ends_with("-mono") || term.contains("eink") || term.contains("e-ink")
+}
+
 impl Palette {
+    pub fn name(self) -> &'static str {
+        match self {
+            Palette::True => "truecolor",
+            Palette::X256 => "256-color",
+            Palette::Ansi => "ANSI 16-color",
+            Palette::Custom { .. } => "custom theme",
+            Palette::Mono => "monochrome",
+        }
+    }
+
     pub fn bg_style(self) -> Style {
         let navy = match self {
             Palette::True => Color::Rgb(18, 29, 56),
             Palette::X256 => Color::Indexed(17),
             Palette::Ansi => Color::Blue,
+            Palette::Custom { bg, .. } => bg,
+            // No fill at all: e-ink redraws whatever is painted
+            Palette::Mono => return Style::default(),
         };
         Style::default().bg(navy)
     }
@@ -36,7 +63,33 @@ impl Palette {
             Palette::True => Color::Rgb(88, 122, 148),
             Palette::X256 => Color::Indexed(66),
             Palette::Ansi => Color::Cyan,
+            Palette::Custom { fg, .. } => fg,
+            Palette::Mono => return Style::default(),
         };
         Style::default().fg(fg_color)
     }
+
+    /// A colour from faint (0.0) to bright (1.0) in this palette's hue,
+    /// for shading: as smooth as the colour depth allows
+    pub fn ramp(self, level: f32) -> Color {
+        let level = level.clamp(0.0, 1.0);
+        let step = |steps: usize| ((level * steps as f32) as usize).min(steps - 1);
+        match self {
+            Palette::True => blend((38, 58, 92), (176, 208, 230), level),
+            Palette::X256 => Color::Indexed([24, 25, 67, 68, 110, 152][step(6)]),
+            Palette::Ansi => [Color::Blue, Color::Cyan, Color::LightCyan, Color::White][step(4)],
+            // A theme's colours blend if they are true colour; otherwise
+            // everything is drawn in its foreground
+            Palette::Custom { fg: Color::Rgb(fr, fg, fb), bg: Color::Rgb(br, bg, bb) } => {
+                blend((br, bg, bb), (fr, fg, fb), 0.35 + level * 0.65)
+            },
+            Palette::Custom { fg, .. } => fg,
+            Palette::Mono => Color::Reset,
+        }
+    }
+}
+
+fn blend(from: (u8, u8, u8), to: (u8, u8, u8), amount: f32) -> Color {
+    let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * amount).round() as u8;
+    Color::Rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
 }
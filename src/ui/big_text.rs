@@ -0,0 +1,65 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+
+/// Rows in every big glyph
+pub const GLYPH_HEIGHT: u16 = 5;
+
+// 3x5 block glyphs; anything without a glyph renders as a blank cell
+fn glyph(c: char) -> [&'static str; 5] {
+    match c {
+        '0' => ["███", "█ █", "█ █", "█ █", "███"],
+        '1' => [" █ ", "██ ", " █ ", " █ ", "███"],
+        '2' => ["███", "  █", "███", "█  ", "███"],
+        '3' => ["███", "  █", "███", "  █", "███"],
+        '4' => ["█ █", "█ █", "███", "  █", "  █"],
+        '5' => ["███", "█  ", "███", "  █", "███"],
+        '6' => ["███", "█  ", "███", "█ █", "███"],
+        '7' => ["███", "  █", "  █", "  █", "  █"],
+        '8' => ["███", "█ █", "███", "█ █", "███"],
+        '9' => ["███", "█ █", "███", "  █", "███"],
+        ':' => ["   ", " █ ", "   ", " █ ", "   "],
+        '%' => ["█ █", "  █", " █ ", "█  ", "█ █"],
+        '-' => ["   ", "   ", "███", "   ", "   "],
+        _ => ["   ", "   ", "   ", "   ", "   "],
+    }
+}
+
+/// Render `text` as rows of block glyphs separated by one blank column
+pub fn big_text_lines(text: &str) -> Vec<String> {
+    (0..GLYPH_HEIGHT as usize)
+        .map(|row| {
+            text.chars()
+                .map(|c| glyph(c)[row])
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect()
+}
+
+/// Width in cells of `text` once rendered big
+pub fn big_text_width(text: &str) -> u16 {
+    let glyphs = text.chars().count() as u16;
+    (glyphs * 4).saturating_sub(1)
+}
+
+/// Draw `text` in big glyphs centred in `area`, falling back to plain
+/// text when the area is too small for the glyphs
+pub fn draw_big_text<B: Backend>(frame: &mut Frame<B>, area: Rect, text: &str, style: Style) {
+    let lines: Vec<Spans> = if area.height >= GLYPH_HEIGHT && area.width >= big_text_width(text) {
+        big_text_lines(text)
+            .into_iter()
+            .map(|line| Spans::from(Span::styled(line, style)))
+            .collect()
+    } else {
+        vec![Spans::from(Span::styled(text.to_string(), style))]
+    };
+
+    let para = Paragraph::new(lines).alignment(Alignment::Center);
+    frame.render_widget(para, area);
+}
@@ -0,0 +1,88 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+use crossterm::event::KeyCode;
+
+use crate::app::App;
+use crate::app::{AppState, TOAST_DURATION};
+use std::time::Instant;
+use crate::screen::Screen;
+use super::big_text::{self, GLYPH_HEIGHT};
+
+/// A break earned by filling a bin. Nothing but quitting until it is over.
+pub struct BreakScreen;
+
+impl Screen for BreakScreen {
+    fn handle_key(&self, app: &mut App, key: KeyCode) {
+        if key == KeyCode::Char('q') {
+            app.running = false;
+        }
+    }
+
+    fn tick(&self, app: &mut App, _now: Instant) {
+        if app.break_remaining().is_zero() {
+            app.break_until = None;
+            app.go_to(AppState::Main);
+            app.show_toast("Your break is over. Please resume refinement.", Some(TOAST_DURATION));
+        }
+    }
+
+    fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, app: &App) {
+        draw_break_screen(frame, area, app);
+    }
+}
+
+/// Draws the break screen, counting down to when refinement may go on
+pub fn draw_break_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let is_small_window = area.height < 20;
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(if is_small_window { 1 } else { 2 })
+        .constraints([
+            Constraint::Length(3),   // Title
+            Constraint::Length(1),   // Divider
+            Constraint::Length(if is_small_window { 0 } else { 1 }),   // Space
+            Constraint::Length(2),   // Explanation
+            Constraint::Length(if is_small_window { 1 } else { GLYPH_HEIGHT + 1 }),   // Countdown
+            Constraint::Length(1),   // Space
+            Constraint::Length(1),   // Instructions
+            Constraint::Min(0),      // Remaining space
+        ])
+        .split(area);
+
+    let title = Paragraph::new("MANDATED WELLNESS BREAK")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    frame.render_widget(title, layout[0]);
+
+    let divider: String = "━".repeat(layout[1].width as usize);
+    frame.render_widget(Paragraph::new(divider).style(app.palette.fg_style()), layout[1]);
+
+    let explanation = vec![
+        Spans::from(Span::styled("A bin is full. Please step away from your terminal.", app.palette.fg_style())),
+        Spans::from(Span::styled("Refinement resumes when your break is over.", app.palette.fg_style())),
+    ];
+    frame.render_widget(Paragraph::new(explanation).alignment(Alignment::Center), layout[3]);
+
+    // Rounded up, so the last second still shows 00:01
+    let left = app.break_remaining();
+    let secs = left.as_secs() + u64::from(left.subsec_nanos() > 0);
+    let countdown = format!("{:02}:{:02}", secs / 60, secs % 60);
+    big_text::draw_big_text(
+        frame,
+        layout[4],
+        &countdown,
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+    );
+
+    let instructions = Paragraph::new("[q] Quit")
+        .alignment(Alignment::Center)
+        .style(app.palette.fg_style());
+    frame.render_widget(instructions, layout[6]);
+}
@@ -0,0 +1,95 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Span, Spans},
+    widgets::{Paragraph, Wrap},
+};
+use crossterm::event::KeyCode;
+
+use crate::app::App;
+use crate::app::AppState;
+use crate::screen::Screen;
+use crate::updates::Version;
+
+/// The release notes, scrolled with the arrows
+pub struct ChangelogScreen;
+
+impl Screen for ChangelogScreen {
+    fn handle_key(&self, app: &mut App, key: KeyCode) {
+        match key {
+            KeyCode::Up | KeyCode::Char('k') => app.changelog_scroll = app.changelog_scroll.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => app.changelog_scroll = app.changelog_scroll.saturating_add(1),
+            KeyCode::PageUp => app.changelog_scroll = app.changelog_scroll.saturating_sub(10),
+            KeyCode::PageDown => app.changelog_scroll = app.changelog_scroll.saturating_add(10),
+            KeyCode::Home => app.changelog_scroll = 0,
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Enter => {
+                app.go_to(AppState::Main);
+            },
+            _ => {}
+        }
+    }
+
+    fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, app: &App) {
+        draw_changelog_screen(frame, area, app);
+    }
+}
+
+/// Draws the release notes opened with `:changelog`
+pub fn draw_changelog_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let is_small_window = area.height < 15;
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(if is_small_window { 1 } else { 2 })
+        .constraints([
+            Constraint::Length(if is_small_window { 1 } else { 3 }),   // Title
+            Constraint::Length(1),   // Divider
+            Constraint::Min(1),      // Notes
+            Constraint::Length(1),   // Instructions
+        ])
+        .split(area);
+
+    let title = Paragraph::new("RELEASE NOTES")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    frame.render_widget(title, layout[0]);
+
+    let divider: String = "━".repeat(layout[1].width as usize);
+    frame.render_widget(Paragraph::new(divider).style(app.palette.fg_style()), layout[1]);
+
+    let current = Version::current();
+    let mut lines = Vec::new();
+    for release in &app.station.changelog {
+        let mut heading = vec![Span::styled(
+            release.version.to_string(),
+            app.palette.fg_style().add_modifier(Modifier::BOLD),
+        )];
+        if let Some(date) = &release.date {
+            heading.push(Span::raw(format!("  {}", date)));
+        }
+        if release.version == current {
+            heading.push(Span::styled("  (this terminal)", Style::default().fg(Color::Cyan)));
+        } else if release.version > current {
+            heading.push(Span::styled("  (new)", Style::default().fg(Color::Yellow)));
+        }
+
+        lines.push(Spans::from(heading));
+        lines.extend(release.notes.iter().map(|note| Spans::from(Span::styled(note.as_str(), app.palette.fg_style()))));
+        lines.push(Spans::from(""));
+    }
+    if lines.is_empty() {
+        lines.push(Spans::from("No release notes have been provided."));
+    }
+
+    let notes = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((app.changelog_scroll, 0));
+    frame.render_widget(notes, layout[2]);
+
+    let instructions = Paragraph::new("[↑/↓] Scroll   [ESC] Return to work")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Yellow));
+    frame.render_widget(instructions, layout[3]);
+}
@@ -0,0 +1,49 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::App;
+use crate::cheats::HELP;
+
+/// The cheat console, boxed in the middle of the main screen
+pub fn draw_cheat_console<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let Some(line) = &app.cheat_console else { return };
+
+    let mut lines: Vec<Spans> = HELP
+        .iter()
+        .map(|(command, what)| {
+            Spans::from(vec![
+                Span::styled(format!(" {:<14}", command), app.palette.fg_style().add_modifier(Modifier::BOLD)),
+                Span::styled(*what, app.palette.fg_style()),
+            ])
+        })
+        .collect();
+    lines.push(Spans::from(""));
+    lines.push(Spans::from(vec![
+        Span::styled(format!(" > {}", line), app.palette.fg_style()),
+        Span::styled(" ", Style::default().bg(Color::White)),
+    ]));
+
+    let width = 44.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let console_area = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let console = Paragraph::new(lines).style(app.palette.bg_style()).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" CHEATS · Esc to close ")
+            .border_style(Style::default().fg(Color::Magenta)),
+    );
+    frame.render_widget(Clear, console_area);
+    frame.render_widget(console, console_area);
+}
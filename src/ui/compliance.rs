@@ -0,0 +1,63 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::compliance::TIME_LIMIT;
+use super::widgets::{ChartStyle, HoldGauge};
+
+/// The compliance check, boxed in the middle of the main screen, with the
+/// time left running down along its foot
+pub fn draw_compliance<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let Some(check) = &app.compliance else { return };
+    let fg = app.palette.fg_style();
+    let now = app.clock.now();
+
+    // What is typed turns red as soon as it strays from the phrase
+    let typed = if check.on_track() { fg } else { Style::default().fg(Color::Red) };
+    let lines = vec![
+        Spans::from(Span::styled(" Retype the handbook phrase:", fg)),
+        Spans::from(""),
+        Spans::from(Span::styled(format!(" {}", check.phrase), fg.add_modifier(Modifier::BOLD))),
+        Spans::from(vec![
+            Span::styled(format!(" {}", check.typed), typed),
+            Span::styled("_", fg.add_modifier(Modifier::SLOW_BLINK)),
+        ]),
+        Spans::from(""),
+        Spans::from(Span::styled(
+            format!(" Enter to submit, Esc to refuse · {}s", check.remaining(now).as_secs_f32().ceil()),
+            fg.add_modifier(Modifier::DIM),
+        )),
+        // Room for the gauge
+        Spans::from(""),
+    ];
+
+    let width = 50.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let check_area = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let widget = Paragraph::new(lines).style(app.palette.bg_style()).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" COMPLIANCE CHECK ")
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(Clear, check_area);
+    frame.render_widget(widget, check_area);
+
+    if check_area.height > 3 && check_area.width > 4 {
+        let left = check.remaining(now).as_secs_f32() / TIME_LIMIT.as_secs_f32();
+        let gauge_area = Rect::new(check_area.x + 2, check_area.y + check_area.height - 2, check_area.width - 4, 1);
+        frame.render_widget(HoldGauge::new(left, ChartStyle::for_app(app, Color::Yellow)), gauge_area);
+    }
+}
@@ -0,0 +1,34 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::App;
+
+/// The right-click menu, next to where it was opened
+pub fn draw_context_menu<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let Some(menu) = &app.context_menu else { return };
+    let fg = app.palette.fg_style();
+    let width = menu.items.iter().map(|item| item.label().chars().count()).max().unwrap_or(0);
+
+    let lines: Vec<Spans> = menu
+        .items
+        .iter()
+        .enumerate()
+        .map(|(idx, item)| {
+            let style = if idx == menu.selected { fg.add_modifier(Modifier::REVERSED) } else { fg };
+            Spans::from(Span::styled(format!(" {:<width$} ", item.label(), width = width), style))
+        })
+        .collect();
+
+    let menu_area = menu.area(area);
+    let widget = Paragraph::new(lines)
+        .style(app.palette.bg_style())
+        .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(Color::Yellow)));
+    frame.render_widget(Clear, menu_area);
+    frame.render_widget(widget, menu_area);
+}
@@ -0,0 +1,56 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::App;
+use super::LayoutProfile;
+
+/// Draws the frame timing overlay in the top-left corner
+pub fn draw_debug_hud<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let stats = &app.station.frame_stats;
+
+    let mut lines = vec![
+        format!("fps     {:>7.1}", stats.fps),
+        format!("render  {:>5.1}ms", stats.last_render.as_secs_f32() * 1000.0),
+        format!("write   {:>5.1}ms", stats.last_write.as_secs_f32() * 1000.0),
+        format!("tick    {:>5}ms", app.tick_rate().as_millis()),
+        format!("queue   {:>7}", stats.queue_depth),
+        format!("allocs  {:>7}", stats.allocations),
+        format!("merged  {:>7}", stats.moves_coalesced),
+        format!("bounced {:>7}", stats.clicks_debounced),
+        format!("layout  {:>7}", LayoutProfile::for_area(area).name()),
+        format!("quality {:>7}", stats.quality.name()),
+        format!("stuck   {:>7}", stats.stuck_frames),
+        format!("stride  {:>7}", stats.stride()),
+        format!("diff  {:>6}/{:<6}", stats.cells_changed, stats.cells_drawn),
+        format!("grid    {:>7}", stats.grid_changed),
+        format!("sent    {:>7}", stats.cells_sent),
+    ];
+    for usage in app.budget_usage() {
+        lines.push(format!("{:<7}{:>5}/{:<6}", usage.name, usage.used, usage.cap));
+    }
+    if let Some(injector) = &app.station.injector {
+        lines.push(format!("inject  {:>3}/{:<3}", injector.applied, injector.rejected));
+    }
+
+    let hud_width = 21.min(area.width);
+    let hud_height = (lines.len() as u16 + 2).min(area.height);
+    let hud_area = Rect::new(area.x, area.y, hud_width, hud_height);
+
+    let text: Vec<Spans> = lines
+        .into_iter()
+        .map(|line| Spans::from(Span::raw(line)))
+        .collect();
+
+    let hud = Paragraph::new(text)
+        .style(Style::default().fg(Color::Green).bg(Color::Black))
+        .block(Block::default().borders(Borders::ALL).title("HUD"));
+
+    frame.render_widget(Clear, hud_area);
+    frame.render_widget(hud, hud_area);
+}
@@ -0,0 +1,114 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Span, Spans},
+    widgets::{Paragraph, Wrap},
+};
+use crossterm::event::KeyCode;
+
+use crate::app::{App, ErrorAction};
+use crate::focus::Routed;
+use crate::screen::Screen;
+use super::widgets::button_row;
+
+/// A failure that can be retried, ignored or quit over
+pub struct ErrorScreen;
+
+impl Screen for ErrorScreen {
+    fn handle_key(&self, app: &mut App, key: KeyCode) {
+        // The arrows move along the buttons as Tab does
+        let key = match key {
+            KeyCode::Left => KeyCode::BackTab,
+            KeyCode::Right => KeyCode::Tab,
+            key => key,
+        };
+        let action = match app.error_buttons.route(key) {
+            Routed::To(action, KeyCode::Enter) => action,
+            Routed::Moved(_) => return,
+            _ => match key {
+                KeyCode::Char('r') | KeyCode::Char('R') => ErrorAction::Retry,
+                KeyCode::Char('i') | KeyCode::Char('I') | KeyCode::Enter => ErrorAction::Ignore,
+                KeyCode::Char('q') | KeyCode::Char('Q') | KeyCode::Esc => ErrorAction::Quit,
+                _ => return,
+            },
+        };
+        app.press_error_button(action);
+    }
+
+    fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, app: &App) {
+        draw_error_screen(frame, area, app);
+    }
+}
+
+/// Draws the screen shown for recoverable failures
+pub fn draw_error_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    // Check if we have a small window
+    let is_small_window = area.height < 15;
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(if is_small_window { 1 } else { 2 })
+        .constraints([
+            Constraint::Length(3),   // Title
+            Constraint::Length(1),   // Divider
+            Constraint::Length(if is_small_window { 1 } else { 2 }),   // Space
+            Constraint::Length(4),   // Headline and reassurance
+            Constraint::Length(3),   // Technical detail
+            Constraint::Length(1),   // Space
+            Constraint::Length(2),   // Instructions
+            Constraint::Min(0),      // Remaining space
+        ])
+        .split(area);
+
+    let title = Paragraph::new("NOTICE OF INTERRUPTION")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    frame.render_widget(title, layout[0]);
+
+    let divider: String = "━".repeat(layout[1].width as usize);
+    frame.render_widget(Paragraph::new(divider).style(app.palette.fg_style()), layout[1]);
+
+    let (headline, reassurance, detail) = match &app.error {
+        Some(err) => (err.kind.headline(), err.kind.reassurance(), err.detail.as_str()),
+        None => ("An unexplained event has occurred.", "Please continue as if it has not.", ""),
+    };
+
+    let message = vec![
+        Spans::from(Span::styled(
+            headline,
+            app.palette.fg_style().add_modifier(Modifier::BOLD)
+        )),
+        Spans::from(""),
+        Spans::from(Span::styled(reassurance, app.palette.fg_style())),
+    ];
+    let message_para = Paragraph::new(message)
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true });
+    frame.render_widget(message_para, layout[3]);
+
+    // Raw detail for whoever has to file the incident report
+    if !detail.is_empty() {
+        let detail_para = Paragraph::new(format!("Incident detail: {}", detail))
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::DarkGray))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(detail_para, layout[4]);
+    }
+
+    let instructions = vec![
+        button_row(
+            &app.error_buttons,
+            |action: ErrorAction| action.label().to_string(),
+            Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+        ),
+        Spans::from(Span::styled(
+            "Management has been notified.",
+            app.palette.fg_style()
+        )),
+    ];
+    let instructions_para = Paragraph::new(instructions)
+        .alignment(Alignment::Center);
+    frame.render_widget(instructions_para, layout[6]);
+}
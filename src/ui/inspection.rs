@@ -0,0 +1,101 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::App;
+use crate::temper::{Provenance, Temper};
+
+/// Most deposits listed, newest first
+pub const INSPECTION_ROWS: usize = 20;
+
+/// A bin's recent deposits, one per row, boxed in the middle of the main
+/// screen
+pub fn draw_inspection<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let Some(bin) = app.inspecting else { return };
+    let Some(container) = app.containers.get(bin) else { return };
+    let fg = app.palette.fg_style();
+
+    let width = 64.min(area.width);
+    // Room left on each row for the cells, past the fixed columns
+    let cells_width = (width as usize).saturating_sub(30);
+    let now = app.clock.now();
+
+    let mut lines = vec![Spans::from(Span::styled(
+        format!(" {:>3}  {:>5}  {:<7}  {:<8}  {}", "#", "VALUE", "TEMPER", "WHEN", "CELLS"),
+        fg.add_modifier(Modifier::BOLD),
+    ))];
+    if container.deposits.is_empty() {
+        lines.push(Spans::from(Span::styled(" Nothing deposited this session", fg)));
+    }
+    let total = container.deposits.len();
+    for (idx, deposit) in container.deposits.iter().rev().take(INSPECTION_ROWS).enumerate() {
+        let cells = if deposit.origins.is_empty() { "direct".to_string() } else { origins(&deposit.origins) };
+        let cells = if cells.chars().count() > cells_width {
+            format!("{}...", cells.chars().take(cells_width.saturating_sub(3)).collect::<String>())
+        } else {
+            cells
+        };
+        lines.push(Spans::from(Span::styled(
+            format!(
+                " {:>3}  {:>5}  {:<7}  {:<8}  {}",
+                total - idx,
+                format!("+{}", deposit.value),
+                deposit.temper.name(),
+                ago(now.saturating_duration_since(deposit.at).as_secs()),
+                cells
+            ),
+            fg,
+        )));
+    }
+    lines.push(Spans::from(""));
+    lines.push(Spans::from(Span::styled(" Tab for the next bin, Esc to close", fg.add_modifier(Modifier::DIM))));
+
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let box_area = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    let title = format!(" BIN {:02} · INSPECTION · {}% ", bin + 1, container.count);
+    let widget = Paragraph::new(lines).style(app.palette.bg_style()).block(
+        Block::default().borders(Borders::ALL).title(title).border_style(Style::default().fg(Color::Cyan)),
+    );
+    frame.render_widget(Clear, box_area);
+    frame.render_widget(widget, box_area);
+}
+
+// The cells a deposit was refined from, grouped by the temper of their
+// quadrant, e.g. "WO 4,3 5,3 FC 40,2"
+fn origins(origins: &[Provenance]) -> String {
+    let mut groups: Vec<(Option<Temper>, Vec<String>)> = Vec::new();
+    for origin in origins {
+        let cell = format!("{},{}", origin.cell.0, origin.cell.1);
+        match groups.iter_mut().find(|(temper, _)| *temper == origin.temper) {
+            Some((_, cells)) => cells.push(cell),
+            None => groups.push((origin.temper, vec![cell])),
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(temper, cells)| match temper {
+            Some(temper) => format!("{} {}", temper.code(), cells.join(" ")),
+            None => cells.join(" "),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// How long ago, to the second under a minute and the minute after
+fn ago(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{}s ago", secs),
+        60..3_600 => format!("{}m ago", secs / 60),
+        _ => format!("{}h ago", secs / 3_600),
+    }
+}
@@ -0,0 +1,177 @@
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+
+/// Height the bins need
+const BINS_HEIGHT: u16 = 6;
+/// The grid is never squeezed below this, so it always has room to breathe
+const MIN_GRID_HEIGHT: u16 = 5;
+/// A side panel only opens while the grid keeps at least this much width
+const MIN_GRID_WIDTH: u16 = 40;
+
+/// Where each part of the main screen goes, worked out from the window
+/// size and whether a side panel is open
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MainLayout {
+    pub title: Rect,
+    pub title_divider: Rect,
+    /// The number grid, narrowed to make room for the panel
+    pub grid: Rect,
+    /// Right of the grid, or left once mirrored; `None` when closed or the
+    /// window is too narrow
+    pub panel: Option<Rect>,
+    pub bins_divider: Rect,
+    pub bins: Rect,
+    pub footer_divider: Rect,
+    pub footer: Rect,
+}
+
+impl MainLayout {
+    /// Lay out `area`, with a side panel `panel_width` wide if one is open
+    pub fn compute(area: Rect, panel_width: Option<u16>) -> Self {
+        // Small windows drop the margin and the padding around the bins
+        let is_small_window = area.height < 25;
+        let padding = if is_small_window { 0 } else { 1 };
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(if is_small_window { 1 } else { 2 })
+            .constraints([
+                Constraint::Length(3),                 // Title bar
+                Constraint::Length(1),                 // Title divider
+                Constraint::Min(MIN_GRID_HEIGHT),      // Grid and panel
+                Constraint::Length(1),                 // Thick divider
+                Constraint::Length(padding),           // Top padding
+                Constraint::Length(BINS_HEIGHT),       // Bins
+                Constraint::Length(padding),           // Bottom padding
+                Constraint::Length(1),                 // Thin divider
+                Constraint::Length(1),                 // Footer text
+            ])
+            .split(area);
+
+        // The grid reflows into whatever the panel leaves
+        let (grid, panel) = match panel_width {
+            Some(width) if rows[2].width >= MIN_GRID_WIDTH + width => {
+                let columns = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Min(MIN_GRID_WIDTH), Constraint::Length(width)])
+                    .split(rows[2]);
+                (columns[0], Some(columns[1]))
+            },
+            _ => (rows[2], None),
+        };
+
+        MainLayout {
+            title: rows[0],
+            title_divider: rows[1],
+            grid,
+            panel,
+            bins_divider: rows[3],
+            bins: rows[5],
+            footer_divider: rows[7],
+            footer: rows[8],
+        }
+    }
+
+    /// The same layout right to left: the panel moves to the left of the
+    /// grid
+    pub fn mirrored(self) -> Self {
+        let Some(panel) = self.panel else { return self };
+        MainLayout {
+            grid: Rect { x: self.grid.x + panel.width, ..self.grid },
+            panel: Some(Rect { x: self.grid.x, ..panel }),
+            ..self
+        }
+    }
+}
+
+/// How much a piece of the title bar matters when there is not room for
+/// all of it. The least important pieces give way first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    Low,
+    Medium,
+    /// Shortened but never dropped
+    High,
+}
+
+/// The logo is worth less than the refiner's name, more than the rest
+const LOGO_PRIORITY: Priority = Priority::Medium;
+
+/// The title bar's three regions fitted to its width: who is working on
+/// the left, a spacer, then completion and the logo's room on the right
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TitleBar {
+    /// Identity pieces joined with middle dots, padded by a space each side
+    pub left: String,
+    /// Blank columns pushing the right region over
+    pub spacer: u16,
+    pub right: String,
+    /// Whether the logo kept its room at the end of the bar
+    pub logo: bool,
+}
+
+impl TitleBar {
+    /// Fit `identity` and `completion` into `width` columns, leaving
+    /// `logo` columns free at the end. Too little room drops the
+    /// lowest-priority pieces (the rightmost first among equals, the logo
+    /// before identity of the same priority), then shortens the left
+    /// region with an ellipsis and finally the right.
+    pub fn fit(width: u16, identity: &[(&str, Priority)], completion: &str, logo: Option<u16>, ascii: bool) -> Self {
+        let width = width as usize;
+        let ellipsis = if ascii { "..." } else { "\u{2026}" };
+        let mut kept: Vec<bool> = vec![true; identity.len()];
+        let mut logo = logo.map(usize::from);
+
+        let joined = |kept: &[bool]| -> String {
+            identity
+                .iter()
+                .zip(kept)
+                .filter(|&(_, &keep)| keep)
+                .map(|((text, _), _)| *text)
+                .collect::<Vec<_>>()
+                .join(" \u{00b7} ")
+        };
+        let padded = |text: String| if text.is_empty() { text } else { format!(" {} ", text) };
+        let right_width = completion.chars().count();
+
+        while padded(joined(&kept)).chars().count() + right_width + logo.unwrap_or(0) > width {
+            // The least important piece left, rightmost first among equals
+            let piece = (0..identity.len())
+                .rev()
+                .filter(|&idx| kept[idx] && identity[idx].1 < Priority::High)
+                .min_by_key(|&idx| identity[idx].1);
+            match (piece, logo) {
+                (Some(idx), Some(_)) if identity[idx].1 < LOGO_PRIORITY => kept[idx] = false,
+                (_, Some(_)) => logo = None,
+                (Some(idx), None) => kept[idx] = false,
+                (None, None) => break,
+            }
+        }
+
+        // Whatever is left over goes to shortening the two sides
+        let right = truncate(completion, width, ellipsis);
+        let room = width - right.chars().count();
+        let left = joined(&kept);
+        let left = if left.chars().count() + 2 <= room {
+            padded(left)
+        } else if room >= ellipsis.chars().count() + 3 {
+            padded(truncate(&left, room - 2, ellipsis))
+        } else {
+            // Not even one letter and the ellipsis fit
+            String::new()
+        };
+
+        let used = left.chars().count() + right.chars().count() + logo.unwrap_or(0);
+        TitleBar { left, spacer: width.saturating_sub(used) as u16, right, logo: logo.is_some() }
+    }
+}
+
+/// `text` cut to `width` columns, ending in `ellipsis` if anything was cut
+fn truncate(text: &str, width: usize, ellipsis: &str) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let keep = width.saturating_sub(ellipsis.chars().count());
+    let mut cut: String = text.chars().take(keep).collect();
+    cut.extend(ellipsis.chars().take(width - keep));
+    cut
+}
@@ -6,99 +6,216 @@ use ratatui::{
     text::{Span, Spans},
     widgets::Paragraph,
 };
+use crossterm::event::KeyCode;
 
 use crate::app::App;
+use crate::app::{AppState, LOADING_COMPLETE_DELAY, LOADING_STEP};
+use crate::random::Stream;
+use crate::screen::Screen;
+use rand::Rng;
+use std::time::Instant;
+use crate::branding::Logo;
+use crate::startup::Status;
+use super::LayoutProfile;
 
-// Lumon logo ASCII art
-const LUMON_LOGO: &[&str] = &[
-    "                                               ZTMIKMLKOVSRSY   ZSSTZTNIHHKQX                                          ",
-    "                                         WPNPWZVZ       VP  Y   Z  OX       NSYTNOU                                    ",
-    "                                     XRRUW   TTP  KKMIGN NRSY   ZSSO XGDHHI  ZRY  UWQQU                                ",
-    "                                  ZSLD  EDCBCL CCCCFGD FCCGGGGGGGGGDCC CGGECCCD BCCD  CGMT                             ",
-    "                                TTS                                                      NTT                           ",
-    "                              YWQ VRLGGGFC CGGGGGGGFLUEGGGGGGGGGGGGGGGFI DGGGGGGGGC CFGGMN OTT                         ",
-    "                             VT  RT     ZXTT       Z YY               ZW W        TSZZ   TTN MT                        ",
-    "                             T SWY    TMMSZ    TMMTSMMT   TMLSXQKMT  TMFHHHGMT UNINMLLT    TM NU                       ",
-    "                            WS T      M  M     N  MM  M   M   U   M  M       M O   B  M     TH N                       ",
-    "                            U RX      G  G     H  GG  G   G       G  G  CGE  G J      G      H H                       ",
-    "                            U XS      L  L     M      M   L  U U  L  L  BFB  L O  B   L     YL M                       ",
-    "                            XQ MT     S     Z  TLZ   NS   S   M   V  R       S T  L   S    YV TT                       ",
-    "                             TN OUZ   ZYRRRYZ   ZWUNPRZ   ZYYZ ZYYZ  ZRFCCGLSZ ZYYYVVVZ   TR  X                        ",
-    "                              TO XSSW   T OUU      U NT               WP T       TSZ X  TTM PWY                        ",
-    "                               VUQ  QRRXTTS OTU    UN NU             WS PT     TTM PWQSSS MTT                          ",
-    "                                 WSSQ  VROLRX OQQW  TN NT           TP SW   URRP HJJVT  STT                            ",
-    "                                    URRV   XQKC  TSOPPN NTT       TSM PVTONQK  KST  ZTSSZ                              ",
-    "                                       YROQXX    T    LGD CGGGGGGGC HHNX        ZUPPVZ                                 ",
-    "                                            XQMNTXSOOW                 XTONQPLNUZ                                      ",
-    "                                                    ZXRLIHHGGGGGGGHHKOUY                                               ",
-];
+/// Block elements from the least ink to the most
+const SHADES: [char; 4] = ['░', '▒', '▓', '█'];
 
-// Loading messages for the loading screen
-pub const LOADING_MESSAGES: &[&str] = &[
-    "Initializing MDR protocol",
-    "Checking refinement quotas",
-    "Verifying department credentials",
-    "Preparing macrodata bins",
-    "Establishing connection to Lumon mainframe",
-    "Running compliance check",
-    "Validating severance chip",
-    "Please enjoy all amenities equally",
+// The founder, for those who log in as him
+const KIER_PORTRAIT: &[&str] = &[
+    "            _.-~~~~~~-._            ",
+    "         .-'  ________  `-.         ",
+    "        /   .'        `.   \\        ",
+    "       |   /  __    __  \\   |       ",
+    "       |  |  (o_)  (o_)  |  |       ",
+    "       |  |      /\\      |  |       ",
+    "       )  |     (__)     |  (       ",
+    "      (   \\\\  .------.  //   )      ",
+    "       )   \\\\ `------' //   (       ",
+    "      (     `\\\\______//`     )      ",
+    "       `-.    `------'    .-'       ",
+    "      ___|`-.________.-'|___        ",
+    "    /`   |  \\  |  |  /  |   `\\      ",
+    "   |     |   \\ |__| /   |     |     ",
+    "   |     |    \\/  \\/    |     |     ",
+    "        KIER EAGAN, FOUNDER         ",
 ];
 
+/// The progress bar between the login and the main screen, which also
+/// restores a saved session a step at a time
+pub struct LoadingScreen;
+
+impl Screen for LoadingScreen {
+    fn handle_key(&self, app: &mut App, key: KeyCode) {
+        match key {
+            KeyCode::Char('q') => {
+                app.running = false;
+            },
+            KeyCode::Enter => app.skip_loading(),
+            KeyCode::Char('[') => app.step_game_speed(-1),
+            KeyCode::Char(']') => app.step_game_speed(1),
+            KeyCode::Esc if app.toast.is_some() => app.toast = None,
+            _ => {}
+        }
+    }
+
+    fn tick(&self, app: &mut App, now: Instant) {
+        // Hold on a full bar for a moment before entering the main screen
+        if app.progress_percentage >= 100.0 {
+            app.progress_percentage = 100.0;
+            let complete_at = *app.loading_complete_at.get_or_insert(now);
+
+            let waited = now.duration_since(complete_at) >= app.game_time(LOADING_COMPLETE_DELAY);
+            if waited && app.station.startup.is_none() {
+                // A break saved part way through is sat out first
+                app.go_to(if app.break_until.is_some() { AppState::Break } else { AppState::Main });
+                // A board nobody has touched yet is timed from here
+                if app.completion_target() == 0.0 {
+                    app.file_started = Some(now);
+                }
+                let code = app.pending_code.take();
+                match app.pending_race.take() {
+                    Some(target) => app.start_race(code.unwrap_or_else(|| app.share_code()), target),
+                    None => {
+                        if let Some(code) = code {
+                            app.open_shared(code);
+                        }
+                    },
+                }
+            }
+            return;
+        }
+
+        let step = app.game_time(LOADING_STEP);
+        let due = *app.next_loading_step.get_or_insert(now + step);
+        if now >= due && !app.restore_steps.is_empty() {
+            app.next_loading_step = Some(now + step);
+            app.run_restore_step();
+        } else if now >= due {
+            app.next_loading_step = Some(now + step);
+
+            let progress_increment = app.random.stream(Stream::Loading).random_range(0.0..13.0);
+
+            let new_progress = app.progress_percentage + progress_increment;
+            if new_progress > 100.0 {
+                app.progress_percentage = 100.0;
+            } else {
+                app.progress_percentage = new_progress;
+            }
+        }
+    }
+
+    fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, app: &App) {
+        draw_loading_screen(frame, area, app);
+    }
+}
+
 /// Renders the loading screen with logo and progress indicator
 pub fn draw_loading_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
-    // Check if the area is large enough for the logo
-    let logo_w = LUMON_LOGO[0].len() as u16;
-    let logo_h = LUMON_LOGO.len() as u16;
-    
-    // Determine if we have enough space for the full logo
-    let has_space_for_logo = area.width >= logo_w && area.height >= logo_h + 5; // +5 for progress bar
-    
-    if has_space_for_logo {
-        // Render full logo
-        let logo_spans: Vec<Spans> = LUMON_LOGO
-            .iter()
-            .map(|&line| Spans::from(Span::styled(line, app.palette.fg_style())))
-            .collect();
+    if app.founder_visit {
+        draw_founder_portrait(frame, area, app);
+        return;
+    }
 
-        let x = area.x + (area.width.saturating_sub(logo_w)) / 2;
-        let y = area.y + (area.height.saturating_sub(logo_h + 5)) / 2; // Center vertically accounting for progress bar
-        let rect = Rect::new(x, y, logo_w.min(area.width), logo_h.min(area.height - 5));
+    // The largest logo that leaves room for the progress bar below it
+    let room = Rect::new(area.x, area.y, area.width, area.height.saturating_sub(5));
+    let logo = Logo::pick(app.station.config.logo, LayoutProfile::for_area(area), room);
+    let (logo_w, logo_h) = logo.map_or((0, 0), |logo| (logo.width(), logo.height()));
 
+    let y = area.y + area.height.saturating_sub(logo_h + 5) / 2; // Center vertically accounting for progress bar
+    if let Some(logo) = logo {
+        // The globe is shaded where block elements render; the rest is plain
+        let lines = logo.lines(app.station.ascii_only);
+        let logo_spans: Vec<Spans> = if logo == Logo::Globe && !app.station.ascii_only {
+            lines.iter().map(|&line| shade_logo_line(line, app)).collect()
+        } else {
+            lines
+                .iter()
+                .map(|&line| Spans::from(Span::styled(line, app.palette.fg_style())))
+                .collect()
+        };
+
+        let x = area.x + area.width.saturating_sub(logo_w) / 2;
+        let rect = Rect::new(x, y, logo_w, logo_h);
         let logo_para = Paragraph::new(logo_spans).style(app.palette.bg_style());
         frame.render_widget(logo_para, rect);
-        
-        // Place progress indicator below logo
-        let progress_y = y + logo_h + 2;
-        if progress_y < area.y + area.height {
-            draw_progress_indicator(frame, area, app, progress_y);
-        }
-    } else {
-        // For small windows, show only text and progress bar
-        let text = "LUMON INDUSTRIES";
-        let text_spans = Spans::from(Span::styled(text, app.palette.fg_style()));
-        
-        let text_y = area.y + area.height / 3;
-        let text_rect = Rect::new(area.x, text_y, area.width, 1);
-        
-        let text_para = Paragraph::new(text_spans)
-            .alignment(ratatui::layout::Alignment::Center)
-            .style(app.palette.fg_style());
-        
-        frame.render_widget(text_para, text_rect);
-        
-        // Place progress indicator in the middle
-        let progress_y = text_y + 2;
-        if progress_y < area.y + area.height {
-            draw_progress_indicator(frame, area, app, progress_y);
+    }
+
+    // Place progress indicator below logo
+    let progress_y = y + logo_h + 2;
+    if progress_y < area.y + area.height {
+        draw_progress_indicator(frame, area, app, progress_y);
+    }
+}
+
+/// Turn one line of the letter art into shaded blocks, each coloured from
+/// the palette's ramp by how much ink its letter stands for
+fn shade_logo_line(line: &str, app: &App) -> Spans<'static> {
+    let mut spans: Vec<Span> = Vec::new();
+    let mut run = String::new();
+    let mut run_style: Option<Style> = None;
+
+    for letter in line.chars() {
+        let (glyph, style) = match letter {
+            'A'..='Z' => {
+                let ink = 1.0 - (letter as u8 - b'A') as f32 / 25.0;
+                let shade = SHADES[((ink * SHADES.len() as f32) as usize).min(SHADES.len() - 1)];
+                (shade, Style::default().fg(app.palette.ramp(ink)))
+            },
+            _ => (' ', Style::default()),
+        };
+        if run_style != Some(style) {
+            if let Some(style) = run_style {
+                spans.push(Span::styled(std::mem::take(&mut run), style));
+            }
+            run_style = Some(style);
         }
+        run.push(glyph);
+    }
+    if let Some(style) = run_style {
+        spans.push(Span::styled(run, style));
+    }
+    Spans::from(spans)
+}
+
+// Kier's portrait appears a line at a time as the bar fills, each new line
+// arriving dim before it settles
+fn draw_founder_portrait<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let portrait_w = KIER_PORTRAIT[0].chars().count() as u16;
+    let portrait_h = KIER_PORTRAIT.len() as u16;
+
+    let shown = ((app.progress_percentage / 100.0 * portrait_h as f32).ceil() as usize).min(KIER_PORTRAIT.len());
+    let lines: Vec<Spans> = KIER_PORTRAIT
+        .iter()
+        .enumerate()
+        .map(|(idx, &line)| {
+            if idx >= shown {
+                Spans::from("")
+            } else if idx + 1 == shown && shown < KIER_PORTRAIT.len() {
+                Spans::from(Span::styled(line, Style::default().fg(Color::DarkGray)))
+            } else {
+                Spans::from(Span::styled(line, app.palette.fg_style()))
+            }
+        })
+        .collect();
+
+    let height = portrait_h.min(area.height.saturating_sub(5));
+    let x = area.x + area.width.saturating_sub(portrait_w) / 2;
+    let y = area.y + area.height.saturating_sub(height + 5) / 2;
+    let rect = Rect::new(x, y, portrait_w.min(area.width), height);
+    frame.render_widget(Paragraph::new(lines).style(app.palette.bg_style()), rect);
+
+    let progress_y = y + height + 2;
+    if progress_y < area.y + area.height {
+        draw_progress_indicator(frame, area, app, progress_y);
     }
 }
 
 /// Helper function to draw progress indicator
 fn draw_progress_indicator<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App, y_position: u16) {
     // Determine which message to show based on progress
-    let total_messages = LOADING_MESSAGES.len();
+    let messages = &app.station.content.loading_messages;
+    let total_messages = messages.len();
     
     let message_idx = if app.progress_percentage >= 100.0 {
         total_messages - 1
@@ -109,7 +226,11 @@ fn draw_progress_indicator<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &A
         0
     };
     
-    let message = LOADING_MESSAGES[message_idx];
+    // A restore names the step it is on instead
+    let message = match app.restore_steps.front() {
+        Some(step) => step.label(),
+        None => messages[message_idx].as_str(),
+    };
 
     let message_span = Span::styled(
         message,
@@ -152,4 +273,58 @@ fn draw_progress_indicator<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &A
         .style(app.palette.fg_style());
     
     frame.render_widget(progress_text, progress_rect);
-} 
\ No newline at end of file
+
+    let hint_y = y_position + 2;
+    if hint_y < area.y + area.height {
+        let hint = Paragraph::new("Press Enter to skip")
+            .alignment(ratatui::layout::Alignment::Center)
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(hint, Rect::new(area.x, hint_y, area.width, 1));
+    }
+
+    // A word from the founder, if there is room for it
+    let quote_y = y_position + 3;
+    if !app.quote.is_empty() && quote_y < area.y + area.height {
+        let quote = Paragraph::new(format!("\u{201c}{}\u{201d}", app.quote))
+            .alignment(ratatui::layout::Alignment::Center)
+            .style(app.palette.fg_style().add_modifier(ratatui::style::Modifier::ITALIC));
+        frame.render_widget(quote, Rect::new(area.x, quote_y, area.width, 1));
+    }
+
+    // Startup reads still out, each with how it went
+    let status_y = y_position + 5;
+    if let Some(startup) = &app.station.startup
+        && status_y < area.y + area.height
+    {
+        let tasks: Vec<String> = startup
+            .tasks
+            .iter()
+            .map(|&(task, status)| {
+                let mark = match (status, app.station.ascii_only) {
+                    (Status::Running, false) => "\u{2026}",
+                    (Status::Running, true) => "...",
+                    (Status::Done, false) => "\u{2713}",
+                    (Status::Done, true) => "ok",
+                    (Status::Failed, _) => "!",
+                };
+                format!("{} {}", task.label(), mark)
+            })
+            .collect();
+        let status = Paragraph::new(tasks.join("   "))
+            .alignment(ratatui::layout::Alignment::Center)
+            .style(Style::default().fg(Color::DarkGray));
+        frame.render_widget(status, Rect::new(area.x, status_y, area.width, 1));
+    }
+
+    // Saves a crash interrupted, and what became of them
+    for (idx, recovery) in app.recovered.iter().enumerate() {
+        let notice_y = y_position + 6 + idx as u16;
+        if notice_y >= area.y + area.height {
+            break;
+        }
+        let notice = Paragraph::new(recovery.notice())
+            .alignment(ratatui::layout::Alignment::Center)
+            .style(Style::default().fg(Color::Yellow));
+        frame.render_widget(notice, Rect::new(area.x, notice_y, area.width, 1));
+    }
+}
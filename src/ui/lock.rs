@@ -0,0 +1,131 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+use crossterm::event::KeyCode;
+
+use crate::app::App;
+use crate::app::{AppState, MAX_USERNAME_LEN};
+use crate::screen::Screen;
+use crate::shift;
+use super::big_text::{self, GLYPH_HEIGHT};
+
+/// Outside shift hours, until a supervisor lets the refiner in
+pub struct LockScreen;
+
+impl Screen for LockScreen {
+    fn handle_key(&self, app: &mut App, key: KeyCode) {
+        app.show_supervisor_error = false;
+
+        match key {
+            KeyCode::Char(c) if app.supervisor_entry.chars().count() < MAX_USERNAME_LEN => {
+                app.supervisor_entry.push(c);
+            },
+            KeyCode::Backspace => {
+                app.supervisor_entry.pop();
+            },
+            KeyCode::Enter => {
+                let accepted = app.station.config.shift.as_ref()
+                    .is_none_or(|shift| app.supervisor_entry == shift.supervisor_code);
+                if accepted {
+                    app.station.shift_override = true;
+                    app.go_to(AppState::Main);
+                } else {
+                    app.show_supervisor_error = true;
+                }
+                app.supervisor_entry.clear();
+            },
+            KeyCode::Esc => {
+                app.running = false;
+            },
+            _ => {}
+        }
+    }
+
+    fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, app: &App) {
+        draw_lock_screen(frame, area, app);
+    }
+}
+
+/// Draws the lock screen shown outside shift hours
+pub fn draw_lock_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let is_small_window = area.height < 20;
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(if is_small_window { 1 } else { 2 })
+        .constraints([
+            Constraint::Length(3),   // Title
+            Constraint::Length(1),   // Divider
+            Constraint::Length(if is_small_window { 0 } else { 1 }),   // Space
+            Constraint::Length(2),   // Explanation
+            Constraint::Length(if is_small_window { 1 } else { GLYPH_HEIGHT + 1 }),   // Shift start time
+            Constraint::Length(1),   // Space
+            Constraint::Length(2),   // Supervisor code entry
+            Constraint::Length(1),   // Denied message
+            Constraint::Length(1),   // Instructions
+            Constraint::Min(0),      // Remaining space
+        ])
+        .split(area);
+
+    let title = Paragraph::new("THE SEVERED FLOOR IS CLOSED")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    frame.render_widget(title, layout[0]);
+
+    let divider: String = "━".repeat(layout[1].width as usize);
+    frame.render_widget(Paragraph::new(divider).style(app.palette.fg_style()), layout[1]);
+
+    let (opens_at, wait) = match &app.station.config.shift {
+        Some(shift) => (
+            shift::format_time(shift.start),
+            shift.minutes_until_open(app.clock.wall()),
+        ),
+        None => ("--:--".to_string(), 0),
+    };
+
+    let explanation = vec![
+        Spans::from(Span::styled(
+            "Refinement is not permitted outside of shift hours.",
+            app.palette.fg_style(),
+        )),
+        Spans::from(Span::styled(
+            format!("Your next shift begins in {}h {:02}m at", wait / 60, wait % 60),
+            app.palette.fg_style(),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(explanation).alignment(Alignment::Center), layout[3]);
+
+    big_text::draw_big_text(
+        frame,
+        layout[4],
+        &opens_at,
+        Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
+    );
+
+    // The code is masked; only its length is shown
+    let entry = vec![
+        Spans::from(Span::styled("Supervisor override code:", app.palette.fg_style())),
+        Spans::from(Span::styled(
+            format!("[{}]", "*".repeat(app.supervisor_entry.chars().count())),
+            Style::default().fg(Color::Green),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(entry).alignment(Alignment::Center), layout[6]);
+
+    if app.show_supervisor_error {
+        let denied = Paragraph::new("Access denied. This attempt has been noted.")
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
+        frame.render_widget(denied, layout[7]);
+    }
+
+    let instructions = Paragraph::new("[ENTER] Submit code   [ESC] Exit")
+        .alignment(Alignment::Center)
+        .style(app.palette.fg_style());
+    frame.render_widget(instructions, layout[8]);
+}
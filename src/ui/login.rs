@@ -6,8 +6,86 @@ use ratatui::{
     text::{Span, Spans},
     widgets::{Block, Borders, Paragraph},
 };
+use crossterm::event::KeyCode;
 
-use crate::app::App;
+use crate::app::{App, LoginStep};
+use crate::screen::Screen;
+use crate::branding::Logo;
+use crate::save::Stored;
+use crate::slots::{self, SlotDetail, SlotPicker};
+use super::LayoutProfile;
+
+/// The login: a name, then a save slot or a passphrase
+pub struct LoginScreen;
+
+impl Screen for LoginScreen {
+    fn handle_key(&self, app: &mut App, key: KeyCode) {
+        match app.login_step {
+            // The key is being worked out; Esc gives up on it
+            LoginStep::Passphrase if app.key_derivation.is_some() => {
+                if key == KeyCode::Esc {
+                    app.restart_login();
+                }
+            },
+            LoginStep::Passphrase => {
+                app.passphrase_error = None;
+
+                match key {
+                    KeyCode::Char(c) => app.passphrase.push(c),
+                    KeyCode::Backspace => {
+                        app.passphrase.pop();
+                    },
+                    KeyCode::Enter => app.submit_passphrase(),
+                    // Back to the name, e.g. after a typo in it
+                    KeyCode::Esc => app.restart_login(),
+                    _ => {}
+                }
+            },
+            LoginStep::Slot => app.on_slot_key(key),
+            LoginStep::Name => {
+                // Any input clears previous error
+                app.show_login_error = false;
+                app.passphrase_error = None;
+                app.login_notice = None;
+
+                match key {
+                    KeyCode::Char(c) => app.insert_username_char(c),
+                    KeyCode::Backspace if app.username_cursor > 0 => {
+                        app.username_cursor -= 1;
+                        let at = app.username_cursor_byte();
+                        app.username.remove(at);
+                    },
+                    KeyCode::Delete if app.username_cursor < app.username.chars().count() => {
+                        let at = app.username_cursor_byte();
+                        app.username.remove(at);
+                    },
+                    KeyCode::Left if app.username_cursor > 0 => {
+                        app.username_cursor -= 1;
+                    },
+                    KeyCode::Right if app.username_cursor < app.username.chars().count() => {
+                        app.username_cursor += 1;
+                    },
+                    KeyCode::Enter => {
+                        if !app.username.trim().is_empty() {
+                            app.submit_username();
+                        } else {
+                            // Set error flag if username is empty
+                            app.show_login_error = true;
+                        }
+                    },
+                    KeyCode::Esc => {
+                        app.running = false;
+                    },
+                    _ => {}
+                }
+            },
+        }
+    }
+
+    fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, app: &App) {
+        draw_login_screen(frame, area, app);
+    }
+}
 
 /// Draws the login screen with username input
 pub fn draw_login_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
@@ -41,27 +119,38 @@ pub fn draw_login_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App
     // Draw divider
     draw_divider(frame, layout[1], app);
 
-    // Draw simple logo only if not in small window mode
-    if !is_small_window {
-        draw_simplified_logo(frame, layout[2], app);
-    }
+    // Draw the logo; a small window leaves it no room
+    draw_logo(frame, layout[2], app);
 
     // Draw login instructions
+    let prompt = match (app.login_step, &app.login_session) {
+        (LoginStep::Name, _) => "Enter your employee identification name:",
+        (LoginStep::Slot, _) => "Choose the file to continue:",
+        (LoginStep::Passphrase, Some(Stored::Sealed(_))) => "Your work is sealed. Enter your passphrase:",
+        (LoginStep::Passphrase, _) => "Choose a passphrase to seal your work:",
+    };
+    let hint = match app.login_step {
+        LoginStep::Name => "Press ENTER to continue.",
+        LoginStep::Slot => "Up/Down to choose, ENTER to continue, DELETE to discard, ESC to change name.",
+        LoginStep::Passphrase => "Press ENTER to continue, ESC to change name.",
+    };
     let login_text = vec![
-        Spans::from(Span::styled(
-            "Enter your employee identification name:",
-            app.palette.fg_style()
-        )),
-        Spans::from(Span::styled(
-            "Press ENTER to continue.",
-            app.palette.fg_style()
-        )),
+        Spans::from(Span::styled(prompt, app.palette.fg_style())),
+        Spans::from(Span::styled(hint, app.palette.fg_style())),
     ];
     
     let login_instructions = Paragraph::new(login_text)
         .alignment(Alignment::Center);
     frame.render_widget(login_instructions, layout[4]);
 
+    // The slot list takes the room of the input field and the controls
+    if let Some(picker) = &app.slot_picker {
+        let bottom = layout[8].y + layout[8].height;
+        let list_area = Rect { height: bottom.saturating_sub(layout[5].y), ..layout[5] };
+        draw_slot_picker(frame, list_area, app, picker);
+        return;
+    }
+
     // Draw input field with cursor
     let input_area = Layout::default()
         .direction(Direction::Horizontal)
@@ -72,10 +161,15 @@ pub fn draw_login_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App
         ])
         .split(layout[5])[1];
 
-    // Create input text with cursor
-    let input_text = if app.username_cursor < app.username.len() {
+    // Create input text with cursor; a passphrase only shows its length
+    let input_text = if app.login_step == LoginStep::Passphrase {
+        vec![
+            Span::styled("•".repeat(app.passphrase.chars().count()), app.palette.fg_style()),
+            Span::styled(" ", Style::default().bg(Color::White))
+        ]
+    } else if app.username_cursor < app.username.chars().count() {
         // Split the username at cursor position
-        let (before, after) = app.username.split_at(app.username_cursor);
+        let (before, after) = app.username.split_at(app.username_cursor_byte());
         let after_chars: Vec<char> = after.chars().collect();
         
         // Make sure there are characters to extract
@@ -119,12 +213,34 @@ pub fn draw_login_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App
     frame.render_widget(input, input_area);
     
     // Draw error message if needed
-    if app.show_login_error {
-        let error_text = "ERROR: Employee name cannot be empty";
+    let error_text = if app.show_login_error {
+        Some("Employee name cannot be empty")
+    } else {
+        app.passphrase_error
+    };
+    if let Some(error_text) = error_text {
+        let error_text = format!("ERROR: {}", error_text);
         let error_message = Paragraph::new(error_text)
             .alignment(Alignment::Center)
             .style(Style::default().fg(Color::Red).add_modifier(Modifier::BOLD));
         frame.render_widget(error_message, layout[6]);
+    } else if let Some(derivation) = &app.key_derivation {
+        // The key takes a few seconds on purpose; count them so the wait
+        // does not look like a hang
+        let doing = match app.login_session {
+            Some(Stored::Sealed(_)) => "Unsealing your work",
+            _ => "Sealing your work",
+        };
+        let waited = app.clock.now().duration_since(derivation.since).as_secs_f32();
+        let progress = Paragraph::new(format!("{}... {:.1}s", doing, waited))
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Yellow));
+        frame.render_widget(progress, layout[6]);
+    } else if let Some(notice) = app.login_notice {
+        let notice = Paragraph::new(notice)
+            .alignment(Alignment::Center)
+            .style(Style::default().fg(Color::Yellow));
+        frame.render_widget(notice, layout[6]);
     }
     
     // Draw app usage instructions (simplified for small windows)
@@ -166,6 +282,47 @@ pub fn draw_login_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App
     frame.render_widget(usage_instructions, layout[8]);
 }
 
+/// Draw the employee's save slots, with a new file last if there is room
+fn draw_slot_picker<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App, picker: &SlotPicker) {
+    let fg = app.palette.fg_style();
+    let now = app.clock.wall();
+
+    let mut rows: Vec<String> = picker
+        .slots
+        .iter()
+        .map(|slot| {
+            let detail = match &slot.detail {
+                SlotDetail::Progress { file, percent } => format!("{:<20} {:>3.0}%", file, percent),
+                SlotDetail::Sealed => format!("{:<25}", "Sealed"),
+                SlotDetail::Unreadable => format!("{:<25}", "Damaged"),
+            };
+            let played = slot.last_played.map(|at| slots::last_played_label(at, now)).unwrap_or_default();
+            format!(" Slot {}   {}   {:<14}", slot.number, detail, played)
+        })
+        .collect();
+    if let Some(number) = slots::free_slot(&picker.slots) {
+        rows.push(format!(" {:<49}", format!("New file (slot {})", number)));
+    }
+
+    let lines: Vec<Spans> = rows
+        .into_iter()
+        .enumerate()
+        .map(|(idx, row)| {
+            if idx != picker.selected {
+                return Spans::from(Span::styled(row, fg));
+            }
+            match picker.highlighted() {
+                Some(slot) if picker.confirm_delete => Spans::from(Span::styled(
+                    format!(" Press DELETE again to discard slot {} for good ", slot.number),
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD | Modifier::REVERSED),
+                )),
+                _ => Spans::from(Span::styled(row, fg.add_modifier(Modifier::REVERSED))),
+            }
+        })
+        .collect();
+    frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center), area);
+}
+
 /// Draw a divider line
 fn draw_divider<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
     let mut divider = String::new();
@@ -177,19 +334,13 @@ fn draw_divider<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
     frame.render_widget(divider_widget, area);
 }
 
-/// Draw simplified Lumon logo 
-fn draw_simplified_logo<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
-    let logo = vec![
-        " _       _    _ __  __  ___  _   _ ",
-        " | |     | |  | |  \\/  |/ _ \\| \\ | |",
-        "| |     | |  | | \\  / | | | |  \\| |",
-        " | |     | |  | | |\\/| | | | | . ` |",
-        "| |___  | |__| | |  | | |_| | |\\  |",
-        " |_____|  \\____/|_|  |_|\\___/|_| \\_|",
-    ];
-    
+/// Draw whichever Lumon logo fits above the login prompt
+fn draw_logo<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let Some(logo) = Logo::pick(app.station.config.logo, LayoutProfile::for_area(frame.size()), area) else { return };
+
     // Convert to spans
     let logo_spans: Vec<Spans> = logo
+        .lines(app.station.ascii_only)
         .iter()
         .map(|&line| {
             Spans::from(Span::styled(
@@ -199,9 +350,8 @@ fn draw_simplified_logo<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App)
         })
         .collect();
     
-    // Center the logo
-    let logo_para = Paragraph::new(logo_spans)
-        .alignment(Alignment::Center);
-    
-    frame.render_widget(logo_para, area);
-} 
\ No newline at end of file
+    // Center the logo, each line by the widest
+    let x = area.x + area.width.saturating_sub(logo.width()) / 2;
+    let logo_para = Paragraph::new(logo_spans);
+    frame.render_widget(logo_para, Rect::new(x, area.y, logo.width(), logo.height()));
+}
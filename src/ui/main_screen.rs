@@ -3,103 +3,333 @@ use ratatui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect, Alignment},
     text::{Span, Spans},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
     style::{Style, Color, Modifier},
 };
+use crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind};
 use std::rc::Rc;
+use std::time::{Duration, Instant};
 
-use crate::app::{App, DataContainer};
+use crate::animate::Animated;
+use crate::app::{
+    ANIMATION_STEP, App, AppState, BinDrag, DataContainer, GridGeometry, IDLE_AFTER, MAX_COMMAND_LEN,
+    MAX_USERNAME_LEN, PRIZE_DELAY, ResetMenu, Staged, TOAST_DURATION,
+};
+use crate::branding::Logo;
+use crate::cheats;
+use crate::diagnostics::Quality;
+use crate::error::MdrError;
+use crate::events::AppEvent;
+use crate::onboarding::Lesson;
+use crate::orientation;
+use crate::race;
+use crate::ranks;
+use crate::screen::Screen;
+use super::LayoutProfile;
+use super::layout::{MainLayout, Priority, TitleBar};
+use super::side_panel;
+use super::widgets::focus_style;
 use rand::{Rng, SeedableRng, rngs::StdRng};
 
-// Small Lumon logo for the title bar
-const SMALL_LOGO: &[&str] = &[
-    "╭──────────╮",
-    "│  LUMON   │",
-    "│ INDUSTRY │",
-    "╰──────────╯",
-];
+/// Width of the side panel, when one is open
+const SIDE_PANEL_WIDTH: u16 = 34;
+/// Smallest window the main screen is laid out in
+const MIN_WIDTH: u16 = 50;
+const MIN_HEIGHT: u16 = 20;
+
+/// Grid cells around a scary cluster dimmed at full dread
+const DREAD_DIM_RADIUS: usize = 4;
+
+/// One full breath of the idle grid
+const BREATH_PERIOD: Duration = Duration::from_secs(8);
+/// Radians each row's breath lags the row above, so the waves roll downwards
+const BREATH_ROW_LAG: f32 = 0.45;
+/// How long the breathing takes to reach full depth once idle
+const BREATH_FADE_IN: Duration = Duration::from_secs(3);
+
+/// A held sticky number fills in a quarter at a time, with plain
+/// characters for terminals without Unicode
+const HOLD_GLYPHS: [char; 4] = ['◔', '◑', '◕', '●'];
+const HOLD_GLYPHS_ASCII: [char; 4] = ['.', 'o', 'O', '@'];
+
+/// In redacted mode, digits closer than this to the cursor stay legible
+const REDACTION_RADIUS: f32 = 6.0;
+
+/// The grid and the bins, where the refining is done
+pub struct MainScreen;
+
+impl Screen for MainScreen {
+    fn handle_key(&self, app: &mut App, key: KeyCode) {
+        // An open prompt, menu or console takes the keys before the grid
+        match key {
+            _ if app.compliance.is_some() => app.on_compliance_key(key),
+            _ if app.cheat_console.is_some() => {
+                let line = app.cheat_console.get_or_insert_default();
+                match key {
+                    KeyCode::Char(c) if line.chars().count() < MAX_USERNAME_LEN => line.push(c),
+                    KeyCode::Backspace => {
+                        line.pop();
+                    },
+                    KeyCode::Enter => {
+                        let line = std::mem::take(line);
+                        let result = match line.parse() {
+                            Ok(cheat) => cheats::apply(app, cheat),
+                            Err(MdrError::Config(msg)) => msg,
+                            Err(err) => err.to_string(),
+                        };
+                        app.show_toast(result, Some(TOAST_DURATION));
+                    },
+                    KeyCode::Esc => app.cheat_console = None,
+                    _ => {}
+                }
+            },
+            _ if app.context_menu.is_some() => app.on_context_menu_key(key),
+            KeyCode::Esc if app.staged.is_some() => {
+                app.staged = None;
+                app.show_toast("Capture called off".to_string(), Some(TOAST_DURATION));
+            },
+            _ if app.inspecting.is_some() => match key {
+                KeyCode::Esc | KeyCode::Char('i') | KeyCode::Char('q') => app.inspecting = None,
+                KeyCode::Tab => app.cycle_inspection(1),
+                KeyCode::BackTab => app.cycle_inspection(-1),
+                _ => {},
+            },
+            _ if app.reset_menu.is_some() => app.on_reset_menu_key(key),
+            _ if app.command_line.is_some() => {
+                let line = app.command_line.get_or_insert_default();
+                match key {
+                    KeyCode::Char(c) if line.chars().count() < MAX_COMMAND_LEN => line.push(c),
+                    // Backspacing past the colon closes the prompt
+                    KeyCode::Backspace if line.is_empty() => app.command_line = None,
+                    KeyCode::Backspace => {
+                        line.pop();
+                    },
+                    KeyCode::Enter => {
+                        let command = app.command_line.take().unwrap_or_default();
+                        app.run_command(&command);
+                    },
+                    KeyCode::Esc => app.command_line = None,
+                    _ => {}
+                }
+            },
+            _ if app.konami.push(key) => app.cheat_console = Some(String::new()),
+            KeyCode::Char('q') => {
+                app.running = false;
+            },
+            // R asks what to reset
+            KeyCode::Char('r') if !app.read_only => {
+                app.reset_menu = Some(ResetMenu::default());
+            },
+            KeyCode::Char('[') => app.step_game_speed(-1),
+            KeyCode::Char(']') => app.step_game_speed(1),
+            KeyCode::Char('s') => {
+                app.go_to(AppState::Report);
+            },
+            KeyCode::Char(':') => {
+                app.command_line = Some(String::new());
+            },
+            KeyCode::Esc if app.toast.is_some() => app.toast = None,
+            KeyCode::Char('p') => app.cycle_side_panel(),
+            KeyCode::Char(' ') => app.press_capture_key(None),
+            KeyCode::Char(c @ '1'..='9') => {
+                let bin = c as usize - '1' as usize;
+                if bin < app.containers.len() {
+                    app.press_bin_key(bin);
+                }
+            },
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Enter | KeyCode::Char('i') => app.on_bin_focus_key(key),
+            KeyCode::Esc => {
+                app.side_panel = None;
+                app.focus_bin(None);
+            },
+            _ => {}
+        }
+        process_click(app);
+    }
+
+    fn handle_mouse(&self, app: &mut App, event: MouseEvent) {
+        // Nothing is refined while a compliance check is up
+        if app.compliance.is_some() {
+            return;
+        }
+        if app.grid_geometry.is_some_and(|geometry| geometry.cell_at(event.column, event.row).is_some()) {
+            app.learn(Lesson::Hover);
+        }
+        app.track_dread();
+        app.check_hold_position();
+
+        let capture_button = if app.station.config.input.swap_buttons { MouseButton::Right } else { MouseButton::Left };
+        // An open menu takes the clicks; one anywhere else closes it
+        if let Some(menu) = &mut app.context_menu {
+            let screen = Rect::new(0, 0, app.station.current_width, app.station.current_height);
+            let item = menu.item_at(screen, event.column, event.row);
+            match event.kind {
+                MouseEventKind::Moved => {
+                    if let Some(idx) = item {
+                        menu.selected = idx;
+                    }
+                    return;
+                },
+                MouseEventKind::Down(button) if button == capture_button => {
+                    match item {
+                        Some(idx) => {
+                            menu.selected = idx;
+                            app.choose_from_context_menu();
+                        },
+                        None => app.context_menu = None,
+                    }
+                    process_click(app);
+                    return;
+                },
+                _ => {},
+            }
+        }
+
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Middle) => {},
+            MouseEventKind::Down(button) if button != capture_button => app.open_context_menu(event.column, event.row),
+            MouseEventKind::Down(_) => {
+                app.last_clicked = Some((event.column, event.row));
+                app.button_down = true;
+                app.bin_drag = app
+                    .slot_at(event.column, event.row)
+                    .and_then(|slot| app.bin_in_slot(slot))
+                    .filter(|_| !app.read_only)
+                    .map(|bin| BinDrag { bin, grab: event.column, column: event.column });
+                process_click(app);
+            },
+            MouseEventKind::Drag(_) => {
+                if let Some(drag) = &mut app.bin_drag {
+                    drag.column = event.column;
+                }
+            },
+            MouseEventKind::Up(_) => {
+                // Letting go of the button lets go of a mouse hold
+                app.button_down = false;
+                if app.hold.as_ref().is_some_and(|hold| hold.key_seen.is_none()) {
+                    app.hold = None;
+                }
+                if let Some(drag) = app.bin_drag.take()
+                    && drag.is_moving()
+                {
+                    let slot = app.drop_slot(drag.column);
+                    app.move_bin(drag.bin, slot);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    fn tick(&self, app: &mut App, now: Instant) {
+        app.check_hold(now);
+        app.check_staged(now);
+        app.check_assist(now);
+        app.check_ambience(now);
+        app.check_compliance(now);
+        app.check_reset_hold(now);
+        orientation::advance(app);
+        app.coach();
+
+        // Check if all containers are filled
+        if app.is_all_complete() {
+            let complete_at = *app.file_complete_at.get_or_insert(now);
+
+            // Give the full bins a moment on screen, then show the prize
+            if now.duration_since(complete_at) >= app.game_time(PRIZE_DELAY) {
+                app.file_complete_at = None;
+                app.go_to(AppState::Prize);
+                app.select_random_prize();
+                app.station.outbox.push(AppEvent::FileCompleted { prize: app.prize_name.clone() });
+                app.issue_voucher();
+                app.issue_card(app.file_started.map(|started| complete_at.duration_since(started)));
+                app.finish_timing(complete_at);
+                app.prize_auto_reset_at = app.station.config.prize.auto_reset
+                    .then(|| now + app.station.config.prize.auto_reset_after);
+            }
+        } else {
+            // Reset timer if containers are not full
+            app.file_complete_at = None;
+        }
+    }
+
+    fn layout(&self, app: &mut App, area: Rect) {
+        let Some(layout) = main_layout(app, area) else { return };
+        let grid = Block::default().inner(layout.grid);
+        if let Some(geometry) = grid_placement(app, grid) {
+            app.fit_grid(geometry);
+        }
+        app.bin_areas = bin_areas(app, layout.bins);
+    }
+
+    fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, app: &App) {
+        draw_main_screen(frame, area, app);
+        super::cheats::draw_cheat_console(frame, area, app);
+        super::inspection::draw_inspection(frame, area, app);
+        super::reset_menu::draw_reset_menu(frame, area, app);
+        super::compliance::draw_compliance(frame, area, app);
+        super::context_menu::draw_context_menu(frame, area, app);
+        super::draw_coach_mark(frame, area, app);
+        super::draw_toast(frame, area, app);
+        super::draw_combo_banner(frame, area, app);
+        super::draw_command_line(frame, area, app);
+        super::draw_ambience(frame, area, app);
+    }
+}
+
+/// How the main screen lays out in `area`, or `None` when the window is
+/// too small for it
+fn main_layout(app: &App, area: Rect) -> Option<MainLayout> {
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
+        return None;
+    }
+    let panel_width = app.side_panel.map(|_| SIDE_PANEL_WIDTH);
+    let layout = MainLayout::compute(area, panel_width);
+    Some(if app.station.config.display.mirror { layout.mirrored() } else { layout })
+}
 
 /// Renders the main screen with data bins
 pub fn draw_main_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
-    // Define minimum required dimensions for proper display
-    let min_width = 50;
-    let min_height = 20;
-    
     // Check if window is too small to render properly
-    if area.width < min_width || area.height < min_height {
+    let Some(layout) = main_layout(app, area) else {
         // Window is too small, render a simple message instead
-        let message = format!("Window too small\nMin size: {}x{}", min_width, min_height);
+        let message = format!("Window too small\nMin size: {}x{}", MIN_WIDTH, MIN_HEIGHT);
         let message_widget = Paragraph::new(message)
             .alignment(Alignment::Center)
             .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
         
         frame.render_widget(message_widget, area);
         return;
-    }
-    
-    // Create the main layout
-    let main_layout = create_main_layout(area);
+    };
 
     // Draw title bar
-    draw_title_bar(frame, main_layout[0], app);
+    draw_title_bar(frame, layout.title, app);
     
     // Draw thick divider under title bar
-    draw_horizontal_divider(frame, main_layout[1], app, true);
+    draw_horizontal_divider(frame, layout.title_divider, app, true);
 
     // Draw main content (number grid)
-    let content_area = main_layout[2];
     let main_content = Block::default()
         .style(app.palette.fg_style());
     
-    frame.render_widget(main_content.clone(), content_area);
-    let inner_area = main_content.inner(content_area);
+    frame.render_widget(main_content.clone(), layout.grid);
+    let inner_area = main_content.inner(layout.grid);
     draw_number_grid(frame, inner_area, app);
 
+    if let (Some(panel), Some(panel_area)) = (app.side_panel, layout.panel) {
+        side_panel::draw_side_panel(frame, panel_area, panel, app);
+    }
+
     // Draw thick horizontal divider above data containers
-    draw_horizontal_divider(frame, main_layout[3], app, true);
+    draw_horizontal_divider(frame, layout.bins_divider, app, true);
 
-    // Top padding is empty
-    
     // Draw data containers
-    draw_data_containers(frame, main_layout[5], app);
-    
-    // Bottom padding is empty
+    draw_data_containers(frame, layout.bins, app);
     
     // Draw thin horizontal divider below data containers
-    draw_horizontal_divider(frame, main_layout[7], app, false);
+    draw_horizontal_divider(frame, layout.footer_divider, app, false);
     
     // Draw footer text
-    draw_footer_text(frame, main_layout[8], app);
-}
-
-/// Creates the main layout structure
-fn create_main_layout(area: Rect) -> Rc<[Rect]> {
-    // Calculate padding - we want equal spacing above and below containers
-    let container_height = 6;  // Actual height needed for containers
-    let padding = 1;           // Equal padding above and below
-    
-    // For very small windows, adjust constraints to ensure minimum functionality
-    let min_content_height = 5; // Minimum height for main content (grid)
-    
-    // Check if window is too small for standard layout
-    let is_small_window = area.height < 25;
-    
-    // Create adaptive layout
-    Layout::default()
-        .direction(Direction::Vertical)
-        .margin(if is_small_window { 1 } else { 2 })
-        .constraints([
-            Constraint::Length(3),           // Title bar (original height)
-            Constraint::Length(1),           // Title divider
-            Constraint::Min(min_content_height), // Main content (grid) with minimum height
-            Constraint::Length(1),           // Thick divider
-            Constraint::Length(if is_small_window { 0 } else { padding }),     // Top padding (remove in small window)
-            Constraint::Length(container_height), // Container section
-            Constraint::Length(if is_small_window { 0 } else { padding }),     // Bottom padding (remove in small window)
-            Constraint::Length(1),           // Thin divider
-            Constraint::Length(1),           // Footer text
-        ])
-        .split(area)
+    draw_footer_text(frame, layout.footer, app);
 }
 
 /// Draw the title bar at the top of the screen
@@ -115,39 +345,61 @@ fn draw_title_bar<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
     // Calculate inner area for content
     let inner_area = title_block.inner(area);
     
-    // Calculate overall completion percentage
-    let total_completion: f32 = app.containers.iter()
-        .map(|container| container.progress)
-        .sum::<f32>() / (app.containers.len() as f32);
-    
-    let completion_percent = (total_completion).round() as u32;
+    // Overall completion percentage, as far as it has counted up
+    let completion_percent = app.completion.value(app.clock.now()).round() as u32;
     let completion_text = format!("{}% Complete", completion_percent);
+    // Racing puts the target and the live delta in front of it
+    let race = app.race.zip(app.race_pace());
+    let race_text = race.map(|(race, pace)| {
+        (format!("Target {} ", race::format_time(race.target)), pace.to_string(), pace.is_ahead())
+    });
+    let completion_text = match &race_text {
+        Some((target, delta, _)) => format!("{}{} \u{00b7} {}", target, delta, completion_text),
+        None => completion_text,
+    };
+    let rank = ranks::rank_for(app.xp).title;
+    let code = app.share_code().to_string();
+    let identity = [
+        (app.file_name.as_str(), Priority::Low),
+        (code.as_str(), Priority::Low),
+        (app.username.as_str(), Priority::High),
+        (rank, Priority::Low),
+    ];
+    let identity: Vec<(&str, Priority)> = identity.into_iter().filter(|(text, _)| !text.is_empty()).collect();
+    
+    // Room for the logo at the end; it may have up to a third of the bar
+    let screen = frame.size();
+    let room = Rect::new(0, 0, area.width / 3, area.height + 1);
+    let logo = Logo::pick(app.station.config.logo, LayoutProfile::for_area(screen), room);
+    let logo_rect = logo.map(|logo| logo_rect(screen, area, logo));
+    // The columns of the bar under the logo, and a gap before it
+    let logo_room = logo_rect.map(|rect| (inner_area.x + inner_area.width).saturating_sub(rect.x) + 2);
+    let bar = TitleBar::fit(inner_area.width, &identity, &completion_text, logo_room, app.station.ascii_only);
+    
+    // Passing a quarter lights the completion up for a moment
+    let completion_style = if app.completion_pulse.is_some() {
+        app.palette.fg_style().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+    } else {
+        app.palette.fg_style()
+    };
     
-    // Add padding for logo
-    let logo_width = 12; // Width of the Lumon logo
-    let logo_padding = logo_width + 2;
-    
-    // Create title content with username on the left and completion on the right
-    let title_spans = vec![
-        // Username on the left
-        Span::styled(
-            format!(" {} ", app.username),
-            app.palette.fg_style()
-        ),
-        // Spacer to push completion percentage to the right
-        Span::styled(
-            format!("{:width$}", "", width = inner_area.width as usize - 
-                   format!(" {} ", app.username).len() - 
-                   completion_text.len() - 
-                   logo_padding as usize),
-            app.palette.fg_style()
-        ),
-        // Completion percentage on the right
-        Span::styled(
-            completion_text.clone(),
-            app.palette.fg_style()
-        ),
+    // Identity on the left, completion on the right
+    let mut title_spans = vec![
+        Span::styled(bar.left, app.palette.fg_style()),
+        Span::styled(" ".repeat(bar.spacer as usize), app.palette.fg_style()),
     ];
+    match race_text {
+        // The delta goes green while ahead of the target and red behind it,
+        // unless the bar had to cut it short
+        Some((target, delta, ahead)) if bar.right == completion_text => {
+            let colour = if ahead { Color::Green } else { Color::Red };
+            let rest = bar.right[target.len() + delta.len()..].to_string();
+            title_spans.push(Span::styled(target, app.palette.fg_style()));
+            title_spans.push(Span::styled(delta, app.palette.fg_style().fg(colour).add_modifier(Modifier::BOLD)));
+            title_spans.push(Span::styled(rest, completion_style));
+        },
+        _ => title_spans.push(Span::styled(bar.right, completion_style)),
+    }
     
     // Create paragraph with the title content
     let title_para = Paragraph::new(Spans::from(title_spans));
@@ -156,24 +408,26 @@ fn draw_title_bar<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
     frame.render_widget(title_para, inner_area);
     
     // Draw the logo at the absolute right edge
-    draw_logo_at_right_edge(frame);
+    if let (Some(logo), Some(rect)) = (logo, logo_rect)
+        && bar.logo
+    {
+        draw_logo_at_right_edge(frame, rect, logo, app);
+    }
 }
 
-/// Draw the Lumon logo at the absolute right edge of the screen
-fn draw_logo_at_right_edge<B: Backend>(frame: &mut Frame<B>) {
-    let screen_size = frame.size();
-    let logo_width = 12; // Fixed width based on logo content
-    let logo_height = 4; // Height based on logo lines
-    
-    // Position at the absolute right edge of the screen
-    let logo_x = screen_size.width.saturating_sub(logo_width) - 2;
-    let logo_y = 1; // Small offset from top for visual balance
-    
-    // Create the logo rectangle
-    let logo_rect = Rect::new(logo_x, logo_y, logo_width, logo_height);
-    
+/// Where the logo goes: the absolute right edge of the screen, centred on
+/// the title bar
+fn logo_rect(screen: Rect, title: Rect, logo: Logo) -> Rect {
+    let logo_x = screen.width.saturating_sub(logo.width() + 2);
+    let logo_y = (title.y + title.height / 2).saturating_sub(logo.height() / 2);
+    Rect::new(logo_x, logo_y, logo.width(), logo.height())
+}
+
+/// Draw the Lumon logo in `logo_rect`
+fn draw_logo_at_right_edge<B: Backend>(frame: &mut Frame<B>, logo_rect: Rect, logo: Logo, app: &App) {
     // Create the logo spans with distinct styling
-    let logo_spans: Vec<Spans> = SMALL_LOGO
+    let logo_spans: Vec<Spans> = logo
+        .lines(app.station.ascii_only)
         .iter()
         .map(|&line| {
             Spans::from(Span::styled(
@@ -201,21 +455,33 @@ fn draw_footer_text<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
     let memory_addr2 = format!("0x{:016x}", containers_ptr as usize);
     
     let footer_text = format!("{} : {}", memory_addr1, memory_addr2);
+    // Room either side of the addresses
+    let margin = (area.width as usize).saturating_sub(footer_text.len()) / 2;
     
     let footer_widget = Paragraph::new(footer_text)
         .alignment(Alignment::Center)
         .style(app.palette.fg_style());
     
     frame.render_widget(footer_widget, area);
+
+    // The bin strategy shows at the left end, where there is room
+    let strategy = format!("BINS {}", app.station.config.bin_strategy.name().to_uppercase());
+    if strategy.len() < margin {
+        let label_area = Rect::new(area.x, area.y, strategy.len() as u16, 1.min(area.height));
+        frame.render_widget(Paragraph::new(strategy).style(app.palette.fg_style().add_modifier(Modifier::DIM)), label_area);
+    }
+
+    // A changed game speed shows at the right end
+    if app.game_speed != 100 {
+        let speed = Paragraph::new(format!("SPEED {:.2}x", app.game_speed as f32 / 100.0))
+            .alignment(Alignment::Right)
+            .style(app.palette.fg_style().add_modifier(Modifier::BOLD));
+        frame.render_widget(speed, area);
+    }
 }
 
 /// Draw the data containers at the bottom of the screen
 fn draw_data_containers<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
-    // Calculate container sizes
-    let total_gap_width = 4 * 5;
-    let available_width = area.width.saturating_sub(total_gap_width);
-    let container_width = (available_width / 5).max(1); // Ensure minimum width of 1
-    
     // If window is very small, draw simplified containers
     let is_extremely_narrow = area.width < 40;
     
@@ -231,53 +497,99 @@ fn draw_data_containers<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App)
                 Constraint::Percentage(20),
             ])
             .split(area);
-        
+
         // Render each container as a simple progress indicator
-        for (idx, container_rect) in simple_container_layout.iter().enumerate() {
-            if idx < app.containers.len() {
-                let container_data = &app.containers[idx];
-                
-                // Draw a simple progress character
-                let progress_char = if container_data.progress >= 100.0 {
-                    "■" // Full
-                } else if container_data.progress >= 75.0 {
-                    "▣" // 3/4 full
-                } else if container_data.progress >= 50.0 {
-                    "▢" // Half full
-                } else if container_data.progress >= 25.0 {
-                    "□" // 1/4 full
-                } else {
-                    "·" // Empty
-                };
-                
-                let progress_text = Paragraph::new(progress_char)
-                    .alignment(Alignment::Center)
-                    .style(app.palette.fg_style());
-                
-                frame.render_widget(progress_text, *container_rect);
-            }
+        let mut simple_positions = simple_container_layout.to_vec();
+        if app.station.config.display.mirror {
+            simple_positions.reverse();
+        }
+        for (bin, container_rect) in app.bin_order().into_iter().zip(simple_positions.iter()) {
+            let progress = app.containers[bin].fill.value(app.clock.now());
+
+            // Draw a simple progress character
+            let progress_char = if progress >= 100.0 {
+                "■" // Full
+            } else if progress >= 75.0 {
+                "▣" // 3/4 full
+            } else if progress >= 50.0 {
+                "▢" // Half full
+            } else if progress >= 25.0 {
+                "□" // 1/4 full
+            } else {
+                "·" // Empty
+            };
+
+            let progress_text = Paragraph::new(progress_char)
+                .alignment(Alignment::Center)
+                .style(app.palette.fg_style());
+
+            frame.render_widget(progress_text, *container_rect);
         }
     } else {
-        // Create container layout for normal windows
-        let containers = create_container_layout(area, container_width);
-        
-        // Get container positions for click detection
-        let container_positions = [
-            containers[0], containers[2], containers[4], containers[6], containers[8]
-        ];
-        
-        // Process clicks on containers
-        process_container_clicks(app, &container_positions);
-        
+        let container_positions = bin_areas(app, area);
+
+        // While a bin is dragged the others make room where it would land
+        let mut order = app.bin_order();
+        let lifted = app.bin_drag.filter(|drag| drag.is_moving());
+        if let Some(drag) = lifted {
+            order.retain(|&bin| bin != drag.bin);
+            order.insert(app.drop_slot(drag.column).min(order.len()), drag.bin);
+        }
+
         // Render all containers
-        let container_indices = [0, 2, 4, 6, 8];
-        for (idx, &container_idx) in container_indices.iter().enumerate() {
-            let container_rect = containers[container_idx];
-            draw_single_container(frame, container_rect, idx, &app.containers[idx], app);
+        for (&bin, &container_rect) in order.iter().zip(&container_positions) {
+            if lifted.is_some_and(|drag| drag.bin == bin) {
+                draw_lifted_container(frame, container_rect, bin, &app.containers[bin], app);
+            } else {
+                draw_single_container(frame, container_rect, bin, &app.containers[bin], app);
+            }
         }
     }
 }
 
+/// Draw a bin raised a row off the floor with a shadow under it, while it
+/// is being dragged
+fn draw_lifted_container<B: Backend>(
+    frame: &mut Frame<B>,
+    container: Rect,
+    idx: usize,
+    container_data: &DataContainer,
+    app: &App,
+) {
+    let screen = frame.size();
+    let shadow = Rect {
+        x: (container.x + 1).min(screen.width.saturating_sub(1)),
+        width: container.width.min(screen.width.saturating_sub(container.x + 1)),
+        ..container
+    };
+    let shadow_fill = vec![Spans::from("░".repeat(shadow.width as usize)); shadow.height as usize];
+    frame.render_widget(Paragraph::new(shadow_fill).style(Style::default().fg(Color::DarkGray)), shadow);
+
+    let lifted = Rect { y: container.y.saturating_sub(1), ..container };
+    frame.render_widget(Clear, lifted);
+    frame.render_widget(Block::default().style(app.palette.bg_style()), lifted);
+    draw_single_container(frame, lifted, idx, container_data, app);
+}
+
+/// Where each slot's bin goes in the bins' `area`, for the mouse to pick
+/// them up; mirrored, the first slot is on the right. Very narrow windows
+/// draw the bins too small to click.
+fn bin_areas(app: &App, area: Rect) -> Vec<Rect> {
+    if area.width < 40 {
+        return Vec::new();
+    }
+    // Calculate container sizes
+    let total_gap_width = 4 * 5;
+    let available_width = area.width.saturating_sub(total_gap_width);
+    let container_width = (available_width / 5).max(1); // Ensure minimum width of 1
+    let containers = create_container_layout(area, container_width);
+    let mut positions = vec![containers[0], containers[2], containers[4], containers[6], containers[8]];
+    if app.station.config.display.mirror {
+        positions.reverse();
+    }
+    positions
+}
+
 /// Create the horizontal layout for containers with gaps
 fn create_container_layout(area: Rect, container_width: u16) -> Rc<[Rect]> {
     // For very small windows, reduce the gaps between containers
@@ -300,22 +612,25 @@ fn create_container_layout(area: Rect, container_width: u16) -> Rc<[Rect]> {
         .split(area)
 }
 
-/// Process mouse clicks on containers
-fn process_container_clicks(app: &App, container_positions: &[Rect]) {
-    if let Some((click_x, click_y)) = app.last_clicked {
-        for (idx, &container_rect) in container_positions.iter().enumerate() {
-            if click_x >= container_rect.x && 
-               click_x < container_rect.x + container_rect.width &&
-               click_y >= container_rect.y && 
-               click_y < container_rect.y + container_rect.height {
-                // Click was on this container
-                let app_ptr = app as *const App as *mut App;
-                unsafe {
-                    (*app_ptr).add_to_container(idx, 3);
-                }
-                break;
-            }
-        }
+/// Act on a click, hit-testing it against where the last frame put the
+/// bins and the grid's digits. A click on the grid that catches nothing
+/// is a miss; one anywhere else is dropped.
+fn process_click(app: &mut App) {
+    let Some((click_x, click_y)) = app.last_clicked else { return };
+    if let Some(idx) = app.slot_at(click_x, click_y).and_then(|slot| app.bin_in_slot(slot)) {
+        app.add_to_container(idx, 3);
+        return;
+    }
+    let Some(geometry) = app.grid_geometry.filter(|geometry| is_click_in_grid_area(app, geometry.area)) else {
+        app.last_clicked = None;
+        return;
+    };
+    // Capture the magnified numbers, or start holding sticky ones
+    let cells = magnified_cells(app, geometry);
+    if cells.is_empty() {
+        app.misclick();
+    } else {
+        app.capture_or_hold(cells);
     }
 }
 
@@ -341,14 +656,28 @@ fn draw_single_container<B: Backend>(
     draw_container_number(frame, container_layout[0], idx, app);
     
     // Draw progress bar
-    draw_progress_bar(frame, container_layout[1], container_data.progress, app);
+    draw_progress_bar(frame, container_layout[1], &container_data.fill, app);
 }
 
 /// Draw the container number square
 fn draw_container_number<B: Backend>(frame: &mut Frame<B>, area: Rect, idx: usize, app: &App) {
+    // The focused bin's square is drawn heavier, as is the one a staged
+    // capture is headed for and one flashing as it fills, and a locked
+    // one faded
+    let celebrating = app.containers[idx].is_lit(app.clock.now()) && !app.is_mono();
+    let style = if app.bin_focus.is_focused(idx)
+        || app.staged.as_ref().is_some_and(|staged| staged.bin == idx)
+        || celebrating
+    {
+        focus_style(app.palette.fg_style(), true)
+    } else if app.is_locked(idx) {
+        app.palette.fg_style().add_modifier(Modifier::DIM)
+    } else {
+        app.palette.fg_style()
+    };
     let square = Block::default()
         .borders(Borders::ALL)
-        .style(app.palette.fg_style());
+        .style(style);
         
     let inner_square = square.inner(area);
     
@@ -357,7 +686,7 @@ fn draw_container_number<B: Backend>(frame: &mut Frame<B>, area: Rect, idx: usiz
     // Draw number
     let count_text = Paragraph::new(format!("0{}", idx + 1))
         .alignment(Alignment::Center)
-        .style(app.palette.fg_style());
+        .style(style);
         
     let center_y = inner_square.y + inner_square.height / 2;
     let centered_rect = Rect::new(
@@ -370,23 +699,30 @@ fn draw_container_number<B: Backend>(frame: &mut Frame<B>, area: Rect, idx: usiz
     frame.render_widget(count_text, centered_rect);
 }
 
-/// Draw a container progress bar with percentage
+/// Draw a container progress bar with percentage. While the bar eases up
+/// after a deposit, the part just added is drawn brighter.
 fn draw_progress_bar<B: Backend>(
     frame: &mut Frame<B>, 
     area: Rect, 
-    progress: f32, 
+    fill: &Animated, 
     app: &App
 ) {
-    let progress_percentage = progress as u16;
+    let now = app.clock.now();
+    let progress_percentage = fill.value(now) as u16;
     let progress_width = area.width.saturating_sub(2);
     let filled = (progress_width as f32 * (progress_percentage as f32 / 100.0)) as u16;
+    let delta_from = if fill.is_moving(now) {
+        ((progress_width as f32 * fill.origin() / 100.0) as u16).min(filled)
+    } else {
+        filled
+    };
     
     // Format the percentage text
     let percentage_text = format!("{}%", progress_percentage);
     let percentage_len = percentage_text.len() as u16;
     
     // Calculate position to center the percentage text
-    let text_start = (progress_width - percentage_len) / 2;
+    let text_start = progress_width.saturating_sub(percentage_len) / 2;
     let text_end = text_start + percentage_len;
     
     // Create progress bar parts
@@ -398,9 +734,19 @@ fn draw_progress_bar<B: Backend>(
         text_end
     );
     
+    // The delta chunk sits after the left border and the older fill
+    let chars: Vec<char> = bar.chars().collect();
+    let cut = |from: u16, to: u16| -> String { chars[from as usize..to as usize].iter().collect() };
+    let delta_style = Style::default().fg(app.palette.ramp(1.0)).add_modifier(Modifier::BOLD);
+    let bar = Spans::from(vec![
+        Span::raw(cut(0, delta_from + 1)),
+        Span::styled(cut(delta_from + 1, filled + 1), delta_style),
+        Span::raw(cut(filled + 1, chars.len() as u16)),
+    ]);
+    
     let progress_text = Paragraph::new(vec![
         Spans::from(top_border),
-        Spans::from(bar),
+        bar,
         Spans::from(bottom_border),
     ])
     .alignment(Alignment::Center)
@@ -455,6 +801,100 @@ fn create_progress_bar_parts(
     (top_border, bar, bottom_border)
 }
 
+/// Where the grid's digits go in the main content `area`; `None` when not
+/// one fits
+fn grid_placement(app: &App, area: Rect) -> Option<GridGeometry> {
+    if area.width < 5 || area.height < 3 {
+        return None;
+    }
+
+    // Calculate grid dimensions
+    let (num_cols, num_rows, horizontal_spacing, vertical_spacing) = 
+        calculate_grid_dimensions(area, app.station.frame_stats.quality == Quality::Sparse);
+        
+    // Skip if we can't fit a grid
+    if num_cols == 0 || num_rows == 0 {
+        return None;
+    }
+    // A shared file keeps the grid it was shared with: no bigger, centred,
+    // and each row's digits drawn from the seed in full even where fewer
+    // columns fit, so every digit lands where the sharer saw it
+    let area = match app.shared.and_then(|code| code.pinned_grid()) {
+        Some((cols, rows)) => {
+            let (num_cols, num_rows) = (num_cols.min(cols as u16), num_rows.min(rows as u16));
+            let width = num_cols * horizontal_spacing + 2;
+            let height = num_rows * vertical_spacing + 1;
+            let x = area.x + (area.width.saturating_sub(width)) / 2;
+            let y = area.y + (area.height.saturating_sub(height)) / 2;
+            Rect::new(x, y, width.min(area.width), height.min(area.height))
+        },
+        None => area,
+    };
+    Some(GridGeometry { area, horizontal_spacing, vertical_spacing })
+}
+
+/// A digit where it sits on the grid this frame
+struct PlacedDigit {
+    col: usize,
+    row: usize,
+    digit: u16,
+    x: u16,
+    y: u16,
+    motion: GridMotion,
+}
+
+/// Every digit on the grid laid out at `geometry`, row by row, where this
+/// frame puts it
+fn placed_digits(app: &App, geometry: GridGeometry) -> impl Iterator<Item = PlacedDigit> + '_ {
+    let GridGeometry { area, horizontal_spacing, vertical_spacing } = geometry;
+    let (num_cols, num_rows) = geometry.cells();
+    // A shared file's rows draw as many digits from the seed as it was
+    // shared with
+    let stride = app.shared.and_then(|code| code.pinned_grid()).map_or(num_cols, |(cols, _)| cols);
+    let now = app.clock.now();
+
+    // Create RNG with static seed for consistent numbers between renders
+    let mut base_rng = StdRng::seed_from_u64(app.grid_seed);
+    
+    let step = app.animation_step();
+    let motion = grid_motion(app, step);
+    // A slow terminal moves only some of the digits each frame
+    let turns = app.station.frame_stats.stride();
+    // After a resize the digits slide to their new places instead
+    let morph = app.grid_morph.filter(|morph| motion != GridMotion::Still && morph.is_moving(now));
+
+    (0..num_rows).flat_map(move |row| (0..stride).map(move |col| (col, row))).filter_map(move |(col, row)| {
+        let digit = get_digit(app, col, row, &mut base_rng);
+        if col >= num_cols {
+            return None;
+        }
+        
+        let motion = if turns > 1 {
+            grid_motion(app, staggered(step, row * num_cols + col, turns))
+        } else {
+            motion
+        };
+        let (x, y) = match morph {
+            Some(morph) => morph.position_of((col, row), now),
+            None => calculate_number_position(col, row, area, horizontal_spacing, vertical_spacing, motion.wiggle(), digit),
+        };
+        // Mid-slide a digit may still be outside the new grid
+        if x < area.x || y < area.y {
+            return None;
+        }
+        Some(PlacedDigit { col, row, digit, x, y, motion })
+    })
+}
+
+/// The digits a click catches: those swollen by the cursor, as the last
+/// frame laid them out
+fn magnified_cells(app: &App, geometry: GridGeometry) -> Vec<(usize, usize, u16)> {
+    placed_digits(app, geometry)
+        .filter(|placed| calculate_scale_factor(app, placed.x, placed.y) > 1.5)
+        .map(|placed| (placed.col, placed.row, placed.digit))
+        .collect()
+}
+
 /// Draw a grid of random numbers in the main content area
 fn draw_number_grid<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
     // Skip rendering if area is too small
@@ -468,52 +908,161 @@ fn draw_number_grid<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
         return;
     }
 
-    // Calculate grid dimensions
-    let (num_cols, num_rows, horizontal_spacing, vertical_spacing) = 
-        calculate_grid_dimensions(area);
-        
-    // Skip if we can't fit a grid
-    if num_cols == 0 || num_rows == 0 {
-        return;
-    }
-
-    // Create RNG with static seed for consistent numbers between renders
-    let mut base_rng = StdRng::seed_from_u64(42);
-    
-    // Animation time based on app counter
-    let time = app.animation_counter as f32 * 0.01;
-    
-    // Track magnified numbers if there was a click
-    let was_click = app.last_clicked.is_some();
-    let mut magnified_positions: Vec<(usize, usize, u16)> = Vec::new();
+    let Some(geometry) = grid_placement(app, area) else { return };
+    let GridGeometry { area, vertical_spacing, .. } = geometry;
+    let now = app.clock.now();
     
     // Process and render each number in the grid
-    for row in 0..num_rows as usize {
-        for col in 0..num_cols as usize {
-            let digit = get_digit(app, col, row, &mut base_rng);
-            
-            let (x, y) = calculate_number_position(
-                col, row, area, horizontal_spacing, vertical_spacing, time, digit
-            );
-            
-            let scale_factor = calculate_scale_factor(app, x, y);
-            
-            // Track magnified numbers on click
-            if was_click && scale_factor > 1.5 && is_click_in_grid_area(app, area) {
-                magnified_positions.push((col, row, digit));
-            }
-            
-            // Render the digit
-            render_digit(frame, x, y, digit, scale_factor, area, app);
+    for PlacedDigit { col, row, digit, x, y, motion } in placed_digits(app, geometry) {
+        let scale_factor = calculate_scale_factor(app, x, y);
+        
+        // Render the digit. Reduced motion and a degraded frame still capture
+        // by proximity but skip drawing the magnification.
+        let still = app.reduce_motion() || app.station.frame_stats.quality < Quality::Full;
+        let display_scale = if still { 1.0 } else { scale_factor };
+        let style = if let Some(style) = dread_style(app, col, row, now) {
+            style
+        } else if let GridMotion::Breathe { time, depth } = motion {
+            breath_style(app.palette.fg_style(), row, time, depth)
+        } else {
+            app.palette.fg_style()
+        };
+        let style = if app.sticky.contains_key(&(col, row)) {
+            style.add_modifier(Modifier::UNDERLINED)
+        } else {
+            style
+        };
+        let style = if app.marked.contains(&(col, row)) {
+            style.add_modifier(Modifier::REVERSED)
+        } else {
+            style
+        };
+        let style = if app.staged.as_ref().is_some_and(|staged| staged.cells.iter().any(|&(c, r, _)| (c, r) == (col, row))) {
+            style.add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            style
+        };
+        // The assist's cluster flashes, or stays lit without motion
+        let style = match &app.assist_pulse {
+            Some(pulse) if pulse.cluster.contains((col, row)) && (app.reduce_motion() || pulse.lit(now)) => {
+                style.add_modifier(Modifier::BOLD | Modifier::REVERSED)
+            },
+            Some(pulse) if pulse.cluster.contains((col, row)) => style.add_modifier(Modifier::BOLD),
+            _ => style,
+        };
+        let glyph = hold_glyph(app, col, row, now).unwrap_or_else(|| digit_glyph(app, x, y, digit));
+        render_digit(frame, x, y, glyph, display_scale, area, style);
+        // Monochrome digits fill the gap below them too, so they read
+        // at twice the height
+        if app.is_mono() && vertical_spacing >= 2 && y + 1 < area.y + area.height {
+            render_single_digit(frame, x, y + 1, glyph, style);
         }
     }
     
-    // Process clicked numbers
-    process_clicked_numbers(app, magnified_positions);
+    if let Some(staged) = &app.staged {
+        draw_staged(frame, area, app, staged);
+    }
+}
+
+/// A capture waiting to land, on the grid's bottom row: its digits, what
+/// they are worth and the bin they are headed for
+fn draw_staged<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App, staged: &Staged) {
+    let digits: Vec<String> = staged.cells.iter().map(|&(_, _, digit)| digit.to_string()).collect();
+    let (arrow, dot) = if app.station.ascii_only { ("->", "-") } else { ("→", "·") };
+    let text = format!(
+        " {} {} {} {} BIN {:02} {} Esc to cancel ",
+        digits.join(" "),
+        arrow,
+        staged.value,
+        dot,
+        staged.bin + 1,
+        dot
+    );
+    let width = (text.chars().count() as u16).min(area.width);
+    let rect = Rect::new(area.x + (area.width - width) / 2, area.y + area.height - 1, width, 1);
+    frame.render_widget(Clear, rect);
+    frame.render_widget(Paragraph::new(text).style(app.palette.fg_style().add_modifier(Modifier::BOLD)), rect);
+}
+
+/// Scary clusters are always bold. While the cursor lingers by one, its
+/// digits turn red and a widening ring of the digits around it dims.
+fn dread_style(app: &App, col: usize, row: usize, now: Instant) -> Option<Style> {
+    let base = app.palette.fg_style();
+    let mut nearby = false;
+    for cluster in &app.scary_clusters {
+        let dread = cluster.dread(now);
+        if cluster.cells.contains(&(col, row)) {
+            let style = base.add_modifier(Modifier::BOLD);
+            return Some(if dread >= 0.5 { style.fg(Color::Red) } else { style });
+        }
+        let reach = (dread * DREAD_DIM_RADIUS as f32).round() as usize;
+        nearby |= cluster.distance((col, row)) <= reach && dread > 0.0;
+    }
+    nearby.then(|| base.add_modifier(Modifier::DIM))
+}
+
+/// How the grid animates this frame
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum GridMotion {
+    /// Eco mode, or the watchdog turned animation off: nothing moves
+    Still,
+    /// Each digit drifts on its own while the refiner is at work
+    Wiggle { time: f32 },
+    /// Once idle, the grid settles and slow waves of brightness roll down
+    /// it; `depth` fades the waves in from 0 to 1
+    Breathe { time: f32, depth: f32 },
+}
+
+impl GridMotion {
+    /// Only the wiggle moves digits about; breathing changes their brightness
+    fn wiggle(self) -> Option<f32> {
+        match self {
+            GridMotion::Wiggle { time } => Some(time),
+            _ => None,
+        }
+    }
 }
 
-/// Calculate the grid dimensions based on available area
-fn calculate_grid_dimensions(area: Rect) -> (u16, u16, u16, u16) {
+/// How the grid animates at animation step `step`
+fn grid_motion(app: &App, step: u32) -> GridMotion {
+    if app.reduce_motion() || app.station.frame_stats.quality < Quality::Full {
+        return GridMotion::Still;
+    }
+    let time = step as f32 * ANIMATION_STEP.as_secs_f32();
+    if app.is_idle() {
+        let idle_for = app.since_input().saturating_sub(IDLE_AFTER);
+        let depth = (idle_for.as_secs_f32() / BREATH_FADE_IN.as_secs_f32()).min(1.0);
+        GridMotion::Breathe { time, depth }
+    } else {
+        // The wiggle was tuned against the raw step count
+        GridMotion::Wiggle { time: step as f32 * 0.01 }
+    }
+}
+
+/// The last animation step at which cell `index` took its turn, when one
+/// cell in every `stride` moves each step
+fn staggered(step: u32, index: usize, stride: usize) -> u32 {
+    step.saturating_sub(((step as usize + index) % stride) as u32)
+}
+
+/// Brightness of one grid row mid-breath: a wave that takes
+/// `BREATH_PERIOD` to rise and fall, reaching each row a little later
+/// than the one above
+fn breath_style(base: Style, row: usize, time: f32, depth: f32) -> Style {
+    let phase = time / BREATH_PERIOD.as_secs_f32() * std::f32::consts::TAU - row as f32 * BREATH_ROW_LAG;
+    let level = 0.5 + depth * phase.sin() * 0.5;
+    if level > 0.7 {
+        base.add_modifier(Modifier::BOLD)
+    } else if level < 0.3 {
+        base.add_modifier(Modifier::DIM)
+    } else {
+        base
+    }
+}
+
+/// Calculate the grid dimensions based on available area; a `sparse` grid
+/// spaces its numbers twice as far apart
+fn calculate_grid_dimensions(area: Rect, sparse: bool) -> (u16, u16, u16, u16) {
     // Minimum spacing requirements
     let min_horizontal_spacing = 3;
     let min_vertical_spacing = 1;
@@ -535,6 +1084,10 @@ fn calculate_grid_dimensions(area: Rect) -> (u16, u16, u16, u16) {
         default_vertical_spacing 
     };
     
+    let thinning = if sparse { 2 } else { 1 };
+    let horizontal_spacing = horizontal_spacing * thinning;
+    let vertical_spacing = vertical_spacing * thinning;
+
     // Calculate max columns and rows that will fit
     let max_width = area.width.saturating_sub(2);
     let max_height = area.height.saturating_sub(1);
@@ -556,13 +1109,35 @@ fn calculate_grid_dimensions(area: Rect) -> (u16, u16, u16, u16) {
 
 /// Get the digit to display at a specific position
 fn get_digit(app: &App, col: usize, row: usize, rng: &mut StdRng) -> u16 {
-    if let Some(replaced_digit) = app.get_replaced_number(col, row) {
-        replaced_digit
+    // Always draw from the seed, so a replaced cell leaves the digits after
+    // it where they were
+    let seeded = rng.random_range(0..=9);
+    app.grid.digit((col, row), seeded)
+}
+
+/// Grid render filter: what to draw for a digit at a screen cell. Redacted
+/// mode blacks out everything outside the cursor's reach; the digit itself
+/// is untouched, so clicks still refine what is really there.
+fn digit_glyph(app: &App, x: u16, y: u16, digit: u16) -> char {
+    let near_cursor = app.mouse_position.is_some_and(|(mouse_x, mouse_y)| {
+        let dx = (x as f32) - (mouse_x as f32);
+        let dy = (y as f32) - (mouse_y as f32);
+        (dx * dx + dy * dy).sqrt() < REDACTION_RADIUS
+    });
+    if app.station.redacted && !near_cursor {
+        '█'
     } else {
-        rng.random_range(0..=9)
+        char::from_digit(digit as u32, 10).unwrap_or('?')
     }
 }
 
+/// A held sticky number shows how close it is to coming loose
+fn hold_glyph(app: &App, col: usize, row: usize, now: Instant) -> Option<char> {
+    let hold = app.hold.as_ref().filter(|hold| hold.cells.iter().any(|&(c, r, _)| (c, r) == (col, row)))?;
+    let glyphs = if app.station.ascii_only { HOLD_GLYPHS_ASCII } else { HOLD_GLYPHS };
+    Some(glyphs[((hold.progress(now) * 4.0) as usize).min(3)])
+}
+
 /// Calculate the position of a number in the grid, including animation
 fn calculate_number_position(
     col: usize, 
@@ -570,7 +1145,7 @@ fn calculate_number_position(
     area: Rect, 
     horizontal_spacing: u16, 
     vertical_spacing: u16,
-    time: f32,
+    time: Option<f32>,
     digit: u16
 ) -> (u16, u16) {
     // Calculate base position
@@ -581,10 +1156,10 @@ fn calculate_number_position(
     let unique_seed = (row as f32 * 0.73) + (col as f32 * 0.37) + (digit as f32 * 0.19);
     
     // Determine movement direction
-    let moves_horizontally = ((row + col + digit as usize) % 2) == 0;
+    let moves_horizontally = (row + col + digit as usize).is_multiple_of(2);
     
     // Calculate animation movement
-    let movement = (time + unique_seed).sin() * 0.8;
+    let movement = time.map_or(0.0, |time| (time + unique_seed).sin() * 0.8);
     
     // Apply movement to either horizontal or vertical, but not both
     let x_offset = if moves_horizontally { movement.round() as i16 } else { 0 };
@@ -627,7 +1202,7 @@ fn calculate_scale_factor(app: &App, x: u16, y: u16) -> f32 {
 
 /// Check if click was in the grid area
 fn is_click_in_grid_area(app: &App, area: Rect) -> bool {
-    app.last_clicked.map_or(false, |(cx, cy)| {
+    app.last_clicked.is_some_and(|(cx, cy)| {
         cx >= area.x && cx < area.x + area.width && 
         cy >= area.y && cy < area.y + area.height
     })
@@ -638,10 +1213,10 @@ fn render_digit<B: Backend>(
     frame: &mut Frame<B>, 
     x: u16, 
     y: u16, 
-    digit: u16, 
+    glyph: char, 
     scale_factor: f32, 
     area: Rect,
-    app: &App
+    style: Style
 ) {
     // Make sure we're still within bounds
     if x < area.x + area.width && y < area.y + area.height {
@@ -665,51 +1240,29 @@ fn render_digit<B: Backend>(
                 
                 for &pos in &positions {
                     let digit_rect = Rect::new(pos.0, pos.1, 1, 1);
-                    let digit_text = Paragraph::new(format!("{}", digit))
-                        .style(app.palette.fg_style());
+                    let digit_text = Paragraph::new(glyph.to_string())
+                        .style(style);
                     frame.render_widget(digit_text, digit_rect);
                 }
             } else {
                 // Default: just render at normal size
-                render_single_digit(frame, x, y, digit, app);
+                render_single_digit(frame, x, y, glyph, style);
             }
         } else {
             // No scaling - render as normal
-            render_single_digit(frame, x, y, digit, app);
+            render_single_digit(frame, x, y, glyph, style);
         }
     }
 }
 
 /// Render a single digit at the specified position
-fn render_single_digit<B: Backend>(frame: &mut Frame<B>, x: u16, y: u16, digit: u16, app: &App) {
+fn render_single_digit<B: Backend>(frame: &mut Frame<B>, x: u16, y: u16, glyph: char, style: Style) {
     let digit_rect = Rect::new(x, y, 1, 1);
-    let digit_text = Paragraph::new(format!("{}", digit))
-        .style(app.palette.fg_style());
+    let digit_text = Paragraph::new(glyph.to_string())
+        .style(style);
     frame.render_widget(digit_text, digit_rect);
 }
 
-/// Process clicked numbers and update the app state
-fn process_clicked_numbers(app: &App, magnified_positions: Vec<(usize, usize, u16)>) {
-    if !magnified_positions.is_empty() && app.last_clicked.is_some() {
-        // Sum all collected magnified numbers
-        let sum: u16 = magnified_positions.iter().map(|&(_, _, digit)| digit).sum();
-        
-        // Extract just the positions for replacing
-        let positions_to_replace: Vec<(usize, usize)> = 
-            magnified_positions.iter().map(|&(col, row, _)| (col, row)).collect();
-        
-        // Add to a random non-full container and replace numbers
-        let app_ptr = app as *const App as *mut App;
-        unsafe {
-            // Add the sum to a container
-            (*app_ptr).add_to_random_non_full_container(sum);
-            
-            // Replace each collected number with a new random one
-            (*app_ptr).replace_numbers(positions_to_replace);
-        }
-    }
-}
-
 /// Draw a horizontal divider line that spans the full width of the screen
 fn draw_horizontal_divider<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App, thick: bool) {
     // Create a horizontal line using appropriate box drawing characters
@@ -2,30 +2,151 @@
 use ratatui::{
     Frame,
     backend::Backend,
-    widgets::Block,
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
 };
+use std::time::Duration;
 
-use crate::app::{App, AppState};
+use crate::ambience::{self, Ambience};
+use crate::animate::Timeline;
+use crate::app::App;
+use crate::screen::{Screen, with_screen};
+use crate::theme::Palette;
 
+mod big_text;
+mod break_screen;
+mod changelog;
+mod context_menu;
+mod cheats;
+mod compliance;
+mod debug_hud;
+mod error;
+mod inspection;
+pub mod layout;
 mod loading;
+mod lock;
 mod main_screen;
 mod login;
 mod prize;
+mod recap;
+mod report;
+mod reset_menu;
+mod setup;
+mod side_panel;
+pub mod widgets;
 
-pub use loading::LOADING_MESSAGES;
+pub use break_screen::BreakScreen;
+pub use changelog::ChangelogScreen;
+pub use error::ErrorScreen;
+pub use loading::LoadingScreen;
+pub use lock::LockScreen;
+pub use login::LoginScreen;
+pub use main_screen::MainScreen;
+pub use prize::PrizeScreen;
+pub use recap::RecapScreen;
+pub use report::ReportScreen;
+pub use setup::SetupScreen;
+
+/// Smallest terminal anything is drawn in
+const MIN_WIDTH: u16 = 20;
+const MIN_HEIGHT: u16 = 10;
+
+/// Broad size classes the screens adapt their layout to
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LayoutProfile {
+    /// Below the main screen's minimum; only simplified views fit
+    Compact,
+    /// Usable, but smaller than the optimal window size
+    Standard,
+    /// At or above the optimal window size
+    Full,
+}
+
+impl LayoutProfile {
+    pub fn for_area(area: Rect) -> Self {
+        use crate::input::{DESIRED_WIDTH, DESIRED_HEIGHT};
+        if area.width < 50 || area.height < 20 {
+            LayoutProfile::Compact
+        } else if area.width < DESIRED_WIDTH || area.height < DESIRED_HEIGHT {
+            LayoutProfile::Standard
+        } else {
+            LayoutProfile::Full
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LayoutProfile::Compact => "compact",
+            LayoutProfile::Standard => "standard",
+            LayoutProfile::Full => "full",
+        }
+    }
+}
+
+/// Work out where the screen's parts go in `area` before drawing it; the
+/// size checks match `draw`'s, so nothing is laid out that is not drawn
+pub fn layout(app: &mut App, area: Rect) {
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT || app.show_size_warning {
+        return;
+    }
+    with_screen!(app.state(), screen => screen.layout(app, area));
+}
 
 /// Main drawing function for the UI
 pub fn draw<B: Backend>(frame: &mut Frame<B>, app: &App) {
+    draw_screen(frame, app);
+    if app.is_mono() {
+        frame.render_widget(Monochrome, frame.size());
+    }
+}
+
+/// Strips every colour from what was drawn. Anything set off by its
+/// background, such as a toast, is shown in reverse video instead.
+struct Monochrome;
+
+impl Widget for Monochrome {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                if cell.bg != Color::Reset {
+                    cell.modifier.insert(Modifier::REVERSED);
+                }
+                cell.fg = Color::Reset;
+                cell.bg = Color::Reset;
+            }
+        }
+    }
+}
+
+/// The floor's lights dipping: everything drawn fades to the faintest
+/// shade of the palette
+struct Flicker(Palette);
+
+impl Widget for Flicker {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let faint = self.0.ramp(0.0);
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                cell.fg = faint;
+                cell.modifier.insert(Modifier::DIM);
+            }
+        }
+    }
+}
+
+fn draw_screen<B: Backend>(frame: &mut Frame<B>, app: &App) {
     let area = frame.size();
 
     // Set background
     frame.render_widget(Block::default().style(app.palette.bg_style()), area);
 
     // Check if terminal is too small for any UI
-    let absolute_min_width = 20;
-    let absolute_min_height = 10;
-    
-    if area.width < absolute_min_width || area.height < absolute_min_height {
+    if area.width < MIN_WIDTH || area.height < MIN_HEIGHT {
         // Draw a minimal message for extremely small terminals
         let min_message = "Terminal\ntoo small";
         let min_widget = ratatui::widgets::Paragraph::new(min_message)
@@ -43,7 +164,7 @@ pub fn draw<B: Backend>(frame: &mut Frame<B>, app: &App) {
         use crate::input::{DESIRED_WIDTH, DESIRED_HEIGHT};
         let warning = format!(
             "⚠️ Window Size Warning ⚠️\n\nOptimal size: {}x{}\nCurrent size: {}x{}\n\nPress any key to continue",
-            DESIRED_WIDTH, DESIRED_HEIGHT, app.current_width, app.current_height
+            DESIRED_WIDTH, DESIRED_HEIGHT, app.station.current_width, app.station.current_height
         );
         
         // Create a floating box in the center of the screen
@@ -70,10 +191,145 @@ pub fn draw<B: Backend>(frame: &mut Frame<B>, app: &App) {
     }
 
     // Draw appropriate screen based on app state
-    match app.state {
-        AppState::Login => login::draw_login_screen(frame, area, app),
-        AppState::Loading => loading::draw_loading_screen(frame, area, app),
-        AppState::Main => main_screen::draw_main_screen(frame, area, app),
-        AppState::Prize => prize::draw_prize_screen(frame, area, app),
+    with_screen!(app.state(), screen => screen.render(frame, area, app));
+
+    if app.read_only {
+        draw_observer_banner(frame, area, app);
     }
-} 
\ No newline at end of file
+
+    // Debug overlay goes on top of everything else
+    if app.station.show_debug_hud {
+        debug_hud::draw_debug_hud(frame, area, app);
+    }
+}
+
+/// Marks the screen as a read-only mirror of another session
+fn draw_observer_banner<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let text = if app.username.is_empty() {
+        " OBSERVING · WAITING FOR SESSION "
+    } else {
+        " OBSERVING · READ ONLY "
+    };
+    let width = (text.chars().count() as u16).min(area.width);
+    let banner_area = Rect::new(area.x + area.width - width, area.y, width, 1);
+
+    let banner = Paragraph::new(text)
+        .style(Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD));
+    frame.render_widget(Clear, banner_area);
+    frame.render_widget(banner, banner_area);
+}
+
+/// A notice floating above the bottom edge of the main screen
+fn draw_toast<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let Some(toast) = &app.toast else { return };
+    let text = format!(" {} ", toast.text);
+    let width = (text.chars().count() as u16).min(area.width);
+    let toast_area = Rect::new(area.x + (area.width - width) / 2, area.y + area.height.saturating_sub(3), width, 1);
+
+    let widget = Paragraph::new(text)
+        .style(Style::default().fg(Color::Black).bg(Color::Cyan).add_modifier(Modifier::BOLD));
+    frame.render_widget(Clear, toast_area);
+    frame.render_widget(widget, toast_area);
+}
+
+/// A combo's banner across the top of the grid, flashing as it lands
+fn draw_combo_banner<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    /// Each flash lasts this long
+    const FLASH: Duration = Duration::from_millis(150);
+
+    let Some((combo, at)) = app.combo else { return };
+    let star = if app.station.ascii_only { '*' } else { '★' };
+    let text = format!("{} {} {}", star, combo, star);
+    let width = (text.chars().count() as u16 + 4).min(area.width);
+    // The grid's last known spot may be stale right after a resize
+    let over = app
+        .grid_geometry
+        .map(|geometry| geometry.area)
+        .filter(|grid| area.intersects(*grid))
+        .map_or(area, |grid| grid.intersection(area));
+    if over.height < 4 {
+        return;
+    }
+    let banner_area = Rect::new(over.x + over.width.saturating_sub(width) / 2, over.y + 1, width.min(over.width), 3);
+
+    let lit = Timeline::flashing(FLASH, 2).value(app.clock.now().duration_since(at)) >= 0.5;
+    let mut style = app.palette.fg_style().add_modifier(Modifier::BOLD);
+    if lit && !app.is_mono() {
+        style = style.add_modifier(Modifier::REVERSED);
+    }
+    let banner = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .style(style)
+        .block(Block::default().borders(Borders::ALL).style(app.palette.fg_style().add_modifier(Modifier::BOLD)));
+    frame.render_widget(Clear, banner_area);
+    frame.render_widget(banner, banner_area);
+}
+
+/// The ambient event on the board: the lights flickering, or a PSA
+/// along the top of the grid
+fn draw_ambience<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let Some((ambience, since)) = app.ambient else { return };
+    match ambience {
+        Ambience::Flicker => {
+            if ambience::flicker().value(app.clock.now().duration_since(since)) >= 0.5 {
+                frame.render_widget(Flicker(app.palette), area);
+            }
+        },
+        Ambience::Psa => {
+            let over = app
+                .grid_geometry
+                .map(|geometry| geometry.area)
+                .filter(|grid| area.intersects(*grid))
+                .map_or(area, |grid| grid.intersection(area));
+            let text = format!(" {} ", ambience::PSA);
+            let width = (text.chars().count() as u16).min(over.width);
+            let banner_area = Rect::new(over.x + (over.width - width) / 2, over.y, width, 1);
+            let banner = Paragraph::new(text)
+                .style(Style::default().fg(Color::Black).bg(Color::Yellow).add_modifier(Modifier::BOLD));
+            frame.render_widget(Clear, banner_area);
+            frame.render_widget(banner, banner_area);
+        },
+        Ambience::Chime | Ambience::Memo => {},
+    }
+}
+
+/// A hint for a new refiner, or Orientation's current instruction, boxed
+/// along the bottom of the grid
+fn draw_coach_mark<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let text = match (app.orientation, app.onboarding.showing()) {
+        (Some(step), _) => step.instruction(),
+        (None, Some(lesson)) => lesson.hint(),
+        (None, None) => return,
+    };
+    let width = (text.chars().count() as u16 + 4).min(area.width);
+    let over = app
+        .grid_geometry
+        .map(|geometry| geometry.area)
+        .filter(|grid| area.intersects(*grid))
+        .map_or(area, |grid| grid.intersection(area));
+    if over.height < 3 {
+        return;
+    }
+    let mark_area = Rect::new(over.x + over.width.saturating_sub(width) / 2, over.y + over.height - 3, width.min(over.width), 3);
+
+    let mark = Paragraph::new(text)
+        .alignment(Alignment::Center)
+        .style(app.palette.fg_style().add_modifier(Modifier::BOLD))
+        .block(Block::default().borders(Borders::ALL).style(app.palette.fg_style()));
+    frame.render_widget(Clear, mark_area);
+    frame.render_widget(mark, mark_area);
+}
+
+/// The `:` prompt along the bottom row
+fn draw_command_line<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let Some(line) = &app.command_line else { return };
+    let prompt_area = Rect::new(area.x, area.y + area.height - 1, area.width, 1);
+
+    let prompt = Paragraph::new(Spans::from(vec![
+        Span::styled(format!(":{}", line), app.palette.fg_style()),
+        Span::styled(" ", Style::default().bg(Color::White)),
+    ]))
+    .style(app.palette.bg_style());
+    frame.render_widget(Clear, prompt_area);
+    frame.render_widget(prompt, prompt_area);
+}
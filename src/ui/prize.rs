@@ -6,8 +6,55 @@ use ratatui::{
     text::{Span, Spans},
     widgets::Paragraph,
 };
+use crossterm::event::KeyCode;
+use std::time::{Duration, Instant};
 
 use crate::app::App;
+use crate::app::AppState;
+use crate::screen::Screen;
+use super::big_text::{self, GLYPH_HEIGHT};
+
+/// The finished file's prize
+pub struct PrizeScreen;
+
+impl Screen for PrizeScreen {
+    fn handle_key(&self, app: &mut App, key: KeyCode) {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => {
+                app.running = false;
+            },
+            KeyCode::Char('c') => {
+                app.prize_auto_reset_at = None;
+                app.copy_report();
+            },
+            KeyCode::Char('s') => {
+                app.prize_auto_reset_at = None;
+                app.copy_card();
+            },
+            KeyCode::Char('r') | KeyCode::Enter | KeyCode::Char(' ') => {
+                // Start the next file and go back to the main screen
+                app.new_file();
+                app.go_to(AppState::Main);
+            },
+            // Any other key keeps the prize on screen
+            _ => app.prize_auto_reset_at = None,
+        }
+    }
+
+    fn tick(&self, app: &mut App, now: Instant) {
+        // Kiosk mode: start the next file without anyone touching a key
+        if app.prize_auto_reset_at.is_some_and(|at| now >= at) {
+            app.new_file();
+            app.go_to(AppState::Main);
+        }
+    }
+
+    fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, app: &App) {
+        draw_prize_screen(frame, area, app);
+        // Copying the report is confirmed where it was copied from
+        super::draw_toast(frame, area, app);
+    }
+}
 
 /// Draws the prize screen that appears when all containers reach 100%
 pub fn draw_prize_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
@@ -23,9 +70,11 @@ pub fn draw_prize_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App
             Constraint::Length(1),   // Divider
             Constraint::Length(if is_small_window { 0 } else { 5 }),   // Trophy/celebration graphic (skip in small windows)
             Constraint::Length(if is_small_window { 1 } else { 2 }),   // Space
-            Constraint::Length(3),   // Prize announcement
+            Constraint::Length(if app.prize_memo.is_some() { 4 } else { 3 }),   // Prize announcement and memo
             Constraint::Length(1),   // Space
             Constraint::Length(2),   // Instructions
+            Constraint::Length(1),   // Space
+            Constraint::Length(if is_small_window { 1 } else { GLYPH_HEIGHT + 1 }),   // Auto-reset countdown
             Constraint::Min(0),      // Remaining space
         ])
         .split(area);
@@ -45,7 +94,7 @@ pub fn draw_prize_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App
     }
 
     // Draw prize announcement
-    let prize_text = vec![
+    let mut prize_text = vec![
         Spans::from(Span::styled(
             format!("Employee {} has been awarded:", app.username),
             app.palette.fg_style()
@@ -56,6 +105,9 @@ pub fn draw_prize_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App
             Style::default().fg(Color::Green).add_modifier(Modifier::BOLD)
         )),
     ];
+    if let Some(memo) = &app.prize_memo {
+        prize_text.push(Spans::from(Span::styled(memo.as_str(), app.palette.fg_style())));
+    }
     
     let prize_para = Paragraph::new(prize_text)
         .alignment(Alignment::Center);
@@ -65,7 +117,7 @@ pub fn draw_prize_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App
     let instructions = if is_small_window {
         vec![
             Spans::from(Span::styled(
-                "Press [R]/[ENTER] to reset, [Q]/[ESC] to exit",
+                "[R]/[ENTER] reset, [C] report, [S] card, [Q]/[ESC] exit",
                 app.palette.fg_style()
             ))
         ]
@@ -76,7 +128,7 @@ pub fn draw_prize_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App
                 app.palette.fg_style()
             )),
             Spans::from(Span::styled(
-                "Press [Q] or [ESC] to exit",
+                "Press [C] to copy the report, [S] the share card, [Q] or [ESC] to exit",
                 app.palette.fg_style()
             )),
         ]
@@ -85,6 +137,40 @@ pub fn draw_prize_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App
     let instructions_para = Paragraph::new(instructions)
         .alignment(Alignment::Center);
     frame.render_widget(instructions_para, layout[6]);
+
+    if let Some(remaining) = app.prize_countdown() {
+        draw_countdown(frame, layout[8], app, remaining, is_small_window);
+    }
+}
+
+/// Draw the kiosk auto-reset countdown in whole seconds, rounded up
+fn draw_countdown<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App, remaining: Duration, is_small_window: bool) {
+    let seconds = remaining.as_millis().div_ceil(1000);
+
+    if is_small_window {
+        let line = Paragraph::new(format!("New file in {}s - press any key to stay", seconds))
+            .alignment(Alignment::Center)
+            .style(app.palette.fg_style());
+        frame.render_widget(line, area);
+        return;
+    }
+
+    let parts = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(1), Constraint::Min(0)])
+        .split(area);
+
+    let caption = Paragraph::new("A fresh file will be assigned shortly. Press any key to remain.")
+        .alignment(Alignment::Center)
+        .style(app.palette.fg_style());
+    frame.render_widget(caption, parts[0]);
+
+    big_text::draw_big_text(
+        frame,
+        parts[1],
+        &seconds.to_string(),
+        Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+    );
 }
 
 /// Draw a divider line
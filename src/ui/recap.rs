@@ -0,0 +1,122 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+use crossterm::event::KeyCode;
+
+use crate::app::App;
+use crate::app::AppState;
+use crate::screen::Screen;
+use crate::ledger::WEEKDAYS;
+use super::widgets::{Bar, BarChart, ChartStyle, Sparkline};
+
+/// Widest the daily bars are allowed to grow
+const MAX_BAR_WIDTH: u16 = 50;
+/// Days of history in the sparkline, ending with the recapped week
+const HISTORY_DAYS: i64 = 28;
+
+/// Last week's numbers, until any key moves on to the login
+pub struct RecapScreen;
+
+impl Screen for RecapScreen {
+    fn handle_key(&self, app: &mut App, _key: KeyCode) {
+        app.go_to(AppState::Login);
+    }
+
+    fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, app: &App) {
+        draw_recap_screen(frame, area, app);
+    }
+}
+
+/// Draws last week's quota recap, shown before login
+pub fn draw_recap_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let Some(recap) = &app.recap else { return };
+    let is_small_window = area.height < 20;
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(if is_small_window { 1 } else { 2 })
+        .constraints([
+            Constraint::Length(2),   // Title
+            Constraint::Length(1),   // Divider
+            Constraint::Length(if is_small_window { 0 } else { 1 }),   // Space
+            Constraint::Length(8),   // Daily chart
+            Constraint::Length(4),   // Totals
+            Constraint::Length(1),   // Instructions
+            Constraint::Min(0),      // Remaining space
+        ])
+        .split(area);
+
+    let title = Paragraph::new(format!("WEEKLY QUOTA RECAP \u{00b7} {}", recap.week))
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    frame.render_widget(title, layout[0]);
+
+    let divider: String = "━".repeat(layout[1].width as usize);
+    frame.render_widget(Paragraph::new(divider).style(app.palette.fg_style()), layout[1]);
+
+    // One bar per day, scaled to the busiest, then four weeks of context
+    let chart_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(7), Constraint::Length(1)])
+        .split(layout[3]);
+    let bars = recap
+        .days
+        .iter()
+        .zip(WEEKDAYS)
+        .map(|(day, name)| {
+            let files = match day.files {
+                0 => String::new(),
+                1 => ", 1 file".to_string(),
+                n => format!(", {} files", n),
+            };
+            Bar::new(&name[..3], day.numbers).note(format!("{}{}", day.numbers, files))
+        })
+        .collect();
+    let chart = BarChart::new(bars, ChartStyle::for_app(app, Color::Cyan)).max_width(MAX_BAR_WIDTH);
+    frame.render_widget(chart, chart_layout[0]);
+
+    let history: Vec<u64> = app
+        .station
+        .ledger
+        .daily(recap.monday + 7 - HISTORY_DAYS, recap.monday + 7)
+        .iter()
+        .map(|day| day.numbers)
+        .collect();
+    let label = "Last 4 weeks ";
+    let trend = chart_layout[1];
+    frame.render_widget(Paragraph::new(label).style(app.palette.fg_style()), trend);
+    let spark_area = Rect { x: trend.x + label.len() as u16, width: trend.width.saturating_sub(label.len() as u16), ..trend };
+    frame.render_widget(Sparkline::new(&history, ChartStyle::for_app(app, Color::Cyan)), spark_area);
+
+    let best_day = recap.best_day.map_or("none", |idx| WEEKDAYS[idx]);
+    let streak = match recap.streak {
+        1 => "1 day".to_string(),
+        n => format!("{} days", n),
+    };
+    let totals = vec![
+        Spans::from(Span::styled(
+            format!(
+                "Files completed: {}   Numbers refined: {}   Bins filled: {}",
+                recap.total.files, recap.total.numbers, recap.total.bins
+            ),
+            app.palette.fg_style(),
+        )),
+        Spans::from(Span::styled(format!("Best day: {}   Longest streak: {}", best_day, streak), app.palette.fg_style())),
+        Spans::from(""),
+        Spans::from(Span::styled(
+            "The Board thanks you for your diligence.",
+            Style::default().fg(Color::Green).add_modifier(Modifier::BOLD),
+        )),
+    ];
+    frame.render_widget(Paragraph::new(totals).alignment(Alignment::Center), layout[4]);
+
+    let instructions = Paragraph::new("Press any key to continue to login")
+        .alignment(Alignment::Center)
+        .style(app.palette.fg_style());
+    frame.render_widget(instructions, layout[5]);
+}
@@ -0,0 +1,133 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect, Alignment},
+    style::{Color, Style, Modifier},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use crossterm::event::KeyCode;
+use crate::app::App;
+use crate::app::AppState;
+use crate::screen::Screen;
+use crate::stats::format_duration;
+use super::widgets::{Bar, BarChart, ChartStyle};
+
+/// Widest the balance bars are allowed to grow
+const MAX_BAR_WIDTH: u16 = 60;
+
+/// The work-life balance report, opened with `s`
+pub struct ReportScreen;
+
+impl Screen for ReportScreen {
+    fn handle_key(&self, app: &mut App, key: KeyCode) {
+        match key {
+            KeyCode::Char('q') => {
+                app.running = false;
+            },
+            KeyCode::Char('c') => app.copy_report(),
+            // Any other key goes back to work
+            _ => {
+                app.go_to(AppState::Main);
+            },
+        }
+    }
+
+    fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, app: &App) {
+        draw_report_screen(frame, area, app);
+        // Copying the report is confirmed where it was copied from
+        super::draw_toast(frame, area, app);
+    }
+}
+
+/// Draws the innie/outie work-life balance report
+pub fn draw_report_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let is_small_window = area.height < 15;
+    let balance = app.station.stats.balance(&app.username);
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(if is_small_window { 1 } else { 2 })
+        .constraints([
+            Constraint::Length(3),   // Title
+            Constraint::Length(1),   // Divider
+            Constraint::Length(if is_small_window { 0 } else { 2 }),   // Space
+            Constraint::Length(5),   // Balance chart
+            Constraint::Length(3),   // Verdict
+            Constraint::Length(1),   // Instructions
+            Constraint::Min(0),      // Journal
+        ])
+        .split(area);
+
+    let title = Paragraph::new(format!("WORK/LIFE BALANCE REPORT: {}", app.username.to_uppercase()))
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    frame.render_widget(title, layout[0]);
+    // Under the title, how to put this file in front of someone else
+    if layout[0].height > 1 {
+        let code_area = Rect { y: layout[0].y + 1, height: 1, ..layout[0] };
+        let code = Paragraph::new(format!("Share code: {}", app.share_code()))
+            .alignment(Alignment::Center)
+            .style(app.palette.fg_style());
+        frame.render_widget(code, code_area);
+    }
+
+    let divider: String = "━".repeat(layout[1].width as usize);
+    frame.render_widget(Paragraph::new(divider).style(app.palette.fg_style()), layout[1]);
+
+    // Both bars share one scale so their lengths compare directly
+    let chart_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(4), Constraint::Length(1)])
+        .split(layout[3]);
+    let bars = vec![
+        Bar::new("Innie", balance.innie.as_millis() as u64)
+            .note(format_duration(balance.innie))
+            .color(Color::Cyan),
+        Bar::new("Outie", balance.outie.as_millis() as u64)
+            .note(format_duration(balance.outie))
+            .color(Color::Magenta),
+    ];
+    let chart = BarChart::new(bars, ChartStyle::for_app(app, Color::Cyan)).max_width(MAX_BAR_WIDTH).gap(1);
+    frame.render_widget(chart, chart_layout[0]);
+
+    let share = Paragraph::new(format!("Innie share: {:.0}%", balance.innie_share() * 100.0)).style(app.palette.fg_style());
+    frame.render_widget(share, chart_layout[1]);
+
+    let verdict = Paragraph::new(balance.verdict())
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Green).add_modifier(Modifier::BOLD))
+        .wrap(Wrap { trim: true });
+    frame.render_widget(verdict, layout[4]);
+
+    let instructions = Paragraph::new("Press [C] to copy the report, any other key to return to work, [Q] to exit")
+        .alignment(Alignment::Center)
+        .style(app.palette.fg_style());
+    frame.render_widget(instructions, layout[5]);
+
+    draw_journal(frame, layout[6], app);
+}
+
+/// The session's notes, latest at the bottom, in a box under the report
+fn draw_journal<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    // Room for the box and at least one note
+    if app.journal.is_empty() || area.height < 3 {
+        return;
+    }
+    let area = Rect { y: area.y + 1, height: area.height - 1, ..area };
+    let shown = (area.height - 2) as usize;
+    let offset = app.utc_offset();
+    let lines: Vec<String> = app
+        .journal
+        .notes
+        .iter()
+        .skip(app.journal.len().saturating_sub(shown))
+        .map(|note| format!("{}  {}", note.stamp(offset), note.text))
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!(" Journal ({}) ", app.journal.len()))
+        .style(app.palette.fg_style());
+    frame.render_widget(Paragraph::new(lines.join("\n")).block(block), area);
+}
+
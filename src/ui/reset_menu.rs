@@ -0,0 +1,86 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::app::{Answer, App, HOLD_TO_CONFIRM, ResetScope};
+use crate::config::ConfirmStyle;
+use super::widgets::{ChartStyle, HoldGauge, button_row, focus_style};
+
+/// The reset menu, or the question it is asking, boxed in the middle of
+/// the main screen
+pub fn draw_reset_menu<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let Some(menu) = &app.reset_menu else { return };
+    let fg = app.palette.fg_style();
+
+    let hold_to_confirm = app.station.config.reset_confirm == ConfirmStyle::Hold;
+    let (title, lines) = match menu.confirming {
+        Some(scope) => {
+            let (verb, title) = match scope {
+                ResetScope::Reshuffle => ("reshuffle", " CONFIRM RESHUFFLE "),
+                _ => ("reset", " CONFIRM RESET "),
+            };
+            let label = |answer| match answer {
+                Answer::Yes if hold_to_confirm => format!("Hold [y] to {}", verb),
+                Answer::Yes => format!("[y] Yes, {}", verb),
+                Answer::No => "[n] No, go back".to_string(),
+            };
+            let mut lines = vec![
+                Spans::from(Span::styled(format!(" {}", scope.warning()), fg.add_modifier(Modifier::BOLD))),
+                Spans::from(""),
+                button_row(&menu.answer, label, fg),
+            ];
+            if hold_to_confirm {
+                // Room for the gauge
+                lines.push(Spans::from(""));
+            }
+            (title, lines)
+        },
+        None => {
+            let mut lines: Vec<Spans> = ResetScope::ALL
+                .iter()
+                .enumerate()
+                .map(|(idx, &scope)| {
+                    let mut label = scope.label().to_string();
+                    if scope == ResetScope::Bin
+                        && let Some(bin) = app.focused_bin()
+                    {
+                        label = format!("Reset bin {}", bin + 1);
+                    }
+                    let style = focus_style(fg, idx == menu.selected);
+                    Spans::from(Span::styled(format!(" {}  {:<30}", idx + 1, label), style))
+                })
+                .collect();
+            lines.push(Spans::from(""));
+            lines.push(Spans::from(Span::styled(" Up/Down and Enter, or 1-4", fg.add_modifier(Modifier::DIM))));
+            (" RESET · Esc to close ", lines)
+        },
+    };
+
+    let width = 44.min(area.width);
+    let height = (lines.len() as u16 + 3).min(area.height);
+    let menu_area = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+
+    let border = if menu.confirming == Some(ResetScope::Factory) { Color::Red } else { Color::Yellow };
+    let widget = Paragraph::new(lines).style(app.palette.bg_style()).wrap(Wrap { trim: false }).block(
+        Block::default().borders(Borders::ALL).title(title).border_style(Style::default().fg(border)),
+    );
+    frame.render_widget(Clear, menu_area);
+    frame.render_widget(widget, menu_area);
+
+    // The gauge fills on the bottom row inside the box while Y is held
+    if hold_to_confirm && menu.confirming.is_some() && menu_area.height > 3 && menu_area.width > 4 {
+        let progress = menu.held.map_or(0.0, |held| held.progress(app.clock.now(), HOLD_TO_CONFIRM));
+        let gauge_area = Rect::new(menu_area.x + 2, menu_area.y + menu_area.height - 2, menu_area.width - 4, 1);
+        frame.render_widget(HoldGauge::new(progress, ChartStyle::for_app(app, border)), gauge_area);
+    }
+}
@@ -0,0 +1,108 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+use crossterm::event::KeyCode;
+
+use crate::app::App;
+use crate::screen::Screen;
+use crate::input::{DESIRED_HEIGHT, DESIRED_WIDTH};
+use crate::setup::SetupStep;
+
+/// The first-run questions
+pub struct SetupScreen;
+
+impl Screen for SetupScreen {
+    fn handle_key(&self, app: &mut App, key: KeyCode) {
+        app.on_setup_key(key);
+    }
+
+    fn render<B: Backend>(&self, frame: &mut Frame<B>, area: Rect, app: &App) {
+        draw_setup_screen(frame, area, app);
+    }
+}
+
+/// Draws the first-run questions, one step at a time
+pub fn draw_setup_screen<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let Some(setup) = &app.setup else { return };
+    let fg = app.palette.fg_style();
+    let is_small_window = area.height < 20;
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(if is_small_window { 1 } else { 2 })
+        .constraints([
+            Constraint::Length(2),   // Title
+            Constraint::Length(1),   // Divider
+            Constraint::Length(if is_small_window { 0 } else { 1 }),   // Space
+            Constraint::Length(2),   // Question
+            Constraint::Length(7),   // Answer
+            Constraint::Length(1),   // Instructions
+            Constraint::Min(0),      // Remaining space
+        ])
+        .split(area);
+
+    let title = Paragraph::new("WELCOME TO LUMON INDUSTRIES")
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+    frame.render_widget(title, layout[0]);
+
+    let divider: String = "━".repeat(layout[1].width as usize);
+    frame.render_widget(Paragraph::new(divider).style(fg), layout[1]);
+
+    let question = format!("Step {} of {}: {}", setup.step.index() + 1, SetupStep::ALL.len(), setup.step.title());
+    frame.render_widget(
+        Paragraph::new(question).alignment(Alignment::Center).style(fg.add_modifier(Modifier::BOLD)),
+        layout[3],
+    );
+
+    let themes: Vec<&str> = app.station.content.themes.iter().map(|theme| theme.name.as_str()).collect();
+    let answer: Vec<Spans> = match setup.step {
+        SetupStep::Terminal => {
+            let fits = app.station.current_width >= DESIRED_WIDTH && app.station.current_height >= DESIRED_HEIGHT;
+            let (verdict, colour) = if fits { ("That fits.", Color::Green) } else { ("Enlarge the window if you can.", Color::Yellow) };
+            vec![
+                Spans::from(Span::styled(format!("This window is {}x{}.", app.station.current_width, app.station.current_height), fg)),
+                Spans::from(Span::styled(format!("The floor is laid out for {}x{} or larger.", DESIRED_WIDTH, DESIRED_HEIGHT), fg)),
+                Spans::from(""),
+                Spans::from(Span::styled(verdict, Style::default().fg(colour).add_modifier(Modifier::BOLD))),
+            ]
+        },
+        SetupStep::Name => vec![
+            Spans::from(Span::styled("Filled in on the login screen. Leave it empty to type it each time.", fg)),
+            Spans::from(""),
+            Spans::from(Span::styled(format!(" {}_ ", setup.name), fg.add_modifier(Modifier::REVERSED))),
+        ],
+        _ => setup
+            .choices(&themes)
+            .into_iter()
+            .enumerate()
+            .map(|(idx, choice)| {
+                let style = if idx == setup.selected { fg.add_modifier(Modifier::REVERSED) } else { fg };
+                Spans::from(Span::styled(format!(" {:<40}", choice), style))
+            })
+            .collect(),
+    };
+    let answer_area = centered(layout[4], 72);
+    frame.render_widget(Paragraph::new(answer).alignment(Alignment::Center), answer_area);
+
+    let hint = match setup.step {
+        SetupStep::Theme => "Up/Down to choose, ENTER to continue, ESC to quit",
+        SetupStep::Mouse | SetupStep::Difficulty => "Up/Down to choose, ENTER to continue, ESC to go back",
+        SetupStep::Terminal => "ENTER to continue, ESC to go back",
+        SetupStep::Name => "ENTER to finish, ESC to go back",
+    };
+    frame.render_widget(
+        Paragraph::new(hint).alignment(Alignment::Center).style(fg.add_modifier(Modifier::DIM)),
+        layout[5],
+    );
+}
+
+fn centered(area: Rect, width: u16) -> Rect {
+    let width = width.min(area.width);
+    Rect { x: area.x + (area.width - width) / 2, width, ..area }
+}
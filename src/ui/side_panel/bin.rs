@@ -0,0 +1,67 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier},
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+
+use crate::app::{App, KEYBOARD_DEPOSIT};
+use crate::temper::Temper;
+use crate::ui::widgets::{Bar, BarChart, ChartStyle};
+
+/// The focused bin, opened out: its temper breakdown, the latest deposits
+/// and the keys that work on it
+pub fn draw_bin<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let Some(container) = app.focused_bin().and_then(|bin| app.containers.get(bin)) else { return };
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),   // Temper heading
+            Constraint::Length(4),   // Temper breakdown
+            Constraint::Length(1),   // Space
+            Constraint::Length(1),   // Deposits heading
+            Constraint::Min(1),      // Deposits log
+            Constraint::Length(1),   // Keys
+        ])
+        .split(area);
+
+    let heading = app.palette.fg_style().add_modifier(Modifier::BOLD);
+    frame.render_widget(Paragraph::new(Span::styled("Temper", heading)), layout[0]);
+
+    let total: u32 = container.tempers.iter().map(|&amount| amount as u32).sum();
+    let bars = Temper::ALL
+        .iter()
+        .map(|&temper| {
+            let amount = container.tempers[temper.index()];
+            let share = (amount as u32 * 100).checked_div(total).unwrap_or(0);
+            Bar::new(temper.code(), amount as u64).note(format!("{}%", share))
+        })
+        .collect();
+    frame.render_widget(BarChart::new(bars, ChartStyle::for_app(app, Color::Cyan)), layout[1]);
+
+    frame.render_widget(Paragraph::new(Span::styled("Recent deposits", heading)), layout[3]);
+    let now = app.clock.now();
+    let log: Vec<Spans> = if container.deposits.is_empty() {
+        vec![Spans::from(Span::styled("None this session", app.palette.fg_style()))]
+    } else {
+        container
+            .deposits
+            .iter()
+            .rev()
+            .map(|deposit| {
+                let ago = now.saturating_duration_since(deposit.at).as_secs();
+                Spans::from(Span::styled(
+                    format!("+{:<3} {:<7} {}s ago", deposit.value, deposit.temper.name(), ago),
+                    app.palette.fg_style(),
+                ))
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(log), layout[4]);
+
+    let keys = format!("[Enter] deposit {}  [i] inspect  [Tab] next bin", KEYBOARD_DEPOSIT);
+    frame.render_widget(Paragraph::new(keys).style(app.palette.fg_style()), layout[5]);
+}
@@ -0,0 +1,105 @@
+//! The main screen's side panel, to the right of the grid. `p` steps
+//! through the views; Tab opens the bin view on a bin.
+
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+
+use crate::app::{App, SidePanel};
+
+mod bin;
+mod stats;
+
+/// Draws `panel` boxed in `area`
+pub fn draw_side_panel<B: Backend>(frame: &mut Frame<B>, area: Rect, panel: SidePanel, app: &App) {
+    let title = match (panel, app.focused_bin()) {
+        (SidePanel::Bin, Some(bin)) => format!(" BIN 0{} \u{00b7} {}% ", bin + 1, app.containers[bin].count),
+        _ => format!(" {} ", panel.title()),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .style(app.palette.fg_style());
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)])
+        .split(inner);
+
+    match panel {
+        SidePanel::Stats => stats::draw_stats(frame, layout[0], app),
+        SidePanel::Goals => draw_goals(frame, layout[0], app),
+        SidePanel::Bin => bin::draw_bin(frame, layout[0], app),
+        SidePanel::Inbox => draw_inbox(frame, layout[0], app),
+        SidePanel::Log => draw_log(frame, layout[0], app),
+    }
+
+    let keys = Paragraph::new("[p] next panel  [Esc] close").style(app.palette.fg_style());
+    frame.render_widget(keys, layout[1]);
+}
+
+/// The session goals, ticked as they are met
+fn draw_goals<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let goals = &app.goals;
+    let lines: Vec<Spans> = if goals.items.is_empty() {
+        vec![Spans::from("No goals yet. Try :goal add \"Fill bin 3\"")]
+    } else {
+        let mut lines = vec![Spans::from(format!("{} of {} met", goals.met(), goals.items.len())), Spans::from("")];
+        for (idx, goal) in goals.items.iter().enumerate() {
+            let check = if goal.done { "[x]" } else { "[ ]" };
+            let progress = match goals.progress(goal) {
+                Some((done, target)) if !goal.done => format!(" ({}/{})", done, target),
+                _ => String::new(),
+            };
+            let style = if goal.done {
+                app.palette.fg_style().add_modifier(Modifier::DIM)
+            } else {
+                app.palette.fg_style()
+            };
+            lines.push(Spans::from(Span::styled(format!("{} {}. {}{}", check, idx + 1, goal.text, progress), style)));
+        }
+        lines
+    };
+    let checklist = Paragraph::new(lines).style(app.palette.fg_style()).wrap(Wrap { trim: true });
+    frame.render_widget(checklist, area);
+}
+
+/// Every notice from this session, latest first
+fn draw_inbox<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let lines: Vec<Spans> = if app.inbox.is_empty() {
+        vec![Spans::from("Nothing yet. Lumon will be in touch.")]
+    } else {
+        app.inbox
+            .iter()
+            .rev()
+            .flat_map(|notice| [Spans::from(format!("\u{2022} {}", notice)), Spans::from("")])
+            .collect()
+    };
+    let inbox = Paragraph::new(lines).style(app.palette.fg_style()).wrap(Wrap { trim: true });
+    frame.render_widget(inbox, area);
+}
+
+/// Session events, latest first
+fn draw_log<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let now = app.clock.now();
+    let lines: Vec<Spans> = if app.event_log.is_empty() {
+        vec![Spans::from("No refinement yet this session")]
+    } else {
+        app.event_log
+            .iter()
+            .rev()
+            .map(|(at, event)| {
+                let ago = now.saturating_duration_since(*at).as_secs();
+                Spans::from(vec![Span::raw(format!("{:>4}s  ", ago)), Span::raw(event.describe())])
+            })
+            .collect()
+    };
+    frame.render_widget(Paragraph::new(lines).style(app.palette.fg_style()), area);
+}
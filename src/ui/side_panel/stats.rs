@@ -0,0 +1,69 @@
+use ratatui::{
+    Frame,
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier},
+    text::{Span, Spans},
+    widgets::Paragraph,
+};
+
+use crate::app::App;
+use crate::ranks;
+use crate::ui::widgets::{ChartStyle, Sparkline};
+
+/// Days of history in the stats sparkline, ending today
+const HISTORY_DAYS: i64 = 14;
+
+/// Today's totals, the refiner's rank and the last two weeks at a glance
+pub fn draw_stats<B: Backend>(frame: &mut Frame<B>, area: Rect, app: &App) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),   // Today
+            Constraint::Length(1),   // Space
+            Constraint::Length(3),   // Rank
+            Constraint::Length(1),   // Space
+            Constraint::Length(2),   // History
+            Constraint::Min(0),      // Remaining space
+        ])
+        .split(area);
+
+    let heading = app.palette.fg_style().add_modifier(Modifier::BOLD);
+    let text = app.palette.fg_style();
+
+    let today = app.today();
+    let totals = app.station.ledger.daily(today, today + 1)[0];
+    let today_lines = vec![
+        Spans::from(Span::styled("Today", heading)),
+        Spans::from(Span::styled(format!("Numbers refined  {}", totals.numbers), text)),
+        Spans::from(Span::styled(format!("Bins filled      {}", totals.bins), text)),
+        Spans::from(Span::styled(format!("Files completed  {}", totals.files), text)),
+    ];
+    frame.render_widget(Paragraph::new(today_lines), layout[0]);
+
+    let rank = ranks::rank_for(app.xp);
+    let next = match ranks::next_rank(app.xp) {
+        Some(next) => format!("{} XP to {}", next.xp - app.xp, next.title),
+        None => "The highest rank there is".to_string(),
+    };
+    let rank_lines = vec![
+        Spans::from(Span::styled(rank.title, heading)),
+        Spans::from(Span::styled(format!("{} XP", app.xp), text)),
+        Spans::from(Span::styled(next, text)),
+    ];
+    frame.render_widget(Paragraph::new(rank_lines), layout[2]);
+
+    let history: Vec<u64> = app
+        .station
+        .ledger
+        .daily(today + 1 - HISTORY_DAYS, today + 1)
+        .iter()
+        .map(|day| day.numbers)
+        .collect();
+    let history_area = layout[4];
+    frame.render_widget(Paragraph::new(Span::styled("Last 2 weeks", heading)), history_area);
+    if history_area.height > 1 {
+        let spark_area = Rect { y: history_area.y + 1, height: 1, ..history_area };
+        frame.render_widget(Sparkline::new(&history, ChartStyle::for_app(app, Color::Cyan)), spark_area);
+    }
+}
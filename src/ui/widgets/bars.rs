@@ -0,0 +1,111 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier},
+    widgets::Widget,
+};
+
+use super::{ChartStyle, EIGHTHS};
+
+/// One row of a bar chart
+#[derive(Clone, Debug)]
+pub struct Bar {
+    pub label: String,
+    pub value: u64,
+    /// Shown after the bar; the value itself when empty
+    pub note: String,
+    /// Overrides the chart's fill colour for this bar
+    pub color: Option<Color>,
+}
+
+impl Bar {
+    pub fn new(label: impl Into<String>, value: u64) -> Self {
+        Self { label: label.into(), value, note: String::new(), color: None }
+    }
+
+    pub fn note(mut self, note: impl Into<String>) -> Self {
+        self.note = note.into();
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// Horizontal bars on a shared scale, one per row:
+///
+/// ```text
+/// Mon ██████████▌ 340
+/// Tue ███▊ 120
+/// ```
+pub struct BarChart {
+    bars: Vec<Bar>,
+    style: ChartStyle,
+    max_width: u16,
+    /// Blank rows between bars
+    gap: u16,
+}
+
+impl BarChart {
+    pub fn new(bars: Vec<Bar>, style: ChartStyle) -> Self {
+        Self { bars, style, max_width: u16::MAX, gap: 0 }
+    }
+
+    /// Cap the length of the longest bar
+    pub fn max_width(mut self, width: u16) -> Self {
+        self.max_width = width;
+        self
+    }
+
+    pub fn gap(mut self, rows: u16) -> Self {
+        self.gap = rows;
+        self
+    }
+}
+
+impl Widget for BarChart {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let label_width = self.bars.iter().map(|bar| bar.label.chars().count()).max().unwrap_or(0) as u16 + 1;
+        let note_width = self
+            .bars
+            .iter()
+            .map(|bar| if bar.note.is_empty() { bar.value.to_string().len() } else { bar.note.chars().count() })
+            .max()
+            .unwrap_or(0) as u16
+            + 1;
+        let bar_width = area.width.saturating_sub(label_width + note_width).min(self.max_width) as u64;
+        let largest = self.bars.iter().map(|bar| bar.value).max().unwrap_or(0).max(1);
+
+        for (idx, bar) in self.bars.iter().enumerate() {
+            let y = area.y + idx as u16 * (self.gap + 1);
+            if y >= area.y + area.height {
+                break;
+            }
+            buf.set_stringn(area.x, y, &bar.label, area.width as usize, self.style.text.add_modifier(Modifier::BOLD));
+
+            // Length in eighths of a cell
+            let eighths = bar.value * bar_width * 8 / largest;
+            let fill = if self.style.ascii {
+                "#".repeat((eighths / 8) as usize)
+            } else {
+                let mut fill = "█".repeat((eighths / 8) as usize);
+                let remainder = (eighths % 8) as usize;
+                if remainder > 0 {
+                    fill.push_str(EIGHTHS[remainder]);
+                }
+                fill
+            };
+            let x = area.x + label_width;
+            let fill_style = bar.color.map_or(self.style.fill, |color| self.style.fill.fg(color));
+            let (after, _) = buf.set_stringn(x, y, &fill, bar_width as usize + 1, fill_style);
+
+            let note = if bar.note.is_empty() { bar.value.to_string() } else { bar.note.clone() };
+            let room = (area.x + area.width).saturating_sub(after + 1) as usize;
+            if room > 0 {
+                buf.set_stringn(after + 1, y, note, room, self.style.text);
+            }
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+use super::{ChartStyle, EIGHTHS};
+
+/// One row that fills from the left as a key is held down
+#[derive(Clone, Debug)]
+pub struct HoldGauge {
+    /// 0 to 1
+    progress: f32,
+    style: ChartStyle,
+}
+
+impl HoldGauge {
+    pub fn new(progress: f32, style: ChartStyle) -> Self {
+        Self { progress: progress.clamp(0.0, 1.0), style }
+    }
+}
+
+impl Widget for HoldGauge {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 {
+            return;
+        }
+        let width = area.width as usize;
+        let eighths = (self.progress * width as f32 * 8.0) as usize;
+        let (full, remainder) = (eighths / 8, eighths % 8);
+
+        let (block, track) = if self.style.ascii { ("#", "-") } else { ("█", "░") };
+        let mut fill = block.repeat(full);
+        if remainder > 0 && !self.style.ascii {
+            fill.push_str(EIGHTHS[remainder]);
+        }
+        let (after, _) = buf.set_stringn(area.x, area.y, &fill, width, self.style.fill);
+        let room = (area.x + area.width).saturating_sub(after) as usize;
+        if room > 0 {
+            buf.set_stringn(after, area.y, track.repeat(room), room, self.style.text);
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Small chart widgets in the Lumon style: flat bars and sparklines made of
+//! block elements, falling back to plain ASCII where those would not render.
+
+mod bars;
+mod gauge;
+mod sparkline;
+
+pub use bars::{Bar, BarChart};
+pub use gauge::HoldGauge;
+pub use sparkline::Sparkline;
+
+use crate::app::App;
+use crate::focus::Focus;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Span, Spans};
+
+/// Eighths of a cell, for bar ends that fall between cells
+const EIGHTHS: [&str; 8] = [" ", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+/// How a chart is drawn
+#[derive(Clone, Copy, Debug)]
+pub struct ChartStyle {
+    /// The filled part of a bar or sparkline
+    pub fill: Style,
+    /// Labels and values
+    pub text: Style,
+    /// Stick to ASCII instead of block elements
+    pub ascii: bool,
+}
+
+impl ChartStyle {
+    /// The app's palette and glyph support, with bars in `fill`
+    pub fn for_app(app: &App, fill: Color) -> Self {
+        Self {
+            fill: Style::default().fg(fill),
+            text: app.palette.fg_style(),
+            ascii: app.station.ascii_only,
+        }
+    }
+}
+
+/// `style`, standing out in reverse video on the widget the keyboard is on
+pub fn focus_style(style: Style, focused: bool) -> Style {
+    if focused { style.add_modifier(Modifier::BOLD | Modifier::REVERSED) } else { style }
+}
+
+/// A row of buttons, one for each widget in `focus`, the focused one
+/// highlighted
+pub fn button_row<T: Copy + PartialEq>(focus: &Focus<T>, label: impl Fn(T) -> String, style: Style) -> Spans<'static> {
+    let mut spans = Vec::new();
+    for &button in focus.order() {
+        if !spans.is_empty() {
+            spans.push(Span::styled("   ", style));
+        }
+        spans.push(Span::styled(format!(" {} ", label(button)), focus_style(style, focus.is_focused(button))));
+    }
+    Spans::from(spans)
+}
@@ -0,0 +1,40 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    widgets::Widget,
+};
+
+use super::ChartStyle;
+
+const BLOCKS: [&str; 8] = ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"];
+const ASCII: [&str; 8] = ["_", ".", ",", ":", "-", "=", "+", "#"];
+
+/// One cell per value, taller for larger values: `▁▃▅█▂`. Only the most
+/// recent values that fit are drawn.
+pub struct Sparkline<'a> {
+    values: &'a [u64],
+    style: ChartStyle,
+}
+
+impl<'a> Sparkline<'a> {
+    pub fn new(values: &'a [u64], style: ChartStyle) -> Self {
+        Self { values, style }
+    }
+}
+
+impl Widget for Sparkline<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 {
+            return;
+        }
+        let shown = &self.values[self.values.len().saturating_sub(area.width as usize)..];
+        let largest = shown.iter().copied().max().unwrap_or(0).max(1);
+        let glyphs = if self.style.ascii { &ASCII } else { &BLOCKS };
+
+        for (idx, &value) in shown.iter().enumerate() {
+            // Nothing at all stays blank, so quiet days stand out
+            let glyph = if value == 0 { " " } else { glyphs[((value * 8).div_ceil(largest) - 1) as usize] };
+            buf.set_string(area.x + idx as u16, area.y, glyph, self.style.fill);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+//! Opt-in check for newer releases, and the notes shown by `:changelog`.
+//!
+//! The release feed is the project's `CHANGELOG.md`. It is fetched on a
+//! background thread with `curl`, so HTTPS works without bundling a TLS
+//! stack, and cached for a day. Nothing is fetched unless the config says
+//! `[updates] check = true`; until then the changelog that shipped with
+//! this build is shown.
+
+use crate::config::UpdatesConfig;
+use crate::error::{MdrError, Result};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+/// The version of this build
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Where releases are announced
+pub const DEFAULT_FEED: &str = "https://raw.githubusercontent.com/theabecaster/lumon-mdr/main/CHANGELOG.md";
+
+/// A fetched feed is trusted for this long before asking again
+const CACHE_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+const FETCH_TIMEOUT_SECS: &str = "10";
+
+const BUNDLED_CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+/// A `major.minor.patch` release number
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Version {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl Version {
+    pub fn current() -> Self {
+        CURRENT_VERSION.parse().expect("package version is major.minor.patch")
+    }
+}
+
+impl FromStr for Version {
+    type Err = MdrError;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let invalid = || MdrError::Config(format!("'{}' is not a version like 1.2.3", text));
+        let mut parts = text.trim().trim_start_matches('v').split('.');
+        let mut next = || parts.next().and_then(|p| p.parse::<u32>().ok()).ok_or_else(invalid);
+        let version = Version { major: next()?, minor: next()?, patch: next()? };
+        match parts.next() {
+            Some(_) => Err(invalid()),
+            None => Ok(version),
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// One release from the changelog
+#[derive(Clone, Debug, PartialEq)]
+pub struct Release {
+    pub version: Version,
+    pub date: Option<String>,
+    pub notes: Vec<String>,
+}
+
+/// Read releases from headings like `## [1.2.0] - 2025-06-01`. Sections
+/// without a version (e.g. "Unreleased") are skipped.
+pub fn parse_changelog(text: &str) -> Vec<Release> {
+    let mut releases: Vec<Release> = Vec::new();
+    let mut current: Option<Release> = None;
+
+    for line in text.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            releases.extend(current.take());
+            let (version, date) = match heading.split_once(" - ") {
+                Some((version, date)) => (version, Some(date.trim().to_string())),
+                None => (heading, None),
+            };
+            let version = version.trim().trim_start_matches('[').trim_end_matches(']');
+            current = version.parse().ok().map(|version| Release { version, date, notes: Vec::new() });
+        } else if let Some(release) = &mut current
+            && (!line.trim().is_empty() || !release.notes.is_empty())
+        {
+            // Leading blank lines are dropped, trailing ones below
+            release.notes.push(line.trim_end().to_string());
+        }
+    }
+    releases.extend(current);
+
+    for release in &mut releases {
+        while release.notes.last().is_some_and(|line| line.is_empty()) {
+            release.notes.pop();
+        }
+    }
+    releases
+}
+
+/// The changelog that shipped with this build
+pub fn bundled() -> Vec<Release> {
+    parse_changelog(BUNDLED_CHANGELOG)
+}
+
+/// The newest release later than this build, if any
+pub fn newer_release(releases: &[Release]) -> Option<&Release> {
+    let current = Version::current();
+    releases.iter().filter(|r| r.version > current).max_by_key(|r| r.version)
+}
+
+/// Where the fetched feed is cached: `changelog.md` in the cache
+/// directory, e.g. `~/.cache/lumon-mdr/changelog.md`
+pub fn default_cache_path() -> Option<PathBuf> {
+    Some(crate::paths::cache_dir()?.join("changelog.md"))
+}
+
+/// A feed fetch running in the background
+pub struct UpdateCheck {
+    rx: Receiver<Result<String>>,
+}
+
+impl UpdateCheck {
+    /// Start fetching the feed, or return `None` when checks are disabled
+    pub fn start(config: &UpdatesConfig, cache: Option<PathBuf>) -> Option<Self> {
+        if !config.check {
+            return None;
+        }
+        let (tx, rx) = mpsc::channel();
+        let feed = config.feed.clone();
+        thread::spawn(move || {
+            let _ = tx.send(fetch(&feed, cache.as_deref()));
+        });
+        Some(Self { rx })
+    }
+
+    /// The changelog text, once it has arrived
+    pub fn poll(&self) -> Option<Result<String>> {
+        self.rx.try_recv().ok()
+    }
+}
+
+fn fetch(feed: &str, cache: Option<&Path>) -> Result<String> {
+    let cached = cache.and_then(|path| {
+        let age = fs::metadata(path).ok()?.modified().ok()?.elapsed().unwrap_or_default();
+        Some((fs::read_to_string(path).ok()?, age))
+    });
+    if let Some((text, age)) = &cached
+        && *age < CACHE_MAX_AGE
+    {
+        return Ok(text.clone());
+    }
+
+    match download(feed) {
+        Ok(text) => {
+            if let Some(path) = cache {
+                // A cache that cannot be written only costs another fetch
+                let _ = path.parent().map(fs::create_dir_all);
+                let _ = fs::write(path, &text);
+            }
+            Ok(text)
+        },
+        // Stale notes beat no notes
+        Err(err) => cached.map(|(text, _)| text).ok_or(err),
+    }
+}
+
+// URLs go through curl; anything else is a local file, e.g. a mirror
+fn download(feed: &str) -> Result<String> {
+    if !(feed.starts_with("https://") || feed.starts_with("http://")) {
+        return Ok(fs::read_to_string(feed)?);
+    }
+
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location", "--max-time", FETCH_TIMEOUT_SECS, feed])
+        .output()
+        .map_err(|err| MdrError::Net(format!("cannot run curl: {}", err)))?;
+    if !output.status.success() {
+        return Err(MdrError::Net(String::from_utf8_lossy(&output.stderr).trim().to_string()));
+    }
+    String::from_utf8(output.stdout).map_err(|_| MdrError::Net(format!("{} is not text", feed)))
+}
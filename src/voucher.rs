@@ -0,0 +1,114 @@
+//! Perk redemption vouchers: a small ASCII card issued for each completed
+//! file, naming the prize and carrying a code to redeem it by, e.g.
+//! `PERK-0093-8NKR-70`.
+//!
+//! Each card is written to the vouchers folder as it is issued and printed
+//! again once the app exits, so there is something to take away from the
+//! severed floor. The code is forty random bits in Crockford base32 with
+//! a two-digit checksum, so a mistyped code is caught at the break room.
+
+use crate::error::{MdrError, Result};
+use crate::ledger;
+use crate::share::DIGITS;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Characters between the card's borders
+const CARD_WIDTH: usize = 44;
+/// Bits of randomness in a code
+pub const CODE_BITS: u32 = 40;
+
+/// A perk awarded for one file
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Voucher {
+    /// The random part of the code, below `1 << CODE_BITS`
+    pub number: u64,
+    pub prize: String,
+    pub employee: String,
+    /// The file it was awarded for
+    pub file: String,
+    /// Day issued, in days since 1970
+    pub day: i64,
+}
+
+impl Voucher {
+    /// The redemption code, e.g. `PERK-0093-8NKR-70`
+    pub fn code(&self) -> String {
+        let number = self.number & ((1 << CODE_BITS) - 1);
+        let body: String = (0..CODE_BITS / 5)
+            .rev()
+            .map(|idx| DIGITS[((number >> (idx * 5)) & 31) as usize] as char)
+            .collect();
+        format!("PERK-{}-{}-{:02}", &body[..4], &body[4..], number % 97)
+    }
+
+    /// The voucher as lines of plain ASCII, ready to print
+    pub fn card(&self) -> String {
+        let border = format!("+{}+", "-".repeat(CARD_WIDTH));
+        let mut lines = vec![
+            border.clone(),
+            centred("LUMON INDUSTRIES"),
+            centred("PERK REDEMPTION VOUCHER"),
+            row(""),
+            row(&format!("  Awarded to: {}", self.employee)),
+            row(&format!("  For file:   {}", self.file)),
+            row(&format!("  Perk:       {}", self.prize)),
+            row(&format!("  Issued:     {}", ledger::format_day(self.day))),
+            row(""),
+            row(&format!("  Code:       {}", self.code())),
+            row(""),
+        ];
+        lines.extend(
+            ["Redeemable at the break room. Not", "transferable. The work is mysterious", "and important."]
+                .iter()
+                .map(|text| row(&format!("  {}", text))),
+        );
+        lines.push(border);
+        lines.join("\n")
+    }
+
+    /// Write the card to `dir` as `<code>.txt`, returning where it went
+    pub fn store(&self, dir: &Path) -> Result<PathBuf> {
+        let path = dir.join(format!("{}.txt", self.code()));
+        fs::create_dir_all(dir)
+            .and_then(|()| fs::write(&path, format!("{}\n", self.card())))
+            .map_err(|err| MdrError::Save(format!("{}: {}", path.display(), err)))?;
+        Ok(path)
+    }
+}
+
+/// Whether `text` reads as a voucher code with a matching checksum
+pub fn is_valid_code(text: &str) -> bool {
+    let upper = text.trim().to_uppercase();
+    let parts: Vec<&str> = upper.split('-').collect();
+    let ["PERK", first, second, check] = parts[..] else { return false };
+    if first.len() != 4 || second.len() != 4 || check.len() != 2 {
+        return false;
+    }
+
+    let mut number: u64 = 0;
+    for c in first.chars().chain(second.chars()) {
+        let Some(digit) = DIGITS.iter().position(|&d| d as char == c) else { return false };
+        number = number * 32 + digit as u64;
+    }
+    check.parse::<u64>().is_ok_and(|check| check == number % 97)
+}
+
+/// Where vouchers are kept: `vouchers` in the data directory
+pub fn default_dir() -> Option<PathBuf> {
+    Some(crate::paths::data_dir()?.join("vouchers"))
+}
+
+// A line of the card, cut short if it would run past the border
+fn row(text: &str) -> String {
+    let text: String = if text.chars().count() > CARD_WIDTH {
+        text.chars().take(CARD_WIDTH - 3).chain("...".chars()).collect()
+    } else {
+        text.to_string()
+    };
+    format!("|{:<width$}|", text, width = CARD_WIDTH)
+}
+
+fn centred(text: &str) -> String {
+    format!("|{:^width$}|", text, width = CARD_WIDTH)
+}
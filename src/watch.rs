@@ -0,0 +1,111 @@
+//! Watching `config.toml` and the content packs while the app runs, so a
+//! refiner iterating on a theme sees each save without restarting.
+//!
+//! A background thread checks the files' sizes and modification times
+//! every `WATCH_INTERVAL`. Once a change has held still for a whole
+//! interval, so an editor is not caught half way through writing, the
+//! changed side is read and parsed on that thread and handed over like a
+//! startup task. Which settings then take effect is up to the app.
+
+use crate::config;
+use crate::content;
+use crate::startup::Loaded;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+/// How often the files are checked
+pub const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Receives reloaded config and content from the watcher thread
+pub struct Watcher {
+    rx: Receiver<Loaded>,
+}
+
+impl Watcher {
+    /// Start watching the config file and packs folder, whichever are set,
+    /// checking every `interval`. What is there now counts as already read.
+    pub fn start(config: Option<PathBuf>, packs: Option<PathBuf>, interval: Duration) -> Self {
+        let (tx, rx) = mpsc::channel();
+        // Looked at here rather than on the thread, which may start late
+        let (config, packs) = (config.map(Watched::new), packs.map(Watched::new));
+        thread::spawn(move || watch(config, packs, interval, &tx));
+        Self { rx }
+    }
+
+    /// Whatever was reloaded since the last call, oldest first
+    pub fn poll(&self) -> Vec<Loaded> {
+        self.rx.try_iter().collect()
+    }
+}
+
+// What a file or folder looked like: size and modification time of each
+// file, so any save shows up as a difference
+type Fingerprint = Vec<(PathBuf, u64, Option<SystemTime>)>;
+
+fn fingerprint(path: &Path) -> Fingerprint {
+    let mut prints = Vec::new();
+    match fs::read_dir(path) {
+        Ok(entries) => {
+            for entry in entries.flatten() {
+                if let Ok(meta) = entry.metadata() {
+                    prints.push((entry.path(), meta.len(), meta.modified().ok()));
+                }
+            }
+            prints.sort();
+        },
+        Err(_) => {
+            if let Ok(meta) = fs::metadata(path) {
+                prints.push((path.to_path_buf(), meta.len(), meta.modified().ok()));
+            }
+        },
+    }
+    prints
+}
+
+// One watched path: how it last looked, and whether a change is waiting
+// for the writes to settle
+struct Watched {
+    path: PathBuf,
+    last: Fingerprint,
+    settling: bool,
+}
+
+impl Watched {
+    fn new(path: PathBuf) -> Self {
+        let last = fingerprint(&path);
+        Self { path, last, settling: false }
+    }
+
+    // Whether the path changed and has since held still
+    fn settled(&mut self) -> bool {
+        let now = fingerprint(&self.path);
+        if now != self.last {
+            self.last = now;
+            self.settling = true;
+            return false;
+        }
+        std::mem::take(&mut self.settling)
+    }
+}
+
+// Runs until the app stops listening
+fn watch(mut config: Option<Watched>, mut packs: Option<Watched>, interval: Duration, tx: &Sender<Loaded>) {
+    loop {
+        thread::sleep(interval);
+        if let Some(watched) = &mut config
+            && watched.settled()
+            && tx.send(Loaded::Config(config::load(&watched.path).map(Box::new))).is_err()
+        {
+            return;
+        }
+        if let Some(watched) = &mut packs
+            && watched.settled()
+            && tx.send(Loaded::Content(content::load(&watched.path))).is_err()
+        {
+            return;
+        }
+    }
+}
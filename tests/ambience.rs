@@ -0,0 +1,46 @@
+use lumon_mdr::ambience::{self, Ambience, Scheduler};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::time::{Duration, Instant};
+
+#[test]
+fn waits_are_random_but_bounded() {
+    let mut rng = StdRng::seed_from_u64(1);
+    let gaps: Vec<Duration> = (0..500).map(|_| ambience::gap(&mut rng)).collect();
+    assert!(gaps.iter().all(|gap| (Duration::from_secs(30)..=Duration::from_secs(15 * 60)).contains(gap)));
+    let mean = gaps.iter().sum::<Duration>() / gaps.len() as u32;
+    assert!(mean > Duration::from_secs(120) && mean < Duration::from_secs(240), "{:?}", mean);
+}
+
+#[test]
+fn the_first_poll_only_starts_the_clock() {
+    let mut rng = StdRng::seed_from_u64(2);
+    let mut scheduler = Scheduler::default();
+    let start = Instant::now();
+    assert_eq!(scheduler.poll(start, &mut rng), None);
+    let due = scheduler.next().unwrap();
+    assert!(due > start);
+    assert_eq!(scheduler.poll(due - Duration::from_millis(1), &mut rng), None);
+    assert!(scheduler.poll(due, &mut rng).is_some());
+}
+
+#[test]
+fn each_event_rests_for_its_cooldown() {
+    let mut rng = StdRng::seed_from_u64(3);
+    let mut scheduler = Scheduler::default();
+    let start = Instant::now();
+    let mut last: Vec<(Ambience, Instant)> = Vec::new();
+    let mut now = start;
+    while now < start + Duration::from_secs(8 * 3600) {
+        if let Some(ambience) = scheduler.poll(now, &mut rng) {
+            if let Some(&(_, before)) = last.iter().rev().find(|(seen, _)| *seen == ambience) {
+                assert!(now.duration_since(before) >= ambience.cooldown(), "{:?} came back early", ambience);
+            }
+            last.push((ambience, now));
+        }
+        now += Duration::from_secs(10);
+    }
+    for ambience in Ambience::ALL {
+        assert!(last.iter().any(|(seen, _)| *seen == ambience), "{:?} never came", ambience);
+    }
+}
@@ -0,0 +1,95 @@
+use lumon_mdr::animate::{Animated, Easing, Timeline};
+use std::time::{Duration, Instant};
+
+const SECOND: Duration = Duration::from_secs(1);
+
+#[test]
+fn easing_runs_from_nothing_to_everything() {
+    for easing in [Easing::Linear, Easing::EaseOut] {
+        assert_eq!(easing.apply(0.0), 0.0);
+        assert_eq!(easing.apply(1.0), 1.0);
+        assert_eq!(easing.apply(2.0), 1.0, "clamped past the end");
+    }
+    assert_eq!(Easing::Linear.apply(0.5), 0.5);
+    assert!(Easing::EaseOut.apply(0.5) > 0.5, "ease-out covers most of the way early");
+}
+
+#[test]
+fn a_value_moves_to_its_target_over_its_duration() {
+    let start = Instant::now();
+    let mut value = Animated::new(0.0, SECOND, Easing::Linear);
+    assert!(!value.is_moving(start));
+
+    value.set(40.0, start);
+    assert_eq!(value.target(), 40.0);
+    assert_eq!(value.value(start), 0.0);
+    assert_eq!(value.value(start + SECOND / 4), 10.0);
+    assert!(value.is_moving(start + SECOND / 4));
+    assert_eq!(value.value(start + SECOND * 2), 40.0);
+    assert!(!value.is_moving(start + SECOND));
+}
+
+#[test]
+fn a_new_target_sets_off_from_where_the_value_is() {
+    let start = Instant::now();
+    let mut value = Animated::new(0.0, SECOND, Easing::Linear);
+
+    value.set(100.0, start);
+    value.set(20.0, start + SECOND / 2);
+    assert_eq!(value.value(start + SECOND / 2), 50.0);
+    assert_eq!(value.origin(), 50.0);
+    assert_eq!(value.value(start + SECOND), 35.0);
+
+    value.jump(70.0);
+    assert_eq!(value.value(start), 70.0);
+    assert_eq!(value.origin(), 70.0);
+    assert!(!value.is_moving(start));
+}
+
+#[test]
+fn every_easing_starts_and_ends_in_place() {
+    for easing in [Easing::Linear, Easing::EaseIn, Easing::EaseOut, Easing::EaseInOut] {
+        assert_eq!(easing.apply(0.0), 0.0, "{:?}", easing);
+        assert_eq!(easing.apply(1.0), 1.0, "{:?}", easing);
+    }
+    assert!(Easing::EaseIn.apply(0.5) < 0.5, "ease-in holds back early");
+    assert_eq!(Easing::EaseInOut.apply(0.5), 0.5, "ease-in-out is half way at half time");
+    assert!(Easing::EaseInOut.apply(0.25) < 0.25);
+    assert!(Easing::EaseInOut.apply(0.75) > 0.75);
+}
+
+#[test]
+fn a_timeline_plays_its_segments_in_turn() {
+    let ms = Duration::from_millis;
+    let timeline = Timeline::new().delay(ms(500)).linear(ms(1000), 10.0).then(Timeline::new().linear(ms(500), 5.0));
+
+    assert_eq!(timeline.length(), ms(2000), "the jump back to 0 takes no time");
+    assert_eq!(timeline.value(ms(0)), 0.0);
+    assert_eq!(timeline.value(ms(499)), 0.0, "nothing moves during the delay");
+    assert_eq!(timeline.value(ms(1000)), 5.0);
+    assert_eq!(timeline.value(ms(1250)), 7.5);
+    assert_eq!(timeline.value(ms(1500)), 0.0, "then starts from its own start");
+    assert_eq!(timeline.value(ms(1750)), 2.5);
+    assert_eq!(timeline.value(ms(9000)), 5.0, "it stays at the end");
+    assert!(!timeline.is_done(ms(1999)));
+    assert!(timeline.is_done(ms(2000)));
+}
+
+#[test]
+fn a_timeline_eases_each_segment_on_its_own() {
+    let timeline = Timeline::starting_at(10.0).ease_in(SECOND, 20.0).ease_out(SECOND, 10.0);
+    assert_eq!(timeline.value(SECOND / 2), 10.0 + 10.0 * Easing::EaseIn.apply(0.5));
+    assert_eq!(timeline.value(SECOND), 20.0);
+    assert_eq!(timeline.value(SECOND * 3 / 2), 20.0 - 10.0 * Easing::EaseOut.apply(0.5));
+    assert_eq!(timeline.end(), 10.0);
+}
+
+#[test]
+fn a_flashing_timeline_turns_on_and_off() {
+    let each = Duration::from_millis(100);
+    let flashing = Timeline::flashing(each, 2);
+    let lit: Vec<bool> = (0..5).map(|n| flashing.value(each * n) >= 0.5).collect();
+    assert_eq!(lit, [true, false, true, false, false]);
+    assert_eq!(flashing.length(), each * 4);
+    assert_eq!(Timeline::new().repeat(3).length(), Duration::ZERO);
+}
@@ -0,0 +1,36 @@
+use lumon_mdr::assist::{self, CLUSTER_SIZE, Cluster};
+use std::time::Duration;
+
+fn sum(cells: &[u16]) -> u16 {
+    cells.iter().sum()
+}
+
+#[test]
+fn the_best_cluster_is_the_square_worth_the_most() {
+    let mut digits = vec![vec![1; 6]; 5];
+    digits[3][4] = 9;
+    digits[4][5] = 9;
+
+    let best = assist::best_cluster(&digits, sum).unwrap();
+    assert_eq!(best, Cluster { corner: (3, 2), value: 7 + 9 + 9 });
+    assert_eq!(CLUSTER_SIZE, 3);
+    assert!(best.contains((5, 4)) && best.contains((3, 2)));
+    assert!(!best.contains((2, 2)) && !best.contains((6, 4)));
+}
+
+#[test]
+fn ties_go_to_the_top_left_and_a_small_grid_has_no_cluster() {
+    let flat = vec![vec![4; 5]; 5];
+    assert_eq!(assist::best_cluster(&flat, sum).unwrap().corner, (0, 0));
+
+    assert_eq!(assist::best_cluster(&vec![vec![9; 2]; 5], sum), None);
+    assert_eq!(assist::best_cluster(&[], sum), None);
+}
+
+#[test]
+fn pulses_come_less_often_as_the_streak_grows() {
+    assert_eq!(assist::interval(0), Duration::from_secs(10));
+    assert_eq!(assist::interval(2), Duration::from_secs(30));
+    assert!(assist::interval(5) > assist::interval(4));
+    assert_eq!(assist::interval(1_000), Duration::from_secs(120), "never gone for good");
+}
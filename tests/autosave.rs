@@ -0,0 +1,73 @@
+use lumon_mdr::autosave::{self, Autosave, Recovery};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("lumon-mdr-autosave-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// The writer thread takes a moment; give it up to a couple of seconds
+fn wait_for(path: &Path, contents: &str) {
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while fs::read_to_string(path).ok().as_deref() != Some(contents) && Instant::now() < deadline {
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(fs::read_to_string(path).unwrap(), contents);
+}
+
+#[test]
+fn a_journaled_write_leaves_only_the_session_file() {
+    let dir = temp_dir("write");
+    let path = dir.join("mark.save");
+
+    autosave::write_journaled(&path, "lumon-mdr save 1\nusername=mark\n").unwrap();
+    assert_eq!(fs::read_to_string(&path).unwrap(), "lumon-mdr save 1\nusername=mark\n");
+    assert!(!autosave::journal_path(&path).exists());
+    assert!(autosave::recover(&dir).is_empty());
+}
+
+#[test]
+fn a_whole_journal_is_replayed_and_a_torn_one_discarded() {
+    let dir = temp_dir("recover");
+    let (mark, helly) = (dir.join("mark.save"), dir.join("helly.save"));
+    fs::write(&mark, "old").unwrap();
+    fs::write(&helly, "old").unwrap();
+
+    // A session file that cannot be replaced stops the write after the
+    // journal, as a crash would
+    fs::remove_file(&mark).unwrap();
+    fs::create_dir_all(mark.join("in-the-way")).unwrap();
+    assert!(autosave::write_journaled(&mark, "new").is_err());
+    fs::remove_dir_all(&mark).unwrap();
+    fs::write(&mark, "old").unwrap();
+    let whole = fs::read_to_string(autosave::journal_path(&mark)).unwrap();
+    fs::write(autosave::journal_path(&helly), &whole[..whole.len() - 1]).unwrap();
+
+    let recovered = autosave::recover(&dir);
+    assert_eq!(recovered, vec![Recovery::Discarded(helly.clone()), Recovery::Replayed(mark.clone())]);
+    assert_eq!(fs::read_to_string(&mark).unwrap(), "new");
+    assert_eq!(fs::read_to_string(&helly).unwrap(), "old");
+    assert!(!autosave::journal_path(&mark).exists() && !autosave::journal_path(&helly).exists());
+    assert_eq!(recovered[1].notice(), "Recovered an interrupted save: mark.save");
+}
+
+#[test]
+fn a_save_at_exit_is_never_overwritten_by_an_older_background_one() {
+    let dir = temp_dir("order");
+    let path = dir.join("mark.save");
+    let autosave = Autosave::start();
+
+    autosave.queue(path.clone(), "first".to_string());
+    wait_for(&path, "first");
+
+    autosave.queue(path.clone(), "second".to_string());
+    autosave.write_now(&path, "final").unwrap();
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "final");
+    assert!(autosave.poll().is_empty());
+}
@@ -0,0 +1,38 @@
+use lumon_mdr::branding::Logo;
+use lumon_mdr::ui::LayoutProfile;
+use ratatui::layout::Rect;
+
+#[test]
+fn each_profile_gets_the_largest_logo_that_fits() {
+    let roomy = Rect::new(0, 0, 200, 60);
+
+    assert_eq!(Logo::pick(None, LayoutProfile::Full, roomy), Some(Logo::Globe));
+    assert_eq!(Logo::pick(None, LayoutProfile::Standard, roomy), Some(Logo::Wordmark));
+    assert_eq!(Logo::pick(None, LayoutProfile::Compact, roomy), Some(Logo::Text));
+
+    // Too short for the globe or the wordmark, so the badge stands in
+    assert_eq!(Logo::pick(None, LayoutProfile::Full, Rect::new(0, 0, 200, 4)), Some(Logo::Badge));
+    assert_eq!(Logo::pick(None, LayoutProfile::Full, Rect::new(0, 0, 10, 4)), None);
+}
+
+#[test]
+fn a_configured_logo_replaces_the_profiles_pick() {
+    let roomy = Rect::new(0, 0, 200, 60);
+
+    assert_eq!(Logo::pick(Some(Logo::Badge), LayoutProfile::Full, roomy), Some(Logo::Badge));
+    assert_eq!(Logo::pick(Some(Logo::Globe), LayoutProfile::Compact, roomy), Some(Logo::Globe));
+    assert_eq!(Logo::pick(Some(Logo::Globe), LayoutProfile::Compact, Rect::new(0, 0, 40, 3)), Some(Logo::Text));
+}
+
+#[test]
+fn sizes_come_from_the_art() {
+    for logo in Logo::ALL {
+        let lines = logo.lines(false);
+        assert_eq!(logo.height() as usize, lines.len());
+        assert!(lines.iter().all(|line| line.chars().count() <= logo.width() as usize), "{}", logo.name());
+        assert_eq!(logo.lines(true).len(), lines.len());
+        assert_eq!(logo.name().parse::<Logo>().unwrap(), logo);
+    }
+    assert_eq!(Logo::Badge.width(), 12);
+    assert!(Logo::lines(Logo::Badge, true).iter().all(|line| line.is_ascii()));
+}
@@ -0,0 +1,66 @@
+use lumon_mdr::app::App;
+use lumon_mdr::budget::{self, Budget};
+use lumon_mdr::stats::Balance;
+use lumon_mdr::theme::Palette;
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+#[test]
+fn full_queues_drop_their_oldest() {
+    let mut queue: VecDeque<u32> = (1..=3).collect();
+
+    budget::push_capped(&mut queue, 4, 3);
+    assert_eq!(queue, [2, 3, 4]);
+
+    // A cap lowered by a config reload catches up on the next push
+    budget::push_capped(&mut queue, 5, 2);
+    assert_eq!(queue, [4, 5]);
+}
+
+#[test]
+fn cells_off_the_grid_go_before_the_far_corner() {
+    let mut cells: HashMap<(usize, usize), u16> = [((0, 0), 1), ((9, 9), 2), ((1, 1), 3), ((50, 0), 4)].into();
+
+    budget::trim_cells(&mut cells, 3, |(col, row)| col < 20 && row < 20);
+    assert!(!cells.contains_key(&(50, 0)), "off the grid");
+
+    budget::trim_cells(&mut cells, 2, |_| true);
+    let mut kept: Vec<_> = cells.keys().copied().collect();
+    kept.sort();
+    assert_eq!(kept, [(0, 0), (1, 1)]);
+}
+
+#[test]
+fn the_refiner_at_the_terminal_keeps_their_balance() {
+    let mut app = App::new(Palette::Ansi);
+    let minutes = |m: u64| Balance { innie: Duration::from_secs(m * 60), outie: Duration::ZERO };
+    app.station.stats.set_balance("mark", minutes(1));
+    app.station.stats.set_balance("helly", minutes(30));
+    app.station.stats.set_balance("irving", minutes(20));
+    app.station.stats.set_balance("dylan", minutes(5));
+
+    app.station.stats.trim("mark", 2);
+
+    assert_eq!(app.station.stats.refiners(), 2);
+    assert_eq!(app.station.stats.balance("mark"), minutes(1));
+    assert_eq!(app.station.stats.balance("helly"), minutes(30));
+    assert_eq!(app.station.stats.balance("irving"), Balance::default());
+}
+
+#[test]
+fn the_app_keeps_to_its_budget() {
+    let mut app = App::new(Palette::Ansi);
+    app.station.config.budget = Budget { inbox: 2, replaced_numbers: 4, deposits: 1, ..Budget::default() };
+
+    for n in 0..5 {
+        app.show_toast(format!("notice {}", n), None);
+        app.add_to_container(0, 1);
+    }
+    app.replace_numbers((0..10).map(|n| (n, 0)).collect());
+
+    assert_eq!(app.inbox, ["notice 3", "notice 4"]);
+    assert_eq!(app.containers[0].deposits.len(), 1);
+    assert_eq!(app.grid.len(), 4);
+    let usage = app.budget_usage();
+    assert!(usage.iter().all(|usage| usage.used <= usage.cap), "{:?}", usage);
+}
@@ -0,0 +1,71 @@
+use lumon_mdr::card::CompletionCard;
+use lumon_mdr::ledger;
+use std::time::Duration;
+
+fn sample() -> CompletionCard {
+    CompletionCard {
+        employee: "Mark S.".to_string(),
+        file: "Cold Harbor".to_string(),
+        time: Some(Duration::from_millis(754_300)),
+        prizes: vec!["Finger Trap".to_string(), "Waffle Party".to_string()],
+        code: "HUSH-49B4996-10".to_string(),
+        day: ledger::parse_day("2025-03-12").unwrap(),
+    }
+}
+
+// The card with its colours taken out
+fn strip_ansi(text: &str) -> String {
+    let mut out = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|&c| c == 'm');
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+#[test]
+fn the_card_names_the_file_and_keeps_its_border_straight() {
+    let mut card = sample();
+    card.file = "Cold Harbor, the one file to rule them all and more".to_string();
+    let plain = card.plain();
+
+    assert!(plain.starts_with("╔═"));
+    assert!(plain.contains("Refiner:    Mark S."));
+    assert!(plain.contains("Waffle Party (+1 earlier)"));
+    assert!(plain.contains("HUSH-49B4996-10"));
+    assert!(plain.contains("2025-03-12"));
+    assert!(plain.contains("Cold Harbor, the one"));
+    let widths: Vec<usize> = plain.lines().map(|line| line.chars().count()).collect();
+    assert!(widths.iter().all(|&width| width == widths[0]), "{:?}", widths);
+}
+
+#[test]
+fn the_coloured_card_is_the_plain_one_painted() {
+    let card = sample();
+    let ansi = card.ansi();
+
+    assert!(ansi.contains("\x1b[1;33mHUSH-49B4996-10\x1b[0m"));
+    assert_eq!(strip_ansi(&ansi), card.plain());
+    assert_eq!(card.chat(), format!("```ansi\n{}\n```", ansi));
+
+    let resumed = CompletionCard { time: None, prizes: Vec::new(), ..card };
+    assert!(resumed.plain().contains("untimed"));
+    assert!(resumed.plain().contains("Prize:      none"));
+}
+
+#[test]
+fn cards_are_written_to_their_folder() {
+    let dir = std::env::temp_dir().join(format!("lumon-mdr-cards-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let card = sample();
+    let path = card.store(&dir).unwrap();
+
+    assert_eq!(path, dir.join("2025-03-12-cold-harbor.txt"));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), format!("{}\n", card.ansi()));
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,25 @@
+use crossterm::event::KeyCode;
+use lumon_mdr::cheats::Cheat;
+use lumon_mdr::keyseq::{KONAMI, KeySequence};
+
+#[test]
+fn the_sequence_survives_false_starts() {
+    let mut konami = KeySequence::new(KONAMI);
+    let keys = [KeyCode::Up, KeyCode::Up, KeyCode::Up].iter().chain(&KONAMI[2..]);
+
+    let completed: Vec<bool> = keys.map(|&key| konami.push(key)).collect();
+
+    assert_eq!(completed.iter().filter(|&&done| done).count(), 1);
+    assert!(completed.last().unwrap());
+    assert!(!konami.push(KeyCode::Char('a')));
+}
+
+#[test]
+fn cheats_parse_from_the_console() {
+    assert_eq!("fill 3".parse::<Cheat>().unwrap(), Cheat::FillBin(2));
+    assert_eq!(" complete ".parse::<Cheat>().unwrap(), Cheat::CompleteFile);
+    assert_eq!("prize Egg Bar".parse::<Cheat>().unwrap(), Cheat::Prize("Egg Bar".to_string()));
+    assert!("fill 6".parse::<Cheat>().is_err());
+    assert!("prize".parse::<Cheat>().is_err());
+    assert!("levitate".parse::<Cheat>().is_err());
+}
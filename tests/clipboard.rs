@@ -0,0 +1,104 @@
+use lumon_mdr::clipboard::{self, Clipboard, CopyError, Method};
+use lumon_mdr::config::ClipboardConfig;
+use lumon_mdr::environment::{Environment, Multiplexer};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+// Collects what the clipboard writes to the terminal
+#[derive(Clone, Default)]
+struct Terminal(Rc<RefCell<Vec<u8>>>);
+
+impl Write for Terminal {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Terminal {
+    fn take(&self) -> String {
+        String::from_utf8(self.0.take()).unwrap()
+    }
+}
+
+fn env(term: &str, term_program: &str, multiplexer: Option<Multiplexer>) -> Environment {
+    Environment {
+        term: term.to_string(),
+        colorterm: String::new(),
+        term_program: term_program.to_string(),
+        multiplexer,
+        unicode: true,
+    }
+}
+
+#[test]
+fn encodes_base64_with_padding() {
+    assert_eq!(clipboard::base64(b""), "");
+    assert_eq!(clipboard::base64(b"f"), "Zg==");
+    assert_eq!(clipboard::base64(b"fo"), "Zm8=");
+    assert_eq!(clipboard::base64(b"foo"), "Zm9v");
+    assert_eq!(clipboard::base64(b"Macrodata?"), "TWFjcm9kYXRhPw==");
+}
+
+#[test]
+fn detects_terminals_that_take_osc_52() {
+    assert!(clipboard::takes_osc52(&env("xterm-256color", "", None)));
+    assert!(clipboard::takes_osc52(&env("xterm-kitty", "", None)));
+    assert!(clipboard::takes_osc52(&env("tmux-256color", "", Some(Multiplexer::Tmux))));
+    assert!(!clipboard::takes_osc52(&env("linux", "", None)));
+    assert!(!clipboard::takes_osc52(&env("dumb", "", None)));
+    assert!(!clipboard::takes_osc52(&env("xterm-256color", "Apple_Terminal", None)));
+}
+
+#[test]
+fn copies_through_the_terminal_with_osc_52() {
+    let config = ClipboardConfig::default();
+    let terminal = Terminal::default();
+    let mut clip = Clipboard::to_terminal(Box::new(terminal.clone()), &env("xterm", "", None));
+
+    assert_eq!(clip.copy("foo", &config).unwrap(), Method::Terminal);
+    assert_eq!(terminal.take(), "\x1b]52;c;Zm9v\x07");
+    assert_eq!(clip.last.as_deref(), Some("foo"));
+
+    // tmux needs the sequence passed through to the outer terminal
+    let mut clip = Clipboard::to_terminal(Box::new(terminal.clone()), &env("screen", "", Some(Multiplexer::Tmux)));
+    clip.copy("foo", &config).unwrap();
+    assert_eq!(terminal.take(), "\x1bPtmux;\x1b\x1b]52;c;Zm9v\x07\x1b\\");
+}
+
+#[test]
+fn holds_back_what_the_terminal_would_not_take() {
+    let terminal = Terminal::default();
+    let mut clip = Clipboard::to_terminal(Box::new(terminal.clone()), &env("xterm", "", None));
+
+    let small = ClipboardConfig { max_bytes: 16, ..ClipboardConfig::default() };
+    let result = clip.copy("a report far too long for sixteen bytes", &small);
+    assert!(matches!(result, Err(CopyError::TooLarge { max: 16, .. })), "{:?}", result);
+
+    let off = ClipboardConfig { osc52: Some(false), ..ClipboardConfig::default() };
+    assert!(matches!(clip.copy("foo", &off), Err(CopyError::Unsupported)));
+    assert_eq!(terminal.take(), "");
+    assert_eq!(clip.last, None);
+
+    // Asked for, it is sent even where detection says no
+    let mut clip = Clipboard::to_terminal(Box::new(terminal.clone()), &env("linux", "", None));
+    let on = ClipboardConfig { osc52: Some(true), ..ClipboardConfig::default() };
+    assert!(matches!(clip.copy("foo", &ClipboardConfig::default()), Err(CopyError::Unsupported)));
+    assert_eq!(clip.copy("foo", &on).unwrap(), Method::Terminal);
+    assert_eq!(terminal.take(), "\x1b]52;c;Zm9v\x07");
+}
+
+#[test]
+fn a_screen_is_copied_as_plain_lines() {
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 8, 4));
+    buffer.set_string(0, 0, "MDR", Default::default());
+    buffer.set_string(2, 1, "42", Default::default());
+
+    assert_eq!(clipboard::screen_text(&buffer), "MDR\n  42");
+}
@@ -0,0 +1,26 @@
+use lumon_mdr::combo::{COMBO_BONUS, Combo};
+
+#[test]
+fn three_equal_digits_make_a_combo() {
+    assert_eq!(Combo::find(&[7, 2, 7, 7]), Some(Combo { digit: 7, count: 3 }));
+    assert_eq!(Combo::find(&[7, 2, 7, 1]), None);
+    assert_eq!(Combo::find(&[]), None);
+}
+
+#[test]
+fn the_biggest_group_wins_then_the_highest_digit() {
+    assert_eq!(Combo::find(&[3, 3, 3, 3, 9, 9, 9]), Some(Combo { digit: 3, count: 4 }));
+    assert_eq!(Combo::find(&[4, 4, 4, 8, 8, 8]), Some(Combo { digit: 8, count: 3 }));
+}
+
+#[test]
+fn bigger_combos_earn_more() {
+    let triple = Combo { digit: 7, count: 3 };
+    let quad = Combo { digit: 7, count: 4 };
+    assert_eq!(triple.bonus(), COMBO_BONUS);
+    assert_eq!(quad.bonus(), 2 * COMBO_BONUS);
+
+    assert_eq!(triple.to_string(), "TRIPLE 7s");
+    assert_eq!(quad.to_string(), "QUADRUPLE 7s");
+    assert_eq!(Combo { digit: 0, count: 9 }.to_string(), "9x 0s");
+}
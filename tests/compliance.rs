@@ -0,0 +1,41 @@
+use lumon_mdr::compliance::{ComplianceCheck, TIME_LIMIT};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::time::{Duration, Instant};
+
+fn check() -> ComplianceCheck {
+    ComplianceCheck::new(&mut StdRng::seed_from_u64(1), Instant::now())
+}
+
+#[test]
+fn the_phrase_is_retyped_forgiving_case() {
+    let mut check = check();
+    let phrase = check.phrase;
+    for c in phrase.to_uppercase().chars() {
+        check.push(c);
+        assert!(check.on_track());
+    }
+    assert!(check.is_typed());
+
+    check.pop();
+    assert!(!check.is_typed());
+    check.push('!');
+    assert!(!check.on_track());
+}
+
+#[test]
+fn typing_stops_well_past_the_phrase() {
+    let mut check = check();
+    for _ in 0..200 {
+        check.push('x');
+    }
+    assert_eq!(check.typed.chars().count(), check.phrase.chars().count() + 8);
+}
+
+#[test]
+fn the_time_runs_out() {
+    let check = check();
+    assert_eq!(check.remaining(check.since), TIME_LIMIT);
+    assert!(!check.is_expired(check.since + TIME_LIMIT - Duration::from_millis(1)));
+    assert!(check.is_expired(check.since + TIME_LIMIT));
+}
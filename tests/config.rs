@@ -0,0 +1,262 @@
+use lumon_mdr::branding::Logo;
+use lumon_mdr::budget::Budget;
+use lumon_mdr::config::{self, Config, ConfirmStyle, LagConfig, schema};
+use lumon_mdr::content::Prize;
+use lumon_mdr::error::MdrError;
+use lumon_mdr::scoring::Difficulty;
+use lumon_mdr::strategy::BinStrategy;
+use std::time::Duration;
+
+fn config_error(source: &str) -> String {
+    match source.parse::<Config>() {
+        Err(MdrError::Config(msg)) => msg,
+        other => panic!("expected a config error, got {:?}", other),
+    }
+}
+
+#[test]
+fn empty_file_gives_defaults() {
+    assert_eq!("".parse::<Config>().unwrap(), Config::default());
+}
+
+#[test]
+fn reads_prize_auto_reset() {
+    let config: Config = "
+        # kiosk setup
+        [prize]
+        auto_reset = true   # come back on your own
+        auto_reset_seconds = 45
+    "
+    .parse()
+    .unwrap();
+
+    assert!(config.prize.auto_reset);
+    assert_eq!(config.prize.auto_reset_after, Duration::from_secs(45));
+}
+
+#[test]
+fn reads_custom_perks() {
+    let config: Config = "
+        [prize]
+        builtin = false
+
+        [[perk]]
+        name = \"30 min of real coffee\"
+        weight = 2
+
+        [[perk]]
+        name = \"Walk outside\"
+    "
+    .parse()
+    .unwrap();
+
+    assert!(!config.prize.builtin);
+    assert_eq!(config.prize.perks, [Prize { name: "30 min of real coffee".to_string(), weight: 2 }, Prize::new("Walk outside")]);
+    assert!(config_error("[[perk]]\nname = \"Nap\"\n[[perk]]\nname = \"Nap\"\n").contains("'Nap' is listed twice"));
+    assert!(config_error("[perk]\nname = \"Nap\"\n").contains("should be written [[perk]]"));
+    assert!(config_error("[[perk]]\nname = \"Nap\"\nweigth = 2\n").contains("did you mean perk.weight?"));
+}
+
+#[test]
+fn reads_save_encryption() {
+    let config: Config = "[save]\nencrypt = true\nkdf_iterations = 200000\n".parse().unwrap();
+
+    assert!(config.save.encrypt);
+    assert_eq!(config.save.kdf_iterations, 200_000);
+    assert!(config_error("[save]\nkdf_iterations = 10\n").starts_with("line 2:"));
+}
+
+#[test]
+fn reads_budget_caps() {
+    let config: Config = "[budget]\ninbox = 5\nreplaced_numbers = 100\n".parse().unwrap();
+
+    assert_eq!(config.budget.inbox, 5);
+    assert_eq!(config.budget.replaced_numbers, 100);
+    assert_eq!(config.budget.event_log, Budget::default().event_log);
+    assert!(config_error("[budget]\nevent_log = 0\n").contains("'event_log' must be between 1 and"));
+}
+
+#[test]
+fn reads_clipboard_settings() {
+    let config: Config = "[clipboard]\nosc52 = false\nmax_bytes = 4096\n".parse().unwrap();
+    assert_eq!(config.clipboard.osc52, Some(false));
+    assert_eq!(config.clipboard.max_bytes, 4096);
+
+    let config: Config = "[clipboard]\nosc52 = \"auto\"\n".parse().unwrap();
+    assert_eq!(config.clipboard.osc52, None);
+    assert!(config_error("[clipboard]\nosc52 = \"sometimes\"\n").contains("'osc52' must be"));
+    assert!(config_error("[clipboard]\nmax_bytes = 0\n").contains("'max_bytes' must be"));
+}
+
+#[test]
+fn reads_the_speech_settings() {
+    let config: Config = "[speech]\nenabled = true\ncommand = \"espeak -s 140\"\n".parse().unwrap();
+    assert!(config.speech.enabled);
+    assert_eq!(config.speech.command.as_deref(), Some("espeak -s 140"));
+    assert!(config_error("[speech]\ncommand = \" \"\n").contains("'command' must not be empty"));
+}
+
+#[test]
+fn reads_the_ambience() {
+    assert!(!"".parse::<Config>().unwrap().ambience);
+    assert!("[ambience]\nenabled = true\n".parse::<Config>().unwrap().ambience);
+}
+
+#[test]
+fn reads_the_compliance_checks() {
+    assert!(!"".parse::<Config>().unwrap().compliance);
+    assert!("[compliance]\nenabled = true\n".parse::<Config>().unwrap().compliance);
+}
+
+#[test]
+fn reads_the_breaks() {
+    let config: Config = "".parse().unwrap();
+    assert!(!config.breaks.enabled);
+
+    let config: Config = "[breaks]\nenabled = true\nminutes = 10\n".parse().unwrap();
+    assert!(config.breaks.enabled);
+    assert_eq!(config.breaks.length, Duration::from_secs(10 * 60));
+    assert!(config_error("[breaks]\nminutes = 0\n").contains("'minutes' must be"));
+}
+
+#[test]
+fn reads_the_bin_strategy() {
+    let config: Config = "".parse().unwrap();
+    assert_eq!(config.bin_strategy, BinStrategy::Random);
+
+    let config: Config = "[bins]\nstrategy = \"round-robin\"\n".parse().unwrap();
+    assert_eq!(config.bin_strategy, BinStrategy::RoundRobin);
+    assert!(config_error("[bins]\nstrategy = \"fullest\"\n").contains("'strategy' must be"));
+}
+
+#[test]
+fn reads_the_lag_thresholds() {
+    let config: Config = "".parse().unwrap();
+    assert_eq!(config.lag, LagConfig::default());
+
+    let config: Config = "[lag]\nslow_write_ms = 80\nframes = 3\nmax_stride = 1\n".parse().unwrap();
+    assert_eq!(config.lag, LagConfig { slow_write: Duration::from_millis(80), frames: 3, max_stride: 1 });
+    assert!(config_error("[lag]\nmax_stride = 0\n").contains("'max_stride' must be"));
+}
+
+#[test]
+fn reads_the_idle_logout() {
+    let config: Config = "[login]\nidle_logout_minutes = 20\n".parse().unwrap();
+    assert_eq!(config.idle_logout, Some(Duration::from_secs(20 * 60)));
+
+    let config: Config = "[login]\nidle_logout_minutes = 0\n".parse().unwrap();
+    assert_eq!(config.idle_logout, None);
+    assert!(config_error("[login]\nidle_logout_minutes = -5\n").contains("'idle_logout_minutes' must be"));
+}
+
+#[test]
+fn reloading_takes_only_the_settings_safe_to_change() {
+    let mut config: Config = "[save]\nencrypt = false\n".parse().unwrap();
+    let saved: Config = "[theme]\nname = \"Optics\"\n[display]\nreduce_motion = true\n[save]\nencrypt = true\n"
+        .parse()
+        .unwrap();
+    assert!(saved.display.reduce_motion && !saved.display.eco);
+
+    let reload = config.reload(saved);
+
+    assert_eq!(reload.applied, ["theme", "display"]);
+    assert_eq!(reload.pending, ["save"]);
+    assert_eq!(config.theme.as_deref(), Some("Optics"));
+    assert!(config.display.reduce_motion);
+    assert!(!config.save.encrypt);
+    assert!(config.clone().reload(config).is_empty());
+    assert!(config_error("[display]\neco = \"yes\"\n").contains("eco"));
+}
+
+#[test]
+fn reads_the_logo() {
+    let config: Config = "[branding]\nlogo = \"badge\"\n".parse().unwrap();
+    assert_eq!(config.logo, Some(Logo::Badge));
+
+    let config: Config = "[branding]\nlogo = \"auto\"\n".parse().unwrap();
+    assert_eq!(config.logo, None);
+    assert!(config_error("[branding]\nlogo = \"banner\"\n").contains("'logo' must be"));
+}
+
+#[test]
+fn reads_session_goals() {
+    let config: Config = "[goals]\nitems = [\"Fill bin 3\", \"Refine 200 numbers\"]\n".parse().unwrap();
+
+    assert_eq!(config.goals, ["Fill bin 3", "Refine 200 numbers"]);
+}
+
+#[test]
+fn reads_scoring_difficulty() {
+    let config: Config = "[scoring]\ndifficulty = \"relaxed\"\n".parse().unwrap();
+
+    assert_eq!(config.difficulty, Difficulty::Relaxed);
+    assert!(config_error("[scoring]\ndifficulty = \"brutal\"\n").starts_with("line 2:"));
+}
+
+#[test]
+fn reads_reset_confirmation_style() {
+    let config: Config = "[reset]\nconfirm = \"hold\"\n".parse().unwrap();
+
+    assert_eq!(config.reset_confirm, ConfirmStyle::Hold);
+    assert_eq!(Config::default().reset_confirm, ConfirmStyle::Modal);
+    assert!(config_error("[reset]\nconfirm = \"twice\"\n").starts_with("line 2:"));
+}
+
+#[test]
+fn errors_point_at_the_offending_line() {
+    let msg = config_error("[prize]\nauto_reset = \"yes\"\n");
+    assert_eq!(msg, "line 2: 'auto_reset' should be a boolean, found string");
+
+    let msg = config_error("[prize]\n\nauto_reset_seconds = 0\n");
+    assert!(msg.starts_with("line 3:"), "{}", msg);
+
+    let msg = config_error("[prize\n");
+    assert!(msg.starts_with("line 1:"), "{}", msg);
+}
+
+#[test]
+fn missing_file_is_not_an_error() {
+    let path = std::env::temp_dir().join("lumon-mdr-no-such-dir").join("config.toml");
+    assert_eq!(config::load(&path).unwrap(), Config::default());
+}
+
+#[test]
+fn unknown_tables_and_keys_are_errors_with_suggestions() {
+    assert_eq!(
+        config_error("[prise]\nauto_reset = true\n"),
+        "line 1: [prise] is not a config table; did you mean [prize]?"
+    );
+    assert_eq!(
+        config_error("[prize]\n\nauto_rest = true\n"),
+        "line 3: prize.auto_rest is not a setting; did you mean prize.auto_reset?"
+    );
+    assert_eq!(config_error("[display]\nsparkle = true\n"), "line 2: display.sparkle is not a setting");
+    assert!(config_error("eco = true\n").starts_with("line 1: 'eco' must go under a table"));
+    assert!(config_error("[[theme]]\nname = \"x\"\n").starts_with("line 1: [[theme]]"));
+
+    let msg = config_error("[prize]\nauto_rest = true\n[save]\nencrypt = 1\n");
+    assert!(msg.ends_with("(and 1 more; see `lumon-mdr config check`)"), "{}", msg);
+}
+
+#[test]
+fn check_lists_every_problem() {
+    assert!(config::check("[prize]\nauto_reset = true\n").is_empty());
+    assert_eq!(
+        config::check("[prize]\nauto_rest = true\n[save]\nencrypt = 1\n"),
+        vec![
+            "line 2: prize.auto_rest is not a setting; did you mean prize.auto_reset?".to_string(),
+            "line 4: 'encrypt' should be a boolean, found integer".to_string(),
+        ]
+    );
+    assert!(config::check("[prize\n")[0].starts_with("line 1:"));
+    assert_eq!(config::check("[prize]\nauto_reset_seconds = 0\n").len(), 1);
+}
+
+#[test]
+fn every_budget_cap_is_in_the_schema() {
+    let (_, keys) = schema::SCHEMA.iter().find(|(name, _)| *name == "budget").unwrap();
+    let mut config = Config::default();
+    for (key, _) in config.budget.caps_mut() {
+        assert!(keys.iter().any(|(known, _)| *known == key), "{} is missing", key);
+    }
+}
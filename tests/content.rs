@@ -0,0 +1,150 @@
+use lumon_mdr::app::App;
+use lumon_mdr::content::{self, Content, Prize};
+use lumon_mdr::error::MdrError;
+use lumon_mdr::scoring::ScoringValues;
+use lumon_mdr::theme::Palette;
+use ratatui::style::Color;
+
+const PACK: &str = r##"
+[pack]
+name = "Optics and Design"
+
+[content]
+prizes = [
+    "Egg Bar",          # a rare treat
+    "Defiant Jazz Dance Experience",
+]
+file_names = ["Jesse"]
+
+[[theme]]
+name = "green-room"
+fg = "#5fbf8f"
+bg = "#0b2015"
+"##;
+
+fn pack_error(text: &str) -> String {
+    match Content::default().merge(text, "odd.toml") {
+        Err(MdrError::Config(msg)) => msg,
+        other => panic!("expected a pack error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn packs_add_to_the_built_in_content() {
+    let mut content = Content::default();
+    let builtin_prizes = content.prizes.len();
+
+    content.merge(PACK, "optics.toml").unwrap();
+
+    assert_eq!(content.prizes.len(), builtin_prizes + 2);
+    assert!(content.prizes.iter().any(|p| p.name == "Defiant Jazz Dance Experience"));
+    assert!(content.file_names.iter().any(|f| f == "Jesse"));
+    assert_eq!(content.packs, ["Optics and Design"]);
+    assert_eq!(content.theme("green-room").unwrap().bg, Color::Rgb(0x0b, 0x20, 0x15));
+}
+
+#[test]
+fn pack_mistakes_name_the_file_and_line() {
+    assert_eq!(
+        pack_error("[pack]\nname = \"x\"\n[content]\nprize = [\"Egg Bar\"]\n"),
+        "odd.toml: line 4: unknown key 'prize'; did you mean 'prizes'?"
+    );
+    assert_eq!(
+        pack_error("[pack]\nname = \"x\"\n[[theme]]\nname = \"t\"\nfg = \"green\"\nbg = \"#000000\"\n"),
+        "odd.toml: line 5: 'fg' should be a colour like \"#1a2b3c\""
+    );
+    assert!(pack_error("[content]\nquotes = [\"Hi\"]\n").contains("[pack]"));
+    assert!(pack_error("[pack]\nname = \"x\"\n[content]\nquotes = [\"\"]\n").contains("empty"));
+    assert!(pack_error("[pack]\nname = \"x\"\n[content]\nquotes = [\n\"open\"\n").contains("never closed"));
+
+    let mut content = Content::default();
+    content.merge(PACK, "a.toml").unwrap();
+    assert!(content.merge(PACK, "b.toml").is_err(), "theme names must be unique");
+}
+
+#[test]
+fn perks_are_weighted_and_listed_once() {
+    let mut content = Content::default();
+    content
+        .merge("[pack]\nname = \"a\"\n[content]\nprizes = [\"Egg Bar\"]\n[[perk]]\nname = \"Walk outside\"\nweight = 3\n", "a.toml")
+        .unwrap();
+    assert_eq!(content.prizes.last(), Some(&Prize { name: "Walk outside".to_string(), weight: 3 }));
+
+    assert_eq!(
+        pack_error("[pack]\nname = \"x\"\n[content]\nprizes = [\"Egg Bar\", \"Egg Bar\"]\n"),
+        "odd.toml: line 4: prize 'Egg Bar' is listed twice"
+    );
+    assert!(pack_error("[pack]\nname = \"x\"\n[[perk]]\nname = \"Egg Bar\"\nweight = 0\n").contains("'weight' must be"));
+    assert!(content.merge("[pack]\nname = \"b\"\n[content]\nprizes = [\"Egg Bar\"]\n", "b.toml").is_err());
+    assert!(pack_error("[pack]\nname = \"x\"\n[content]\nprizes = [\"Melon Bar\"]\n").contains("already in the pool"));
+}
+
+#[test]
+fn the_prize_pool_takes_the_config_perks() {
+    let mut content = Content::default();
+    content.merge("[pack]\nname = \"a\"\n[content]\nprizes = [\"Egg Bar\"]\n", "a.toml").unwrap();
+    let coffee = Prize { name: "30 min of real coffee".to_string(), weight: 2 };
+    let melon = Prize { name: "Melon Bar".to_string(), weight: 5 };
+
+    let pool = content.prize_pool(&[coffee.clone(), melon.clone()], true);
+    assert_eq!(pool.len(), content.prizes.len() + 1);
+    assert_eq!(pool.iter().filter(|prize| prize.name == "Melon Bar").count(), 1);
+    assert!(pool.contains(&melon));
+
+    let pool = content.prize_pool(std::slice::from_ref(&coffee), false);
+    assert_eq!(pool, [Prize::new("Egg Bar"), coffee]);
+}
+
+#[test]
+fn heavier_prizes_take_more_of_the_rolls() {
+    let pool = [Prize::new("Egg Bar"), Prize { name: "Walk outside".to_string(), weight: 3 }];
+    let names: Vec<&str> = (0..5).map(|roll| content::pick_prize(&pool, roll).unwrap().name.as_str()).collect();
+
+    assert_eq!(names, ["Egg Bar", "Walk outside", "Walk outside", "Walk outside", "Egg Bar"]);
+    assert!(content::pick_prize(&[], 7).is_none());
+}
+
+#[test]
+fn packs_tweak_scoring_values() {
+    let mut content = Content::default();
+    content.merge("[pack]\nname = \"a\"\n[scoring]\ncombo_bonus = 8\nstreak_from = 3\n", "a.toml").unwrap();
+    content.merge("[pack]\nname = \"b\"\n[scoring]\ncombo_bonus = 10\n", "b.toml").unwrap();
+
+    assert_eq!(content.scoring.combo_bonus, 10, "the last pack wins");
+    assert_eq!(content.scoring.streak_from, 3);
+    assert_eq!(content.scoring.misclick_penalty, ScoringValues::default().misclick_penalty);
+
+    assert_eq!(
+        pack_error("[pack]\nname = \"x\"\n[scoring]\ncombo_min = 1\n"),
+        "odd.toml: line 4: 'combo_min' must be between 2 and 9"
+    );
+    assert!(pack_error("[pack]\nname = \"x\"\n[scoring]\ncombo = 3\n").contains("did you mean 'combo_min'"));
+}
+
+#[test]
+fn a_pack_theme_replaces_the_palette() {
+    let dir = std::env::temp_dir().join(format!("lumon-mdr-packs-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("optics.toml"), PACK).unwrap();
+
+    let mut app = App::new(Palette::Ansi);
+    app.station.packs_dir = Some(dir.clone());
+    app.station.config.theme = Some("green-room".to_string());
+    app.reload_content();
+
+    assert!(matches!(app.palette, Palette::Custom { .. }));
+    assert!(app.error.is_none());
+
+    app.station.config.theme = Some("break-room".to_string());
+    app.reload_content();
+    assert!(app.error.as_ref().unwrap().detail.contains("available: green-room"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn parses_hex_colours() {
+    assert_eq!(content::parse_color("#ff8000"), Some(Color::Rgb(255, 128, 0)));
+    assert_eq!(content::parse_color("ff8000"), None);
+    assert_eq!(content::parse_color("#ff80"), None);
+}
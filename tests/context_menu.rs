@@ -0,0 +1,31 @@
+use lumon_mdr::context_menu::{ContextMenu, MenuItem, MenuTarget};
+use ratatui::layout::Rect;
+
+fn menu(anchor: (u16, u16)) -> ContextMenu {
+    ContextMenu::new(anchor, MenuTarget::Bin(0), vec![MenuItem::Deposit, MenuItem::BinDetails])
+}
+
+#[test]
+fn the_menu_opens_by_the_click_and_stays_on_screen() {
+    let screen = Rect::new(0, 0, 80, 24);
+
+    assert_eq!(menu((10, 5)).area(screen), Rect::new(11, 6, 16, 4));
+    // Flipped left and up where the screen runs out
+    assert_eq!(menu((78, 22)).area(screen), Rect::new(62, 18, 16, 4));
+}
+
+#[test]
+fn items_are_hit_inside_the_border_and_the_highlight_wraps() {
+    let screen = Rect::new(0, 0, 80, 24);
+    let mut menu = menu((10, 5));
+
+    assert_eq!(menu.item_at(screen, 12, 7), Some(0));
+    assert_eq!(menu.item_at(screen, 12, 8), Some(1));
+    assert_eq!(menu.item_at(screen, 11, 7), None, "the border is not an item");
+    assert_eq!(menu.item_at(screen, 12, 9), None);
+
+    menu.select(-1);
+    assert_eq!(menu.chosen(), Some(MenuItem::BinDetails));
+    menu.select(1);
+    assert_eq!(menu.chosen(), Some(MenuItem::Deposit));
+}
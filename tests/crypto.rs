@@ -0,0 +1,38 @@
+use lumon_mdr::crypto::{self, SealKey};
+
+fn hex(text: &str) -> Vec<u8> {
+    let text: String = text.split_whitespace().collect();
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+// RFC 8439 section 2.8.2
+#[test]
+fn aead_matches_the_rfc() {
+    let key: [u8; 32] = std::array::from_fn(|i| 0x80 + i as u8);
+    let nonce: [u8; 12] = hex("070000004041424344454647").try_into().unwrap();
+    let aad = hex("50515253c0c1c2c3c4c5c6c7");
+    let plaintext = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+    let sealed = crypto::seal(&key, &nonce, &aad, plaintext);
+
+    assert_eq!(sealed[..16].to_vec(), hex("d31a8d34648e60db7b86afbc53ef7ec2"));
+    assert_eq!(sealed[sealed.len() - 16..].to_vec(), hex("1ae10b594f09e26a7e902ecbd0600691"));
+    assert_eq!(crypto::open(&key, &nonce, &aad, &sealed).unwrap(), plaintext.to_vec());
+}
+
+#[test]
+fn tampering_or_a_wrong_key_is_rejected() {
+    let key = SealKey::derive("kier", [7; 16], 1000);
+    let wrong = SealKey::derive("eagan", [7; 16], 1000);
+    let nonce = crypto::random_nonce();
+
+    let mut sealed = crypto::seal(&key.key, &nonce, b"", b"waffle party");
+    assert!(crypto::open(&wrong.key, &nonce, b"", &sealed).is_none());
+
+    sealed[3] ^= 1;
+    assert!(crypto::open(&key.key, &nonce, b"", &sealed).is_none());
+    assert!(crypto::open(&key.key, &nonce, b"", &sealed[..4]).is_none());
+}
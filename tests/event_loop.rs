@@ -0,0 +1,2081 @@
+use lumon_mdr::app::{
+    ACTIVE_TICK_RATE, ANIMATION_STEP, App, AppState, BIN_FILLED_FLASH, COMBO_BANNER_DURATION, DEFAULT_TICK_RATE, ECO_TICK_RATE, ErrorAction, FOUNDER_PRIZE, IDLE_TICK_RATE,
+    DREAD_RAMP, GRID_MORPH, LoginStep, RESHUFFLE_PENALTY, RestoreStep, STAGE_DURATION, ScaryCluster, SidePanel,
+};
+use lumon_mdr::ambience::{self, Ambience};
+use lumon_mdr::assist::{self, PULSE_LENGTH};
+use lumon_mdr::autosave::{self, AUTOSAVE_INTERVAL, Autosave};
+use lumon_mdr::branding::Logo;
+use lumon_mdr::clock::{Clock, MockClock};
+use lumon_mdr::compliance;
+use lumon_mdr::crypto;
+use lumon_mdr::diagnostics::Quality;
+use lumon_mdr::config::{ConfirmStyle, LagConfig};
+use lumon_mdr::events::AppEvent;
+use lumon_mdr::inject::Injector;
+use lumon_mdr::ledger;
+use lumon_mdr::observe::SessionLink;
+use lumon_mdr::onboarding::{Lesson, Onboarding};
+use lumon_mdr::orientation::{ORIENTATION_CLUSTER, Step};
+use lumon_mdr::random::RngService;
+use lumon_mdr::scoring::{Capture, Difficulty};
+use lumon_mdr::screen::Screen;
+use lumon_mdr::selfcheck;
+use lumon_mdr::share::ShareCode;
+use lumon_mdr::shift::Shift;
+use lumon_mdr::stats;
+use lumon_mdr::error::{FailureKind, MdrError, RecoverableError};
+use lumon_mdr::input::{self, DESIRED_HEIGHT, DESIRED_WIDTH};
+use lumon_mdr::strategy::BinStrategy;
+use lumon_mdr::temper::Temper;
+use lumon_mdr::testing::{self, ScriptedEvents};
+use lumon_mdr::theme::{self, Palette};
+use lumon_mdr::ui::{self, MainScreen, ReportScreen};
+use lumon_mdr::updates::UpdateCheck;
+use lumon_mdr::watch::Watcher;
+use crossterm::event::KeyCode;
+use ratatui::{Terminal, backend::TestBackend, style::{Color, Modifier}};
+use std::rc::Rc;
+use std::time::{Duration, UNIX_EPOCH};
+
+/// Master seed for every harness app
+const HARNESS_SEED: u64 = 1;
+
+struct Harness {
+    terminal: Terminal<TestBackend>,
+    clock: MockClock,
+    app: App,
+}
+
+impl Harness {
+    fn new() -> Self {
+        Self::with_clock(MockClock::new())
+    }
+
+    fn with_clock(clock: MockClock) -> Self {
+        Self {
+            terminal: Terminal::new(TestBackend::new(DESIRED_WIDTH, DESIRED_HEIGHT)).unwrap(),
+            // A pinned seed, so every run draws the same grids and deposits
+            app: App::with_random(Palette::Ansi, Rc::new(clock.clone()), RngService::new(HARNESS_SEED)),
+            clock,
+        }
+    }
+
+    fn in_main() -> Self {
+        let mut harness = Self::new();
+        harness.app.username = "mark".to_string();
+        harness.app.go_to(AppState::Loading);
+        harness.app.go_to(AppState::Main);
+        harness
+    }
+
+    fn run(&mut self, script: &str) {
+        let mut events = script
+            .parse::<ScriptedEvents>()
+            .unwrap()
+            .with_clock(self.clock.clone());
+        input::event_loop(&mut self.terminal, &mut self.app, &mut events).unwrap();
+    }
+
+    /// Wait for the key for a passphrase just given to be worked out
+    fn derive_key(&mut self) {
+        while self.app.key_derivation.is_some() {
+            std::thread::sleep(Duration::from_millis(1));
+            self.app.poll_key_derivation();
+        }
+    }
+
+    fn find(&self, text: &str) -> (u16, u16) {
+        testing::find_text(self.terminal.backend().buffer(), text).unwrap()
+    }
+}
+
+#[test]
+fn login_moves_to_loading() {
+    let mut h = Harness::new();
+
+    h.run("type:mark;enter");
+
+    assert_eq!(h.app.username, "mark");
+    assert!(matches!(h.app.state(), AppState::Loading));
+}
+
+#[test]
+fn kier_is_welcomed_with_his_portrait_and_prize() {
+    let mut h = Harness::new();
+
+    h.run("type:Kier;enter");
+    assert!(h.app.founder_visit);
+    h.app.progress_percentage = 100.0;
+    h.run("wait:1");
+    h.find("KIER EAGAN, FOUNDER");
+
+    h.app.station.content.prizes.clear();
+    h.app.select_random_prize();
+    assert_eq!(h.app.prize_name, FOUNDER_PRIZE);
+}
+
+#[test]
+fn the_first_launch_of_a_week_recaps_the_last_one() {
+    let wednesday = ledger::parse_day("2025-03-12").unwrap();
+    let mut h = Harness::with_clock(MockClock::at(UNIX_EPOCH + Duration::from_secs(wednesday as u64 * 86_400 + 3_600)));
+    h.app.station.ledger.record(wednesday - 8, &AppEvent::FileCompleted { prize: "Melon Bar".to_string() });
+    h.app.station.ledger.record(wednesday - 8, &AppEvent::NumbersRefined { count: 120 });
+
+    h.app.offer_recap();
+    assert_eq!(h.app.state(), AppState::Recap);
+    h.run("wait:1");
+    h.find("WEEKLY QUOTA RECAP");
+    h.find("Best day: Tuesday");
+
+    h.run("enter");
+    assert_eq!(h.app.state(), AppState::Login);
+
+    // Once a week is enough
+    h.app.offer_recap();
+    assert_eq!(h.app.state(), AppState::Login);
+}
+
+#[test]
+fn empty_login_shows_error() {
+    let mut h = Harness::new();
+
+    h.run("enter");
+
+    assert!(h.app.show_login_error);
+    assert!(matches!(h.app.state(), AppState::Login));
+}
+
+#[test]
+fn brackets_change_the_game_speed() {
+    let mut h = Harness::new();
+    h.app.go_to(AppState::Loading);
+    h.app.progress_percentage = 100.0;
+
+    // Clamped at double speed, so the 1.8s pause on a full bar takes 0.9s
+    h.run("key:];key:];key:];key:];key:]");
+    assert_eq!(h.app.game_speed, 200);
+    h.run("pause:1100");
+    assert_eq!(h.app.state(), AppState::Main);
+    h.run("wait:1");
+    h.find("SPEED 2.00x");
+
+    h.run("key:[;key:[;key:[;key:[;key:[;key:[");
+    assert_eq!(h.app.game_speed, 50);
+    assert_eq!(h.app.game_time(Duration::from_secs(1)), Duration::from_secs(2));
+}
+
+#[test]
+fn the_completion_counts_up_and_pulses_past_each_quarter() {
+    let mut h = Harness::in_main();
+    h.run("wait:1");
+    h.find(" 0% Complete");
+
+    let bins = h.app.containers.len() as f32;
+    h.app.containers[0].add(100);
+    h.app.containers[1].add(100);
+    let target = 200.0 / bins;
+
+    h.run("pause:300");
+    let shown = h.app.completion.value(h.clock.now());
+    assert!(shown > 0.0 && shown < target, "counting up, at {}", shown);
+
+    h.run("pause:200");
+    assert!(h.app.completion_pulse.is_some(), "passed 25%");
+
+    h.run("pause:1200");
+    assert!(h.app.completion_pulse.is_none());
+    h.find(&format!(" {}% Complete", target.round()));
+
+    // Emptying a bin shows straight away
+    h.app.containers[0].empty();
+    h.run("wait:1");
+    h.find(&format!(" {}% Complete", (100.0 / bins).round()));
+}
+
+#[test]
+fn a_bin_eases_up_to_a_deposit_showing_what_was_added() {
+    let delta_cells = |h: &Harness| {
+        let bright = Palette::Ansi.ramp(1.0);
+        let buffer = h.terminal.backend().buffer();
+        buffer.content().iter().filter(|cell| cell.symbol == "\u{2588}" && cell.fg == bright && cell.modifier.contains(Modifier::BOLD)).count()
+    };
+    let mut h = Harness::in_main();
+    h.app.containers[0].add(30);
+    h.run("pause:600");
+    assert_eq!(h.app.containers[0].fill.value(h.clock.now()), 30.0);
+
+    h.app.containers[0].add(40);
+    h.run("pause:200");
+    let shown = h.app.containers[0].fill.value(h.clock.now());
+    assert!(shown > 30.0 && shown < 70.0, "easing, at {}", shown);
+    assert!(delta_cells(&h) > 0);
+
+    h.run("pause:500");
+    assert_eq!(h.app.containers[0].fill.value(h.clock.now()), 70.0);
+    assert_eq!(delta_cells(&h), 0);
+    h.find("70%");
+}
+
+#[test]
+fn coach_marks_wait_for_a_pause_and_go_once_learned() {
+    let mut h = Harness::in_main();
+    h.run("pause:5000");
+    assert_eq!(h.app.onboarding.showing(), None);
+
+    h.run("pause:1500");
+    h.find("Move your mouse near the numbers");
+
+    // Hovering the grid is learned for good and the next hint waits its turn
+    let grid = h.app.grid_geometry.unwrap().area;
+    h.run(&format!("move:{},{};wait:1", grid.x + 2, grid.y + 2));
+    assert!(h.app.station.ledger.learned.contains(&Lesson::Hover));
+    assert!(testing::find_text(h.terminal.backend().buffer(), "Move your mouse").is_none());
+    h.run("pause:6500");
+    h.find("Click when they grow");
+    assert_eq!(h.app.station.ledger.to_string().lines().nth(1), Some("learned=hover"));
+}
+
+#[test]
+fn refiners_past_their_first_file_are_not_coached() {
+    let mut h = Harness::in_main();
+    h.app.station.ledger = "lumon-mdr ledger 1\n2025-03-03 numbers=340 bins=11 files=2\n".parse().unwrap();
+
+    h.run("pause:7000");
+    assert_eq!(h.app.onboarding, Onboarding::Finished);
+    assert!(testing::find_text(h.terminal.backend().buffer(), "Move your mouse").is_none());
+}
+
+#[test]
+fn orientation_steps_along_as_each_instruction_is_followed() {
+    let mut h = Harness::in_main();
+    h.run("key::;type:orientation;enter;wait:1");
+    assert_eq!(h.app.orientation, Some(Step::Look));
+    assert_eq!(h.app.file_name, "Orientation");
+    h.find("Welcome to Orientation.");
+
+    // Only bin 01 takes deposits until the last step
+    h.app.add_to_container(2, 5);
+    assert_eq!(h.app.containers[2].count, 0);
+
+    let geometry = h.app.grid_geometry.unwrap();
+    let at = |(col, row): (usize, usize)| {
+        (
+            geometry.area.x + col as u16 * geometry.horizontal_spacing + 2,
+            geometry.area.y + row as u16 * geometry.vertical_spacing + geometry.vertical_spacing / 2,
+        )
+    };
+    let (x, y) = at((12, 6));
+    h.run(&format!("move:{},{};wait:1", x, y));
+    assert_eq!(h.app.orientation, Some(Step::Sense));
+    assert_eq!(h.app.scary_clusters.len(), 1);
+
+    let (x, y) = at(ORIENTATION_CLUSTER[0]);
+    h.run(&format!("move:{},{};pause:2000", x, y));
+    assert_eq!(h.app.orientation, Some(Step::Refine));
+
+    for cell in ORIENTATION_CLUSTER {
+        let (x, y) = at(cell);
+        h.run(&format!("click:{},{};wait:1", x, y));
+    }
+    h.run("wait:1");
+    assert!(h.app.scary_clusters.is_empty());
+    // On the harness seed the digits the clicks turn up leave bin 01 short
+    assert_eq!(h.app.orientation, Some(Step::FirstBin));
+    assert!(h.app.containers[1..].iter().all(|bin| bin.count == 0), "what was refined went to bin 01");
+
+    h.app.add_to_container(0, 100);
+    h.run("wait:1");
+    assert_eq!(h.app.orientation, Some(Step::AllBins));
+    for bin in 1..h.app.containers.len() {
+        h.app.add_to_container(bin, 100);
+    }
+    h.run("wait:1");
+    assert_eq!(h.app.orientation, None);
+    assert!(h.app.toast.as_ref().unwrap().text.starts_with("Orientation complete"));
+}
+
+#[test]
+fn the_logo_is_shaded_in_blocks_with_letters_as_the_fallback() {
+    let mut h = Harness::new();
+    h.app.go_to(AppState::Loading);
+
+    h.run("wait:1");
+    let (x, y) = h.find("\u{2588}");
+    let ramp: Vec<Color> = (0..=4).map(|step| Palette::Ansi.ramp(step as f32 / 4.0)).collect();
+    assert!(ramp.contains(&h.terminal.backend().buffer().get(x, y).fg));
+    assert!(testing::find_text(h.terminal.backend().buffer(), "ZTMIKMLKOVSRSY").is_none());
+
+    h.app.station.ascii_only = true;
+    h.run("wait:1");
+    h.find("ZTMIKMLKOVSRSY");
+}
+
+#[test]
+fn the_logo_follows_the_window_size_unless_configured() {
+    let mut h = Harness::new();
+    h.app.go_to(AppState::Loading);
+
+    // A key dismisses the size warning each run starts with
+    h.terminal.backend_mut().resize(90, 30);
+    h.run("key:x;wait:1");
+    h.find("|_____|");
+
+    h.app.station.config.logo = Some(Logo::Badge);
+    h.run("key:x;wait:1");
+    h.find("\u{2502} INDUSTRY \u{2502}");
+    assert!(testing::find_text(h.terminal.backend().buffer(), "|_____|").is_none());
+
+    h.app.go_to(AppState::Main);
+    h.app.station.config.logo = Some(Logo::Text);
+    h.run("key:x;wait:1");
+    let (x, _) = h.find("LUMON INDUSTRIES");
+    assert_eq!(x, 90 - 16 - 2, "at the right edge");
+}
+
+#[test]
+fn loading_runs_to_the_main_screen() {
+    let mut h = Harness::new();
+
+    h.run("type:mark;enter;wait:5");
+    assert!(matches!(h.app.state(), AppState::Loading));
+
+    h.run("wait:300");
+    assert!(matches!(h.app.state(), AppState::Main));
+    assert_eq!(h.app.progress_percentage, 100.0);
+}
+
+#[test]
+fn finished_loading_waits_before_main() {
+    let mut h = Harness::new();
+    h.app.go_to(AppState::Loading);
+    h.app.progress_percentage = 100.0;
+
+    h.run("pause:1700");
+    assert!(matches!(h.app.state(), AppState::Loading));
+
+    h.run("pause:300");
+    assert!(matches!(h.app.state(), AppState::Main));
+}
+
+#[test]
+fn clicking_a_bin_deposits_into_it() {
+    let mut h = Harness::in_main();
+
+    h.run("wait:1");
+    let (x, y) = h.find("03");
+    h.run(&format!("click:{},{}", x, y));
+
+    assert_eq!(h.app.containers[2].count, 3);
+    assert!(h.app.containers.iter().enumerate().all(|(i, c)| i == 2 || c.count == 0));
+}
+
+#[test]
+fn left_handed_refiners_can_mirror_the_floor_and_swap_buttons() {
+    let mut plain = Harness::in_main();
+    plain.run("wait:1");
+
+    let mut h = Harness::in_main();
+    h.app.station.config.display.mirror = true;
+    h.app.station.config.input.swap_buttons = true;
+    h.run("wait:1");
+    let (x, y) = h.find("01");
+    assert_eq!(x, plain.find("05").0, "bin 01 stands where bin 05 did");
+
+    // The capturing button deposits, the other opens the bin's menu
+    h.run(&format!("click:{},{};wait:1", x, y));
+    assert_eq!(h.app.containers[0].count, 0);
+    h.run("down;down;enter;wait:1");
+    assert_eq!(h.app.focused_bin(), Some(0));
+    h.find("BIN 01");
+    h.run(&format!("rclick:{},{}", x, y));
+    assert_eq!(h.app.containers[0].count, 3);
+
+    let (panel_x, _) = h.find("BIN 01");
+    assert!(panel_x < DESIRED_WIDTH / 2, "the side panel opens on the left");
+}
+
+#[test]
+fn right_clicking_opens_a_menu_for_the_bin_or_cell_under_it() {
+    let mut h = Harness::in_main();
+    h.run("wait:1");
+    let (x, y) = h.find("02");
+
+    h.run(&format!("rclick:{},{};wait:1", x, y));
+    let (item_x, item_y) = h.find("Deposit here");
+    assert_eq!((item_x, item_y), (x + 3, y + 2), "the menu opens just below the click");
+    h.find("Bin details");
+    h.run(&format!("click:{},{};wait:1", item_x, item_y));
+    assert!(h.app.context_menu.is_none());
+    assert_eq!(h.app.containers[1].count, 3);
+
+    let (col, row) = (12, 6);
+    let (gx, gy) = h.app.grid_geometry.unwrap().position_of((col, row));
+    h.run(&format!("rclick:{},{};wait:1", gx, gy));
+    h.find("Refine here");
+    h.run("down;enter;wait:1");
+    assert_eq!(h.app.marked.len(), 9);
+    assert!(h.app.marked.contains(&(col + 1, row - 1)));
+
+    h.run(&format!("rclick:{},{};wait:1", gx, gy));
+    h.find("Unmark cluster");
+    h.run("esc;wait:1");
+    assert!(h.app.context_menu.is_none());
+    assert_eq!(h.app.marked.len(), 9, "Esc closes without choosing");
+
+    h.run(&format!("rclick:{},{};click:1,1", gx, gy));
+    assert!(h.app.context_menu.is_none(), "a click elsewhere closes the menu");
+    assert_eq!(h.app.containers.iter().map(|c| c.count).sum::<u16>(), 3);
+
+    h.app.reset_containers();
+    assert!(h.app.marked.is_empty());
+}
+
+#[test]
+fn dragging_a_bin_moves_it_along_the_floor() {
+    let mut h = Harness::in_main();
+
+    h.run("wait:1");
+    let (x, y) = h.find("01");
+    let (target, _) = h.find("04");
+    h.run(&format!("press:{x},{y};drag:{},{y};wait:1", x + 4));
+    // Lifted a row off the floor, with the rest not yet moved
+    assert_eq!(h.find("01"), (x, y - 1));
+    h.run(&format!("drag:{target},{y};wait:1;release:{target},{y};wait:1"));
+
+    assert_eq!(h.app.bin_order(), [1, 2, 3, 0, 4]);
+    assert_eq!(h.find("01"), (target, y));
+    assert_eq!(h.find("02").0, x);
+
+    h.app.reset_containers();
+    assert_eq!(h.app.bin_order(), [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn tab_opens_a_panel_for_the_focused_bin() {
+    let mut h = Harness::in_main();
+
+    h.run("tab;tab;enter;enter;wait:1");
+    assert_eq!(h.app.focused_bin(), Some(1));
+    assert_eq!(h.app.containers[1].count, 10);
+    assert_eq!(h.app.containers[1].tempers.iter().sum::<u16>(), 10);
+    assert_eq!(h.app.containers[1].deposits.len(), 2);
+    h.find("BIN 02");
+    h.find("Recent deposits");
+
+    h.run("backtab;backtab;wait:1");
+    assert_eq!(h.app.focused_bin(), Some(4), "focus wraps around the floor");
+
+    h.run("esc;wait:1");
+    assert_eq!(h.app.focused_bin(), None);
+    assert!(testing::find_text(h.terminal.backend().buffer(), "Recent deposits").is_none());
+}
+
+#[test]
+fn a_bins_recent_deposits_can_be_inspected() {
+    let mut h = Harness::in_main();
+    h.run("wait:1");
+    h.app.sticky.clear();
+    h.app.capture_or_hold(vec![(4, 3, 5), (5, 3, 7)]);
+    let bin = h.app.containers.iter().position(|c| !c.deposits.is_empty()).unwrap();
+    let deposit = &h.app.containers[bin].deposits[0];
+    assert_eq!(deposit.origins.iter().map(|origin| origin.cell).collect::<Vec<_>>(), [(4, 3), (5, 3)]);
+    assert_eq!(deposit.temper, Temper::Woe, "both numbers came from the top left quadrant");
+
+    h.app.focus_bin(Some(bin));
+    h.run("key:i;wait:1");
+    assert_eq!(h.app.inspecting, Some(bin));
+    h.find(&format!("BIN {:02} · INSPECTION", bin + 1));
+    h.find("WO 4,3 5,3");
+
+    h.run("tab;wait:1");
+    let next = h.app.bin_order()[(bin + 1) % h.app.containers.len()];
+    assert_eq!(h.app.inspecting, Some(next));
+    if h.app.containers[next].deposits.is_empty() {
+        h.find("Nothing deposited this session");
+    }
+    h.run("esc;wait:1");
+    assert_eq!(h.app.inspecting, None);
+
+    let (x, y) = h.find("01");
+    h.run(&format!("rclick:{},{};down;enter;wait:1", x, y));
+    assert_eq!(h.app.inspecting, Some(0));
+    h.find("BIN 01 · INSPECTION");
+}
+
+#[test]
+fn a_reviewed_capture_waits_a_moment_and_esc_calls_it_off() {
+    let mut h = Harness::in_main();
+    h.app.station.config.input.review_captures = true;
+    h.app.focus_bin(Some(2));
+    h.run("wait:1");
+    h.app.sticky.clear();
+
+    h.app.capture_or_hold(vec![(4, 3, 7), (5, 3, 3), (6, 3, 3), (7, 3, 9)]);
+    h.run("wait:1");
+    let staged = h.app.staged.clone().unwrap();
+    assert_eq!(staged.bin, 2);
+    h.find(&format!("7 3 3 9 → {} · BIN 03", staged.value));
+    assert_eq!(h.app.containers[2].count, 0, "nothing lands while it is on show");
+
+    h.clock.advance(STAGE_DURATION);
+    h.run("wait:1");
+    assert!(h.app.staged.is_none());
+    assert_eq!(h.app.containers[2].count, staged.value);
+
+    h.app.capture_or_hold(vec![(4, 3, 1)]);
+    h.run("esc;wait:1");
+    assert!(h.app.staged.is_none());
+    h.clock.advance(STAGE_DURATION);
+    h.run("wait:1");
+    assert_eq!(h.app.containers[2].count, staged.value, "a called off capture never lands");
+    assert_eq!(h.app.focused_bin(), Some(2), "Esc only calls off the capture");
+}
+
+#[test]
+fn the_bin_strategy_can_be_switched_while_playing() {
+    let mut h = Harness::in_main();
+    h.run("wait:1");
+    h.find("BINS RANDOM");
+
+    h.run("key::;type:bins least-full;enter;wait:1");
+    assert_eq!(h.app.station.config.bin_strategy, BinStrategy::LeastFull);
+    h.find("BINS LEAST-FULL");
+    h.app.sticky.clear();
+    for bin in [0, 2, 3, 4] {
+        h.app.add_to_container(bin, 10);
+    }
+    h.app.capture_or_hold(vec![(4, 3, 5)]);
+    assert!(h.app.containers[1].count > 0, "the emptiest bin takes the capture");
+
+    h.run("key::;type:bins fullest;enter;wait:1");
+    assert_eq!(h.app.station.config.bin_strategy, BinStrategy::LeastFull);
+    h.find("is not a bin strategy");
+}
+
+#[test]
+fn the_assist_pulses_the_best_cluster_for_relaxed_refiners() {
+    let mut h = Harness::in_main();
+    h.app.station.config.display.reduce_motion = true;
+    h.run("wait:1");
+    assert!(!h.app.assist_on(), "off at the standard difficulty");
+
+    h.app.station.config.difficulty = Difficulty::Relaxed;
+    assert!(h.app.assist_on());
+    h.run("wait:1");
+    h.clock.advance(assist::interval(0));
+    h.run("wait:1");
+    let pulse = h.app.assist_pulse.clone().expect("a pulse is due");
+
+    // The cluster is valued from the digits on screen
+    let geometry = h.app.grid_geometry.unwrap();
+    let buffer = h.terminal.backend().buffer().clone();
+    let (col, row) = pulse.cluster.corner;
+    let on_screen = |(col, row)| {
+        let (x, y) = geometry.position_of((col, row));
+        buffer.get(x, y).symbol.parse::<u16>().unwrap()
+    };
+    let digits: Vec<u16> = (row..row + 3).flat_map(|r| (col..col + 3).map(move |c| (c, r))).map(on_screen).collect();
+    assert_eq!(pulse.cluster.value, h.app.scoring.score(&Capture { digits, streak: 0, misclicks: 0 }));
+    let (x, y) = geometry.position_of((col, row));
+    assert!(buffer.get(x, y).modifier.contains(Modifier::REVERSED));
+
+    h.clock.advance(PULSE_LENGTH);
+    h.run("wait:1");
+    assert!(h.app.assist_pulse.is_none());
+
+    // A streak keeps the pulse after next further off
+    h.app.streak = 5;
+    h.clock.advance(assist::interval(0) - PULSE_LENGTH);
+    h.run("wait:1");
+    assert!(h.app.assist_pulse.is_some());
+    h.clock.advance(assist::interval(0));
+    h.run("wait:1");
+    assert!(h.app.assist_pulse.is_none());
+    h.clock.advance(assist::interval(5) - assist::interval(0));
+    h.run("wait:1");
+    assert!(h.app.assist_pulse.is_some());
+}
+
+#[test]
+fn p_steps_through_the_side_panels() {
+    let mut h = Harness::in_main();
+    h.app.show_toast("Please enjoy each number equally.", None);
+    h.app.station.outbox.push(AppEvent::BinFilled { bin: 3 });
+
+    h.run("key:p;wait:1");
+    assert_eq!(h.app.side_panel, Some(SidePanel::Stats));
+    h.find(" STATS ");
+    h.find("Refiner I");
+
+    h.run("key:p;wait:1");
+    h.find(" GOALS ");
+
+    h.run("key:p;wait:1");
+    assert_eq!(h.app.side_panel, Some(SidePanel::Bin));
+    assert_eq!(h.app.focused_bin(), Some(0));
+    h.find("BIN 01");
+
+    h.run("key:p;wait:1");
+    h.find("Please enjoy each number equally.");
+
+    h.run("key:p;wait:1");
+    assert_eq!(h.app.focused_bin(), None);
+    h.find("Bin 03 filled");
+
+    h.run("key:p;wait:1");
+    assert_eq!(h.app.side_panel, None);
+}
+
+#[test]
+fn goals_are_added_by_command_and_met_by_events() {
+    let mut h = Harness::in_main();
+    h.app.containers[1].add(100);
+
+    h.run("key::;type:goal add \"Fill bin 3\";enter;key::;type:goal add Fill bin 2;enter");
+    h.run("key::;type:goal add Refine 5 numbers;enter;key:p;key:p;wait:1");
+    assert_eq!(h.app.goals.items.len(), 3);
+    assert!(h.app.goals.items[1].done, "bin 2 was already full");
+    h.find("[ ] 1. Fill bin 3");
+    h.find("[ ] 3. Refine 5 numbers (0/5)");
+
+    h.app.deposit(2, 100);
+    h.app.station.outbox.push(AppEvent::NumbersRefined { count: 5 });
+    h.run("wait:1");
+    assert!(h.app.goals.all_met());
+    assert_eq!(h.app.toast.as_ref().unwrap().text, "Every session goal met. The Board is pleased.");
+    h.find("3 of 3 met");
+}
+
+#[test]
+fn the_header_shows_the_refiners_rank() {
+    let mut h = Harness::in_main();
+    h.app.xp = 4_200;
+
+    h.run("wait:1");
+
+    h.find("mark \u{00b7} Senior Refiner");
+}
+
+#[test]
+fn completing_every_bin_awards_a_prize() {
+    let mut h = Harness::in_main();
+    for (idx, container) in h.app.containers.iter_mut().enumerate() {
+        container.add(if idx == 0 { 97 } else { 100 });
+    }
+
+    h.run("wait:1");
+    let (x, y) = h.find("01");
+    h.run(&format!("click:{},{};pause:2500", x, y));
+    assert!(matches!(h.app.state(), AppState::Main));
+
+    h.run("pause:500");
+    assert!(matches!(h.app.state(), AppState::Prize));
+    assert!(!h.app.prize_name.is_empty());
+    assert_eq!(h.app.vouchers.len(), 1);
+    assert_eq!(h.app.vouchers[0].prize, h.app.prize_name);
+    assert_eq!(h.app.vouchers[0].employee, "mark");
+    let card = h.app.card.clone().unwrap();
+    assert_eq!(card.prizes, [h.app.prize_name.clone()]);
+    assert_eq!(card.code, h.app.share_code().to_string());
+
+    h.run("enter");
+
+    assert!(matches!(h.app.state(), AppState::Main));
+    assert!(h.app.containers.iter().all(|c| c.count == 0));
+}
+
+fn show_prize(h: &mut Harness) {
+    for container in h.app.containers.iter_mut() {
+        container.add(100);
+    }
+    h.run("pause:3000");
+    assert!(matches!(h.app.state(), AppState::Prize));
+}
+
+#[test]
+fn prize_auto_reset_counts_down_to_a_fresh_file() {
+    let mut h = Harness::in_main();
+    h.app.station.config.prize.auto_reset = true;
+    show_prize(&mut h);
+
+    h.run("wait:1");
+    h.find("Press any key to remain");
+
+    h.run("pause:25000");
+    assert!(matches!(h.app.state(), AppState::Prize));
+
+    h.run("pause:5000");
+    assert!(matches!(h.app.state(), AppState::Main));
+    assert!(h.app.containers.iter().all(|c| c.count == 0));
+}
+
+#[test]
+fn any_key_cancels_the_prize_auto_reset() {
+    let mut h = Harness::in_main();
+    h.app.station.config.prize.auto_reset = true;
+    show_prize(&mut h);
+
+    h.run("key:x;pause:40000");
+
+    assert!(matches!(h.app.state(), AppState::Prize));
+    assert!(h.app.prize_countdown().is_none());
+}
+
+#[test]
+fn prize_waits_without_auto_reset() {
+    let mut h = Harness::in_main();
+    show_prize(&mut h);
+
+    h.run("pause:40000");
+
+    assert!(matches!(h.app.state(), AppState::Prize));
+}
+
+#[test]
+fn refining_time_goes_to_the_innie_and_the_rest_to_the_outie() {
+    let mut h = Harness::in_main();
+
+    // Active and focused: the innie's time
+    h.run("move:10,10;pause:5000");
+    // Window in the background: the outie's
+    h.run("blur;pause:5000;focus");
+    // Focused again, but idle: still the outie's
+    h.run("pause:20000");
+
+    let balance = h.app.station.stats.balance("mark");
+    assert!(balance.innie.abs_diff(Duration::from_secs(5)) <= Duration::from_millis(500), "{:?}", balance);
+    assert!(balance.outie.abs_diff(Duration::from_secs(25)) <= Duration::from_millis(500), "{:?}", balance);
+}
+
+#[test]
+fn report_screen_shows_the_balance() {
+    let mut h = Harness::in_main();
+
+    h.run("move:10,10;pause:3000;key:s");
+    assert_eq!(h.app.state(), AppState::Report);
+    h.find("WORK/LIFE BALANCE REPORT: MARK");
+    h.find("Innie share:");
+
+    h.run("esc");
+    assert_eq!(h.app.state(), AppState::Main);
+}
+
+#[test]
+fn notes_go_in_the_journal_and_on_the_report() {
+    let mut h = Harness::with_clock(MockClock::at(UNIX_EPOCH + Duration::from_secs(1_704_067_200 + 14 * 3600)));
+    h.app.username = "mark".to_string();
+    h.app.go_to(AppState::Loading);
+    h.app.go_to(AppState::Main);
+
+    h.run("key::;type:note Expense report for Q3;enter");
+    assert_eq!(h.app.journal.len(), 1);
+    h.find("Noted in the journal");
+
+    h.run("key::;type:note;enter");
+    assert_eq!(h.app.journal.len(), 1);
+    h.find("Usage: :note <text>");
+
+    h.run("key:s");
+    h.find("Journal (1)");
+    h.find("14:00  Expense report for Q3");
+}
+
+#[test]
+fn the_report_can_be_copied() {
+    let mut h = Harness::in_main();
+
+    h.run("key::;type:note Lunch with Petey;enter;key:s;key:c");
+    assert_eq!(h.app.state(), AppState::Report);
+    h.find("Report sent to the terminal's clipboard");
+    let copied = h.app.station.clipboard.last.clone().unwrap();
+    assert!(copied.contains("Performance report: mark (Refiner I)"), "{}", copied);
+    assert!(copied.contains("Lunch with Petey"), "{}", copied);
+}
+
+#[test]
+fn seeds_and_screens_can_be_copied_from_the_command_line() {
+    let mut h = Harness::in_main();
+    h.app.grid_seed = 9_001;
+
+    h.run("key::;type:copy seed;enter");
+    assert_eq!(h.app.station.clipboard.last.as_deref(), Some("9001"));
+    h.find("Seed sent to the terminal's clipboard");
+
+    h.run("key::;type:copy screen;enter;wait:1");
+    let screen = h.app.station.clipboard.last.clone().unwrap();
+    assert!(screen.contains(&h.app.file_name), "{}", screen);
+    h.find("Screen sent to the terminal's clipboard");
+}
+
+#[test]
+fn a_share_code_opens_the_same_board_at_its_size() {
+    let mut h = Harness::in_main();
+    let shared = ShareCode { seed: 1_234, difficulty: Difficulty::Relaxed, grid: (8, 5) };
+
+    h.run(&format!("key::;type:code {};enter;wait:1", shared.to_string().to_lowercase()));
+    assert_eq!(h.app.shared, Some(shared));
+    assert_eq!(h.app.grid_seed, 1_234);
+    assert_eq!(h.app.difficulty(), Difficulty::Relaxed);
+    assert_eq!(h.app.grid_geometry.unwrap().cells(), (8, 5));
+    assert_eq!(h.app.share_code(), shared);
+    h.find(&shared.to_string());
+
+    h.run("key::;type:code HUSH-49B4997-10;enter");
+    h.find("the checksum does not match");
+    assert_eq!(h.app.shared, Some(shared));
+
+    // The next file is the refiner's own again
+    h.app.new_file();
+    assert_eq!(h.app.shared, None);
+}
+
+#[test]
+fn a_race_shows_the_delta_and_is_kept_in_the_ledger() {
+    let mut h = Harness::in_main();
+    h.run("key::;type:race pb;enter;wait:1");
+    h.find("No personal best to race yet");
+    assert_eq!(h.app.race, None);
+
+    h.run("key::;type:race 1:00;enter;wait:1");
+    assert_eq!(h.app.race.map(|race| race.target), Some(Duration::from_secs(60)));
+
+    // Half done in just over 20 seconds is nearly ten seconds up on a one
+    // minute pace
+    for container in h.app.containers.iter_mut() {
+        container.add(50);
+    }
+    h.run("pause:20000;wait:1");
+    h.find("Target 1:00.0 -0:09.");
+
+    for container in h.app.containers.iter_mut() {
+        container.add(50);
+    }
+    h.run("wait:1;pause:3000;wait:1");
+    assert_eq!(h.app.state(), AppState::Prize);
+    h.find("Race won by 0:3");
+    assert_eq!(h.app.race, None);
+    assert_eq!(h.app.station.ledger.races.len(), 1);
+    assert!(h.app.station.ledger.races[0].won());
+    let best = h.app.station.ledger.best.unwrap();
+    assert!(best > Duration::from_secs(20) && best < Duration::from_secs(25), "{:?}", best);
+}
+
+#[test]
+fn a_saved_config_takes_effect_without_a_restart() {
+    let mut h = Harness::in_main();
+    let path = std::env::temp_dir().join(format!("lumon-mdr-reload-{}.toml", std::process::id()));
+    std::fs::write(&path, "").unwrap();
+    h.app.station.watcher = Some(Watcher::start(Some(path.clone()), None, Duration::from_millis(20)));
+
+    std::fs::write(&path, "[display]\nreduce_motion = true\n[save]\nencrypt = true\n").unwrap();
+    for _ in 0..250 {
+        h.run("wait:1");
+        if h.app.station.config.display.reduce_motion {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+
+    assert!(h.app.reduce_motion());
+    assert!(!h.app.station.config.save.encrypt);
+    h.run("wait:1");
+    h.find("Config reloaded: display; save after a restart");
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn the_first_launch_asks_the_setup_questions_and_writes_the_config() {
+    let dir = std::env::temp_dir().join(format!("lumon-mdr-setup-{}", std::process::id()));
+    let path = dir.join("config.toml");
+    let mut h = Harness::new();
+    h.app.station.config_path = Some(path.clone());
+    h.app.offer_setup();
+    h.run("wait:1");
+    h.find("Step 1 of 5: Choose a colour theme");
+
+    h.run("enter;enter;down;enter;up;enter;wait:1");
+    h.find("Step 5 of 5");
+    h.run("type:Dylan G.;enter;wait:1");
+
+    assert_eq!(h.app.state(), AppState::Login);
+    assert_eq!(h.app.username, "Dylan G.");
+    assert!(!h.app.station.config.input.mouse);
+    assert_eq!(h.app.station.config.difficulty, Difficulty::Relaxed);
+    let written = std::fs::read_to_string(&path).unwrap();
+    assert!(written.contains("name = \"Dylan G.\""), "{}", written);
+
+    // Once written, the questions are not asked again
+    let mut again = Harness::new();
+    again.app.station.config_path = Some(path);
+    again.app.offer_setup();
+    assert_eq!(again.app.state(), AppState::Login);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn selfcheck_reports_on_demand_and_every_hour() {
+    let mut h = Harness::in_main();
+    h.run("key::;type:selfcheck;enter;wait:1");
+    h.find("Self-check passed");
+
+    h.app.containers[0].tempers[0] = 40;
+    h.clock.advance(Duration::from_secs(60 * 60));
+    h.run("wait:1");
+    assert_eq!(h.app.state(), AppState::Error);
+    assert_eq!(h.app.error.as_ref().unwrap().kind, FailureKind::Integrity);
+    assert!(h.app.inbox.iter().any(|notice| notice == "Self-check: bins: bin 1 holds 0 but its deposits add up to 40"));
+}
+
+// Monday 2024-01-01, one minute before a 09:00-17:00 weekday shift
+fn before_shift() -> Harness {
+    let monday_0859 = UNIX_EPOCH + Duration::from_secs(1_704_067_200 + 8 * 3600 + 59 * 60);
+    let mut h = Harness::with_clock(MockClock::at(monday_0859));
+    h.app.username = "mark".to_string();
+    h.app.go_to(AppState::Loading);
+    h.app.go_to(AppState::Main);
+    h.app.station.config.shift = Some(Shift {
+        start: 9 * 60,
+        end: 17 * 60,
+        days: [true, true, true, true, true, false, false],
+        utc_offset: 0,
+        supervisor_code: "kier".to_string(),
+    });
+    h
+}
+
+#[test]
+fn the_floor_is_locked_outside_shift_hours() {
+    let mut h = before_shift();
+
+    h.run("wait:1");
+    assert_eq!(h.app.state(), AppState::Locked);
+    h.find("THE SEVERED FLOOR IS CLOSED");
+
+    // Refinement keys do nothing while locked
+    h.run("key:r;key:s");
+    assert_eq!(h.app.state(), AppState::Locked);
+
+    h.run("pause:61000");
+    assert_eq!(h.app.state(), AppState::Main);
+    assert!(h.app.supervisor_entry.is_empty());
+}
+
+#[test]
+fn a_supervisor_code_opens_the_floor_early() {
+    let mut h = before_shift();
+    h.run("wait:1");
+
+    h.run("type:cobel;enter");
+    assert_eq!(h.app.state(), AppState::Locked);
+    h.find("Access denied.");
+
+    h.run("type:kier;enter;pause:5000");
+    assert_eq!(h.app.state(), AppState::Main);
+}
+
+#[test]
+fn an_observer_mirrors_a_published_session_read_only() {
+    let path = std::env::temp_dir().join(format!("lumon-mdr-observe-{}.save", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let mut observer = Harness::new();
+    observer.app.read_only = true;
+    observer.app.observe_state(AppState::Main);
+    observer.app.session_link = Some(SessionLink::observe(path.clone()));
+    observer.run("wait:2");
+    observer.find("WAITING FOR SESSION");
+
+    let mut refiner = Harness::in_main();
+    refiner.app.session_link = Some(SessionLink::publish(path.clone()));
+    refiner.run("wait:1");
+    let (x, y) = refiner.find("03");
+    refiner.run(&format!("click:{},{};wait:2", x, y));
+    let deposited = refiner.app.containers[2].count;
+    assert!(deposited > 0);
+
+    observer.run("wait:2");
+    assert_eq!(observer.app.username, "mark");
+    assert_eq!(observer.app.containers[2].count, deposited);
+    observer.find("READ ONLY");
+
+    // Input on the observer changes nothing
+    let (x, y) = observer.find("03");
+    observer.run(&format!("click:{},{};key:r;wait:2", x, y));
+    assert_eq!(observer.app.containers[2].count, deposited);
+
+    observer.run("key:q");
+    assert!(!observer.app.running);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn injected_progress_lands_in_the_bins() {
+    use std::io::Write;
+
+    let path = std::env::temp_dir().join(format!("lumon-mdr-inject-{}.jsonl", std::process::id()));
+    std::fs::write(&path, "{\"bin\": 1, \"add\": 50}\n").unwrap();
+
+    let mut h = Harness::in_main();
+    h.app.station.injector = Some(Injector::watch(path.clone()));
+    h.run("wait:1");
+    // Give the watcher a moment to open the file and seek past old lines
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    let mut file = std::fs::OpenOptions::new().append(true).open(&path).unwrap();
+    writeln!(file, r#"{{"bin": 2, "add": 5}}"#).unwrap();
+    writeln!(file, r#"{{"bin": 9, "add": 5}}"#).unwrap();
+    writeln!(file, r#"{{"bin": 2, "add": 7}}"#).unwrap();
+
+    // The watcher runs on real time, so wait for it for real
+    for _ in 0..50 {
+        h.run("wait:1");
+        let injector = h.app.station.injector.as_ref().unwrap();
+        if injector.applied + injector.rejected == 3 {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+
+    let injector = h.app.station.injector.as_ref().unwrap();
+    assert_eq!((injector.applied, injector.rejected), (2, 1));
+    assert!(injector.last_rejection.as_ref().unwrap().contains("\"bin\" must be 1 to 5"));
+    assert_eq!(h.app.containers[0].count, 0, "lines written before startup are skipped");
+    assert_eq!(h.app.containers[1].count, 12);
+
+    let _ = std::fs::remove_file(&path);
+}
+
+fn sessions_dir(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join(format!("lumon-mdr-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn with_sessions(dir: &std::path::Path, encrypt: bool) -> Harness {
+    let mut h = Harness::new();
+    h.app.station.save_dir = Some(dir.to_path_buf());
+    h.app.station.config.save.encrypt = encrypt;
+    // Real key derivation is deliberately slow
+    h.app.station.config.save.kdf_iterations = 1000;
+    h
+}
+
+#[test]
+fn the_session_is_saved_in_the_background_and_a_torn_save_recovered() {
+    let dir = sessions_dir("autosave");
+    let path = dir.join("mark.save");
+
+    let mut h = with_sessions(&dir, false);
+    h.app.station.autosave = Some(Autosave::start());
+    h.run("type:mark;enter;wait:300");
+    assert_eq!(h.app.state(), AppState::Main);
+    h.app.containers[2].add(17);
+    h.clock.advance(AUTOSAVE_INTERVAL);
+    h.run("wait:1");
+    let deadline = std::time::Instant::now() + Duration::from_secs(2);
+    while !std::fs::read_to_string(&path).is_ok_and(|text| text.contains("bins=0,0,17,0,0")) {
+        assert!(std::time::Instant::now() < deadline, "no background save");
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    // A journal cut short by a crash is dropped, and the loading screen
+    // says so
+    std::fs::write(autosave::journal_path(&path), "lumon-mdr journal 1\nlength=900\n").unwrap();
+    let mut h = with_sessions(&dir, false);
+    h.app.recover_sessions();
+    h.run("type:mark;enter;enter;wait:1");
+    h.find("Discarded an unfinished save: mark.save");
+    assert!(!autosave::journal_path(&path).exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn a_sealed_session_needs_its_passphrase() {
+    let dir = sessions_dir("sealed");
+
+    let mut h = with_sessions(&dir, true);
+    h.run("type:mark;enter");
+    h.find("Choose a passphrase");
+    h.run("type:woe;enter");
+    h.derive_key();
+    assert_eq!(h.app.state(), AppState::Loading);
+    h.app.containers[1].add(42);
+    h.app.store_session().unwrap();
+
+    let text = std::fs::read_to_string(dir.join("mark.save")).unwrap();
+    assert!(text.starts_with("lumon-mdr sealed 1\n"));
+    assert!(!text.contains("mark"), "nothing readable outside the floor");
+
+    let mut h = with_sessions(&dir, true);
+    h.run("type:mark;enter;enter");
+    h.find("Your work is sealed");
+    h.run("type:frolic;enter");
+    h.derive_key();
+    h.run("wait:1");
+    h.find("Passphrase not recognised");
+    assert_eq!(h.app.state(), AppState::Login);
+
+    h.run("type:woe;enter");
+    h.derive_key();
+    assert_eq!(h.app.state(), AppState::Loading);
+    assert_eq!(h.app.containers[1].count, 42);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn repeated_wrong_passphrases_start_the_login_over() {
+    let dir = sessions_dir("attempts");
+
+    let mut h = with_sessions(&dir, true);
+    h.run("type:mark;enter;type:woe;enter");
+    h.derive_key();
+    h.app.store_session().unwrap();
+
+    let mut h = with_sessions(&dir, true);
+    h.run("type:mark;enter;enter");
+    for guess in ["a", "b", "c"] {
+        h.run(&format!("type:{};enter", guess));
+        h.derive_key();
+    }
+    h.run("wait:1");
+
+    assert_eq!(h.app.login_step, LoginStep::Name);
+    h.find("Too many attempts");
+    assert!(h.app.passphrase.is_empty());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn the_screen_keeps_going_while_the_passphrase_is_worked_on() {
+    let dir = sessions_dir("deriving");
+
+    let mut h = with_sessions(&dir, true);
+    h.app.station.config.save.kdf_iterations = crypto::DEFAULT_ITERATIONS;
+    h.run("type:mark;enter;type:woe;enter;wait:1");
+    h.find("Sealing your work...");
+    h.run("type:x;esc");
+    assert_eq!(h.app.login_step, LoginStep::Name);
+    assert!(h.app.key_derivation.is_none() && h.app.save_key.is_none(), "Esc gives up on the key");
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn a_plain_session_is_sealed_once_encryption_is_on() {
+    let dir = sessions_dir("migrate");
+
+    let mut h = with_sessions(&dir, false);
+    h.run("type:mark;enter");
+    h.app.containers[0].add(17);
+    h.app.store_session().unwrap();
+    assert!(std::fs::read_to_string(dir.join("mark.save")).unwrap().starts_with("lumon-mdr save 1"));
+
+    // Without encryption the session simply comes back
+    let mut h = with_sessions(&dir, false);
+    h.run("type:mark;enter;enter");
+    assert_eq!(h.app.containers[0].count, 17);
+
+    let mut h = with_sessions(&dir, true);
+    h.run("type:mark;enter;enter");
+    h.find("Choose a passphrase");
+    h.run("type:woe;enter");
+    h.derive_key();
+    assert_eq!(h.app.containers[0].count, 17);
+    assert!(std::fs::read_to_string(dir.join("mark.save")).unwrap().starts_with("lumon-mdr sealed 1"));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn filling_a_bin_starts_a_break_that_refinement_waits_out() {
+    let mut h = Harness::in_main();
+    h.app.station.config.breaks.enabled = true;
+    h.app.containers[1].add(90);
+
+    h.run("key::;type:sel");
+    h.app.deposit(1, 10);
+    h.run("wait:1");
+    assert_eq!(h.app.state(), AppState::Break);
+    assert!(h.app.command_line.is_none());
+    h.find("MANDATED WELLNESS BREAK");
+
+    h.run("down;enter;key:c;wait:1");
+    assert_eq!(h.app.state(), AppState::Break);
+
+    h.clock.advance(Duration::from_secs(4 * 60));
+    h.run("wait:1");
+    assert_eq!(h.app.state(), AppState::Break);
+
+    h.clock.advance(Duration::from_secs(61));
+    h.run("wait:1");
+    assert_eq!(h.app.state(), AppState::Main);
+    assert!(h.app.break_until.is_none());
+    h.find("Your break is over");
+}
+
+#[test]
+fn a_long_idle_spell_saves_and_logs_out() {
+    let dir = sessions_dir("idle");
+
+    let mut h = with_sessions(&dir, false);
+    h.app.station.config.idle_logout = Some(Duration::from_secs(30 * 60));
+    h.run("type:mark;enter");
+    h.app.go_to(AppState::Main);
+    h.app.containers[2].add(23);
+    h.run("key::;type:sel");
+
+    h.clock.advance(Duration::from_secs(29 * 60));
+    h.run("wait:1");
+    assert_eq!(h.app.state(), AppState::Main);
+
+    h.clock.advance(Duration::from_secs(2 * 60));
+    h.run("wait:1");
+    assert_eq!(h.app.state(), AppState::Login);
+    assert!(h.app.username.is_empty());
+    assert!(h.app.command_line.is_none());
+    assert_eq!(h.app.containers[2].count, 0);
+    h.find("Your innie has gone home");
+
+    h.run("type:mark;enter;enter");
+    assert_eq!(h.app.state(), AppState::Loading);
+    assert_eq!(h.app.containers[2].count, 23);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn each_save_slot_is_picked_at_login_and_can_be_discarded() {
+    let dir = sessions_dir("slots");
+
+    let mut h = with_sessions(&dir, false);
+    h.run("type:mark;enter");
+    h.app.containers[0].add(40);
+    h.app.store_session().unwrap();
+
+    let mut h = with_sessions(&dir, false);
+    h.run("type:mark;enter;wait:1");
+    h.find("Choose the file to continue");
+    h.find(" 8%");
+    h.find("New file (slot 2)");
+    h.run("down;enter");
+    assert_eq!(h.app.save_slot, 2);
+    assert_eq!(h.app.containers[0].count, 0);
+    h.app.containers[1].add(10);
+    h.app.store_session().unwrap();
+    assert!(dir.join("mark.2.save").exists());
+
+    let mut h = with_sessions(&dir, false);
+    h.run("type:mark;enter;up;up;delete;wait:1");
+    h.find("Press DELETE again to discard slot 1");
+    h.run("delete");
+    assert!(!dir.join("mark.save").exists());
+    h.run("enter");
+    assert_eq!(h.app.save_slot, 2);
+    assert_eq!(h.app.containers[1].count, 10);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn a_restored_session_loads_step_by_step_and_enter_skips_the_wait() {
+    let dir = sessions_dir("restore");
+
+    let mut h = with_sessions(&dir, false);
+    h.run("type:mark;enter");
+    h.app.containers[2].add(23);
+    h.app.store_session().unwrap();
+
+    let mut h = with_sessions(&dir, false);
+    h.app.grid.replace((3, 4), 9, 100);
+    h.run("type:mark;enter;enter;wait:1");
+    assert_eq!(h.app.restore_steps, [RestoreStep::CheckLedger, RestoreStep::RebuildGrid]);
+    assert_eq!(h.app.containers[2].count, 23);
+    h.find("Validating ledger...");
+    h.find("Press Enter to skip");
+    h.find(" 33%");
+
+    h.run("pause:950");
+    assert_eq!(h.app.restore_steps, [RestoreStep::RebuildGrid]);
+    h.find("Rebuilding the grid...");
+
+    h.run("enter");
+    assert_eq!(h.app.state(), AppState::Main);
+    assert!(h.app.restore_steps.is_empty());
+    assert!(h.app.grid.is_empty());
+
+    // A fresh session skips its made-up progress just the same
+    let mut h = Harness::new();
+    h.run("type:helly;enter;enter");
+    assert_eq!(h.app.state(), AppState::Main);
+    assert_eq!(h.app.progress_percentage, 100.0);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn colon_changelog_opens_the_release_notes() {
+    let mut h = Harness::in_main();
+
+    h.run("key::;type:changelog;enter");
+    assert_eq!(h.app.state(), AppState::Changelog);
+    h.find("RELEASE NOTES");
+
+    h.run("esc;key::;type:kier;enter");
+    assert_eq!(h.app.state(), AppState::Main);
+    h.find("Unknown command: kier");
+}
+
+#[test]
+fn a_newer_release_is_announced() {
+    let feed = std::env::temp_dir().join(format!("lumon-mdr-feed-{}.md", std::process::id()));
+    std::fs::write(&feed, "## [99.0.0] - 2099-01-01\n\n- Board-approved improvements.\n").unwrap();
+
+    let mut h = Harness::in_main();
+    let config = lumon_mdr::config::UpdatesConfig { check: true, feed: feed.display().to_string() };
+    h.app.station.update_check = UpdateCheck::start(&config, None);
+
+    // The feed is read on its own thread
+    for _ in 0..50 {
+        h.run("wait:1");
+        if h.app.station.update_check.is_none() {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    }
+
+    h.find("Version 99.0.0 is available");
+    assert_eq!(h.app.station.changelog[0].notes, ["- Board-approved improvements."]);
+    h.run("esc");
+    assert!(h.app.toast.is_none());
+
+    let _ = std::fs::remove_file(&feed);
+}
+
+#[test]
+fn the_konami_code_opens_the_cheat_console() {
+    let mut h = Harness::in_main();
+
+    h.run("up;up;down;down;left;right;left;right;key:b;key:a;wait:1");
+    assert!(h.app.cheat_console.is_some());
+    h.find("CHEATS");
+
+    h.run("type:fill 2;enter;type:cluster;enter;type:prize Egg Bar;enter;esc");
+    assert!(h.app.cheat_console.is_none());
+    assert!(h.app.containers[1].is_full());
+    assert_eq!(h.app.scary_clusters.len(), 1);
+
+    for container in &mut h.app.containers {
+        container.add(100);
+    }
+    h.run("pause:3000");
+    assert_eq!(h.app.state(), AppState::Prize);
+    assert_eq!(h.app.prize_name, "Egg Bar");
+}
+
+#[test]
+fn quit_key_stops_the_loop() {
+    let mut h = Harness::in_main();
+
+    h.run("key:q;wait:5");
+
+    assert!(!h.app.running);
+}
+
+#[test]
+fn tick_rate_follows_activity() {
+    let mut h = Harness::in_main();
+
+    h.run("move:10,10");
+    assert_eq!(h.app.tick_rate(), ACTIVE_TICK_RATE);
+
+    h.run("pause:5000");
+    assert_eq!(h.app.tick_rate(), DEFAULT_TICK_RATE);
+
+    h.run("pause:10000");
+    assert_eq!(h.app.tick_rate(), IDLE_TICK_RATE);
+
+    // Animation keeps its pace even though ticks are now sparse
+    h.run("pause:30000");
+    let elapsed = h.app.clock.now().duration_since(h.app.station.started_at);
+    assert_eq!(h.app.animation_step(), (elapsed.as_millis() / ANIMATION_STEP.as_millis()) as u32);
+}
+
+fn count_cells(h: &Harness, wanted: impl Fn(&str) -> bool) -> usize {
+    h.terminal.backend().buffer().content().iter().filter(|cell| wanted(&cell.symbol)).count()
+}
+
+#[test]
+fn an_idle_grid_breathes_in_waves() {
+    let is_digit = |cell: &&ratatui::buffer::Cell| cell.symbol.chars().all(|c| c.is_ascii_digit()) && !cell.symbol.is_empty();
+    let dim_digits = |h: &Harness| {
+        let buffer = h.terminal.backend().buffer();
+        buffer.content().iter().filter(is_digit).filter(|cell| cell.modifier.contains(Modifier::DIM)).count()
+    };
+    let mut h = Harness::in_main();
+
+    h.run("key:x;wait:1");
+    assert!(!h.app.is_idle());
+    assert_eq!(dim_digits(&h), 0);
+
+    h.run("pause:16000");
+    assert!(h.app.is_idle());
+    let dim = dim_digits(&h);
+    let digits = h.terminal.backend().buffer().content().iter().filter(is_digit).count();
+    assert!(dim > 0 && dim < digits, "only part of the grid is in the trough: {} of {}", dim, digits);
+
+    // Any input wakes the grid straight back up
+    h.run("key:x;wait:1");
+    assert_eq!(dim_digits(&h), 0);
+}
+
+#[test]
+fn lingering_by_a_scary_cluster_builds_dread() {
+    let mut h = Harness::in_main();
+    h.app.station.eco_mode = true;
+    h.app.scary_clusters.push(ScaryCluster::new(vec![(4, 3), (5, 3), (4, 4), (5, 4)]));
+    h.run("wait:1");
+
+    let geometry = h.app.grid_geometry.unwrap();
+    let x = geometry.area.x + 4 * geometry.horizontal_spacing + 2;
+    let y = geometry.area.y + 3 * geometry.vertical_spacing + geometry.vertical_spacing / 2;
+    let cell_style = |h: &Harness| h.terminal.backend().buffer().get(x, y).clone();
+    assert_ne!(cell_style(&h).fg, Color::Red);
+
+    h.run(&format!("move:{},{};pause:{}", x, y, DREAD_RAMP.as_millis()));
+    assert!(h.app.dread() >= 1.0);
+    assert_eq!(cell_style(&h).fg, Color::Red);
+    let dimmed = h.terminal.backend().buffer().content().iter().filter(|c| c.modifier.contains(Modifier::DIM)).count();
+    assert!(dimmed > 20, "the digits around the cluster dim, got {}", dimmed);
+
+    // Moving away releases it at once
+    h.run("move:1,1;wait:1");
+    assert_eq!(h.app.dread(), 0.0);
+    assert_ne!(cell_style(&h).fg, Color::Red);
+}
+
+#[test]
+fn sticky_numbers_must_be_held_to_capture() {
+    let total = |h: &Harness| h.app.containers.iter().map(|c| c.count as u32).sum::<u32>();
+    let all_sticky = || (0..30).flat_map(|col| (0..20).map(move |row| ((col, row), Duration::from_millis(800)))).collect();
+    let mut h = Harness::in_main();
+    h.app.sticky = all_sticky();
+    h.run("wait:1");
+
+    let geometry = h.app.grid_geometry.unwrap();
+    let x = geometry.area.x + 6 * geometry.horizontal_spacing + 2;
+    let y = geometry.area.y + 4 * geometry.vertical_spacing + geometry.vertical_spacing / 2;
+
+    // Letting go too soon captures nothing
+    h.run(&format!("press:{},{};pause:300", x, y));
+    assert!(h.app.hold.is_some());
+    h.run(&format!("release:{},{};pause:1000", x, y));
+    assert!(h.app.hold.is_none());
+    assert_eq!(total(&h), 0);
+
+    // Holding on fills the ring and then captures
+    h.run(&format!("press:{},{};pause:300", x, y));
+    h.find("◑");
+    h.run("pause:600");
+    assert!(h.app.hold.is_none());
+    assert!(total(&h) > 0);
+    h.run(&format!("release:{},{}", x, y));
+
+    // Space works too, for as long as its auto-repeat keeps coming
+    h.app.sticky = all_sticky();
+    let before = total(&h);
+    h.run("space;pause:1300");
+    assert!(h.app.hold.is_none());
+    assert_eq!(total(&h), before);
+    let spaces: Vec<&str> = (0..10).map(|_| "space;pause:100").collect();
+    h.run(&format!("move:{},{};{}", x, y, spaces.join(";")));
+    assert!(total(&h) > before);
+}
+
+#[test]
+fn a_bin_number_sends_the_magnified_numbers_to_that_bin() {
+    let counts = |h: &Harness| h.app.containers.iter().map(|c| c.count).collect::<Vec<_>>();
+    let mut h = Harness::in_main();
+    h.app.sticky.clear();
+    h.run("wait:1");
+
+    let geometry = h.app.grid_geometry.unwrap();
+    let x = geometry.area.x + 6 * geometry.horizontal_spacing + 2;
+    let y = geometry.area.y + 4 * geometry.vertical_spacing + geometry.vertical_spacing / 2;
+    h.run(&format!("move:{},{};key:3;wait:1", x, y));
+    let aimed = counts(&h);
+    assert!(aimed[2] > 0);
+    assert_eq!(aimed.iter().filter(|&&count| count > 0).count(), 1);
+
+    // Away from the grid, the number tops the bin up by hand
+    h.run("move:0,0;key:5;wait:1");
+    assert_eq!(counts(&h)[4], 5);
+}
+
+#[test]
+fn capturing_equal_digits_lands_a_combo() {
+    let mut h = Harness::in_main();
+    h.app.sticky.clear();
+    for (col, row) in (0..30).flat_map(|col| (0..20).map(move |row| (col, row))) {
+        h.app.grid.replace((col, row), 1, 1000);
+    }
+    h.run("wait:1");
+
+    let geometry = h.app.grid_geometry.unwrap();
+    let x = geometry.area.x + 6 * geometry.horizontal_spacing + 2;
+    let y = geometry.area.y + 4 * geometry.vertical_spacing + geometry.vertical_spacing / 2;
+    h.run(&format!("click:{},{};wait:1", x, y));
+
+    let (combo, _) = h.app.combo.unwrap();
+    assert_eq!(combo.digit, 1);
+    let total: u32 = h.app.containers.iter().map(|c| c.count as u32).sum();
+    assert_eq!(total, (combo.count as u32 + combo.bonus() as u32).min(100));
+    h.find(&format!("{} ", combo));
+
+    h.run(&format!("pause:{}", COMBO_BANNER_DURATION.as_millis()));
+    assert!(h.app.combo.is_none());
+}
+
+#[test]
+fn r_asks_what_to_reset() {
+    let mut h = Harness::in_main();
+    for (idx, container) in h.app.containers.iter_mut().enumerate() {
+        container.add(10 * (idx as u16 + 1));
+    }
+    let (file, seed) = (h.app.file_name.clone(), h.app.grid_seed);
+
+    // Nothing happens without saying yes
+    h.run("key:r;wait:1");
+    h.find("RESET");
+    h.run("down;enter;wait:1");
+    h.find("Empty every bin on this file?");
+    h.run("key:n;esc;wait:1");
+    assert!(h.app.reset_menu.is_none());
+    assert_eq!(h.app.containers[4].count, 50);
+
+    // One bin
+    h.run("tab;tab;key:r;key:1;key:y");
+    assert_eq!(h.app.containers.iter().map(|c| c.count).collect::<Vec<_>>(), [10, 0, 30, 40, 50]);
+
+    // Every bin, same file
+    h.run("key:r;key:2;enter");
+    assert!(h.app.containers.iter().all(|c| c.count == 0));
+    assert_eq!((h.app.file_name.as_str(), h.app.grid_seed), (file.as_str(), seed));
+
+    // A new file gets a new grid
+    h.run("key:r;key:3;key:y");
+    assert_ne!(h.app.grid_seed, seed);
+}
+
+#[test]
+fn repeats_and_releases_only_act_where_they_should() {
+    let mut h = Harness::new();
+    h.run("type:ma;repeat:r;keyup:r");
+    assert_eq!(h.app.username, "mar", "held keys type again but releases do not");
+
+    let mut h = Harness::in_main();
+    h.run("key:p;keyup:p;repeat:p;repeat:p");
+    assert_eq!(h.app.side_panel, Some(SidePanel::ALL[0]));
+}
+
+#[test]
+fn chords_focus_bins_and_quit() {
+    let mut h = Harness::in_main();
+
+    h.run("key:alt+3");
+    assert_eq!(h.app.focused_bin(), Some(2));
+    assert_eq!(h.app.side_panel, Some(SidePanel::Bin));
+
+    // A chord is never typed into the prompt
+    h.run("key::;key:ctrl+x;key:x");
+    assert_eq!(h.app.command_line.as_deref(), Some("x"));
+
+    h.run("key:ctrl+c");
+    assert!(!h.app.running);
+}
+
+#[test]
+fn reshuffling_draws_a_new_grid_for_a_small_cost() {
+    let mut h = Harness::in_main();
+    h.run("wait:1");
+    for _ in 0..3 {
+        h.app.deposit(0, 10);
+    }
+    h.app.deposit(1, 100);
+    h.app.marked.insert((4, 3));
+    let seed = h.app.grid_seed;
+
+    h.run("key::;type:reshuffle;enter;wait:1");
+    h.find("Reshuffle the grid?");
+    h.run("key:n;wait:1");
+    assert!(h.app.reset_menu.is_none(), "declining goes straight back to the grid");
+    assert_eq!(h.app.grid_seed, seed);
+
+    h.run("key::;type:reshuffle;enter;key:y;wait:1");
+    assert_ne!(h.app.grid_seed, seed);
+    assert!(h.app.marked.is_empty());
+    assert_eq!(h.app.containers[0].count, 30 - RESHUFFLE_PENALTY);
+    assert_eq!(h.app.containers[1].count, 100, "full bins keep their numbers");
+    assert_eq!(h.app.containers[0].tempers.iter().sum::<u16>(), 30 - RESHUFFLE_PENALTY);
+    assert!(selfcheck::run(&h.app).is_clean());
+    h.find("Grid reshuffled");
+    assert!(stats::performance_summary(&h.app).contains("Grid reshuffled once"));
+
+    h.app.new_file();
+    assert_eq!(h.app.reshuffles, 0);
+}
+
+#[test]
+fn hold_to_confirm_needs_y_held_for_a_second() {
+    let mut h = Harness::in_main();
+    h.app.station.config.reset_confirm = ConfirmStyle::Hold;
+    h.app.containers[0].add(40);
+
+    // A tap is not enough, and neither is Enter
+    h.run("key:r;key:2;enter;key:y;pause:1500");
+    assert_eq!(h.app.containers[0].count, 40);
+    h.find("Hold [y] to reset");
+
+    // Letting go part way drops the hold
+    h.run("key:y;pause:300;key:y;pause:300;wait:1");
+    h.find("█");
+    h.run("keyup:y");
+    assert!(h.app.reset_menu.as_ref().unwrap().held.is_none());
+    h.run("pause:1000");
+    assert_eq!(h.app.containers[0].count, 40);
+
+    // Held down, the key repeats until the gauge fills
+    let repeats: Vec<&str> = (0..12).map(|_| "key:y;pause:100").collect();
+    h.run(&repeats.join(";"));
+    assert_eq!(h.app.containers[0].count, 0);
+    assert!(h.app.reset_menu.is_none());
+}
+
+#[test]
+fn a_factory_reset_erases_the_ledger_and_sets_the_config_aside() {
+    let dir = std::env::temp_dir().join(format!("lumon-mdr-factory-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let config = dir.join("config.toml");
+    std::fs::write(&config, "[goals]\nitems = [\"Fill bin 3\"]\n").unwrap();
+
+    let mut h = Harness::in_main();
+    h.app.station.config_path = Some(config.clone());
+    h.app.station.ledger_path = Some(dir.join("ledger"));
+    h.app.reload_config();
+    h.app.station.ledger.record(h.app.today(), &AppEvent::NumbersRefined { count: 40 });
+    assert_eq!(h.app.goals.items.len(), 1);
+
+    h.run("key:r;key:4;wait:1");
+    h.find("Erase the ledger");
+    h.run("key:y");
+
+    assert!(h.app.station.ledger.days.is_empty());
+    assert!(!config.exists());
+    assert!(dir.join("config.toml.bak").exists());
+    assert!(h.app.goals.items.is_empty());
+    assert!(h.app.error.is_none());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn redacted_mode_hides_digits_away_from_the_cursor() {
+    let is_digit = |s: &str| s.chars().all(|c| c.is_ascii_digit()) && !s.is_empty();
+    let mut plain = Harness::in_main();
+    plain.app.station.eco_mode = true;
+    plain.run("wait:1");
+
+    let mut h = Harness::in_main();
+    h.app.station.eco_mode = true;
+    h.app.station.redacted = true;
+    h.run("wait:1");
+    let hidden = count_cells(&h, is_digit);
+    let blocks = count_cells(&h, |s| s == "█") - count_cells(&plain, |s| s == "█");
+    assert!(blocks > 100, "only {} digits redacted", blocks);
+    assert!(hidden + 100 < count_cells(&plain, is_digit));
+
+    h.run("move:60,20;wait:1");
+    assert!(count_cells(&h, is_digit) > hidden);
+}
+
+#[test]
+fn mono_mode_draws_tall_digits_in_black_and_white() {
+    let is_digit = |s: &str| s.chars().all(|c| c.is_ascii_digit()) && !s.is_empty();
+    let mut plain = Harness::in_main();
+    plain.app.station.eco_mode = true;
+    plain.run("wait:1");
+
+    let mut h = Harness::in_main();
+    h.app.palette = Palette::Mono;
+    h.app.containers[0].add(40);
+    h.run("wait:1");
+    assert!(h.app.reduce_motion());
+    assert!(!h.app.is_animating(), "the bin's bar jumps rather than eases");
+    assert!(count_cells(&h, is_digit) > count_cells(&plain, is_digit) * 3 / 2);
+
+    let buffer = h.terminal.backend().buffer();
+    assert!(buffer.content().iter().all(|cell| cell.fg == Color::Reset && cell.bg == Color::Reset));
+
+    h.app.show_toast("Hello", None);
+    h.run("wait:1");
+    let (x, y) = h.find("Hello");
+    assert!(h.terminal.backend().buffer().get(x, y).modifier.contains(Modifier::REVERSED));
+
+    assert!(theme::is_mono_term("vt100-mono") && theme::is_mono_term("xterm-m"));
+    assert!(!theme::is_mono_term("xterm-256color"));
+}
+
+#[test]
+fn eco_mode_caps_redraws() {
+    let mut h = Harness::in_main();
+    h.app.station.eco_mode = true;
+    let moves: Vec<String> = (0..20).map(|i| format!("move:{},5;pause:100", i)).collect();
+
+    h.run(&moves.join(";"));
+
+    // 2 seconds of constant input at 2 FPS
+    assert_eq!(h.app.tick_rate(), ECO_TICK_RATE);
+    assert!(h.app.station.frame_stats.frames_drawn <= 6, "drew {} frames", h.app.station.frame_stats.frames_drawn);
+}
+
+#[test]
+fn mouse_motion_is_coalesced_and_capped_per_frame() {
+    let mut h = Harness::in_main();
+    let script: Vec<String> = (0..100).map(|i| format!("move:{},5", i)).collect();
+
+    h.run(&script.join(";"));
+
+    // 100 moves arrive as a batch of 64 and a batch of 36
+    assert_eq!(h.app.station.frame_stats.moves_coalesced, 98);
+    assert_eq!(h.app.mouse_position, Some((99, 5)));
+}
+
+#[test]
+fn bouncing_clicks_are_dropped() {
+    let mut h = Harness::in_main();
+
+    h.run("wait:1");
+    let (x, y) = h.find("02");
+    h.run(&format!("click:{x},{y};click:{x},{y};wait:1;click:{x},{y}"));
+
+    assert_eq!(h.app.station.frame_stats.clicks_debounced, 1);
+    assert_eq!(h.app.containers[1].count, 6);
+}
+
+#[test]
+fn stuck_frames_step_the_quality_down() {
+    let mut h = Harness::in_main();
+    h.run("wait:1");
+    let dense = h.app.grid_geometry.unwrap();
+
+    let stuck = Duration::from_millis(400);
+    assert_eq!(h.app.station.frame_stats.record_frame(stuck, Duration::from_millis(5), 0), None);
+    let quality = h.app.station.frame_stats.record_frame(stuck, stuck, 0).unwrap();
+    assert_eq!(quality, Quality::Still);
+    h.app.on_stuck_frame(stuck, quality);
+    assert!(h.app.inbox.back().unwrap().contains("took 400ms"));
+
+    assert_eq!(h.app.station.frame_stats.record_frame(stuck, stuck, 0), Some(Quality::Sparse));
+    assert_eq!(h.app.station.frame_stats.record_frame(stuck, stuck, 0), None, "nothing lower to drop to");
+    assert_eq!(h.app.station.frame_stats.stuck_frames, 3);
+
+    h.run("wait:1");
+    let sparse = h.app.grid_geometry.unwrap();
+    assert_eq!(sparse.horizontal_spacing, dense.horizontal_spacing * 2);
+    assert_eq!(sparse.vertical_spacing, dense.vertical_spacing * 2);
+}
+
+#[test]
+fn a_slow_terminal_thins_out_the_grids_animation() {
+    let mut h = Harness::in_main();
+    let lag = LagConfig::default();
+    let slow = lag.slow_write * 2;
+
+    for _ in 1..lag.frames {
+        h.app.station.frame_stats.record_write(slow, &lag);
+    }
+    h.app.station.frame_stats.record_write(lag.slow_write / 2 + Duration::from_millis(1), &lag);
+    h.app.station.frame_stats.record_write(slow, &lag);
+    assert_eq!(h.app.station.frame_stats.stride(), 1, "an in-between frame breaks the run");
+
+    for _ in 0..lag.frames * 10 {
+        h.app.station.frame_stats.record_write(slow, &lag);
+    }
+    assert_eq!(h.app.station.frame_stats.stride(), lag.max_stride);
+
+    for _ in 0..lag.frames * 4 {
+        h.app.station.frame_stats.record_write(Duration::from_millis(1), &lag);
+    }
+    assert_eq!(h.app.station.frame_stats.stride(), lag.max_stride - 1);
+
+    h.app.station.show_debug_hud = true;
+    h.run("wait:1");
+    h.find(&format!("stride  {:>7}", lag.max_stride - 1));
+    let geometry = h.app.grid_geometry.unwrap();
+    let (x, y) = geometry.position_of((2, 2));
+    let digit_near = (y - 1..=y + 1).any(|y| (x - 1..=x + 1).any(|x| h.terminal.backend().buffer().get(x, y).symbol.parse::<u16>().is_ok()));
+    assert!(digit_near, "the grid still draws");
+}
+
+#[test]
+fn the_hud_counts_the_cells_each_frame_changes() {
+    let mut h = Harness::in_main();
+    h.app.station.config.display.reduce_motion = true;
+    h.app.station.show_debug_hud = true;
+    h.run("wait:1");
+    assert_eq!(h.app.station.frame_stats.cells_drawn, usize::from(DESIRED_WIDTH) * usize::from(DESIRED_HEIGHT));
+
+    // A still screen sends only what the HUD's own readings change
+    let sent = h.app.station.frame_stats.cells_sent;
+    h.run("wait:2");
+    let stats = &h.app.station.frame_stats;
+    let still = stats.cells_sent - sent;
+    assert!(stats.cells_changed < stats.cells_drawn / 20, "{} of {}", stats.cells_changed, stats.cells_drawn);
+    assert!(stats.grid_changed <= stats.cells_changed);
+    h.find(&format!("/{}", stats.cells_drawn));
+
+    let sent = h.app.station.frame_stats.cells_sent;
+    h.app.station.redacted = true;
+    h.run("wait:2");
+    assert!(h.app.station.frame_stats.cells_sent - sent > still + 100, "redacting repaints the grid");
+
+    h.app.station.show_debug_hud = false;
+    let sent = h.app.station.frame_stats.cells_sent;
+    h.run("wait:2");
+    assert_eq!(h.app.station.frame_stats.cells_sent, sent, "nothing counted with the HUD closed");
+}
+
+#[test]
+fn each_screen_answers_its_own_input() {
+    // A click on the login is not kept for the grid to pick up later
+    let mut h = Harness::new();
+    h.run("click:10,10");
+    assert_eq!(h.app.last_clicked, None);
+
+    let mut h = Harness::in_main();
+    MainScreen.handle_key(&mut h.app, KeyCode::Char(':'));
+    assert_eq!(h.app.command_line.as_deref(), Some(""));
+    MainScreen.handle_key(&mut h.app, KeyCode::Char('x'));
+    assert_eq!(h.app.command_line.as_deref(), Some("x"), "the open prompt takes the key");
+
+    h.app.go_to(AppState::Report);
+    ReportScreen.handle_key(&mut h.app, KeyCode::Char('x'));
+    assert_eq!(h.app.state(), AppState::Main);
+}
+
+#[test]
+fn leaving_a_screen_tidies_up_after_it() {
+    let mut h = Harness::new();
+    assert!(!h.app.go_to(AppState::Main), "no refining before logging in");
+    assert_eq!(h.app.state(), AppState::Login);
+
+    let mut h = Harness::in_main();
+    h.app.sticky.insert((4, 3), Duration::from_secs(1));
+    h.app.button_down = true;
+    h.app.capture_or_hold(vec![(4, 3, 5)]);
+    assert!(h.app.hold.is_some());
+    h.app.last_clicked = Some((10, 10));
+    h.run("key::;type:chang");
+    assert!(h.app.go_to(AppState::Report));
+    assert!(h.app.hold.is_none() && h.app.last_clicked.is_none() && h.app.command_line.is_none());
+
+    // The error screen interrupts without tidying, and only goes back
+    h.app.go_to(AppState::Main);
+    h.app.sticky.insert((4, 3), Duration::from_secs(1));
+    h.app.button_down = true;
+    h.app.capture_or_hold(vec![(4, 3, 5)]);
+    h.app.report_error(RecoverableError::new(FailureKind::SaveFile, "disk full"));
+    assert!(!h.app.go_to(AppState::Report));
+    h.app.dismiss_error();
+    assert_eq!(h.app.state(), AppState::Main);
+    assert!(h.app.hold.is_some(), "the hold survives the interruption");
+
+    h.app.changelog_scroll = 7;
+    h.run("key::;type:changelog;enter");
+    assert_eq!(h.app.state(), AppState::Changelog);
+    assert_eq!(h.app.changelog_scroll, 0);
+}
+
+#[test]
+fn ignoring_an_error_resumes_the_interrupted_screen() {
+    let mut h = Harness::in_main();
+    h.app.report_error(RecoverableError::new(FailureKind::SaveFile, "checksum mismatch"));
+
+    h.run("wait:1");
+    assert!(h.find("Your refinement records could not be verified.").0 > 0);
+
+    h.run("key:i");
+    assert_eq!(h.app.state(), AppState::Main);
+    assert!(h.app.error.is_none());
+    assert!(h.app.running);
+}
+
+#[test]
+fn retrying_an_error_flags_the_failed_subsystem() {
+    let mut h = Harness::in_main();
+    h.app.report_error(RecoverableError::new(FailureKind::SaveFile, "checksum mismatch"));
+
+    h.run("key:r");
+
+    assert_eq!(h.app.state(), AppState::Main);
+    assert_eq!(h.app.pending_retry, Some(FailureKind::SaveFile));
+}
+
+#[test]
+fn a_bin_flashes_as_it_fills() {
+    let mut h = Harness::in_main();
+    h.app.station.config.breaks.enabled = false;
+    h.app.deposit(0, 99);
+    assert!(!h.app.containers[0].is_celebrating(h.clock.now()));
+
+    h.app.deposit(0, 1);
+    let now = h.clock.now();
+    assert!(h.app.containers[0].is_lit(now));
+    assert!(!h.app.containers[0].is_lit(now + BIN_FILLED_FLASH));
+    assert!(h.app.containers[0].is_lit(now + BIN_FILLED_FLASH * 2));
+    assert!(h.app.is_animating());
+    assert!(!h.app.containers[0].is_celebrating(now + BIN_FILLED_FLASH * 6));
+
+    h.app.containers[0].empty();
+    assert!(!h.app.containers[0].is_celebrating(now));
+}
+
+#[test]
+fn the_floor_has_ambience_when_asked_for() {
+    let mut h = Harness::in_main();
+    h.run("wait:1");
+    h.clock.advance(Duration::from_secs(20 * 60));
+    h.run("wait:1");
+    assert_eq!(h.app.ambience.next(), None, "off by default");
+
+    h.app.station.config.ambience = true;
+    h.run("wait:1");
+    h.clock.advance(Duration::from_secs(15 * 60));
+    h.app.toast = None;
+    h.run("wait:1");
+    assert!(h.app.ambient.is_some() || h.app.toast.is_some(), "an event is due");
+
+    h.app.start_ambience(Ambience::Psa, h.clock.now());
+    h.run("wait:1");
+    h.find(ambience::PSA);
+    h.clock.advance(Duration::from_secs(5));
+    h.run("wait:1");
+    assert_eq!(h.app.ambient, None);
+
+    h.app.start_ambience(Ambience::Flicker, h.clock.now());
+    h.terminal.draw(|frame| ui::draw(frame, &h.app)).unwrap();
+    assert!(h.terminal.backend().buffer().get(0, 0).modifier.contains(Modifier::DIM), "the lights are out");
+    h.app.station.config.display.reduce_motion = true;
+    h.app.ambient = None;
+    h.app.start_ambience(Ambience::Flicker, h.clock.now());
+    assert_eq!(h.app.ambient, None, "no flicker with reduced motion");
+}
+
+#[test]
+fn a_compliance_check_is_retyped_in_time() {
+    let mut h = Harness::in_main();
+    h.app.station.config.compliance = true;
+    h.run("wait:1");
+    h.clock.advance(Duration::from_secs(2 * 3600));
+    h.run("wait:1");
+    let phrase = h.app.compliance.as_ref().expect("a check is due").phrase;
+    h.find("COMPLIANCE CHECK");
+    h.find(phrase);
+
+    // Its keys are the check's, not the grid's
+    h.run("key:q;backspace");
+    assert!(h.app.running);
+    let progress = |app: &App| app.containers.iter().map(|c| c.count).sum::<u16>();
+    let before = progress(&h.app);
+    h.run(&format!("type:{};enter", phrase));
+    assert_eq!(h.app.compliance, None);
+    assert_eq!(progress(&h.app), before + compliance::BONUS);
+
+    // Letting the time run out costs some of the streak
+    h.app.streak = 8;
+    h.clock.advance(Duration::from_secs(2 * 3600));
+    h.run("wait:1");
+    assert!(h.app.compliance.is_some());
+    h.clock.advance(compliance::TIME_LIMIT);
+    h.run("wait:1");
+    assert_eq!(h.app.compliance, None);
+    assert_eq!(h.app.streak, 8 - compliance::STREAK_PENALTY);
+}
+
+#[test]
+fn the_error_screens_buttons_are_picked_with_tab() {
+    let mut h = Harness::in_main();
+    h.app.report_error(RecoverableError::new(FailureKind::SaveFile, "checksum mismatch"));
+    h.run("wait:1");
+    assert_eq!(h.app.error_buttons.focused(), Some(ErrorAction::Ignore));
+
+    h.run("left;enter");
+    assert_eq!(h.app.state(), AppState::Main);
+    assert_eq!(h.app.pending_retry, Some(FailureKind::SaveFile), "Enter pressed the retry button");
+
+    h.app.report_error(RecoverableError::new(FailureKind::SaveFile, "checksum mismatch"));
+    h.run("tab;enter");
+    assert!(!h.app.running);
+}
+
+#[test]
+fn a_reset_is_called_off_from_the_no_button() {
+    let mut h = Harness::in_main();
+    h.app.containers[0].add(40);
+
+    h.run("key:r;key:2;tab;enter");
+    assert_eq!(h.app.containers[0].count, 40);
+    let menu = h.app.reset_menu.as_ref().unwrap();
+    assert_eq!(menu.confirming, None, "back at the menu");
+
+    h.run("key:2;right;left;enter");
+    assert_eq!(h.app.containers[0].count, 0);
+}
+
+#[test]
+fn rejects_unknown_steps() {
+    assert!(matches!("jump".parse::<ScriptedEvents>(), Err(MdrError::Config(_))));
+    assert!(matches!("click:1".parse::<ScriptedEvents>(), Err(MdrError::Config(_))));
+}
+
+#[test]
+fn the_grid_slides_into_a_resized_window() {
+    let mut h = Harness::in_main();
+    h.run("wait:1");
+    let before = h.app.grid_geometry.unwrap();
+
+    h.terminal.backend_mut().resize(DESIRED_WIDTH + 30, DESIRED_HEIGHT + 10);
+    h.run(&format!("resize:{},{};wait:1", DESIRED_WIDTH + 30, DESIRED_HEIGHT + 10));
+    let morph = h.app.grid_morph.expect("the resize starts a slide");
+    assert_eq!(morph.from, before);
+    assert_eq!(morph.to, h.app.grid_geometry.unwrap());
+    assert!(h.app.is_animating());
+
+    h.clock.advance(GRID_MORPH);
+    h.run("wait:1");
+    assert!(!morph.is_moving(h.clock.now()));
+
+    // Without motion the grid jumps
+    h.app.grid_morph = None;
+    h.app.station.config.display.reduce_motion = true;
+    h.terminal.backend_mut().resize(DESIRED_WIDTH, DESIRED_HEIGHT);
+    h.run(&format!("resize:{},{};wait:1", DESIRED_WIDTH, DESIRED_HEIGHT));
+    assert!(h.app.grid_morph.is_none());
+}
+
+#[test]
+fn a_click_lands_without_waiting_for_a_frame() {
+    let mut h = Harness::in_main();
+    h.app.station.eco_mode = true;
+    h.run("wait:1");
+    let (x, y) = h.app.grid_geometry.unwrap().position_of((3, 3));
+
+    // Eco mode draws once as the run starts, before the click comes
+    h.run(&format!("click:{},{}", x, y));
+    assert_eq!(h.app.last_clicked, None);
+    assert!(h.app.containers.iter().any(|bin| bin.count > 0) || h.app.hold.is_some(), "the click caught the digits by it");
+    assert_eq!(h.app.misclicks, 0);
+}
@@ -0,0 +1,31 @@
+use lumon_mdr::app::{App, AppState, PRIZE_DELAY};
+use lumon_mdr::clock::MockClock;
+use lumon_mdr::events::AppEvent;
+use lumon_mdr::theme::Palette;
+use std::rc::Rc;
+
+#[test]
+fn filling_bins_and_files_is_announced_once() {
+    let clock = MockClock::new();
+    let mut app = App::with_clock(Palette::Ansi, Rc::new(clock.clone()));
+    app.go_to(AppState::Loading);
+    app.go_to(AppState::Main);
+
+    app.deposit(2, 60);
+    assert!(app.station.outbox.is_empty());
+    app.deposit(2, 60);
+    app.deposit(2, 10);
+    assert_eq!(app.station.outbox, vec![AppEvent::BinFilled { bin: 3 }]);
+
+    app.station.outbox.clear();
+    for idx in [0, 1, 3, 4] {
+        app.add_to_container(idx, 100);
+    }
+    app.tick();
+    clock.advance(PRIZE_DELAY);
+    app.tick();
+
+    assert_eq!(app.state(), AppState::Prize);
+    assert_eq!(app.station.outbox.len(), 5);
+    assert_eq!(app.station.outbox[4], AppEvent::FileCompleted { prize: app.prize_name.clone() });
+}
@@ -0,0 +1,42 @@
+use crossterm::event::KeyCode;
+use lumon_mdr::focus::{Focus, Routed};
+
+#[test]
+fn tab_moves_along_the_order_and_wraps() {
+    let mut focus = Focus::new(vec!['a', 'b', 'c']);
+    assert_eq!(focus.focused(), None);
+    assert_eq!(focus.route(KeyCode::Tab), Routed::Moved('a'));
+    assert_eq!(focus.route(KeyCode::BackTab), Routed::Moved('c'), "wraps at the start");
+    assert_eq!(focus.route(KeyCode::Tab), Routed::Moved('a'), "and at the end");
+
+    let mut focus = Focus::new(vec!['a', 'b', 'c']);
+    assert_eq!(focus.cycle(-1), Some('c'), "backwards from nothing starts at the end");
+    assert_eq!(Focus::<char>::default().cycle(1), None);
+}
+
+#[test]
+fn other_keys_go_to_the_focused_widget() {
+    let mut focus = Focus::new(vec![1, 2]);
+    assert_eq!(focus.route(KeyCode::Enter), Routed::Unfocused(KeyCode::Enter));
+    assert!(focus.focus(2));
+    assert_eq!(focus.route(KeyCode::Enter), Routed::To(2, KeyCode::Enter));
+
+    assert!(!focus.focus(3), "an unregistered widget cannot take the focus");
+    assert!(focus.is_focused(2));
+    focus.blur();
+    assert_eq!(focus.focused(), None);
+}
+
+#[test]
+fn the_focus_follows_its_widget_through_a_new_order() {
+    let mut focus = Focus::on(vec![0, 1, 2], 1);
+    focus.set_order(vec![2, 1, 0]);
+    assert_eq!(focus.focused(), Some(1));
+    assert_eq!(focus.cycle(1), Some(0));
+
+    focus.set_order(vec![1, 2]);
+    assert_eq!(focus.focused(), None, "a widget no longer there loses the focus");
+    focus.register(3);
+    focus.register(3);
+    assert_eq!(focus.order(), &[1, 2, 3]);
+}
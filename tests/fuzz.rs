@@ -0,0 +1,10 @@
+use lumon_mdr::testing::fuzz;
+
+#[test]
+fn random_event_storms_keep_invariants() {
+    for seed in 0..12 {
+        if let Err(failure) = fuzz::run(seed, 300) {
+            panic!("{}", failure);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use lumon_mdr::events::AppEvent;
+use lumon_mdr::goals::{Goals, Target};
+
+#[test]
+fn goal_wording_picks_the_target() {
+    assert_eq!(Target::parse("Fill bin 3"), Target::BinFull(3));
+    assert_eq!(Target::parse("Get bin 2 full before lunch"), Target::BinFull(2));
+    assert_eq!(Target::parse("Refine 200 numbers"), Target::Numbers(200));
+    assert_eq!(Target::parse("Complete a file"), Target::Files(1));
+    assert_eq!(Target::parse("complete 2 files"), Target::Files(2));
+    assert_eq!(Target::parse("Visit the break room"), Target::Manual);
+    assert_eq!(Target::parse("Look at bin 4"), Target::Manual);
+}
+
+#[test]
+fn events_tick_off_matching_goals() {
+    let mut goals = Goals::default();
+    assert!(goals.add("\"Fill bin 3\""));
+    assert!(goals.add("Refine 10 numbers"));
+    assert!(goals.add("Wave at Milchick"));
+    assert!(!goals.add("fill bin 3"), "the same goal is only listed once");
+
+    assert!(goals.observe(&AppEvent::BinFilled { bin: 2 }).is_empty());
+    assert!(goals.observe(&AppEvent::NumbersRefined { count: 6 }).is_empty());
+    assert_eq!(goals.progress(&goals.items[1]), Some((6, 10)));
+    assert_eq!(goals.observe(&AppEvent::NumbersRefined { count: 6 }), ["Refine 10 numbers"]);
+    assert_eq!(goals.observe(&AppEvent::BinFilled { bin: 3 }), ["Fill bin 3"]);
+    assert_eq!(goals.met(), 2);
+    assert!(!goals.all_met());
+
+    goals.items[2].done = true;
+    assert!(goals.all_met());
+    let summary = goals.to_string();
+    assert!(summary.starts_with("Every session goal met. The Board is pleased. (3 of 3)"));
+    assert!(summary.contains("  [x] Wave at Milchick"));
+    assert!(summary.ends_with("Numbers refined: 12   Files completed: 0"));
+}
@@ -0,0 +1,151 @@
+use lumon_mdr::app::{App, AppState, GRID_MORPH, GridGeometry, GridMorph, ScaryCluster};
+use lumon_mdr::grid::{GridModel, GridSnapshot};
+use lumon_mdr::input::{DESIRED_HEIGHT, DESIRED_WIDTH};
+use lumon_mdr::save::SaveState;
+use lumon_mdr::theme::Palette;
+use ratatui::{Terminal, backend::TestBackend, layout::Rect};
+use std::time::Instant;
+
+#[test]
+fn replacements_expire_when_their_cells_are_recycled() {
+    let mut grid = GridModel::default();
+    assert!(grid.replace((15, 2), 7, 100), "any cell before the first frame");
+    grid.replace((2, 2), 4, 100);
+
+    grid.resize(20, 10);
+    assert_eq!(grid.resize(10, 10), 1);
+    assert_eq!(grid.replaced((15, 2)), None);
+    assert_eq!(grid.digit((2, 2), 0), 4);
+    assert_eq!(grid.digit((3, 2), 6), 6, "untouched cells show the seed's digit");
+
+    // Growing back does not bring the old number back
+    grid.resize(20, 10);
+    assert_eq!(grid.replaced((15, 2)), None);
+}
+
+#[test]
+fn nothing_is_replaced_off_the_grid() {
+    let mut grid = GridModel::default();
+    grid.resize(10, 5);
+
+    assert!(!grid.replace((10, 0), 1, 100));
+    assert!(!grid.replace((0, 5), 1, 100));
+    assert!(grid.replace((9, 4), 1, 100));
+    assert_eq!(grid.len(), 1);
+}
+
+#[test]
+fn captured_cells_are_always_on_screen() {
+    let mut terminal = Terminal::new(TestBackend::new(DESIRED_WIDTH, DESIRED_HEIGHT)).unwrap();
+    let mut app = App::new(Palette::Ansi);
+    app.go_to(AppState::Loading);
+    app.go_to(AppState::Main);
+
+    for (width, height) in [(DESIRED_WIDTH, DESIRED_HEIGHT), (70, 30), (DESIRED_WIDTH, DESIRED_HEIGHT)] {
+        terminal.backend_mut().resize(width, height);
+        app.on_resize(width, height);
+        lumon_mdr::ui::layout(&mut app, Rect::new(0, 0, width, height));
+        terminal.draw(|frame| lumon_mdr::ui::draw(frame, &app)).unwrap();
+
+        // Every cell the cursor can land on is one the model counts as on
+        // the grid, and capturing it replaces it
+        let geometry = app.grid_geometry.unwrap();
+        let area = geometry.area;
+        for (x, y) in [(area.x, area.y), (area.x + area.width - 1, area.y + area.height - 1)] {
+            let cell = geometry.cell_at(x, y).unwrap();
+            app.replace_numbers(vec![cell]);
+            assert!(app.grid.replaced(cell).is_some(), "{:?} on a {}x{} screen", cell, width, height);
+        }
+        assert!(app.grid.cells().all(|cell| app.grid.contains(cell)));
+    }
+}
+
+#[test]
+fn a_snapshot_scales_to_the_same_relative_place() {
+    let saved = GridSnapshot {
+        size: (58, 19),
+        replaced: vec![((0, 0), 1), ((57, 18), 2), ((29, 9), 3)],
+        clusters: vec![vec![(28, 9), (29, 9), (30, 9), (29, 10)]],
+        cursor: Some((29, 9)),
+    };
+
+    for (cols, rows) in [(38, 11), (58, 19), (90, 30), (12, 4)] {
+        let fitted = saved.remap(cols, rows);
+        let on_grid = |&(col, row): &(usize, usize)| col < cols && row < rows;
+
+        assert_eq!(fitted.size, (cols, rows));
+        assert_eq!(fitted.replaced.len(), 3, "corners and centre stay apart at {}x{}", cols, rows);
+        assert_eq!(fitted.replaced[0].0, (0, 0));
+        assert_eq!(fitted.replaced[1].0, (cols - 1, rows - 1));
+        assert!(fitted.replaced.iter().all(|(cell, _)| on_grid(cell)));
+        let cursor = fitted.cursor.unwrap();
+        assert!(cursor.0.abs_diff(cols / 2) <= 1 && cursor.1.abs_diff(rows / 2) <= 1, "{:?} at {}x{}", cursor, cols, rows);
+        let cluster = &fitted.clusters[0];
+        assert!(!cluster.is_empty() && cluster.iter().all(on_grid));
+        assert!(cluster.contains(&cursor), "the cursor stays in the cluster at {}x{}", cols, rows);
+    }
+
+    // A cluster keeps its share of the grid, so it grows on a bigger one
+    assert!(saved.remap(116, 38).clusters[0].len() > 4);
+    assert_eq!(saved.remap(58, 19), saved);
+}
+
+#[test]
+fn numbers_that_land_on_one_cell_keep_the_last_digit() {
+    let saved = GridSnapshot { size: (10, 10), replaced: vec![((5, 5), 1), ((6, 6), 2)], ..Default::default() };
+    assert_eq!(saved.remap(2, 2).replaced, vec![((1, 1), 2)]);
+}
+
+#[test]
+fn a_session_saved_in_a_big_window_resumes_in_a_small_one() {
+    let mut terminal = Terminal::new(TestBackend::new(DESIRED_WIDTH, DESIRED_HEIGHT)).unwrap();
+    let mut app = App::new(Palette::Ansi);
+    app.go_to(AppState::Loading);
+    app.go_to(AppState::Main);
+    lumon_mdr::ui::layout(&mut app, terminal.size().unwrap());
+    terminal.draw(|frame| lumon_mdr::ui::draw(frame, &app)).unwrap();
+    let geometry = app.grid_geometry.unwrap();
+    let (cols, rows) = geometry.cells();
+    app.replace_numbers(vec![(cols - 1, rows - 1)]);
+    app.scary_clusters.push(ScaryCluster::new(vec![(cols - 2, 0), (cols - 1, 0)]));
+    app.mouse_position = Some(geometry.position_of((cols - 1, rows - 1)));
+    let save = SaveState::capture(&app);
+
+    let mut resumed = App::new(Palette::Ansi);
+    save.apply(&mut resumed);
+    resumed.go_to(AppState::Loading);
+    resumed.go_to(AppState::Main);
+    terminal.backend_mut().resize(80, 24);
+    resumed.on_resize(80, 24);
+    lumon_mdr::ui::layout(&mut resumed, Rect::new(0, 0, 80, 24));
+    terminal.draw(|frame| lumon_mdr::ui::draw(frame, &resumed)).unwrap();
+
+    let small = resumed.grid_geometry.unwrap();
+    let (small_cols, small_rows) = small.cells();
+    assert!(small_cols < cols && small_rows < rows);
+    assert_eq!(resumed.grid.cells().collect::<Vec<_>>(), [(small_cols - 1, small_rows - 1)]);
+    assert!(resumed.scary_clusters[0].cells.contains(&(small_cols - 1, 0)));
+    let (x, y) = resumed.mouse_position.unwrap();
+    assert_eq!(small.cell_at(x, y), Some((small_cols - 1, small_rows - 1)));
+    assert_eq!(resumed.pending_grid, None);
+}
+
+#[test]
+fn a_resized_grid_slides_each_cell_from_where_it_was() {
+    let small = GridGeometry { area: Rect::new(0, 0, 22, 10), horizontal_spacing: 4, vertical_spacing: 2 };
+    let big = GridGeometry { area: Rect::new(10, 4, 42, 20), horizontal_spacing: 6, vertical_spacing: 3 };
+    let start = Instant::now();
+    let morph = GridMorph::new(small, big, start);
+
+    assert_eq!(morph.position_of((2, 1), start), small.position_of((2, 1)));
+    assert_eq!(morph.position_of((2, 1), start + GRID_MORPH), big.position_of((2, 1)));
+    let (x, _) = morph.position_of((2, 1), start + GRID_MORPH / 2);
+    assert!(x > small.position_of((2, 1)).0 && x < big.position_of((2, 1)).0);
+
+    // Cells set off from where they scale back to on the small grid
+    assert_eq!((small.cells(), big.cells()), ((5, 4), (6, 6)));
+    assert_eq!(morph.position_of((5, 5), start), small.position_of((4, 3)));
+    assert_eq!(morph.position_of((0, 0), start), small.position_of((0, 0)));
+    assert!(morph.is_moving(start + GRID_MORPH / 2));
+    assert!(!morph.is_moving(start + GRID_MORPH));
+}
@@ -0,0 +1,34 @@
+use lumon_mdr::inject::Contribution;
+
+#[test]
+fn parses_contributions() {
+    assert_eq!(
+        r#"{"bin": 2, "add": 5}"#.parse::<Contribution>().unwrap(),
+        Contribution { bin: 2, add: 5 }
+    );
+    assert_eq!(
+        r#"  {"add":100,"bin":5}  "#.parse::<Contribution>().unwrap(),
+        Contribution { bin: 5, add: 100 }
+    );
+}
+
+#[test]
+fn rejects_invalid_commands() {
+    for bad in [
+        "",
+        "bin=2",
+        r#"{"bin": 2}"#,
+        r#"{"bin": 0, "add": 5}"#,
+        r#"{"bin": 6, "add": 5}"#,
+        r#"{"bin": 2, "add": 0}"#,
+        r#"{"bin": 2, "add": 101}"#,
+        r#"{"bin": 2, "add": -5}"#,
+        r#"{"bin": 2, "add": 2.5}"#,
+        r#"{"bin": 2, "add": "5"}"#,
+        r#"{"bin": 2, "add": 5, "bonus": 1}"#,
+        r#"{"bin": 2, "bin": 3, "add": 5}"#,
+        r#"{bin: 2, add: 5}"#,
+    ] {
+        assert!(bad.parse::<Contribution>().is_err(), "accepted {:?}", bad);
+    }
+}
@@ -0,0 +1,28 @@
+use lumon_mdr::journal::{Journal, Note};
+use std::time::{Duration, UNIX_EPOCH};
+
+#[test]
+fn notes_are_stamped_in_the_shift_clock() {
+    // 2024-01-01 23:30 UTC
+    let note = Note::new(UNIX_EPOCH + Duration::from_secs(1_704_151_800), "  Expense report  ");
+
+    assert_eq!(note.text, "Expense report");
+    assert_eq!(note.stamp(0), "23:30");
+    assert_eq!(note.stamp(60), "00:30");
+    assert_eq!(note.stamp(-90), "22:00");
+}
+
+#[test]
+fn the_journal_keeps_its_latest_notes() {
+    let mut journal = Journal::default();
+    let at = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+
+    assert!(!journal.add(at, "   ", 2));
+    for text in ["one", "two", "three"] {
+        assert!(journal.add(at, text, 2));
+    }
+
+    let texts: Vec<&str> = journal.notes.iter().map(|note| note.text.as_str()).collect();
+    assert_eq!(texts, ["two", "three"]);
+    assert_eq!(journal.summary(0), "Session journal (2 notes)\n  00:00  two\n  00:00  three");
+}
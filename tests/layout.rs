@@ -0,0 +1,111 @@
+use lumon_mdr::ui::layout::{MainLayout, Priority, TitleBar};
+use ratatui::layout::Rect;
+
+#[test]
+fn the_grid_makes_room_for_a_side_panel() {
+    let area = Rect::new(0, 0, 120, 40);
+    let closed = MainLayout::compute(area, None);
+    let open = MainLayout::compute(area, Some(34));
+
+    assert_eq!(closed.panel, None);
+    let panel = open.panel.unwrap();
+    assert_eq!(panel.width, 34);
+    assert_eq!(open.grid.width + panel.width, closed.grid.width);
+    assert_eq!(panel.x, open.grid.x + open.grid.width);
+    assert_eq!((panel.y, panel.height), (closed.grid.y, closed.grid.height));
+    // Everything else stays put
+    assert_eq!(open.bins, closed.bins);
+    assert_eq!(open.footer, closed.footer);
+}
+
+#[test]
+fn a_mirrored_layout_puts_the_panel_left_of_the_grid() {
+    let open = MainLayout::compute(Rect::new(0, 0, 120, 40), Some(34));
+    let mirrored = open.mirrored();
+
+    let panel = mirrored.panel.unwrap();
+    assert_eq!(panel.x, open.grid.x);
+    assert_eq!(mirrored.grid.x, panel.x + panel.width);
+    assert_eq!(mirrored.grid.width, open.grid.width);
+    assert_eq!(mirrored.bins, open.bins);
+    assert_eq!(MainLayout::compute(Rect::new(0, 0, 120, 40), None).mirrored().panel, None);
+}
+
+#[test]
+fn narrow_windows_keep_the_whole_grid() {
+    let layout = MainLayout::compute(Rect::new(0, 0, 60, 24), Some(34));
+
+    assert_eq!(layout.panel, None);
+    assert_eq!(layout.grid.width, 58);
+}
+
+const IDENTITY: [(&str, Priority); 3] = [
+    ("Cold Harbor", Priority::Low),
+    ("mark", Priority::High),
+    ("Refiner", Priority::Low),
+];
+
+fn bar_width(bar: &TitleBar, logo: u16) -> usize {
+    bar.left.chars().count() + bar.spacer as usize + bar.right.chars().count() + if bar.logo { logo as usize } else { 0 }
+}
+
+#[test]
+fn a_wide_title_bar_keeps_everything() {
+    let bar = TitleBar::fit(100, &IDENTITY, "42% Complete", Some(14), false);
+
+    assert_eq!(bar.left, " Cold Harbor \u{00b7} mark \u{00b7} Refiner ");
+    assert_eq!(bar.right, "42% Complete");
+    assert!(bar.logo);
+    assert_eq!(bar_width(&bar, 14), 100);
+}
+
+#[test]
+fn the_least_important_pieces_give_way_first() {
+    // The rank goes before the file name, then the logo
+    let bar = TitleBar::fit(52, &IDENTITY, "42% Complete", Some(14), false);
+    assert_eq!((bar.left.as_str(), bar.logo), (" Cold Harbor \u{00b7} mark ", true));
+
+    let bar = TitleBar::fit(40, &IDENTITY, "42% Complete", Some(14), false);
+    assert_eq!((bar.left.as_str(), bar.logo), (" mark ", true));
+
+    let bar = TitleBar::fit(24, &IDENTITY, "42% Complete", Some(14), false);
+    assert_eq!((bar.left.as_str(), bar.logo), (" mark ", false));
+    assert_eq!(bar.spacer, 6);
+}
+
+#[test]
+fn a_long_name_is_shortened_with_an_ellipsis() {
+    let name = "Helena Eagan of the Eagan family, visiting from the board";
+    let identity = [(name, Priority::High)];
+
+    let bar = TitleBar::fit(40, &identity, "42% Complete", Some(14), false);
+    assert!(!bar.logo);
+    assert_eq!(bar.left, " Helena Eagan of the Eagan\u{2026} ");
+    assert_eq!(bar.spacer, 0);
+    assert_eq!(bar_width(&bar, 14), 40);
+
+    let bar = TitleBar::fit(40, &identity, "42% Complete", None, true);
+    assert!(bar.left.ends_with("... "));
+}
+
+#[test]
+fn no_width_is_too_narrow() {
+    let long = "x".repeat(300);
+    let identities: [&[(&str, Priority)]; 3] = [&IDENTITY, &[(long.as_str(), Priority::High)], &[]];
+
+    for identity in identities {
+        for width in 0..=120 {
+            for logo in [None, Some(14)] {
+                let bar = TitleBar::fit(width, identity, "100% Complete", logo, false);
+                assert!(bar_width(&bar, 14) <= width as usize, "{} columns: {:?}", width, bar);
+                assert!(bar.right.chars().count() <= width as usize);
+                assert!(bar.left.is_empty() || bar.left.chars().count() >= 4, "{:?}", bar.left);
+            }
+        }
+    }
+
+    // Only the completion is left, cut short
+    let bar = TitleBar::fit(8, &IDENTITY, "100% Complete", Some(14), false);
+    assert_eq!((bar.left.as_str(), bar.right.as_str(), bar.logo), ("", "100% Co\u{2026}", false));
+    assert_eq!(TitleBar::fit(0, &IDENTITY, "100% Complete", None, false).right, "");
+}
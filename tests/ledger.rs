@@ -0,0 +1,99 @@
+use lumon_mdr::events::AppEvent;
+use lumon_mdr::ledger::{self, DayTotals, Ledger, RACES_KEPT, Week};
+use lumon_mdr::onboarding::Lesson;
+use lumon_mdr::race::RaceResult;
+use lumon_mdr::scoring::Difficulty;
+use lumon_mdr::share::ShareCode;
+use std::time::Duration;
+
+fn day(text: &str) -> i64 {
+    ledger::parse_day(text).unwrap()
+}
+
+#[test]
+fn dates_and_weeks_follow_the_calendar() {
+    assert_eq!(day("1970-01-01"), 0);
+    assert_eq!(ledger::format_day(day("2024-02-29")), "2024-02-29");
+    assert_eq!(ledger::parse_day("2025-02-29"), None);
+    assert_eq!(ledger::weekday(day("2025-03-03")), 0);
+    assert_eq!(ledger::week_start(day("2025-03-09")), day("2025-03-03"));
+
+    assert_eq!(ledger::iso_week(day("2025-03-03")), Week { year: 2025, week: 10 });
+    // The first days of January can belong to the last week of the year before
+    assert_eq!(ledger::iso_week(day("2021-01-03")), Week { year: 2020, week: 53 });
+    assert_eq!(ledger::iso_week(day("2024-12-30")), Week { year: 2025, week: 1 });
+    assert_eq!("2025-W10".parse::<Week>().unwrap().to_string(), "2025-W10");
+}
+
+#[test]
+fn a_week_is_summed_by_day() {
+    let mut ledger = Ledger::default();
+    let monday = day("2025-03-03");
+    for offset in [0, 1, 3, 4, 5] {
+        ledger.record(monday + offset, &AppEvent::NumbersRefined { count: 10 * (offset as usize + 1) });
+    }
+    ledger.record(monday + 1, &AppEvent::FileCompleted { prize: "Melon Bar".to_string() });
+    ledger.record(monday + 7, &AppEvent::NumbersRefined { count: 999 });
+
+    let recap = ledger.recap(monday);
+
+    assert_eq!(recap.week.to_string(), "2025-W10");
+    assert_eq!(recap.total, DayTotals { numbers: 180, bins: 0, files: 1 });
+    assert_eq!(recap.best_day, Some(1));
+    assert_eq!(recap.streak, 3);
+    assert!(recap.days[2].is_empty());
+}
+
+#[test]
+fn round_trips_through_text() {
+    let mut ledger = Ledger::default();
+    ledger.record(day("2025-03-03"), &AppEvent::BinFilled { bin: 2 });
+    ledger.recap_week = Some(Week { year: 2025, week: 11 });
+
+    let text = ledger.to_string();
+
+    assert_eq!(text, "lumon-mdr ledger 1\nrecap=2025-W11\n2025-03-03 numbers=0 bins=1 files=0\n");
+    assert_eq!(text.parse::<Ledger>().unwrap(), ledger);
+    assert!("lumon-mdr ledger 1\n2025-13-01 numbers=1\n".parse::<Ledger>().is_err());
+}
+
+#[test]
+fn keeps_the_lessons_learned() {
+    let mut ledger = Ledger::default();
+    ledger.learned.insert(Lesson::Refine);
+    ledger.learned.insert(Lesson::Hover);
+
+    let text = ledger.to_string();
+
+    assert_eq!(text, "lumon-mdr ledger 1\nlearned=hover,refine\n");
+    assert_eq!(text.parse::<Ledger>().unwrap(), ledger);
+    // A lesson from a newer build is skipped
+    let newer: Ledger = "lumon-mdr ledger 1\nlearned=fill,juggle\n".parse().unwrap();
+    assert_eq!(newer.learned.into_iter().collect::<Vec<_>>(), [Lesson::FillBins]);
+}
+
+#[test]
+fn keeps_races_and_the_personal_best() {
+    let code = ShareCode { seed: 9_001_234, difficulty: Difficulty::Exacting, grid: (19, 15) };
+    let mut ledger = Ledger { best: Some(Duration::from_millis(272_100)), ..Ledger::default() };
+    for _ in 0..=RACES_KEPT {
+        ledger.record_race(RaceResult {
+            day: day("2025-03-04"),
+            code,
+            target: Duration::from_secs(300),
+            time: Duration::from_millis(272_100),
+        });
+    }
+    assert_eq!(ledger.races.len(), RACES_KEPT);
+
+    ledger.races.truncate(1);
+    let text = ledger.to_string();
+
+    assert_eq!(
+        text,
+        "lumon-mdr ledger 1\nbest=272100\nrace=2025-03-04 HUSH-49B4996-10 target=300000 time=272100\n"
+    );
+    assert_eq!(text.parse::<Ledger>().unwrap(), ledger);
+    assert!("lumon-mdr ledger 1\nrace=2025-03-04 HUSH-49B4996-11 target=1 time=1\n".parse::<Ledger>().is_err());
+    assert!("lumon-mdr ledger 1\nrace=2025-03-04 HUSH-49B4996-10 target=1\n".parse::<Ledger>().is_err());
+}
@@ -0,0 +1,40 @@
+use lumon_mdr::events::AppEvent;
+use lumon_mdr::metrics::Metrics;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::sync::Arc;
+
+fn get(addr: std::net::SocketAddr, path: &str) -> String {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(stream, "GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    response
+}
+
+#[test]
+fn counts_events() {
+    let metrics = Metrics::new();
+    metrics.record(&AppEvent::NumbersRefined { count: 4 });
+    metrics.record(&AppEvent::NumbersRefined { count: 3 });
+    metrics.record(&AppEvent::BinFilled { bin: 2 });
+
+    let text = metrics.render();
+    assert!(text.contains("# TYPE numbers_refined_total counter\nnumbers_refined_total 7\n"));
+    assert!(text.contains("bins_completed_total 1\n"));
+    assert!(text.contains("# TYPE session_seconds gauge\n"));
+}
+
+#[test]
+fn serves_metrics_over_http() {
+    let metrics = Arc::new(Metrics::new());
+    let addr = metrics.serve("127.0.0.1:0").unwrap();
+    metrics.record(&AppEvent::FileCompleted { prize: "Melon Bar".to_string() });
+
+    let response = get(addr, "/metrics");
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+    assert!(response.contains("Content-Type: text/plain; version=0.0.4"));
+    assert!(response.contains("files_completed_total 1\n"));
+
+    assert!(get(addr, "/").starts_with("HTTP/1.1 404"));
+}
@@ -0,0 +1,44 @@
+use lumon_mdr::onboarding::{COACH_AFTER, Lesson, Onboarding};
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+#[test]
+fn lessons_are_coached_in_order_until_learned() {
+    let mut learned = BTreeSet::new();
+    assert_eq!(Lesson::next(&learned), Some(Lesson::Hover));
+
+    learned.insert(Lesson::Hover);
+    learned.insert(Lesson::FillBins);
+    assert_eq!(Lesson::next(&learned), Some(Lesson::Refine));
+
+    learned.insert(Lesson::Refine);
+    assert_eq!(Lesson::next(&learned), None);
+}
+
+#[test]
+fn a_mark_shows_after_a_pause_and_goes_with_the_next_input() {
+    let mut onboarding = Onboarding::default();
+    let busy = Duration::from_secs(1);
+
+    assert_eq!(onboarding.step(busy, Some(Lesson::Hover), true), None);
+    assert_eq!(onboarding, Onboarding::Watching);
+
+    assert_eq!(onboarding.step(COACH_AFTER, Some(Lesson::Hover), true), Some(Lesson::Hover));
+    assert_eq!(onboarding.showing(), Some(Lesson::Hover));
+
+    assert_eq!(onboarding.step(busy, Some(Lesson::Refine), true), None);
+    assert_eq!(onboarding.step(COACH_AFTER, Some(Lesson::Refine), true), Some(Lesson::Refine));
+}
+
+#[test]
+fn coaching_finishes_with_the_lessons_or_the_first_file() {
+    let mut onboarding = Onboarding::default();
+    assert_eq!(onboarding.step(COACH_AFTER, None, true), None);
+    assert_eq!(onboarding, Onboarding::Finished);
+
+    let mut onboarding = Onboarding::Showing(Lesson::FillBins);
+    assert_eq!(onboarding.step(COACH_AFTER, Some(Lesson::FillBins), false), None);
+    assert_eq!(onboarding, Onboarding::Finished);
+    // and stays finished
+    assert_eq!(onboarding.step(COACH_AFTER, Some(Lesson::FillBins), true), None);
+}
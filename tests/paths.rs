@@ -0,0 +1,63 @@
+use lumon_mdr::paths::{self, Dirs, Platform};
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+fn resolve(platform: Platform, vars: &[(&str, &str)], existing: &[&str]) -> Option<Dirs> {
+    let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+    paths::resolve(
+        platform,
+        |name| vars.get(name).map(OsString::from),
+        |path| existing.iter().any(|dir| Path::new(dir) == path),
+    )
+}
+
+fn dirs(config: &str, data: &str, cache: &str) -> Option<Dirs> {
+    Some(Dirs { config: PathBuf::from(config), data: PathBuf::from(data), cache: PathBuf::from(cache) })
+}
+
+#[test]
+fn each_platform_has_its_own_places() {
+    assert_eq!(
+        resolve(Platform::Xdg, &[("HOME", "/home/mark")], &[]),
+        dirs("/home/mark/.config/lumon-mdr", "/home/mark/.local/share/lumon-mdr", "/home/mark/.cache/lumon-mdr")
+    );
+    assert_eq!(
+        resolve(Platform::Xdg, &[("HOME", "/home/mark"), ("XDG_DATA_HOME", "/data"), ("XDG_CACHE_HOME", "/tmp/cache")], &[]),
+        dirs("/home/mark/.config/lumon-mdr", "/data/lumon-mdr", "/tmp/cache/lumon-mdr")
+    );
+    assert_eq!(
+        resolve(Platform::MacOs, &[("HOME", "/Users/mark")], &[]),
+        dirs(
+            "/Users/mark/Library/Application Support/lumon-mdr",
+            "/Users/mark/Library/Application Support/lumon-mdr",
+            "/Users/mark/Library/Caches/lumon-mdr"
+        )
+    );
+    let windows = resolve(Platform::Windows, &[("APPDATA", "C:/Roaming"), ("LOCALAPPDATA", "C:/Local")], &[]).unwrap();
+    assert_eq!(windows.config, Path::new("C:/Roaming").join("lumon-mdr"));
+    assert_eq!(windows.cache, Path::new("C:/Local").join("lumon-mdr").join("cache"));
+
+    assert_eq!(resolve(Platform::Xdg, &[], &[]), None);
+}
+
+#[test]
+fn a_portable_home_overrides_everything() {
+    let vars = [("HOME", "/home/mark"), ("XDG_CONFIG_HOME", "/xdg"), ("LUMON_MDR_HOME", "/media/usb/mdr")];
+    for platform in [Platform::Xdg, Platform::MacOs, Platform::Windows] {
+        assert_eq!(resolve(platform, &vars, &[]), dirs("/media/usb/mdr/config", "/media/usb/mdr/data", "/media/usb/mdr/cache"));
+    }
+}
+
+#[test]
+fn folders_from_older_builds_are_kept() {
+    let vars = [("HOME", "/Users/mark")];
+    let macos = resolve(Platform::MacOs, &vars, &["/Users/mark/.local/share/lumon-mdr"]).unwrap();
+    assert_eq!(macos.data, Path::new("/Users/mark/.local/share/lumon-mdr"));
+    assert_eq!(macos.config, Path::new("/Users/mark/Library/Application Support/lumon-mdr"));
+
+    // Once the new folder exists it wins
+    let moved = ["/Users/mark/.local/share/lumon-mdr", "/Users/mark/Library/Application Support/lumon-mdr"];
+    let macos = resolve(Platform::MacOs, &vars, &moved).unwrap();
+    assert_eq!(macos.data, Path::new("/Users/mark/Library/Application Support/lumon-mdr"));
+}
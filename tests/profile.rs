@@ -0,0 +1,83 @@
+use lumon_mdr::error::MdrError;
+use lumon_mdr::profile::{self, Bundle, Locations};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn machine(name: &str) -> (PathBuf, Locations) {
+    let root = std::env::temp_dir().join(format!("lumon-mdr-profile-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    let locations = Locations {
+        config: Some(root.join("config.toml")),
+        sessions: Some(root.join("sessions")),
+    };
+    (root, locations)
+}
+
+fn write(path: &Path, contents: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
+
+#[test]
+fn a_profile_moves_between_machines() {
+    let (old_root, old) = machine("old");
+    write(&old_root.join("config.toml"), "[prize]\nauto_reset = true\n");
+    write(&old_root.join("sessions/mark.save"), "lumon-mdr save 1\nusername=mark\n");
+    write(&old_root.join("sessions/helly.save"), "lumon-mdr sealed 1\ndata=00\n");
+
+    let bundle_path = old_root.join("out.mdr");
+    let bundle = profile::export(&old, &bundle_path).unwrap();
+    assert_eq!(bundle.entries.len(), 3);
+
+    let (new_root, new) = machine("new");
+    let report = profile::import(&new, &bundle_path, false).unwrap();
+    assert_eq!(report.written, ["config.toml", "sessions/helly.save", "sessions/mark.save"]);
+    assert_eq!(
+        fs::read_to_string(new_root.join("sessions/mark.save")).unwrap(),
+        "lumon-mdr save 1\nusername=mark\n"
+    );
+
+    // A second import finds nothing to do
+    let report = profile::import(&new, &bundle_path, false).unwrap();
+    assert_eq!(report.unchanged.len(), 3);
+
+    let _ = fs::remove_dir_all(&old_root);
+    let _ = fs::remove_dir_all(&new_root);
+}
+
+#[test]
+fn conflicts_are_kept_unless_replaced() {
+    let (root, locations) = machine("conflict");
+    write(&root.join("config.toml"), "# mine\n");
+    let bundle = Bundle { entries: vec![("config.toml".to_string(), b"# theirs\n".to_vec())] };
+
+    let report = bundle.install(&locations, false).unwrap();
+    assert_eq!(report.kept, ["config.toml"]);
+    assert_eq!(fs::read_to_string(root.join("config.toml")).unwrap(), "# mine\n");
+
+    let report = bundle.install(&locations, true).unwrap();
+    assert_eq!(report.replaced, ["config.toml"]);
+    assert_eq!(fs::read_to_string(root.join("config.toml")).unwrap(), "# theirs\n");
+    assert_eq!(fs::read_to_string(root.join("config.toml.bak")).unwrap(), "# mine\n");
+
+    let _ = fs::remove_dir_all(&root);
+}
+
+#[test]
+fn rejects_bad_bundles() {
+    let (root, locations) = machine("hostile");
+    let bundle_error = |data: &[u8]| matches!(Bundle::from_bytes(data), Err(MdrError::Save(_)));
+
+    assert!(bundle_error(b"hello\n"));
+    assert!(bundle_error(b"lumon-mdr profile 9\n"));
+    assert!(bundle_error(b"lumon-mdr profile 1\nentry config.toml 99\nshort\n"));
+    assert!(bundle_error(b"lumon-mdr profile 1\nentry a 0\n\nentry a 0\n\n"));
+
+    // Nothing outside the profile folders is ever written
+    let hostile = Bundle::from_bytes(b"lumon-mdr profile 1\nentry sessions/../../evil.save 1\nx\n").unwrap();
+    assert!(hostile.install(&locations, true).is_err());
+    assert!(!root.join("evil.save").exists());
+
+    let _ = fs::remove_dir_all(&root);
+}
@@ -0,0 +1,43 @@
+use lumon_mdr::race::{self, Pace, Race, RaceResult, Target};
+use lumon_mdr::scoring::Difficulty;
+use lumon_mdr::share::ShareCode;
+use std::time::Duration;
+
+const CODE: ShareCode = ShareCode { seed: 9_001_234, difficulty: Difficulty::Exacting, grid: (19, 15) };
+
+#[test]
+fn times_read_and_print_like_a_stopwatch() {
+    assert_eq!(race::parse_time("4:32"), Some(Duration::from_secs(272)));
+    assert_eq!(race::parse_time("4:32.5"), Some(Duration::from_millis(272_500)));
+    assert_eq!(race::parse_time("1:02:03"), Some(Duration::from_secs(3723)));
+    assert_eq!(race::parse_time("95"), Some(Duration::from_secs(95)));
+    for bad in ["", "4:60", "1:60:00", "-5", "1:2:3:4", "soon"] {
+        assert_eq!(race::parse_time(bad), None, "{}", bad);
+    }
+
+    assert_eq!(race::format_time(Duration::from_millis(272_549)), "4:32.5");
+    assert_eq!(race::format_time(Duration::from_millis(3_723_000)), "1:02:03.0");
+
+    assert_eq!("pb".parse::<Target>().unwrap(), Target::PersonalBest);
+    assert_eq!("4:32".parse::<Target>().unwrap(), Target::Time(Duration::from_secs(272)));
+    assert!("0:00".parse::<Target>().is_err());
+}
+
+#[test]
+fn pace_is_measured_against_an_even_run_to_the_target() {
+    let race = Race { code: CODE, target: Duration::from_secs(100) };
+
+    let ahead = race.pace(Duration::from_secs(40), 50.0);
+    assert_eq!(ahead, Pace::Ahead(Duration::from_secs(10)));
+    assert_eq!(ahead.to_string(), "-0:10.0");
+
+    let behind = race.pace(Duration::from_secs(30), 25.0);
+    assert_eq!(behind, Pace::Behind(Duration::from_secs(5)));
+    assert_eq!(behind.to_string(), "+0:05.0");
+
+    let result = RaceResult { day: 0, code: CODE, target: race.target, time: Duration::from_secs(100) };
+    assert!(result.won());
+    assert_eq!(result.verdict(), "Race won by 0:00.0");
+    let result = RaceResult { time: Duration::from_millis(112_300), ..result };
+    assert_eq!(result.verdict(), "Race lost by 0:12.3");
+}
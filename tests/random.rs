@@ -0,0 +1,42 @@
+use lumon_mdr::app::App;
+use lumon_mdr::clock::MockClock;
+use lumon_mdr::random::{RngService, Stream};
+use lumon_mdr::theme::Palette;
+use rand::Rng;
+use std::rc::Rc;
+
+fn draws(random: &mut RngService, stream: Stream) -> Vec<u32> {
+    (0..8).map(|_| random.stream(stream).random()).collect()
+}
+
+#[test]
+fn a_seed_replays_every_stream() {
+    let (mut first, mut second) = (RngService::new(42), RngService::new(42));
+    for stream in Stream::ALL {
+        assert_eq!(draws(&mut first, stream), draws(&mut second, stream), "{:?}", stream);
+    }
+    assert_ne!(draws(&mut RngService::new(43), Stream::Grid), draws(&mut RngService::new(42), Stream::Grid));
+}
+
+#[test]
+fn drawing_from_one_stream_leaves_the_others_alone() {
+    let mut quiet = RngService::new(7);
+    let mut busy = RngService::new(7);
+    draws(&mut busy, Stream::Effects);
+    draws(&mut busy, Stream::Loading);
+    assert_eq!(draws(&mut quiet, Stream::Grid), draws(&mut busy, Stream::Grid));
+    assert_eq!(draws(&mut quiet, Stream::Prizes), draws(&mut busy, Stream::Prizes));
+    assert_ne!(draws(&mut quiet, Stream::Grid), draws(&mut quiet, Stream::Prizes));
+}
+
+#[test]
+fn apps_on_the_same_seed_start_alike() {
+    let app = |seed| App::with_random(Palette::Ansi, Rc::new(MockClock::new()), RngService::new(seed));
+    let (mut first, mut second) = (app(5), app(5));
+    assert_eq!(first.sticky, second.sticky);
+    assert_eq!(first.file_name, second.file_name);
+    first.random.stream(Stream::Effects).random::<u64>();
+    first.scatter_sticky();
+    second.scatter_sticky();
+    assert_eq!(first.sticky, second.sticky);
+}
@@ -0,0 +1,28 @@
+use lumon_mdr::app::App;
+use lumon_mdr::events::AppEvent;
+use lumon_mdr::ranks::{self, RANKS};
+use lumon_mdr::theme::Palette;
+
+#[test]
+fn ranks_follow_the_thresholds() {
+    assert_eq!(ranks::rank_for(0).title, "Refiner I");
+    assert_eq!(ranks::rank_for(499).title, "Refiner I");
+    assert_eq!(ranks::rank_for(500).title, "Refiner II");
+    assert_eq!(ranks::rank_for(u64::MAX).title, "Department Head");
+    assert_eq!(ranks::next_rank(4_000).unwrap().title, "Department Head");
+    assert_eq!(ranks::next_rank(10_000), None);
+    assert!(RANKS.windows(2).all(|pair| pair[0].xp < pair[1].xp));
+
+    assert_eq!(ranks::xp_for(&AppEvent::NumbersRefined { count: 7 }), 7);
+    assert_eq!(ranks::xp_for(&AppEvent::FileCompleted { prize: String::new() }), 100);
+}
+
+#[test]
+fn a_promotion_is_announced() {
+    let mut app = App::new(Palette::Ansi);
+    app.gain_xp(499);
+    assert!(app.toast.is_none());
+
+    app.gain_xp(1);
+    assert_eq!(app.toast.unwrap().text, "Promoted to Refiner II. Kier is proud of you.");
+}
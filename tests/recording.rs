@@ -0,0 +1,68 @@
+use lumon_mdr::recording::{self, Recorder};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use std::fs;
+use std::time::{Duration, Instant};
+
+fn buffer(text: &str) -> Buffer {
+    let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 2));
+    buffer.set_string(0, 0, text, Style::default());
+    buffer
+}
+
+#[test]
+fn rows_carry_their_colours() {
+    let mut buffer = buffer("ab");
+    buffer.set_string(1, 0, "b", Style::default().fg(Color::Green).add_modifier(Modifier::BOLD));
+    buffer.set_string(0, 1, "c", Style::default().bg(Color::Rgb(1, 2, 3)));
+
+    let rows = recording::ansi_rows(&buffer);
+
+    assert_eq!(rows, ["\x1b[0ma\x1b[0;1;32mb\x1b[0m  \x1b[0m", "\x1b[0;48;2;1;2;3mc\x1b[0m   \x1b[0m"]);
+    assert_eq!(
+        recording::cast_event(Duration::from_millis(1500), "o", "\x1b[H\"a\"\r\n"),
+        r#"[1.500000, "o", "\u001b[H\"a\"\r\n"]"#
+    );
+}
+
+#[test]
+fn frames_are_written_once_each() {
+    let dir = std::env::temp_dir().join(format!("lumon-mdr-frames-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let start = Instant::now();
+
+    let mut recorder = Recorder::create(&dir).unwrap();
+    recorder.record(&buffer("one"), start).unwrap();
+    recorder.record(&buffer("one"), start + Duration::from_millis(100)).unwrap();
+    recorder.record(&buffer("two"), start + Duration::from_millis(200)).unwrap();
+
+    assert_eq!(recorder.frames, 2);
+    assert!(fs::read_to_string(dir.join("frame-000001.ans")).unwrap().contains("one"));
+    assert!(fs::read_to_string(dir.join("frame-000002.ans")).unwrap().contains("two"));
+    assert!(!dir.join("frame-000003.ans").exists());
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn a_cast_keeps_the_timing() {
+    let path = std::env::temp_dir().join(format!("lumon-mdr-frames-{}.cast", std::process::id()));
+    let start = Instant::now();
+
+    let mut recorder = Recorder::create(&path).unwrap();
+    recorder.record(&buffer("one"), start).unwrap();
+    recorder.record(&buffer("two"), start + Duration::from_millis(250)).unwrap();
+    let mut wider = Buffer::empty(Rect::new(0, 0, 6, 2));
+    wider.set_string(0, 0, "three", Style::default());
+    recorder.record(&wider, start + Duration::from_millis(500)).unwrap();
+
+    let text = fs::read_to_string(&path).unwrap();
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 5, "{}", text);
+    assert!(lines[0].starts_with(r#"{"version": 2, "width": 4, "height": 2, "#), "{}", lines[0]);
+    assert!(lines[1].starts_with(r#"[0.000000, "o", "#) && lines[1].contains("one"));
+    assert!(lines[2].starts_with(r#"[0.250000, "o", "#) && lines[2].contains("two"));
+    assert_eq!(lines[3], r#"[0.500000, "r", "6x2"]"#);
+    assert!(lines[4].contains("three"));
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,120 @@
+use lumon_mdr::app::{App, AppState, DEFAULT_GRID_SEED};
+use lumon_mdr::error::MdrError;
+use lumon_mdr::grid::GridSnapshot;
+use lumon_mdr::journal::Note;
+use lumon_mdr::scoring::Difficulty;
+use lumon_mdr::share::ShareCode;
+use lumon_mdr::crypto::SealKey;
+use lumon_mdr::save::{self, SaveState, Sealed};
+use lumon_mdr::stats::Balance;
+use lumon_mdr::theme::Palette;
+use std::time::Duration;
+
+fn sample() -> SaveState {
+    SaveState {
+        state: AppState::Prize,
+        username: "helly = r".to_string(),
+        file_name: "Siena".to_string(),
+        bins: vec![100, 100, 100, 100, 100],
+        order: vec![2, 3, 1, 4, 5],
+        seed: 7_001,
+        loading_progress: 100.0,
+        prize_name: "Waffle Party".to_string(),
+        balance: Balance {
+            innie: Duration::from_millis(61_500),
+            outie: Duration::from_secs(12),
+        },
+        xp: 1_234,
+        code: Some(ShareCode { seed: 7_001, difficulty: Difficulty::Relaxed, grid: (12, 8) }),
+        notes: vec![
+            Note { at: 1_741_000_000, text: "Expense report for Q3".to_string() },
+            Note { at: 1_741_003_600, text: "bins = feelings?".to_string() },
+        ],
+        grid: Some(GridSnapshot {
+            size: (58, 19),
+            replaced: vec![((3, 4), 7), ((57, 18), 0)],
+            clusters: vec![vec![(10, 2), (11, 2), (10, 3)]],
+            cursor: Some((12, 5)),
+        }),
+        break_until: Some(1_741_003_900),
+        reshuffles: 2,
+    }
+}
+
+#[test]
+fn round_trips_through_text() {
+    let save = sample();
+    let text = save.to_string();
+
+    assert!(text.starts_with("lumon-mdr save 1\n"));
+    assert_eq!(text.parse::<SaveState>().unwrap(), save);
+}
+
+#[test]
+fn apply_restores_the_session() {
+    let mut app = App::new(Palette::Ansi);
+    let screen = sample().apply(&mut app);
+
+    assert_eq!(screen, AppState::Prize, "the screen is the caller's to go to");
+    assert_eq!(app.state(), AppState::Login);
+    assert_eq!(app.username, "helly = r");
+    assert_eq!(app.username_cursor, 9);
+    assert!(app.is_all_complete());
+    assert_eq!(app.station.stats.balance("helly = r").innie, Duration::from_millis(61_500));
+    assert_eq!(app.xp, 1_234);
+    assert_eq!(app.bin_order(), [1, 2, 0, 3, 4]);
+    assert_eq!(app.grid_seed, 7_001);
+    assert_eq!(app.journal.len(), 2);
+    assert_eq!(app.difficulty(), Difficulty::Relaxed);
+    assert_eq!(SaveState { state: screen, ..SaveState::capture(&app) }, sample());
+}
+
+#[test]
+fn rejects_bad_files() {
+    let save_error = |text: &str| matches!(text.parse::<SaveState>(), Err(MdrError::Save(_)));
+
+    assert!(save_error("hello"));
+    assert!(save_error("lumon-mdr save 99\n"));
+    assert!(save_error("lumon-mdr save 1\nbins=1,2,300\n"));
+    assert!(save_error("lumon-mdr save 1\nstate=break-room\n"));
+    assert!(save_error("lumon-mdr save 1\norder=0,1\n"));
+    assert!(save_error("lumon-mdr save 1\nnote=soon Lunch\n"));
+    assert!(save_error("lumon-mdr save 1\nbreak=soon\n"));
+    // Saves from before seeds keep the grid they had
+    assert_eq!("lumon-mdr save 1\n".parse::<SaveState>().unwrap().seed, DEFAULT_GRID_SEED);
+    // Keys from newer builds are skipped
+    assert!("lumon-mdr save 1\nfavourite_melon=honeydew\n".parse::<SaveState>().is_ok());
+}
+
+#[test]
+fn sealed_saves_round_trip_and_hide_their_contents() {
+    let key = SealKey::generate("woe", 1000);
+    let text = Sealed::seal(&sample(), &key).to_string();
+
+    assert!(text.starts_with("lumon-mdr sealed 1\n"));
+    assert!(!text.contains("Waffle"));
+
+    let sealed: Sealed = text.parse().unwrap();
+    assert_eq!(sealed.open(&sealed.key_for("woe")).unwrap(), sample());
+    assert!(matches!(sealed.open(&sealed.key_for("frolic")), Err(MdrError::Save(_))));
+}
+
+#[test]
+fn rejects_bad_sealed_files() {
+    let sealed_error = |text: &str| matches!(text.parse::<Sealed>(), Err(MdrError::Save(_)));
+
+    assert!(sealed_error("lumon-mdr save 1\n"));
+    assert!(sealed_error("lumon-mdr sealed 2\n"));
+    assert!(sealed_error("lumon-mdr sealed 1\nsalt=zz\n"));
+    assert!(sealed_error("lumon-mdr sealed 1\nsalt=00\niterations=1\nnonce=00\ndata=00\n"));
+}
+
+#[test]
+fn session_files_are_named_after_the_refiner() {
+    let dir = std::path::Path::new("/sessions");
+
+    assert_eq!(save::session_path(dir, "Mark S."), dir.join("mark_s_.save"));
+    assert_eq!(save::session_path(dir, "../helly"), dir.join("___helly.save"));
+    assert_eq!(save::slot_path(dir, "Mark S.", 1), dir.join("mark_s_.save"));
+    assert_eq!(save::slot_path(dir, "Mark S.", 3), dir.join("mark_s_.3.save"));
+}
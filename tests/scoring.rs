@@ -0,0 +1,63 @@
+use lumon_mdr::scoring::{
+    BaseValue, Capture, ComboBonus, Difficulty, MisclickPenalty, Scoring, ScoringRule, ScoringValues, StreakMultiplier,
+};
+
+fn capture(digits: &[u16], streak: u32, misclicks: u32) -> Capture {
+    Capture { digits: digits.to_vec(), streak, misclicks }
+}
+
+#[test]
+fn the_base_value_is_the_sum_of_the_digits() {
+    assert_eq!(BaseValue.apply(&capture(&[1, 2, 3], 0, 0), 99), 6);
+}
+
+#[test]
+fn streaks_multiply_up_to_a_cap() {
+    let rule = StreakMultiplier { from: 5, step: 10, max: 150 };
+    assert_eq!(rule.apply(&capture(&[], 4, 0), 20), 20);
+    assert_eq!(rule.apply(&capture(&[], 5, 0), 20), 22);
+    assert_eq!(rule.apply(&capture(&[], 7, 0), 20), 26);
+    assert_eq!(rule.apply(&capture(&[], 40, 0), 20), 30);
+}
+
+#[test]
+fn combos_add_for_each_digit_past_the_pair() {
+    let rule = ComboBonus { min: 3, per_digit: 5 };
+    assert_eq!(rule.apply(&capture(&[7, 7, 1], 0, 0), 15), 15);
+    assert_eq!(rule.apply(&capture(&[7, 7, 7], 0, 0), 21), 26);
+    assert_eq!(rule.apply(&capture(&[7, 7, 7, 7], 0, 0), 28), 38);
+
+    let pairs = ComboBonus { min: 2, per_digit: 5 };
+    assert_eq!(pairs.apply(&capture(&[7, 7, 1], 0, 0), 15), 20);
+}
+
+#[test]
+fn misclicks_cost_but_never_go_below_nothing() {
+    let rule = MisclickPenalty { per_misclick: 2 };
+    assert_eq!(rule.apply(&capture(&[], 0, 3), 10), 4);
+
+    let scoring = Scoring::default();
+    assert_eq!(scoring.score(&capture(&[1], 0, 10)), 0);
+}
+
+#[test]
+fn difficulty_decides_which_rules_apply() {
+    let values = ScoringValues::default();
+    let sloppy = capture(&[4, 4, 4, 2], 9, 2);
+
+    let relaxed = Scoring::compose(Difficulty::Relaxed, &values);
+    assert_eq!(format!("{:?}", relaxed), r#"["base value", "combo bonus"]"#);
+    assert_eq!(relaxed.score(&sloppy), 14 + 5);
+
+    let standard = Scoring::compose(Difficulty::Standard, &values);
+    assert_eq!(standard.score(&sloppy), 19 * 150 / 100 - 2 * 2);
+
+    let exacting = Scoring::compose(Difficulty::Exacting, &values);
+    assert_eq!(exacting.score(&sloppy), 19 * 125 / 100 - 2 * 4);
+}
+
+#[test]
+fn difficulties_parse_by_name() {
+    assert_eq!("Exacting".parse::<Difficulty>().unwrap(), Difficulty::Exacting);
+    assert!("hard".parse::<Difficulty>().is_err());
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "scripting")]
+
+use lumon_mdr::app::{App, AppState};
+use lumon_mdr::error::{FailureKind, MdrError};
+use lumon_mdr::scripting::Rules;
+use lumon_mdr::theme::Palette;
+
+fn load_error(source: &str) -> String {
+    match Rules::load(source, "rules.lua") {
+        Err(MdrError::Config(msg)) => msg,
+        other => panic!("expected a script error, got ok={}", other.is_ok()),
+    }
+}
+
+#[test]
+fn hooks_override_the_built_in_rules() {
+    let rules = Rules::load(
+        r#"
+        function digit(col, row) return (col + row) % 10 end
+        function deposit(bin, value) return bin * value end
+        function prize(prizes) return prizes[#prizes] end
+        function memo(user, prize) return user .. " earned " .. prize end
+        "#,
+        "rules.lua",
+    )
+    .unwrap();
+
+    assert_eq!(rules.digit(0, 3).unwrap(), Some(5));
+    assert_eq!(rules.deposit(2, 4).unwrap(), Some(12));
+    let prizes = vec!["Melon Bar".to_string(), "Egg Bar".to_string()];
+    assert_eq!(rules.prize(&prizes).unwrap().as_deref(), Some("Egg Bar"));
+    assert_eq!(rules.memo("helly", "Egg Bar").unwrap().as_deref(), Some("helly earned Egg Bar"));
+}
+
+#[test]
+fn missing_hooks_keep_the_defaults() {
+    let rules = Rules::load("function digit() return nil end", "rules.lua").unwrap();
+    assert_eq!(rules.digit(1, 1).unwrap(), None);
+    assert_eq!(rules.deposit(0, 3).unwrap(), None);
+    assert_eq!(rules.memo("mark", "Finger Trap").unwrap(), None);
+}
+
+#[test]
+fn scripts_are_sandboxed() {
+    assert!(load_error("os.execute('true')").contains("os"));
+    assert!(load_error("io.open('/etc/passwd')").contains("io"));
+    assert!(load_error("require('socket')").contains("require"));
+    assert!(load_error("while true do end").contains("took too long"));
+    // Catching the error that stops it would let a script run forever
+    assert!(load_error("while true do pcall(function() while true do end end) end").contains("pcall"));
+    assert!(load_error("xpcall(print, print)").contains("xpcall"));
+    let rules = Rules::load("function digit() while true do pcall(function() while true do end end) end end", "rules.lua").unwrap();
+    assert!(matches!(rules.digit(0, 0), Err(MdrError::Config(msg)) if msg.contains("pcall")));
+
+    let rules = Rules::load("function digit() return 12 end", "rules.lua").unwrap();
+    match rules.digit(0, 0) {
+        Err(MdrError::Config(msg)) => assert_eq!(msg, "rules.lua: 'digit' should return a digit from 0 to 9, got 12"),
+        other => panic!("expected an error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn a_failing_script_is_reported_and_switched_off() {
+    let mut app = App::new(Palette::Ansi);
+    app.go_to(AppState::Loading);
+    app.go_to(AppState::Main);
+    app.station.rules = Some(Rules::load("function deposit(bin, value) error('no') end", "rules.lua").unwrap());
+
+    app.add_to_container(1, 3);
+
+    assert_eq!(app.containers[1].count, 3);
+    assert!(app.station.rules.is_none());
+    assert_eq!(app.state(), AppState::Error);
+    assert_eq!(app.error.as_ref().unwrap().kind, FailureKind::Config);
+}
+
+#[test]
+fn the_example_script_loads() {
+    let source = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/examples/rules.lua")).unwrap();
+    let rules = Rules::load(&source, "rules.lua").unwrap();
+    assert_eq!(rules.digit(0, 5).unwrap(), Some(7));
+    assert_eq!(rules.deposit(3, 9).unwrap(), Some(4));
+}
@@ -0,0 +1,34 @@
+use lumon_mdr::app::{App, ScaryCluster};
+use lumon_mdr::selfcheck;
+use lumon_mdr::theme::Palette;
+
+#[test]
+fn a_fresh_session_passes() {
+    let report = selfcheck::run(&App::new(Palette::Ansi));
+    assert!(report.is_clean(), "{:?}", report.discrepancies);
+    assert!(report.checked > 20);
+}
+
+#[test]
+fn corrupted_state_is_reported_by_area() {
+    let mut app = App::new(Palette::Ansi);
+    app.containers[1].add(10);
+    app.containers[1].tempers[0] = 25;
+    app.containers[3].slot = 0;
+    app.grid.resize(10, 5);
+    app.scary_clusters.push(ScaryCluster::new(vec![(2, 2), (12, 2)]));
+    app.station.config.budget.inbox = 1;
+    app.inbox.extend(["one".to_string(), "two".to_string()]);
+
+    let report = selfcheck::run(&app);
+    let found: Vec<String> = report.discrepancies.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        found,
+        [
+            "bins: bin 2 holds 10 but its deposits add up to 25",
+            "bins: bins stand in slots [0, 0, 1, 2, 4]",
+            "grid: scary cluster 1 has 1 cells off the grid",
+            "caps: inbox holds 2 of 1",
+        ]
+    );
+}
@@ -0,0 +1,41 @@
+use lumon_mdr::config::Config;
+use lumon_mdr::scoring::Difficulty;
+use lumon_mdr::setup::{Setup, SetupStep};
+
+#[test]
+fn the_answers_become_a_config_that_reads_back() {
+    let themes = ["Cold Harbor", "Waffle Party"];
+    let mut setup = Setup::default();
+
+    setup.select(2, 3);
+    assert!(setup.advance(&themes));
+    assert_eq!(setup.step, SetupStep::Terminal);
+    assert!(setup.advance(&themes));
+    setup.select(1, 2);
+    assert!(setup.advance(&themes));
+    setup.select(-1, 3);
+    assert!(setup.advance(&themes));
+    setup.name = "Helly \"R.\"".to_string();
+    assert!(!setup.advance(&themes));
+
+    let config: Config = setup.to_toml().parse().unwrap();
+    assert_eq!(config.theme.as_deref(), Some("Waffle Party"));
+    assert!(!config.input.mouse);
+    assert_eq!(config.difficulty, Difficulty::Relaxed);
+    assert_eq!(config.login_name.as_deref(), Some("Helly \"R.\""));
+}
+
+#[test]
+fn going_back_keeps_the_answers() {
+    let themes = ["Cold Harbor"];
+    let mut setup = Setup::default();
+
+    setup.select(1, 2);
+    setup.advance(&themes);
+    assert!(setup.back(&themes));
+    assert_eq!((setup.step, setup.selected), (SetupStep::Theme, 1));
+    assert!(!setup.back(&themes));
+
+    let defaults: Config = Setup::default().to_toml().parse().unwrap();
+    assert_eq!(defaults, Config::default());
+}
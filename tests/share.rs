@@ -0,0 +1,54 @@
+use lumon_mdr::scoring::Difficulty;
+use lumon_mdr::share::ShareCode;
+
+fn code(seed: u64, difficulty: Difficulty, grid: (usize, usize)) -> ShareCode {
+    ShareCode { seed, difficulty, grid }
+}
+
+#[test]
+fn codes_are_short_words_and_round_trip() {
+    let shared = code(9_001_234, Difficulty::Exacting, (19, 15));
+    let text = shared.to_string();
+    assert_eq!(text, "HUSH-49B4996-10");
+
+    let parts: Vec<&str> = text.split('-').collect();
+    assert_eq!(parts.len(), 3, "{}", text);
+    assert_eq!(parts[0].len(), 4);
+    assert!(parts[1].len() <= 7, "{}", text);
+    assert_eq!(parts[2].len(), 2);
+    assert_eq!(text.parse::<ShareCode>().unwrap(), shared);
+    assert_eq!(text.to_lowercase().parse::<ShareCode>().unwrap(), shared);
+
+    for shared in [
+        code(0, Difficulty::Relaxed, (0, 0)),
+        code(42, Difficulty::Standard, (63, 63)),
+        code(u64::MAX, Difficulty::Exacting, (1, 1)),
+    ] {
+        assert_eq!(shared.to_string().parse::<ShareCode>().unwrap(), shared);
+    }
+}
+
+#[test]
+fn typos_are_caught_by_the_checksum() {
+    let shared = code(9_001_234, Difficulty::Exacting, (19, 15));
+    assert!("HUSH-49B4997-10".parse::<ShareCode>().is_err());
+    assert!("HUSH-49B4696-10".parse::<ShareCode>().is_err());
+    assert!("HUSH-49B4996-11".parse::<ShareCode>().is_err());
+    // Letters that look like digits are forgiven
+    assert_eq!(code(0, Difficulty::Relaxed, (0, 0)).to_string(), "COLD-0000-00");
+    assert_eq!("cold-oooo-00".parse::<ShareCode>().unwrap(), code(0, Difficulty::Relaxed, (0, 0)));
+    assert_eq!("hush-49b4996-10".parse::<ShareCode>().unwrap(), shared);
+
+    let error = |text: &str| text.parse::<ShareCode>().unwrap_err().to_string();
+    assert!(error("HUSH-49B4996").contains("three parts"));
+    assert!(error("COLDER-49B4996-10").contains("unknown word"));
+    assert!(error("HUSH-49B4!96-10").contains("unexpected character"));
+    assert!(error("HUSH-49B4996-100").contains("two digits"));
+    assert!(error("HUSH-49B4997-10").contains("checksum"));
+}
+
+#[test]
+fn only_a_drawn_grid_is_pinned() {
+    assert_eq!(code(1, Difficulty::Standard, (19, 15)).pinned_grid(), Some((19, 15)));
+    assert_eq!(code(1, Difficulty::Standard, (0, 0)).pinned_grid(), None);
+}
@@ -0,0 +1,64 @@
+use lumon_mdr::config::Config;
+use lumon_mdr::shift::{self, Shift};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Midnight UTC on Monday 2024-01-01
+const MONDAY: u64 = 1_704_067_200;
+
+fn at(day: u64, hour: u64, minute: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(MONDAY + day * 86_400 + hour * 3600 + minute * 60)
+}
+
+fn shift(config: &str) -> Shift {
+    config.parse::<Config>().unwrap().shift.unwrap()
+}
+
+#[test]
+fn weekday_shift() {
+    let s = shift("[shift]\nstart = \"09:00\"\nend = \"17:30\"\ndays = [\"mon\", \"tue\", \"wed\", \"thu\", \"fri\"]");
+
+    assert!(!s.is_open(at(0, 8, 59)));
+    assert!(s.is_open(at(0, 9, 0)));
+    assert!(s.is_open(at(4, 17, 29)));
+    assert!(!s.is_open(at(4, 17, 30)));
+    assert!(!s.is_open(at(5, 12, 0)));
+
+    assert_eq!(s.minutes_until_open(at(0, 8, 0)), 60);
+    // Friday evening waits for Monday morning
+    assert_eq!(s.minutes_until_open(at(4, 18, 0)), (2 * 24 + 15) * 60);
+    assert_eq!(s.minutes_until_open(at(2, 10, 0)), 0);
+}
+
+#[test]
+fn overnight_shift_belongs_to_the_day_it_starts() {
+    let s = shift("[shift]\nstart = \"22:00\"\nend = \"06:00\"\ndays = [\"fri\"]");
+
+    assert!(s.is_open(at(4, 23, 0)));
+    assert!(s.is_open(at(5, 5, 59)));
+    assert!(!s.is_open(at(5, 6, 0)));
+    assert!(!s.is_open(at(0, 2, 0)));
+}
+
+#[test]
+fn utc_offset_shifts_the_clock() {
+    let s = shift("[shift]\nstart = \"09:00\"\nend = \"17:00\"\nutc_offset = \"-05:00\"");
+
+    assert!(!s.is_open(at(0, 9, 0)));
+    assert!(s.is_open(at(0, 14, 0)));
+}
+
+#[test]
+fn parses_times_and_offsets() {
+    assert_eq!(shift::parse_time("07:05"), Some(425));
+    assert_eq!(shift::parse_time("24:00"), None);
+    assert_eq!(shift::parse_time("9:5"), None);
+    assert_eq!(shift::parse_offset("+05:30"), Some(330));
+    assert_eq!(shift::parse_offset("05:30"), None);
+}
+
+#[test]
+fn shift_table_needs_start_and_end() {
+    assert!("[shift]\nstart = \"09:00\"".parse::<Config>().is_err());
+    assert!("[shift]\nstart = \"09:00\"\nend = \"17:00\"\ndays = [\"someday\"]".parse::<Config>().is_err());
+    assert!("".parse::<Config>().unwrap().shift.is_none());
+}
@@ -0,0 +1,39 @@
+use lumon_mdr::slots::{self, MAX_SLOTS, Slot, SlotDetail, SlotPicker};
+use std::time::{Duration, UNIX_EPOCH};
+
+fn slot(number: usize, played_secs: u64) -> Slot {
+    Slot {
+        number,
+        last_played: Some(UNIX_EPOCH + Duration::from_secs(played_secs)),
+        detail: SlotDetail::Sealed,
+    }
+}
+
+#[test]
+fn the_picker_opens_on_the_latest_slot_and_offers_the_lowest_free_one() {
+    let mut picker = SlotPicker::new(vec![slot(1, 100), slot(3, 900), slot(4, 500)]);
+
+    assert_eq!(picker.selected, 1);
+    assert_eq!(picker.chosen(), Some(3));
+    assert_eq!(picker.rows(), 4);
+
+    picker.select(5);
+    assert!(picker.highlighted().is_none());
+    assert_eq!(picker.chosen(), Some(2));
+
+    let full = SlotPicker::new((1..=MAX_SLOTS).map(|number| slot(number, 0)).collect());
+    assert_eq!(full.rows(), MAX_SLOTS);
+    assert_eq!(slots::free_slot(&full.slots), None);
+}
+
+#[test]
+fn last_played_reads_as_a_rough_age() {
+    let at = UNIX_EPOCH + Duration::from_secs(1_000_000);
+    let label = |secs| slots::last_played_label(at, at + Duration::from_secs(secs));
+
+    assert_eq!(label(20), "just now");
+    assert_eq!(label(60), "1 minute ago");
+    assert_eq!(label(3 * 3_600 + 5), "3 hours ago");
+    assert_eq!(label(2 * 86_400), "2 days ago");
+    assert_eq!(slots::last_played_label(at, UNIX_EPOCH), "just now");
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "speech")]
+
+use lumon_mdr::config::SpeechConfig;
+use lumon_mdr::events::AppEvent;
+use lumon_mdr::speech::{self, Engine, MIN_GAP, Speech};
+use std::time::{Duration, Instant};
+
+#[test]
+fn milestones_are_spoken_in_words() {
+    assert_eq!(
+        speech::announcement(&AppEvent::BinFilled { bin: 3 }).unwrap(),
+        "Bin three is full. Please enjoy each number equally."
+    );
+    assert_eq!(
+        speech::announcement(&AppEvent::FileCompleted { prize: "Waffle Party".to_string() }).unwrap(),
+        "File complete. Please enjoy your Waffle Party."
+    );
+    assert_eq!(speech::announcement(&AppEvent::NumbersRefined { count: 4 }), None);
+}
+
+#[test]
+fn a_configured_command_is_the_engine() {
+    let config = SpeechConfig { enabled: true, command: Some("espeak -s 140".to_string()) };
+    assert_eq!(
+        Engine::find(&config),
+        Some(Engine::Program(vec!["espeak".to_string(), "-s".to_string(), "140".to_string()]))
+    );
+    assert!(Speech::start(&SpeechConfig::default()).unwrap().is_none());
+}
+
+#[test]
+fn announcements_too_close_together_are_dropped() {
+    let config = SpeechConfig { enabled: true, command: Some("true".to_string()) };
+    let speech = Speech::start(&config).unwrap().unwrap();
+    let start = Instant::now();
+
+    assert!(speech.announce(&AppEvent::BinFilled { bin: 1 }, start));
+    assert!(!speech.announce(&AppEvent::BinFilled { bin: 2 }, start + Duration::from_secs(1)));
+    assert!(!speech.announce(&AppEvent::NumbersRefined { count: 3 }, start + MIN_GAP));
+    assert!(speech.announce(&AppEvent::BinFilled { bin: 2 }, start + MIN_GAP));
+}
@@ -0,0 +1,82 @@
+use lumon_mdr::app::App;
+use lumon_mdr::startup::{Loaded, Startup, Status, Task};
+use lumon_mdr::theme::Palette;
+use std::path::PathBuf;
+
+const CONFIG: &str = "[theme]\nname = \"green-room\"\n\n[goals]\nitems = [\"Complete a file\"]\n";
+
+const PACK: &str = r##"
+[pack]
+name = "Optics and Design"
+
+[[theme]]
+name = "green-room"
+fg = "#5fbf8f"
+bg = "#0b2015"
+"##;
+
+const LEDGER: &str = "lumon-mdr ledger 1\n2025-03-03 numbers=340 bins=11 files=2\n";
+
+// A config, a pack folder and a ledger in a fresh folder
+fn startup_files(name: &str, ledger: &str) -> (PathBuf, PathBuf, PathBuf, PathBuf) {
+    let dir = std::env::temp_dir().join(format!("lumon-mdr-startup-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let packs = dir.join("packs");
+    std::fs::create_dir_all(&packs).unwrap();
+    std::fs::write(dir.join("config.toml"), CONFIG).unwrap();
+    std::fs::write(packs.join("optics.toml"), PACK).unwrap();
+    std::fs::write(dir.join("ledger"), ledger).unwrap();
+    (dir.join("config.toml"), packs, dir.join("ledger"), dir)
+}
+
+#[test]
+fn the_config_is_handed_over_before_the_packs() {
+    let (config, packs, ledger, dir) = startup_files("order", LEDGER);
+
+    let mut startup = Startup::start(Some(config), Some(packs), Some(ledger));
+    let loaded: Vec<Task> = startup.wait().iter().map(Loaded::task).collect();
+
+    assert_eq!(loaded.len(), 3);
+    let position = |task| loaded.iter().position(|&t| t == task).unwrap();
+    assert!(position(Task::Config) < position(Task::Content));
+    assert!(startup.is_done());
+    assert!(startup.tasks.iter().all(|&(_, status)| status == Status::Done));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn only_tasks_with_somewhere_to_read_run_and_failures_are_marked() {
+    let (_, _, ledger, dir) = startup_files("failed", "not a ledger\n");
+
+    let mut startup = Startup::start(None, None, Some(ledger));
+    let loaded = startup.wait_for(Task::Ledger);
+
+    assert!(matches!(loaded[..], [Loaded::Ledger(Err(_))]));
+    assert_eq!(startup.tasks, [(Task::Ledger, Status::Failed)]);
+    assert_eq!(startup.status(Task::Config), None);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn startup_work_lands_in_the_app_as_it_arrives() {
+    let (config, packs, ledger, dir) = startup_files("app", LEDGER);
+
+    let mut app = App::new(Palette::Ansi);
+    app.station.config_path = Some(config);
+    app.station.packs_dir = Some(packs);
+    app.station.ledger_path = Some(ledger);
+    app.start_loading();
+    while app.station.startup.is_some() {
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        app.poll_startup();
+    }
+
+    assert!(app.error.is_none());
+    assert!(matches!(app.palette, Palette::Custom { .. }), "the pack's theme, picked by the config");
+    assert_eq!(app.goals.items.len(), 1);
+    assert_eq!(app.station.ledger.days.values().map(|day| day.files).sum::<u64>(), 2);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,40 @@
+use lumon_mdr::state::AppState;
+
+const ALL: [AppState; 11] = [
+    AppState::Login,
+    AppState::Loading,
+    AppState::Main,
+    AppState::Prize,
+    AppState::Error,
+    AppState::Report,
+    AppState::Locked,
+    AppState::Changelog,
+    AppState::Recap,
+    AppState::Setup,
+    AppState::Break,
+];
+
+#[test]
+fn refinement_is_reached_only_through_the_login_and_loading() {
+    assert!(AppState::Login.can_go_to(AppState::Loading));
+    assert!(AppState::Loading.can_go_to(AppState::Main));
+    assert!(!AppState::Login.can_go_to(AppState::Main));
+    assert!(!AppState::Login.can_go_to(AppState::Prize));
+    assert!(!AppState::Report.can_go_to(AppState::Prize), "the prize is won on the main screen");
+    assert!(!AppState::Main.can_go_to(AppState::Login), "logging out starts a new app");
+
+    // Screens off the main one go back to it
+    for state in [AppState::Prize, AppState::Report, AppState::Locked, AppState::Changelog, AppState::Break] {
+        assert!(AppState::Main.can_go_to(state), "{:?}", state);
+        assert!(state.can_go_to(AppState::Main), "{:?}", state);
+    }
+}
+
+#[test]
+fn any_screen_may_be_interrupted_by_an_error() {
+    for state in ALL {
+        assert!(state.can_go_to(AppState::Error), "{:?}", state);
+        assert!(state.can_go_to(state));
+        assert_eq!(state.is_interruption(), state == AppState::Error);
+    }
+}
@@ -0,0 +1,99 @@
+use lumon_mdr::app::DataContainer;
+use lumon_mdr::strategy::BinStrategy;
+use lumon_mdr::temper::Temper;
+use rand::{SeedableRng, rngs::StdRng};
+
+fn bins(counts: &[u16]) -> Vec<DataContainer> {
+    counts
+        .iter()
+        .map(|&count| {
+            let mut bin = DataContainer::new();
+            bin.count = count;
+            bin
+        })
+        .collect()
+}
+
+// Drop `deposits` captures of `value` into whichever bin `strategy` picks,
+// returning how often each bin was picked
+fn run(strategy: BinStrategy, bins: &mut [DataContainer], open: &[usize], deposits: usize, value: u16) -> Vec<usize> {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut picks = vec![0; bins.len()];
+    let mut last = None;
+    for _ in 0..deposits {
+        let bin = strategy.choose(bins, open, None, last, &mut rng).unwrap();
+        last = Some(bin);
+        picks[bin] += 1;
+        bins[bin].count += value;
+    }
+    picks
+}
+
+#[test]
+fn random_spreads_evenly_over_the_open_bins_only() {
+    let mut floor = bins(&[0; 5]);
+    let picks = run(BinStrategy::Random, &mut floor, &[0, 1, 3, 4], 4_000, 0);
+
+    assert_eq!(picks[2], 0, "a closed bin is never picked");
+    for bin in [0, 1, 3, 4] {
+        assert!((850..1_150).contains(&picks[bin]), "bin {} picked {} times", bin, picks[bin]);
+    }
+}
+
+#[test]
+fn least_full_keeps_the_bins_level() {
+    let mut floor = bins(&[30, 0, 10, 0, 20]);
+    run(BinStrategy::LeastFull, &mut floor, &[0, 1, 2, 3, 4], 20, 5);
+
+    let counts: Vec<u16> = floor.iter().map(|bin| bin.count).collect();
+    assert_eq!(counts.iter().sum::<u16>(), 160);
+    assert!(counts.iter().max().unwrap() - counts.iter().min().unwrap() <= 5, "{:?}", counts);
+}
+
+#[test]
+fn round_robin_takes_the_open_bins_in_turn() {
+    let mut floor = bins(&[0; 5]);
+    let mut rng = StdRng::seed_from_u64(1);
+    let order: Vec<usize> = (0..6)
+        .scan(None, |last, _| {
+            *last = BinStrategy::RoundRobin.choose(&floor, &[0, 2, 3], None, *last, &mut rng);
+            *last
+        })
+        .collect();
+    assert_eq!(order, [0, 2, 3, 0, 2, 3]);
+
+    // The bin picked last closing does not lose the place
+    assert_eq!(BinStrategy::RoundRobin.choose(&floor, &[0, 3], None, Some(2), &mut rng), Some(3));
+    floor.clear();
+    assert_eq!(BinStrategy::RoundRobin.choose(&floor, &[], None, None, &mut rng), None);
+}
+
+#[test]
+fn temper_matched_evens_out_each_bins_tempers() {
+    let mut floor = bins(&[0; 4]);
+    let mut rng = StdRng::seed_from_u64(3);
+    for round in 0..32 {
+        let temper = Temper::ALL[round % 4];
+        let bin = BinStrategy::TemperMatched.choose(&floor, &[0, 1, 2, 3], Some(temper), None, &mut rng).unwrap();
+        floor[bin].tempers[temper.index()] += 2;
+        floor[bin].count += 2;
+    }
+
+    for bin in &floor {
+        assert_eq!(bin.tempers, [4, 4, 4, 4]);
+    }
+    assert_eq!(
+        BinStrategy::TemperMatched.choose(&bins(&[9, 4, 6]), &[0, 1, 2], None, None, &mut rng),
+        Some(1),
+        "without a temper it picks the emptiest bin"
+    );
+}
+
+#[test]
+fn strategies_are_named_in_the_config() {
+    for strategy in BinStrategy::ALL {
+        assert_eq!(strategy.name().parse::<BinStrategy>().unwrap(), strategy);
+    }
+    assert_eq!(" Least-Full ".parse::<BinStrategy>().unwrap(), BinStrategy::LeastFull);
+    assert!("fullest".parse::<BinStrategy>().is_err());
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "telemetry")]
+
+use lumon_mdr::config::TelemetryConfig;
+use lumon_mdr::events::AppEvent;
+use lumon_mdr::telemetry::{self, Sink, Telemetry};
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::time::Duration;
+
+#[test]
+fn payloads_are_json() {
+    assert_eq!(
+        telemetry::payload("mark \"s\"", &AppEvent::BinFilled { bin: 3 }),
+        r#"{"event":"bin_filled","user":"mark \"s\"","bin":3}"#
+    );
+    assert_eq!(
+        telemetry::payload("helly", &AppEvent::FileCompleted { prize: "Melon Bar".to_string() }),
+        r#"{"event":"file_complete","user":"helly","prize":"Melon Bar"}"#
+    );
+}
+
+#[test]
+fn parses_sink_urls() {
+    assert_eq!(
+        Sink::webhook("http://hub.local:8123/api/webhook/mdr").unwrap(),
+        Sink::Webhook { host: "hub.local".into(), port: 8123, path: "/api/webhook/mdr".into() }
+    );
+    assert_eq!(
+        Sink::mqtt("mqtt://broker/lumon/mdr").unwrap(),
+        Sink::Mqtt { host: "broker".into(), port: 1883, topic: "lumon/mdr".into() }
+    );
+    assert!(Sink::webhook("https://example.com/hook").is_err());
+    assert!(Sink::mqtt("mqtt://broker").is_err());
+}
+
+#[test]
+fn posts_events_to_the_webhook() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let config = TelemetryConfig {
+        webhook: Some(format!("http://127.0.0.1:{}/hook", port)),
+        mqtt: None,
+    };
+
+    let telemetry = Telemetry::start(&config).unwrap().unwrap();
+    telemetry.send("mark", &AppEvent::BinFilled { bin: 1 });
+
+    let (mut stream, _) = listener.accept().unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+    let mut request = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !String::from_utf8_lossy(&request).contains("\"bin\":1}") {
+        let n = stream.read(&mut buf).unwrap();
+        assert!(n > 0, "connection closed early");
+        request.extend_from_slice(&buf[..n]);
+    }
+    stream.write_all(b"HTTP/1.1 204 No Content\r\n\r\n").unwrap();
+
+    let request = String::from_utf8(request).unwrap();
+    assert!(request.starts_with("POST /hook HTTP/1.1\r\n"));
+    assert!(request.contains("Content-Type: application/json"));
+}
+
+#[test]
+fn publishes_events_over_mqtt() {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let config = TelemetryConfig {
+        webhook: None,
+        mqtt: Some(format!("mqtt://127.0.0.1:{}/lumon/mdr", port)),
+    };
+
+    let telemetry = Telemetry::start(&config).unwrap().unwrap();
+    telemetry.send("mark", &AppEvent::FileCompleted { prize: "Waffle Party".to_string() });
+
+    let (mut stream, _) = listener.accept().unwrap();
+    stream.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+
+    let mut header = [0u8; 2];
+    stream.read_exact(&mut header).unwrap();
+    assert_eq!(header[0], 0x10, "expected CONNECT");
+    let mut connect = vec![0u8; header[1] as usize];
+    stream.read_exact(&mut connect).unwrap();
+    assert_eq!(&connect[2..6], b"MQTT");
+    stream.write_all(&[0x20, 0x02, 0x00, 0x00]).unwrap();
+
+    stream.read_exact(&mut header).unwrap();
+    assert_eq!(header[0], 0x30, "expected PUBLISH");
+    let mut publish = vec![0u8; header[1] as usize];
+    stream.read_exact(&mut publish).unwrap();
+    let topic_len = u16::from_be_bytes([publish[0], publish[1]]) as usize;
+    assert_eq!(&publish[2..2 + topic_len], b"lumon/mdr");
+    let payload = String::from_utf8_lossy(&publish[2 + topic_len..]);
+    assert!(payload.contains("\"prize\":\"Waffle Party\""), "{}", payload);
+}
+
+#[test]
+fn nothing_configured_means_no_thread() {
+    assert!(Telemetry::start(&TelemetryConfig::default()).unwrap().is_none());
+}
@@ -0,0 +1,29 @@
+use lumon_mdr::temper::{self, Provenance, Temper};
+
+fn origin(cell: (usize, usize), digit: u16, temper: Option<Temper>) -> Provenance {
+    Provenance { cell, digit, temper }
+}
+
+#[test]
+fn each_quadrant_of_the_grid_has_its_temper() {
+    let size = (80, 20);
+
+    assert_eq!(Temper::of_cell((0, 0), size), Temper::Woe);
+    assert_eq!(Temper::of_cell((39, 9), size), Temper::Woe);
+    assert_eq!(Temper::of_cell((40, 0), size), Temper::Frolic);
+    assert_eq!(Temper::of_cell((0, 10), size), Temper::Dread);
+    assert_eq!(Temper::of_cell((79, 19), size), Temper::Malice);
+}
+
+#[test]
+fn a_capture_goes_to_the_temper_its_numbers_add_up_to_most_in() {
+    let frolic = origin((50, 2), 9, Some(Temper::Frolic));
+    let woe = |digit| origin((3, 2), digit, Some(Temper::Woe));
+
+    assert_eq!(temper::attribute(&[woe(4), woe(4), frolic]), Some(Temper::Frolic));
+    assert_eq!(temper::attribute(&[woe(5), woe(4), frolic]), Some(Temper::Woe), "a tie goes to more numbers");
+    assert_eq!(temper::attribute(&[woe(9), frolic]), Some(Temper::Woe), "then to the earlier temper");
+    assert_eq!(temper::attribute(&[woe(0)]), Some(Temper::Woe));
+    assert_eq!(temper::attribute(&[origin((3, 2), 7, None)]), None, "nothing known before the grid is drawn");
+    assert_eq!(temper::attribute(&[]), None);
+}
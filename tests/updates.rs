@@ -0,0 +1,47 @@
+use lumon_mdr::updates::{self, Version};
+
+const CHANGELOG: &str = "# Changelog
+
+## [Unreleased]
+
+- Nothing yet.
+
+## [0.10.0] - 2026-02-02
+
+- Music/dance experience now has music.
+
+## 0.9.1
+
+- Fixed the melon bar.
+";
+
+#[test]
+fn versions_compare_numerically() {
+    let v = |text: &str| text.parse::<Version>().unwrap();
+
+    assert!(v("0.10.0") > v("0.9.1"));
+    assert_eq!(v("v1.2.3").to_string(), "1.2.3");
+    assert!("1.2".parse::<Version>().is_err());
+    assert!("1.2.3.4".parse::<Version>().is_err());
+}
+
+#[test]
+fn reads_releases_from_the_changelog() {
+    let releases = updates::parse_changelog(CHANGELOG);
+
+    assert_eq!(releases.len(), 2);
+    assert_eq!(releases[0].version.to_string(), "0.10.0");
+    assert_eq!(releases[0].date.as_deref(), Some("2026-02-02"));
+    assert_eq!(releases[0].notes, ["- Music/dance experience now has music."]);
+    assert_eq!(releases[1].date, None);
+
+    assert_eq!(updates::newer_release(&releases).unwrap().version.to_string(), "0.10.0");
+}
+
+#[test]
+fn the_bundled_changelog_covers_this_build() {
+    let releases = updates::bundled();
+
+    assert!(releases.iter().any(|r| r.version == Version::current()));
+    assert!(updates::newer_release(&releases).is_none());
+}
@@ -0,0 +1,51 @@
+use lumon_mdr::ledger;
+use lumon_mdr::voucher::{self, Voucher};
+
+fn sample() -> Voucher {
+    Voucher {
+        number: 0x00_1234_5678,
+        prize: "Waffle Party".to_string(),
+        employee: "Mark S.".to_string(),
+        file: "Cold Harbor".to_string(),
+        day: ledger::parse_day("2025-03-12").unwrap(),
+    }
+}
+
+#[test]
+fn codes_carry_a_checksum_that_catches_typos() {
+    let code = sample().code();
+
+    assert_eq!(code, "PERK-0093-8NKR-70");
+    assert!(voucher::is_valid_code(&code));
+    assert!(voucher::is_valid_code(&code.to_lowercase()));
+    assert!(!voucher::is_valid_code("PERK-0093-8NKR-71"));
+    assert!(!voucher::is_valid_code("PERK-0093-8NKS-70"));
+    assert!(!voucher::is_valid_code("HUSH-49B4996-10"));
+}
+
+#[test]
+fn the_card_names_the_perk_and_keeps_its_border_straight() {
+    let mut voucher = sample();
+    voucher.prize = "Defiant Jazz Dance Experience with Music Dance Experience".to_string();
+    let card = voucher.card();
+
+    assert!(card.contains("Awarded to: Mark S."));
+    assert!(card.contains("Issued:     2025-03-12"));
+    assert!(card.contains(&voucher.code()));
+    assert!(card.contains("Defiant Jazz Dance"));
+    let widths: Vec<usize> = card.lines().map(|line| line.chars().count()).collect();
+    assert!(widths.iter().all(|&width| width == widths[0]), "{:?}", widths);
+}
+
+#[test]
+fn vouchers_are_written_to_their_folder() {
+    let dir = std::env::temp_dir().join(format!("lumon-mdr-vouchers-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let voucher = sample();
+    let path = voucher.store(&dir).unwrap();
+
+    assert_eq!(path, dir.join("PERK-0093-8NKR-70.txt"));
+    assert!(std::fs::read_to_string(&path).unwrap().starts_with("+---"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
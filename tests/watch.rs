@@ -0,0 +1,41 @@
+use lumon_mdr::startup::Loaded;
+use lumon_mdr::watch::Watcher;
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const INTERVAL: Duration = Duration::from_millis(20);
+
+// Wait for the watcher to hand something over, or give up
+fn next(watcher: &Watcher) -> Option<Loaded> {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if let Some(loaded) = watcher.poll().into_iter().next() {
+            return Some(loaded);
+        }
+        thread::sleep(INTERVAL);
+    }
+    None
+}
+
+#[test]
+fn a_saved_config_is_read_again() {
+    let dir = std::env::temp_dir().join(format!("lumon-mdr-watch-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("config.toml");
+    fs::write(&path, "[display]\neco = false\n").unwrap();
+
+    let watcher = Watcher::start(Some(path.clone()), None, INTERVAL);
+    thread::sleep(INTERVAL * 3);
+    assert!(watcher.poll().is_empty(), "the file as it was counts as read");
+
+    fs::write(&path, "[display]\neco = true\nreduce_motion = true\n").unwrap();
+    match next(&watcher) {
+        Some(Loaded::Config(Ok(config))) => assert!(config.display.eco),
+        other => panic!("expected the new config, got {:?}", other),
+    }
+
+    fs::write(&path, "[display]\neco = 3\n").unwrap();
+    assert!(matches!(next(&watcher), Some(Loaded::Config(Err(_)))));
+    fs::remove_dir_all(&dir).unwrap();
+}
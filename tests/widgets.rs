@@ -0,0 +1,43 @@
+use lumon_mdr::ui::widgets::{Bar, BarChart, ChartStyle, Sparkline};
+use ratatui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
+
+fn style(ascii: bool) -> ChartStyle {
+    ChartStyle { fill: Style::default(), text: Style::default(), ascii }
+}
+
+fn rows(buf: &Buffer) -> Vec<String> {
+    let area = buf.area;
+    (area.y..area.y + area.height)
+        .map(|y| (area.x..area.x + area.width).map(|x| buf.get(x, y).symbol.as_str()).collect::<String>())
+        .map(|row| row.trim_end().to_string())
+        .collect()
+}
+
+#[test]
+fn bars_share_a_scale_with_partial_ends() {
+    let area = Rect::new(0, 0, 20, 3);
+    let mut buf = Buffer::empty(area);
+    let bars = vec![Bar::new("Mon", 80), Bar::new("Tue", 45), Bar::new("Wed", 0).note("none")];
+    BarChart::new(bars, style(false)).max_width(8).render(area, &mut buf);
+
+    assert_eq!(rows(&buf), ["Mon ████████ 80", "Tue ████▌ 45", "Wed  none"]);
+
+    let mut buf = Buffer::empty(area);
+    let bars = vec![Bar::new("Mon", 80), Bar::new("Tue", 45)];
+    BarChart::new(bars, style(true)).max_width(8).render(area, &mut buf);
+    assert_eq!(rows(&buf), ["Mon ######## 80", "Tue #### 45", ""]);
+}
+
+#[test]
+fn sparklines_keep_the_most_recent_values() {
+    let values = [9, 0, 1, 2, 4, 8];
+    let area = Rect::new(0, 0, 5, 1);
+
+    let mut buf = Buffer::empty(area);
+    Sparkline::new(&values, style(false)).render(area, &mut buf);
+    assert_eq!(rows(&buf), [" ▁▂▄█"]);
+
+    let mut buf = Buffer::empty(area);
+    Sparkline::new(&values, style(true)).render(area, &mut buf);
+    assert_eq!(rows(&buf), [" _.:#"]);
+}